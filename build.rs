@@ -0,0 +1,22 @@
+//! Build script that records the git revision for experiment manifests.
+//!
+//! The hash is exposed to the crate as the `O8_GIT_HASH` environment variable.
+//! Builds outside a git checkout (e.g. from crates.io) simply leave it unset.
+
+use std::process::Command;
+
+fn main() {
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    println!("cargo:rerun-if-changed=.git/refs");
+
+    let hash = Command::new("git")
+        .args(["rev-parse", "--short=12", "HEAD"])
+        .output()
+        .ok()
+        .filter(|out| out.status.success())
+        .and_then(|out| String::from_utf8(out.stdout).ok());
+
+    if let Some(hash) = hash {
+        println!("cargo:rustc-env=O8_GIT_HASH={}", hash.trim());
+    }
+}
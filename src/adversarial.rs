@@ -0,0 +1,172 @@
+//! # Adversarial Module
+//!
+//! Searches the puzzle state space for boards that maximize the work a chosen
+//! strategy has to do. Starting from random scrambles, a hill climber perturbs
+//! the current board with a few random moves, scores every candidate with a
+//! quick probe solve, and keeps the candidate whenever it is at least as hard
+//! as the current one. The hardest boards seen across all restarts are
+//! returned so heuristic weaknesses can be studied.
+
+use crate::time::Instant;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+#[cfg(feature = "cli")]
+use clap::ValueEnum;
+#[cfg(feature = "cli")]
+use indicatif::ParallelProgressIterator;
+use rand::{Rng, rng, seq::IndexedRandom};
+#[cfg(feature = "parallel")]
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+use crate::board::{ALL_DIRECTIONS, Board};
+use crate::registry::{StrategyConfig, StrategyEntry};
+use crate::stats::Stats;
+
+/// Maximum number of random moves applied when perturbing a board
+const MAX_PERTURBATION_MOVES: usize = 3;
+
+/// Quantity the adversarial generator tries to maximize
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "cli", derive(ValueEnum))]
+pub enum AdversarialObjective {
+    /// Number of nodes expanded by the probe solve
+    #[default]
+    Nodes,
+    /// Wall-clock time of the probe solve (microseconds)
+    Time,
+}
+
+/// Parameters of the hill-climbing search
+#[derive(Clone, Copy, Debug)]
+pub struct AdversarialConfig {
+    /// Quantity to maximize
+    pub objective: AdversarialObjective,
+    /// Number of independent hill-climbing restarts
+    pub restarts: usize,
+    /// Number of probe solves performed per restart
+    pub iterations: usize,
+    /// Number of scramble steps used to create each starting board
+    pub scramble_steps: usize,
+    /// Number of instances to keep in the final ranking
+    pub top: usize,
+}
+
+/// A board found by the generator together with its probe results
+#[derive(Clone, Copy, Debug)]
+pub struct AdversarialInstance {
+    /// The adversarial board
+    pub board: Board,
+    /// Objective value measured by the probe solve
+    pub score: u64,
+    /// Full statistics of the probe solve
+    pub stats: Stats,
+}
+
+/// Searches for the boards that make `strategy` work the hardest
+///
+/// Restarts run in parallel with the `parallel` feature. Each restart climbs from its own random board and
+/// every probed board is a candidate for the final ranking, so the result can
+/// contain boards from several restarts.
+///
+/// # Arguments
+///
+/// * `strategy` - The strategy under attack, built with the default options for every probe
+/// * `config` - Hill-climbing parameters
+///
+/// # Returns
+///
+/// Up to `config.top` distinct boards, sorted by decreasing score
+#[must_use]
+pub fn generate(strategy: &StrategyEntry, config: &AdversarialConfig) -> Vec<AdversarialInstance> {
+    #[cfg(feature = "parallel")]
+    let probed: Vec<AdversarialInstance> = {
+        let restarts = (0..config.restarts).into_par_iter();
+        #[cfg(feature = "cli")]
+        let restarts = restarts.progress();
+        restarts
+            .flat_map_iter(|_| hill_climb(strategy, config))
+            .collect()
+    };
+    #[cfg(not(feature = "parallel"))]
+    let probed: Vec<AdversarialInstance> = (0..config.restarts)
+        .flat_map(|_| hill_climb(strategy, config))
+        .collect();
+
+    let mut unique: HashMap<Board, AdversarialInstance> = HashMap::new();
+    for instance in probed {
+        unique.entry(instance.board).or_insert(instance);
+    }
+
+    let mut ranking: Vec<AdversarialInstance> = unique.into_values().collect();
+    ranking.sort_unstable_by_key(|instance| Reverse(instance.score));
+    ranking.truncate(config.top);
+    ranking
+}
+
+/// Runs a single hill-climbing restart and returns every probed board
+fn hill_climb(strategy: &StrategyEntry, config: &AdversarialConfig) -> Vec<AdversarialInstance> {
+    let mut rng = rng();
+    let mut current = probe(
+        strategy,
+        Board::random_with_solution(config.scramble_steps),
+        config.objective,
+    );
+    let mut probed = vec![current];
+
+    for _ in 0..config.iterations {
+        let candidate = probe(strategy, perturb(current.board, &mut rng), config.objective);
+        probed.push(candidate);
+
+        // Accept sideways moves too, so the climber can cross plateaus
+        if candidate.score >= current.score {
+            current = candidate;
+        }
+    }
+
+    probed
+}
+
+/// Applies a few random valid moves to a board
+///
+/// Moving the empty space never changes solvability, so the perturbed board is
+/// always solvable.
+fn perturb(mut board: Board, rng: &mut impl Rng) -> Board {
+    let moves = rng.random_range(1..=MAX_PERTURBATION_MOVES);
+
+    for _ in 0..moves {
+        let direction = *ALL_DIRECTIONS
+            .choose(rng)
+            .expect("This should never happen");
+
+        if let Ok(b) = board.move_space(direction) {
+            board = b;
+        }
+    }
+
+    board
+}
+
+/// Solves a board once and scores it according to the objective
+fn probe(
+    strategy: &StrategyEntry,
+    board: Board,
+    objective: AdversarialObjective,
+) -> AdversarialInstance {
+    let mut solver = strategy.build(&StrategyConfig::default());
+    let start = Instant::now();
+    solver.solve(board).expect("No solution found");
+    let elapsed = start.elapsed();
+    let stats = solver.get_solution_stats();
+
+    let score = match objective {
+        AdversarialObjective::Nodes => stats.nodes_explored as u64,
+        AdversarialObjective::Time => u64::try_from(elapsed.as_micros()).unwrap_or(u64::MAX),
+    };
+
+    AdversarialInstance {
+        board,
+        score,
+        stats,
+    }
+}
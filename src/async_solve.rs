@@ -0,0 +1,219 @@
+//! # Async Solve Module
+//!
+//! Helpers for awaiting solves from async code without blocking the executor.
+//! The search itself stays synchronous; it runs on a dedicated thread and the
+//! caller gets back a [`SolveFuture`] for the final result plus a
+//! [`ProgressStream`] of periodic [`SearchProgress`] snapshots.
+//!
+//! Everything here is runtime-agnostic and only uses `std`. Applications that
+//! already own a blocking pool (e.g. `tokio::task::spawn_blocking`) can use
+//! [`blocking_task`] instead of spawning a thread per solve.
+//!
+//! ```no_run
+//! # async fn demo() {
+//! use o8::async_solve::solve_async;
+//! use o8::board::Board;
+//! use o8::search_strategies::HeuristicSearchStrategy;
+//! use o8::solver::Solver;
+//!
+//! let solver = Solver::new(HeuristicSearchStrategy::default());
+//! let (result, mut progress) = solve_async(solver, Board::random_with_solution(60), 1_000);
+//!
+//! while let Some(snapshot) = progress.recv().await {
+//!     println!("explored {} nodes", snapshot.nodes_explored);
+//! }
+//! let outcome = result.await;
+//! println!("{}", outcome.stats);
+//! # }
+//! ```
+
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::task::{Context, Poll, Waker};
+use std::thread;
+
+use crate::board::{Board, BoardWithSteps};
+use crate::search_strategies::SearchStrategy;
+use crate::solver::{SearchProgress, Solver};
+use crate::stats::Stats;
+
+/// Final result of an asynchronous solve
+#[derive(Clone, Debug)]
+pub struct SolveOutcome {
+    /// Boards from start to goal, or `None` if no solution exists
+    pub solution: Option<Vec<Board>>,
+    /// Statistics of the search
+    pub stats: Stats,
+}
+
+/// Result slot shared between the solving thread and [`SolveFuture`]
+#[derive(Default)]
+struct ResultSlot {
+    result: Option<thread::Result<SolveOutcome>>,
+    waker: Option<Waker>,
+}
+
+/// Progress state shared between the solving thread and [`ProgressStream`]
+#[derive(Default)]
+struct ProgressSlot {
+    latest: Option<SearchProgress>,
+    finished: bool,
+    waker: Option<Waker>,
+}
+
+/// Future resolving to the [`SolveOutcome`] once the background search ends
+///
+/// If the search panics, the panic is resumed in the task awaiting this future.
+pub struct SolveFuture {
+    slot: Arc<Mutex<ResultSlot>>,
+}
+
+impl Future for SolveFuture {
+    type Output = SolveOutcome;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut slot = lock(&self.slot);
+        match slot.result.take() {
+            Some(Ok(outcome)) => Poll::Ready(outcome),
+            Some(Err(payload)) => panic::resume_unwind(payload),
+            None => {
+                slot.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// Stream of progress snapshots from a background search
+///
+/// Snapshots are coalesced: if the consumer is slower than the solver, only
+/// the most recent snapshot is kept, so memory use stays constant no matter
+/// how long the search runs.
+pub struct ProgressStream {
+    slot: Arc<Mutex<ProgressSlot>>,
+}
+
+impl ProgressStream {
+    /// Waits for the next snapshot
+    ///
+    /// Resolves to `None` once the search has finished and every snapshot has
+    /// been consumed.
+    pub fn recv(&mut self) -> NextProgress<'_> {
+        NextProgress { stream: self }
+    }
+
+    /// Returns the latest unread snapshot without waiting, if any
+    #[must_use]
+    pub fn try_next(&mut self) -> Option<SearchProgress> {
+        lock(&self.slot).latest.take()
+    }
+}
+
+/// Future returned by [`ProgressStream::recv`]
+pub struct NextProgress<'a> {
+    stream: &'a mut ProgressStream,
+}
+
+impl Future for NextProgress<'_> {
+    type Output = Option<SearchProgress>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut slot = lock(&self.stream.slot);
+        if let Some(progress) = slot.latest.take() {
+            Poll::Ready(Some(progress))
+        } else if slot.finished {
+            Poll::Ready(None)
+        } else {
+            slot.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+/// Solves a board on a dedicated thread
+///
+/// # Arguments
+///
+/// * `solver` - A configured `Solver`; any progress observer it already has is replaced
+/// * `board` - The board to solve
+/// * `progress_interval` - Number of explored nodes between progress snapshots
+///
+/// # Returns
+///
+/// A future for the final outcome and a stream of progress snapshots. Either
+/// can be dropped independently without affecting the search.
+pub fn solve_async<T>(
+    solver: Solver<T>,
+    board: Board,
+    progress_interval: usize,
+) -> (SolveFuture, ProgressStream)
+where
+    T: SearchStrategy<BoardWithSteps> + Default + Clone + Send + 'static,
+{
+    let result_slot = Arc::new(Mutex::new(ResultSlot::default()));
+    let progress_slot = Arc::new(Mutex::new(ProgressSlot::default()));
+
+    let observer_slot = Arc::clone(&progress_slot);
+    let solver = solver.with_progress_observer(
+        progress_interval,
+        Arc::new(move |progress: &SearchProgress| {
+            let mut slot = lock(&observer_slot);
+            slot.latest = Some(progress.clone());
+            if let Some(waker) = slot.waker.take() {
+                waker.wake();
+            }
+        }),
+    );
+
+    let thread_result = Arc::clone(&result_slot);
+    let thread_progress = Arc::clone(&progress_slot);
+    thread::spawn(move || {
+        let result = panic::catch_unwind(AssertUnwindSafe(blocking_task(solver, board)));
+
+        let mut progress = lock(&thread_progress);
+        progress.finished = true;
+        if let Some(waker) = progress.waker.take() {
+            waker.wake();
+        }
+        drop(progress);
+
+        let mut slot = lock(&thread_result);
+        slot.result = Some(result);
+        if let Some(waker) = slot.waker.take() {
+            waker.wake();
+        }
+    });
+
+    (
+        SolveFuture { slot: result_slot },
+        ProgressStream {
+            slot: progress_slot,
+        },
+    )
+}
+
+/// Packages a solve as a blocking closure for an external blocking pool
+///
+/// Use this with `spawn_blocking`-style APIs, e.g.
+/// `tokio::task::spawn_blocking(blocking_task(solver, board)).await`.
+pub fn blocking_task<T>(mut solver: Solver<T>, board: Board) -> impl FnOnce() -> SolveOutcome + Send
+where
+    T: SearchStrategy<BoardWithSteps> + Default + Clone + Send,
+{
+    move || {
+        let solution = solver.solve(board).map(|_| solver.step_by_step_solution());
+        SolveOutcome {
+            solution,
+            stats: solver.get_solution_stats(),
+        }
+    }
+}
+
+/// Locks a mutex, recovering the data if another thread panicked while holding it
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+}
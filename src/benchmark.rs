@@ -0,0 +1,751 @@
+//! # Benchmark Module
+//!
+//! The comparison behind `o8 benchmark`: every built-in strategy solves the
+//! same ordered boards in parallel, with a progress bar per strategy, and the
+//! aggregated statistics are printed as one table. The boards are generated
+//! from a seed, one sub-seed per board, or read from a board set.
+//!
+//! A [`Budget`] caps the wall time of each strategy, and a [`CiTarget`] keeps
+//! adding batches of boards until every median solve time is certain enough.
+//! The results can be exported with a reproducibility manifest and checked
+//! against thresholds in a JUnit report.
+
+use std::fmt::{self, Display};
+use std::ops::Range;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use indicatif::ParallelProgressIterator;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng, rng};
+use rayon::ThreadPoolBuilder;
+use rayon::iter::{
+    IndexedParallelIterator, IntoParallelIterator, IntoParallelRefIterator, ParallelIterator,
+};
+
+use crate::bidirectional;
+use crate::board::{Board, BoardWithSteps, board_seed};
+use crate::boardset::BoardSource;
+use crate::broadening;
+use crate::commands::{CommandError, load_board_set};
+use crate::database::StateSpaceDatabase;
+use crate::dfbnb;
+use crate::ehc;
+use crate::export::{self, Manifest};
+use crate::fringe;
+use crate::frontier_search;
+use crate::hda;
+use crate::heuristic::SharedHeuristic;
+use crate::human;
+use crate::junit::{self, Threshold};
+use crate::lrta;
+use crate::oracle;
+use crate::perimeter::{self, Perimeter};
+use crate::schema::{OutputFormat, RunRecord};
+use crate::scramble::Scramble;
+use crate::search_strategies::{
+    FocalSearchStrategy, HeuristicSearchStrategy, SearchStrategy, SimpleSearchStrategy,
+};
+use crate::sma;
+use crate::solver::{ExplorerStrategy, Solver};
+use crate::stats::{
+    BOOTSTRAP_CONFIDENCE, GenerationStats, Stats, StatsSummary, bootstrap_median,
+    print_blank_heatmap, print_comparison_table, print_generation_stats,
+};
+use crate::time::Instant;
+
+/// Wall-clock time each strategy may spend in a benchmark
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Budget(Duration);
+
+impl Budget {
+    /// Whether a strategy that started at `started` has used up the budget
+    fn is_spent(self, started: Instant) -> bool {
+        started.elapsed() >= self.0
+    }
+}
+
+impl FromStr for Budget {
+    type Err = &'static str;
+
+    /// Parses `<N><ms|s|m|h>`, optionally followed by `-per-strategy`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        const FORMAT: &str = "Invalid budget: expected a duration such as 500ms, 60s, 5m or 1h, optionally followed by -per-strategy";
+
+        let s = s.strip_suffix("-per-strategy").unwrap_or(s);
+        let split = s.find(|c: char| !c.is_ascii_digit()).ok_or(FORMAT)?;
+        let (amount, unit) = s.split_at(split);
+        let amount: u64 = amount.parse().map_err(|_| FORMAT)?;
+        let duration = match unit {
+            "ms" => Duration::from_millis(amount),
+            "s" => Duration::from_secs(amount),
+            "m" => Duration::from_secs(amount.saturating_mul(60)),
+            "h" => Duration::from_secs(amount.saturating_mul(3600)),
+            _ => return Err(FORMAT),
+        };
+        if duration.is_zero() {
+            return Err("Invalid budget: must be greater than zero");
+        }
+        Ok(Self(duration))
+    }
+}
+
+impl Display for Budget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} per strategy", self.0)
+    }
+}
+
+/// Largest accepted relative width of a confidence interval, e.g. `5%`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CiTarget(f64);
+
+impl FromStr for CiTarget {
+    type Err = &'static str;
+
+    /// Parses a percentage such as `5%` or a fraction such as `0.05`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        const FORMAT: &str =
+            "Invalid CI target: expected a percentage such as 5% or a fraction such as 0.05";
+
+        let fraction = match s.strip_suffix('%') {
+            Some(percent) => percent.trim().parse::<f64>().map_err(|_| FORMAT)? / 100.0,
+            None => s.trim().parse::<f64>().map_err(|_| FORMAT)?,
+        };
+        if fraction.is_finite() && fraction > 0.0 {
+            Ok(Self(fraction))
+        } else {
+            Err("Invalid CI target: must be greater than zero")
+        }
+    }
+}
+
+impl Display for CiTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}%", self.0 * 100.0)
+    }
+}
+
+/// Where the boards of a benchmark come from and how long each strategy may run
+#[derive(Clone, Copy)]
+pub struct BenchmarkInput<'a> {
+    /// Number of random boards to generate
+    pub runs: usize,
+    /// Length and model of the walk that scrambles each random board
+    pub scramble: &'a Scramble,
+    /// Seed for board generation (random if `None`)
+    pub seed: Option<u64>,
+    /// Board set to use instead of random boards
+    pub board_source: Option<&'a BoardSource>,
+    /// Wall-clock budget of each strategy
+    pub budget: Option<Budget>,
+    /// Keep adding batches of `runs` boards until every median is this certain
+    pub until_ci: Option<CiTarget>,
+    /// Upper bound on the number of boards in `until_ci` mode
+    pub max_runs: usize,
+    /// Heuristic of the generic solver's strategies, instead of the Manhattan distance
+    pub heuristic: Option<&'a SharedHeuristic>,
+    /// Measure the compressed length of every solution
+    pub compress: bool,
+}
+
+/// Optional reports written at the end of a benchmark
+pub struct BenchmarkReports<'a> {
+    /// Destination of the results and manifest export
+    pub export: Option<&'a Path>,
+    /// Encoding of the export
+    pub format: OutputFormat,
+    /// Destination of the JUnit XML report
+    pub junit: Option<&'a Path>,
+    /// Checks turned into JUnit test cases
+    pub thresholds: &'a [Threshold],
+    /// Print the blank-position heatmap of each strategy
+    pub heatmap: bool,
+}
+
+/// Print how many runs a strategy completed if its budget cut it short
+fn print_budget_usage(budget: Option<Budget>, completed: usize, total: usize) {
+    if let Some(budget) = budget
+        && completed < total
+    {
+        println!("  Budget of {budget} spent: {completed} of {total} runs completed");
+    }
+}
+
+/// Run a search algorithm on a collection of boards in parallel
+///
+/// `solver` is cloned once per chunk of work handed to a Rayon worker (a few
+/// times per thread) and reset between boards, so its containers are reused
+/// instead of re-allocated for every board. With a
+/// `budget`, boards are skipped once the strategy has used it up, so fewer
+/// runs than boards may be returned.
+///
+/// # Arguments
+///
+/// * `boards` - A slice of puzzle boards to solve
+/// * `solver` - A configured `Solver` with the chosen strategy
+/// * `budget` - Optional wall-clock budget for the whole strategy
+/// * `compress` - Also measure the compressed length of each solution
+///
+/// # Returns
+///
+/// The statistics of each solved board, and the blank-position heatmap summed over all runs
+fn run_search<T>(
+    boards: &[Board],
+    solver: &Solver<T>,
+    budget: Option<Budget>,
+    compress: bool,
+) -> (Vec<(usize, Stats)>, Vec<usize>)
+where
+    T: SearchStrategy<BoardWithSteps> + Default + Send + Sync + Clone,
+{
+    let started = Instant::now();
+    let runs: Vec<_> = boards
+        .par_iter()
+        .enumerate()
+        .progress()
+        .map_init(
+            || solver.clone(),
+            |solver, (index, b)| {
+                if budget.is_some_and(|budget| budget.is_spent(started)) {
+                    return None;
+                }
+                solver.reset();
+                solver.solve(*b).expect("No solution found");
+                let mut stats = solver.get_solution_stats();
+                if compress {
+                    stats.record_compression(&solver.step_by_step_solution());
+                }
+                Some((index, stats, solver.blank_heatmap()))
+            },
+        )
+        .flatten()
+        .collect();
+    print_budget_usage(budget, runs.len(), boards.len());
+
+    let mut heatmap = Vec::new();
+    for (_, _, counts) in &runs {
+        add_heatmap(&mut heatmap, counts);
+    }
+
+    (
+        runs.into_iter()
+            .map(|(index, stats, _)| (index, stats))
+            .collect(),
+        heatmap,
+    )
+}
+
+/// A solver running `strategy`, guided by `heuristic` instead of the Manhattan distance if given
+fn guided_solver<T>(strategy: T, heuristic: Option<&SharedHeuristic>) -> Solver<T>
+where
+    T: SearchStrategy<BoardWithSteps> + Default + Clone,
+{
+    let solver = Solver::new(strategy);
+    match heuristic {
+        Some(heuristic) => solver.with_heuristic(Arc::clone(heuristic)),
+        None => solver,
+    }
+}
+
+/// Run a solver without a frontier, such as the human-style layered solver,
+/// on a collection of boards in parallel
+///
+/// `solve` returns the solution path and its statistics. The `budget` and
+/// `compress` are applied as in [`run_search`].
+///
+/// # Returns
+///
+/// A vector of statistics for each solved board
+fn run_standalone<F>(
+    boards: &[Board],
+    budget: Option<Budget>,
+    compress: bool,
+    solve: F,
+) -> Vec<(usize, Stats)>
+where
+    F: Fn(Board) -> Option<(Vec<Board>, Stats)> + Sync,
+{
+    let started = Instant::now();
+    let runs: Vec<(usize, Stats)> = boards
+        .par_iter()
+        .enumerate()
+        .progress()
+        .filter_map(|(index, b)| {
+            if budget.is_some_and(|budget| budget.is_spent(started)) {
+                return None;
+            }
+            let (path, mut stats) = solve(*b).expect("No solution found");
+            if compress {
+                stats.record_compression(&path);
+            }
+            Some((index, stats))
+        })
+        .collect();
+    print_budget_usage(budget, runs.len(), boards.len());
+    runs
+}
+
+/// Generate boards `indices` of the set seeded with `seed` in parallel, with a progress bar
+///
+/// Every board has its own sub-seed, so the result does not depend on the
+/// number of threads.
+#[must_use]
+pub fn generate_boards(seed: u64, indices: Range<usize>, scramble: &Scramble) -> Vec<Board> {
+    indices
+        .into_par_iter()
+        .progress()
+        .map(|index| {
+            scramble
+                .model
+                .board_in_set(seed, index as u64, scramble.steps)
+        })
+        .collect()
+}
+
+/// Seeds of boards `indices` of the set seeded with `seed` (see [`generate_boards`])
+fn board_seeds(seed: u64, indices: Range<usize>) -> Vec<Option<u64>> {
+    indices
+        .map(|index| Some(board_seed(seed, index as u64)))
+        .collect()
+}
+
+/// Adds a blank-position heatmap to a running total
+fn add_heatmap(total: &mut Vec<usize>, counts: &[usize]) {
+    total.resize(counts.len(), 0);
+    for (total, count) in total.iter_mut().zip(counts) {
+        *total += count;
+    }
+}
+
+/// Runs and heatmaps collected for every strategy of a benchmark
+///
+/// Every run is stored with the index of its board in the benchmark's board list.
+#[derive(Default)]
+struct StrategyRuns {
+    /// Statistics of the DFS runs
+    dfs: Vec<(usize, Stats)>,
+    /// Statistics of the BFS runs
+    bfs: Vec<(usize, Stats)>,
+    /// Statistics of the UCS runs
+    ucs: Vec<(usize, Stats)>,
+    /// Statistics of the heuristic runs
+    heuristic: Vec<(usize, Stats)>,
+    /// Statistics of the focal search runs
+    focal: Vec<(usize, Stats)>,
+    /// Statistics of the human-style runs
+    human: Vec<(usize, Stats)>,
+    /// Statistics of the iterative broadening runs
+    broadening: Vec<(usize, Stats)>,
+    /// Statistics of the bidirectional MM runs
+    bidirectional: Vec<(usize, Stats)>,
+    /// Statistics of the SMA* runs
+    sma: Vec<(usize, Stats)>,
+    /// Statistics of the fringe search runs
+    fringe: Vec<(usize, Stats)>,
+    /// Statistics of the frontier search runs
+    frontier: Vec<(usize, Stats)>,
+    /// Statistics of the depth-first branch-and-bound runs
+    dfbnb: Vec<(usize, Stats)>,
+    /// Statistics of the LRTA* runs
+    lrta: Vec<(usize, Stats)>,
+    /// Statistics of the enforced hill climbing runs
+    ehc: Vec<(usize, Stats)>,
+    /// Statistics of the HDA* runs
+    hda: Vec<(usize, Stats)>,
+    /// Statistics of the perimeter search runs
+    perimeter: Vec<(usize, Stats)>,
+    /// Statistics of the oracle runs
+    oracle: Vec<(usize, Stats)>,
+    /// Blank-position heatmap summed over the DFS runs
+    dfs_heatmap: Vec<usize>,
+    /// Blank-position heatmap summed over the BFS runs
+    bfs_heatmap: Vec<usize>,
+    /// Blank-position heatmap summed over the UCS runs
+    ucs_heatmap: Vec<usize>,
+    /// Blank-position heatmap summed over the heuristic runs
+    heuristic_heatmap: Vec<usize>,
+}
+
+impl StrategyRuns {
+    /// Run every strategy on the same `boards` and add the results
+    ///
+    /// `first_index` is the index of `boards[0]` in the benchmark's board list.
+    /// `heuristic` replaces the Manhattan distance in the generic solver's
+    /// strategies; the standalone algorithms keep their own. With `compress`,
+    /// the compressed length of every solution is measured too.
+    #[allow(clippy::too_many_lines)]
+    fn run(
+        &mut self,
+        boards: &[Board],
+        first_index: usize,
+        budget: Option<Budget>,
+        heuristic: Option<&SharedHeuristic>,
+        compress: bool,
+    ) {
+        let offset = |runs: Vec<(usize, Stats)>| {
+            runs.into_iter()
+                .map(move |(index, stats)| (first_index + index, stats))
+        };
+
+        println!("Running DFS...");
+        let (runs, heatmap) = run_search(
+            boards,
+            &guided_solver(SimpleSearchStrategy::new(ExplorerStrategy::Dfs), heuristic),
+            budget,
+            compress,
+        );
+        self.dfs.extend(offset(runs));
+        add_heatmap(&mut self.dfs_heatmap, &heatmap);
+
+        println!("Running BFS...");
+        let (runs, heatmap) = run_search(
+            boards,
+            &guided_solver(SimpleSearchStrategy::new(ExplorerStrategy::Bfs), heuristic),
+            budget,
+            compress,
+        );
+        self.bfs.extend(offset(runs));
+        add_heatmap(&mut self.bfs_heatmap, &heatmap);
+
+        println!("Running UCS...");
+        let (runs, heatmap) = run_search(
+            boards,
+            &guided_solver(SimpleSearchStrategy::new(ExplorerStrategy::Ucs), heuristic),
+            budget,
+            compress,
+        );
+        self.ucs.extend(offset(runs));
+        add_heatmap(&mut self.ucs_heatmap, &heatmap);
+
+        println!("Running Heuristic Search (A*-style) ...");
+        let (runs, heatmap) = run_search(
+            boards,
+            &guided_solver(HeuristicSearchStrategy::default(), heuristic),
+            budget,
+            compress,
+        );
+        self.heuristic.extend(offset(runs));
+        add_heatmap(&mut self.heuristic_heatmap, &heatmap);
+
+        println!("Running Focal search...");
+        let (runs, _) = run_search(
+            boards,
+            &guided_solver(FocalSearchStrategy::default(), heuristic),
+            budget,
+            compress,
+        );
+        self.focal.extend(offset(runs));
+
+        println!("Running Human-style layered solver...");
+        self.human
+            .extend(offset(run_standalone(boards, budget, compress, |board| {
+                human::solve(board).map(|solution| (solution.path(), solution.stats))
+            })));
+
+        println!("Running Iterative Broadening...");
+        self.broadening
+            .extend(offset(run_standalone(boards, budget, compress, |board| {
+                broadening::solve(board).map(|solution| (solution.path, solution.stats))
+            })));
+
+        println!("Running Bidirectional MM...");
+        self.bidirectional
+            .extend(offset(run_standalone(boards, budget, compress, |board| {
+                bidirectional::solve(board).map(|solution| (solution.path, solution.stats))
+            })));
+
+        println!("Running SMA*...");
+        self.sma
+            .extend(offset(run_standalone(boards, budget, compress, |board| {
+                sma::solve(board).map(|solution| (solution.path, solution.stats))
+            })));
+
+        println!("Running Fringe search...");
+        self.fringe
+            .extend(offset(run_standalone(boards, budget, compress, |board| {
+                fringe::solve(board).map(|solution| (solution.path, solution.stats))
+            })));
+
+        println!("Running Frontier search...");
+        self.frontier
+            .extend(offset(run_standalone(boards, budget, compress, |board| {
+                frontier_search::solve(board).map(|solution| (solution.path, solution.stats))
+            })));
+
+        println!("Running DFBnB...");
+        self.dfbnb
+            .extend(offset(run_standalone(boards, budget, compress, |board| {
+                dfbnb::solve(board).map(|solution| (solution.path, solution.stats))
+            })));
+
+        println!("Running LRTA*...");
+        self.lrta
+            .extend(offset(run_standalone(boards, budget, compress, |board| {
+                lrta::solve(board).map(|solution| (solution.path, solution.stats))
+            })));
+
+        println!("Running Enforced hill climbing...");
+        self.ehc
+            .extend(offset(run_standalone(boards, budget, compress, |board| {
+                ehc::solve(board).map(|solution| (solution.path, solution.stats))
+            })));
+
+        println!("Running HDA*...");
+        self.hda
+            .extend(offset(run_standalone(boards, budget, compress, |board| {
+                hda::solve(board).map(|solution| (solution.path, solution.stats))
+            })));
+
+        println!("Running Perimeter search...");
+        let table = Perimeter::new(perimeter::DEFAULT_DEPTH);
+        self.perimeter
+            .extend(offset(run_standalone(boards, budget, compress, |board| {
+                perimeter::solve_with_perimeter(board, &table)
+                    .map(|solution| (solution.path, solution.stats))
+            })));
+
+        println!("Running Oracle...");
+        let database = StateSpaceDatabase::global();
+        self.oracle
+            .extend(offset(run_standalone(boards, budget, compress, |board| {
+                oracle::solve_with(board, database).map(|solution| (solution.path, solution.stats))
+            })));
+    }
+
+    /// Strategy names paired with their runs, in display order
+    fn by_strategy(&self) -> [(&'static str, &[(usize, Stats)]); 17] {
+        [
+            ("DFS", &self.dfs),
+            ("BFS", &self.bfs),
+            ("UCS", &self.ucs),
+            ("Heuristic", &self.heuristic),
+            ("Focal", &self.focal),
+            ("Human", &self.human),
+            ("Broadening", &self.broadening),
+            ("Bidirectional", &self.bidirectional),
+            ("SMA*", &self.sma),
+            ("Fringe", &self.fringe),
+            ("Frontier", &self.frontier),
+            ("DFBnB", &self.dfbnb),
+            ("LRTA*", &self.lrta),
+            ("EHC", &self.ehc),
+            ("HDA*", &self.hda),
+            ("Perimeter", &self.perimeter),
+            ("Oracle", &self.oracle),
+        ]
+    }
+
+    /// Aggregated statistics of every strategy, in display order
+    fn summaries(&self) -> Vec<(&'static str, StatsSummary)> {
+        self.by_strategy()
+            .into_iter()
+            .map(|(name, runs)| {
+                let stats: Vec<Stats> = runs.iter().map(|&(_, stats)| stats).collect();
+                (name, stats.as_slice().into())
+            })
+            .collect()
+    }
+
+    /// One record per run, grouped by strategy in display order
+    ///
+    /// `seeds` holds the seed of each board of `boards`, if known.
+    fn records(&self, boards: &[Board], seeds: &[Option<u64>]) -> Vec<RunRecord> {
+        self.by_strategy()
+            .into_iter()
+            .flat_map(|(name, runs)| {
+                runs.iter().map(move |&(index, stats)| {
+                    RunRecord::new(name, boards[index], index, seeds[index], stats)
+                })
+            })
+            .collect()
+    }
+
+    /// The strategy whose median solve time is least certain, with the
+    /// relative width of its bootstrap confidence interval
+    fn widest_median_ci(&self, rng: &mut StdRng) -> (&'static str, f64) {
+        self.by_strategy()
+            .into_iter()
+            .map(|(name, runs)| {
+                let times: Vec<u64> = runs
+                    .iter()
+                    .map(|(_, s)| u64::try_from(s.duration_ms).unwrap_or(u64::MAX))
+                    .collect();
+                let width =
+                    bootstrap_median(&times, rng).map_or(f64::INFINITY, |ci| ci.relative_width());
+                (name, width)
+            })
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .unwrap_or(("", 0.0))
+    }
+}
+
+/// Benchmark the performance of the available strategies on random boards
+///
+/// Every strategy runs on the same ordered `boards`, which are written to the
+/// export next to the results. With `board_source` set, the boards of that set
+/// are used instead and the manifest records the set's seed (0 if it has none).
+/// With a `budget`, each strategy only solves the boards it reaches in time.
+///
+/// The benchmark runs inside its own thread pool of `threads` workers (Rayon's
+/// default when `None`), so it can be invoked more than once per process.
+///
+/// # Errors
+///
+/// Returns a usage error if the board set is invalid, and an error if the
+/// thread pool cannot be started or a report cannot be written.
+pub fn benchmark(
+    input: &BenchmarkInput,
+    threads: Option<usize>,
+    reports: &BenchmarkReports,
+) -> Result<(), CommandError> {
+    let mut builder = ThreadPoolBuilder::new();
+    if let Some(t) = threads {
+        builder = builder.num_threads(t);
+        println!("Using {t} threads for parallel execution.");
+    }
+    let pool = builder
+        .build()
+        .map_err(|error| CommandError::Failed(format!("Failed to build thread pool: {error}")))?;
+
+    pool.install(|| run_benchmark(input, reports))
+}
+
+/// Body of [`benchmark`], run on the worker pool of the current invocation
+fn run_benchmark(input: &BenchmarkInput, reports: &BenchmarkReports) -> Result<(), CommandError> {
+    let BenchmarkInput {
+        runs,
+        scramble,
+        seed,
+        board_source,
+        budget,
+        until_ci,
+        max_runs,
+        heuristic,
+        compress,
+    } = *input;
+
+    let started = Instant::now();
+    let set = board_source
+        .map(|source| load_board_set(source).map(|set| (source, set)))
+        .transpose()?;
+    let seed = match &set {
+        Some((_, set)) => set.seed.unwrap_or_default(),
+        None => seed.unwrap_or_else(|| rng().random()),
+    };
+    let (mut boards, mut seeds) = if let Some((source, set)) = set {
+        println!(
+            "Comparing strategies on {} boards from {source}...",
+            set.entries.len()
+        );
+        let seeds = set.entries.iter().map(|entry| entry.seed).collect();
+        (set.boards(), seeds)
+    } else {
+        println!(
+            "Generating {runs} random boards ({scramble}, seed {seed}) and comparing strategies..."
+        );
+        (
+            generate_boards(seed, 0..runs, scramble),
+            board_seeds(seed, 0..runs),
+        )
+    };
+    let mut generation_time = started.elapsed();
+    print_generation_stats(&GenerationStats::new(seed, &boards, generation_time));
+
+    let mut collected = StrategyRuns::default();
+    collected.run(&boards, 0, budget, heuristic, compress);
+
+    if let Some(target) = until_ci {
+        let mut ci_rng = StdRng::seed_from_u64(seed);
+        loop {
+            let (name, width) = collected.widest_median_ci(&mut ci_rng);
+            println!(
+                "After {} runs the widest {}% CI on the median solve time is {name}'s, at {:.1}% of the median (target {target})",
+                boards.len(),
+                BOOTSTRAP_CONFIDENCE * 100.0,
+                width * 100.0
+            );
+            if width <= target.0 {
+                break;
+            }
+            if boards.len() >= max_runs {
+                println!("Stopping at --max-runs {max_runs} before reaching the target");
+                break;
+            }
+
+            let end = (boards.len() + runs.max(1)).min(max_runs);
+            let started = Instant::now();
+            let batch = generate_boards(seed, boards.len()..end, scramble);
+            generation_time += started.elapsed();
+            collected.run(&batch, boards.len(), None, heuristic, compress);
+            seeds.extend(board_seeds(seed, boards.len()..end));
+            boards.extend(batch);
+        }
+    }
+
+    let summaries = collected.summaries();
+    let results: Vec<(&str, &StatsSummary)> = summaries
+        .iter()
+        .map(|(name, summary)| (*name, summary))
+        .collect();
+
+    print_comparison_table(&results);
+
+    if reports.heatmap {
+        print_blank_heatmap("DFS", &collected.dfs_heatmap);
+        print_blank_heatmap("BFS", &collected.bfs_heatmap);
+        print_blank_heatmap("UCS", &collected.ucs_heatmap);
+        print_blank_heatmap("Heuristic", &collected.heuristic_heatmap);
+    }
+
+    if let Some(path) = reports.export {
+        let mut manifest = Manifest::capture(seed, &boards, rayon::current_num_threads());
+        manifest.generation = Some(GenerationStats::new(seed, &boards, generation_time));
+        let records = collected.records(&boards, &seeds);
+        export::write_benchmark(path, reports.format, &manifest, &boards, &results, &records)
+            .map_err(|error| {
+                CommandError::Failed(format!("Failed to write benchmark export: {error}"))
+            })?;
+        println!("\nResults exported to {}", path.display());
+    }
+
+    if let Some(path) = reports.junit {
+        let failures =
+            junit::write_junit_report(path, "o8 benchmark", &results, reports.thresholds).map_err(
+                |error| CommandError::Failed(format!("Failed to write JUnit report: {error}")),
+            )?;
+        println!(
+            "\nJUnit report written to {} ({} of {} checks failed)",
+            path.display(),
+            failures,
+            reports.thresholds.len()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn budgets_and_ci_targets_parse_their_documented_forms() {
+        assert_eq!("500ms".parse(), Ok(Budget(Duration::from_millis(500))));
+        assert_eq!(
+            "60s-per-strategy".parse(),
+            Ok(Budget(Duration::from_mins(1)))
+        );
+        assert_eq!("2h".parse(), Ok(Budget(Duration::from_hours(2))));
+        for invalid in ["0s", "5", "s", "5d", "-5s"] {
+            assert!(invalid.parse::<Budget>().is_err(), "{invalid}");
+        }
+
+        assert_eq!("5%".parse(), Ok(CiTarget(0.05)));
+        assert_eq!("0.05".parse(), Ok(CiTarget(0.05)));
+        for invalid in ["0%", "-1", "NaN", "five"] {
+            assert!(invalid.parse::<CiTarget>().is_err(), "{invalid}");
+        }
+    }
+}
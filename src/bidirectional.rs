@@ -0,0 +1,202 @@
+//! # Bidirectional Module
+//!
+//! Meet-in-the-middle bidirectional heuristic search (MM, Holte et al. 2016).
+//! Two A*-style searches run at once, one forward from the start towards the
+//! goal and one backward from the goal towards the start, each guided by the
+//! Manhattan distance to the other end. A node is ordered by
+//! `max(g + h, 2g)`, so neither search goes past the midpoint of the optimal
+//! solution before the other one catches up.
+//!
+//! Every time a search reaches a board the other one has reached, the two
+//! partial paths give a candidate solution. The search stops once the best
+//! candidate costs no more than the lowest priority left in either frontier,
+//! which proves it optimal. The side with the lower priority is expanded
+//! first, so work is split between the two frontiers as evenly as the
+//! heuristic allows.
+
+use crate::time::Instant;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+use crate::board::{ALL_DIRECTIONS, Board};
+use crate::stats::Stats;
+
+/// A solution found by the bidirectional search
+#[derive(Clone, Debug)]
+pub struct BidirectionalSolution {
+    /// Every board from the start to the goal
+    pub path: Vec<Board>,
+    /// Board where the forward and backward searches met
+    pub meeting: Board,
+    /// Nodes expanded by the search from the start
+    pub forward_expansions: usize,
+    /// Nodes expanded by the search from the goal
+    pub backward_expansions: usize,
+    /// Work of both searches combined; `max_frontier` counts both frontiers
+    pub stats: Stats,
+}
+
+/// One direction of the search
+struct Half {
+    /// Board this direction's heuristic estimates the distance to
+    target: Board,
+    /// Boards to expand, ordered by priority then depth, with stale entries
+    open: BinaryHeap<Reverse<(usize, usize, Board)>>,
+    /// Best known depth of every reached board
+    depths: HashMap<Board, usize>,
+    /// Predecessor of every reached board on its best known path
+    parents: HashMap<Board, Board>,
+    /// Boards already expanded at their current depth
+    closed: HashSet<Board>,
+    /// Number of expansions
+    expansions: usize,
+}
+
+impl Half {
+    /// A search rooted at `root` heading for `target`
+    fn new(root: Board, target: Board) -> Self {
+        let mut half = Half {
+            target,
+            open: BinaryHeap::new(),
+            depths: HashMap::from([(root, 0)]),
+            parents: HashMap::new(),
+            closed: HashSet::new(),
+            expansions: 0,
+        };
+        half.open.push(Reverse((half.priority(root, 0), 0, root)));
+        half
+    }
+
+    /// MM's ordering key: `max(g + h, 2g)`
+    fn priority(&self, board: Board, depth: usize) -> usize {
+        (depth + usize::from(board.manhattan_distance_to(self.target))).max(2 * depth)
+    }
+
+    /// Lowest priority in the frontier, dropping the stale entries on top
+    fn min_priority(&mut self) -> Option<usize> {
+        while let Some(&Reverse((priority, depth, board))) = self.open.peek() {
+            if !self.closed.contains(&board) && self.depths[&board] == depth {
+                return Some(priority);
+            }
+            self.open.pop();
+        }
+        None
+    }
+
+    /// Number of reached boards not expanded yet
+    fn frontier_len(&self) -> usize {
+        self.depths.len() - self.closed.len()
+    }
+
+    /// Expands the top of the frontier, recording any meeting with `other` in `best`
+    ///
+    /// Must follow a [`Half::min_priority`] that found a node, so the top entry is live.
+    fn expand(&mut self, other: &Half, best: &mut Option<(usize, Board)>, stats: &mut Stats) {
+        let Some(Reverse((_, depth, board))) = self.open.pop() else {
+            return;
+        };
+        self.closed.insert(board);
+        self.expansions += 1;
+        stats.nodes_explored += 1;
+        stats.max_depth_reached = stats.max_depth_reached.max(depth);
+
+        let child_depth = depth + 1;
+        for direction in ALL_DIRECTIONS {
+            let Ok(child) = board.move_space(direction) else {
+                continue;
+            };
+            stats.generated_nodes += 1;
+            if self
+                .depths
+                .get(&child)
+                .is_some_and(|&known| known <= child_depth)
+            {
+                stats.duplicates_pruned += 1;
+                continue;
+            }
+
+            // A shorter path reopens a closed board
+            self.closed.remove(&child);
+            self.depths.insert(child, child_depth);
+            self.parents.insert(child, board);
+            self.open.push(Reverse((
+                self.priority(child, child_depth),
+                child_depth,
+                child,
+            )));
+            stats.enqueued_nodes += 1;
+
+            if let Some(&other_depth) = other.depths.get(&child) {
+                let cost = child_depth + other_depth;
+                if best.is_none_or(|(moves, _)| cost < moves) {
+                    *best = Some((cost, child));
+                }
+            }
+        }
+    }
+
+    /// Boards from `board` back to the root of this search
+    fn chain(&self, mut board: Board) -> Vec<Board> {
+        let mut chain = vec![board];
+        while let Some(&parent) = self.parents.get(&board) {
+            chain.push(parent);
+            board = parent;
+        }
+        chain
+    }
+}
+
+/// Solves a board with the MM bidirectional heuristic search
+///
+/// The solution is optimal, since the Manhattan distance is consistent in
+/// both directions.
+///
+/// # Returns
+///
+/// `Some(solution)`, or `None` if the board cannot reach the goal
+#[must_use]
+pub fn solve(board: Board) -> Option<BidirectionalSolution> {
+    let started = Instant::now();
+    if !board.is_solvable() {
+        return None;
+    }
+
+    let goal = Board::default();
+    let mut forward = Half::new(board, goal);
+    let mut backward = Half::new(goal, board);
+    let mut stats = Stats::default();
+    let mut best = (board == goal).then_some((0, board));
+
+    while let (Some(forward_min), Some(backward_min)) =
+        (forward.min_priority(), backward.min_priority())
+    {
+        if best.is_some_and(|(moves, _)| moves <= forward_min.min(backward_min)) {
+            break;
+        }
+        if forward_min <= backward_min {
+            forward.expand(&backward, &mut best, &mut stats);
+        } else {
+            backward.expand(&forward, &mut best, &mut stats);
+        }
+        stats.max_frontier = stats
+            .max_frontier
+            .max(forward.frontier_len() + backward.frontier_len());
+    }
+
+    let (_, meeting) = best?;
+    let mut path = forward.chain(meeting);
+    path.reverse();
+    path.extend(backward.chain(meeting).into_iter().skip(1));
+
+    stats.solution_moves = path.len() - 1;
+
+    stats.solution_cost = stats.solution_moves;
+    stats.duration_ms = started.elapsed().as_millis();
+    Some(BidirectionalSolution {
+        path,
+        meeting,
+        forward_expansions: forward.expansions,
+        backward_expansions: backward.expansions,
+        stats,
+    })
+}
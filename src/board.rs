@@ -19,7 +19,8 @@
 //! - Tile 1's position is stored in bits 0-3
 //! - Tile 2's position is stored in bits 4-7
 //! - And so on...
-//! - The empty space position is found by determining which position (0-8) is not occupied
+//! - The empty space position is the one position (0-8) no tile occupies; it
+//!   is cached next to the encoding so moves never have to search for it
 //!
 //! ### Example Encoding
 //!
@@ -43,10 +44,19 @@
 //!
 //! This gives us the magic number: `SOLVED_BOARD = 1985229328`
 
-use std::{cmp::Ordering, fmt::Display, sync::LazyLock};
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display},
+    hash::{Hash, Hasher},
+    str::FromStr,
+    sync::LazyLock,
+};
 
+#[cfg(feature = "cli")]
 use colored::Colorize;
-use rand::{rng, seq::IndexedRandom};
+use rand::{Rng, SeedableRng, rng, rngs::StdRng, seq::IndexedRandom};
+
+use crate::database;
 
 use Direction::{Down, Left, Right, Up};
 
@@ -60,20 +70,51 @@ const SOLVED_BOARD: [u8; BOARD_AREA as usize] = [
     7, 6, 5
 ];
 
-/// The solved board state represented as a 32-bit integer
-static SOLVED_BOARD_ENCODED: LazyLock<u32> = LazyLock::new(|| Board::from_arr(&SOLVED_BOARD).0);
+/// The solved board state
+static SOLVED: LazyLock<Board> = LazyLock::new(|| Board::from_arr(&SOLVED_BOARD));
 
 /// The side length of the square board (3x3 grid)
-const BOARD_SIDE: u8 = 3;
+pub(crate) const BOARD_SIDE: u8 = 3;
 
 /// The total number of positions on the board (9 positions)
-const BOARD_AREA: u8 = BOARD_SIDE * BOARD_SIDE;
+pub(crate) const BOARD_AREA: u8 = BOARD_SIDE * BOARD_SIDE;
 
 /// Number of bits used to represent each tile position
 const TILE_BIT_SIZE: u8 = 4;
 
+/// The lowest bit of every tile's 4-bit field
+const LOW_FIELD_BITS: u32 = 0x1111_1111;
+
+/// The highest bit of every tile's 4-bit field
+const HIGH_FIELD_BITS: u32 = 0x8888_8888;
+
+/// Derives the seed of one board of a seeded board set
+///
+/// Mixes `set_seed` and `index` with `SplitMix64`, so each board gets its own
+/// independent seed and can be generated on any thread, in any order, with the
+/// same result (see [`Board::random_in_set`]).
+///
+/// # Arguments
+///
+/// * `set_seed` - Seed of the whole set
+/// * `index` - Position of the board in the set
+#[must_use]
+pub fn board_seed(set_seed: u64, index: u64) -> u64 {
+    let mut z = set_seed.wrapping_add(index.wrapping_add(1).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
 /// Represents the four possible directions for moving tiles in the puzzle
-#[derive(Clone, Copy)]
+///
+/// With the `serde` feature it is (de)serialized by its [name](Direction::name).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
 pub enum Direction {
     /// Move a tile upward (space moves down)
     Up,
@@ -85,6 +126,221 @@ pub enum Direction {
     Right,
 }
 
+impl Direction {
+    /// Lower-case name of the direction (`"up"`, `"down"`, `"left"`, `"right"`)
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Up => "up",
+            Down => "down",
+            Left => "left",
+            Right => "right",
+        }
+    }
+
+    /// Upper-case letter of the direction in move notation (`U`, `D`, `L`, `R`)
+    #[must_use]
+    pub fn letter(self) -> char {
+        match self {
+            Up => 'U',
+            Down => 'D',
+            Left => 'L',
+            Right => 'R',
+        }
+    }
+
+    /// The direction that undoes a move in this direction
+    #[must_use]
+    pub fn opposite(self) -> Direction {
+        match self {
+            Up => Down,
+            Down => Up,
+            Left => Right,
+            Right => Left,
+        }
+    }
+}
+
+/// Writes the direction's [letter](Direction::letter)
+impl Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.letter())
+    }
+}
+
+/// Parses a direction from its letter or its name, in any case
+impl FromStr for Direction {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ALL_DIRECTIONS
+            .into_iter()
+            .find(|direction| {
+                s.eq_ignore_ascii_case(direction.name())
+                    || s.eq_ignore_ascii_case(direction.letter().encode_utf8(&mut [0; 4]))
+            })
+            .ok_or("Invalid move: expected U, D, L or R")
+    }
+}
+
+/// Formats moves as a compact string of letters, e.g. `"ULDDRU"`
+#[must_use]
+pub fn format_moves(moves: &[Direction]) -> String {
+    moves.iter().map(|direction| direction.letter()).collect()
+}
+
+/// Parses a string of move letters, e.g. `"ULDDRU"`, the inverse of [`format_moves`]
+///
+/// Letters may be lower case and separated by whitespace or commas.
+///
+/// # Errors
+///
+/// Fails on any other character
+pub fn parse_moves(s: &str) -> Result<Vec<Direction>, &'static str> {
+    s.chars()
+        .filter(|c| !c.is_whitespace() && *c != ',')
+        .map(|c| c.encode_utf8(&mut [0; 4]).parse())
+        .collect()
+}
+
+/// Error returned when the empty space cannot be moved
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MoveError {
+    /// The empty space would leave the board
+    OffBoard {
+        /// Position of the empty space (0-8)
+        from: u8,
+        /// The rejected direction
+        direction: Direction,
+    },
+    /// No tile sits next to the empty space, so the board encoding is corrupt
+    MissingTile {
+        /// Position that should hold the tile to slide (0-8)
+        position: u8,
+    },
+}
+
+impl Display for MoveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MoveError::OffBoard { from, direction } => write!(
+                f,
+                "Invalid move: cannot move space {} from position {from}",
+                direction.name()
+            ),
+            MoveError::MissingTile { position } => {
+                write!(f, "Invalid board: no tile at position {position}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for MoveError {}
+
+/// Error returned when a move of a sequence cannot be applied
+///
+/// See [`Board::apply_moves`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MoveSequenceError {
+    /// Index of the failed move in the sequence
+    pub index: usize,
+    /// Why the move failed
+    pub error: MoveError,
+}
+
+impl Display for MoveSequenceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Move {}: {}", self.index, self.error)
+    }
+}
+
+impl std::error::Error for MoveSequenceError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Error returned when an array is not a valid board
+///
+/// A valid array holds every value 0-8 exactly once, 0 being the empty space.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BoardError {
+    /// A value above 8
+    OutOfRange {
+        /// The rejected value
+        value: u8,
+    },
+    /// The empty space appears zero times or more than once
+    BlankCount {
+        /// Number of cells holding 0
+        count: usize,
+    },
+    /// A tile appears more than once
+    DuplicateTile {
+        /// The repeated tile (1-8)
+        tile: u8,
+    },
+}
+
+impl Display for BoardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BoardError::OutOfRange { value } => {
+                write!(f, "Invalid board: {value} is not a tile (expected 0-8)")
+            }
+            BoardError::BlankCount { count } => {
+                write!(f, "Invalid board: expected one empty space, found {count}")
+            }
+            BoardError::DuplicateTile { tile } => {
+                write!(f, "Invalid board: tile {tile} appears more than once")
+            }
+        }
+    }
+}
+
+impl std::error::Error for BoardError {}
+
+/// Parity of the number of inversions among a board's tiles in reading order
+///
+/// No move changes it, so a board can reach the goal exactly when its parity
+/// matches the goal's (see [`Board::is_solvable`]).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Parity {
+    /// An even number of inversions
+    Even,
+    /// An odd number of inversions
+    Odd,
+}
+
+impl Parity {
+    /// Lower-case name of the parity (`"even"` or `"odd"`)
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Parity::Even => "even",
+            Parity::Odd => "odd",
+        }
+    }
+
+    /// The other parity
+    #[must_use]
+    pub fn flipped(self) -> Parity {
+        match self {
+            Parity::Even => Parity::Odd,
+            Parity::Odd => Parity::Even,
+        }
+    }
+}
+
+/// A swap of two tiles that turns an unsolvable board into a solvable one
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TileSwap {
+    /// The two tiles exchanged, lower first
+    pub tiles: (u8, u8),
+    /// The board after the swap
+    pub board: Board,
+}
+
 /// Represents an 8-puzzle board state
 ///
 /// ## Compact 32-bit Encoding
@@ -96,7 +352,7 @@ pub enum Direction {
 ///
 /// ### Why This Encoding?
 ///
-/// 1. **Memory Efficient**: Only 32 bits instead of 36+ bytes for arrays
+/// 1. **Memory Efficient**: Only 32 bits (plus the cached empty space) instead of 36+ bytes for arrays
 /// 2. **Copy Efficient**: Single integer copy instead of array copy
 /// 3. **Hash Friendly**: Perfect for HashMap/HashSet keys
 /// 4. **Cache Friendly**: Fits in a single cache line
@@ -128,8 +384,32 @@ pub enum Direction {
 /// ```
 ///
 /// This produces: `0111_0110_0101_0100_0011_0010_0001_0000` = 1985229328
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
-pub struct Board(u32);
+///
+/// ### Ordering
+///
+/// `Ord` compares the raw encodings. The order carries no meaning beyond being
+/// total, stable and consistent with `Eq`, which is what sorting and `BTreeMap`
+/// keys need. To order boards by their distance estimate use [`ScoredBoard`].
+///
+/// ### Serde
+///
+/// With the `serde` feature a board is (de)serialized in its digit form,
+/// e.g. `"123804765"`, never as the raw encoding.
+///
+/// ### Cached Empty Space
+///
+/// The position of the empty space follows from the encoding, but finding it
+/// takes a pass over all eight tiles, and every move starts from it. The board
+/// therefore carries it alongside the encoding, so [`Board::move_space`] never
+/// searches. It is a function of the encoding, so hashing and equality only
+/// look at the encoding.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Board {
+    /// Position of each tile, 4 bits per tile (see the bit layout above)
+    tiles: u32,
+    /// Position of the empty space (0-8), the one position no tile claims
+    blank: u8,
+}
 
 impl Board {
     /// Generates a random board by performing random moves from the solved state
@@ -144,13 +424,54 @@ impl Board {
     /// # Returns
     ///
     /// A randomly scrambled but solvable board
+    #[must_use]
     pub fn random_with_solution(steps: usize) -> Board {
+        Self::random_with_solution_using(steps, &mut rng())
+    }
+
+    /// Generates board `index` of the set seeded with `set_seed`
+    ///
+    /// The board only depends on its own [`board_seed`], so a set can be
+    /// generated in parallel and stays reproducible regardless of thread count.
+    ///
+    /// # Arguments
+    ///
+    /// * `set_seed` - Seed of the whole set
+    /// * `index` - Position of the board in the set
+    /// * `steps` - Number of random moves to perform for scrambling
+    ///
+    /// # Returns
+    ///
+    /// A randomly scrambled but solvable board
+    #[must_use]
+    pub fn random_in_set(set_seed: u64, index: u64, steps: usize) -> Board {
+        let mut board_rng = StdRng::seed_from_u64(board_seed(set_seed, index));
+        Self::random_with_solution_using(steps, &mut board_rng)
+    }
+
+    /// Generates a random board using the provided random number generator
+    ///
+    /// Identical to [`Board::random_with_solution`], but draws the scramble moves
+    /// from `rng`, so a seeded generator yields a reproducible board sequence.
+    ///
+    /// # Arguments
+    ///
+    /// * `steps` - Number of random moves to perform for scrambling
+    /// * `rng` - Source of randomness for the scramble moves
+    ///
+    /// # Returns
+    ///
+    /// A randomly scrambled but solvable board
+    ///
+    /// # Panics
+    ///
+    /// Never panics in practice: the direction list is never empty
+    pub fn random_with_solution_using<R: Rng + ?Sized>(steps: usize, rng: &mut R) -> Board {
         let mut board = Board::default();
-        let mut rng = rng();
 
         for _ in 0..steps {
             let direction = *ALL_DIRECTIONS
-                .choose(&mut rng)
+                .choose(rng)
                 .expect("This should never happen");
 
             if let Ok(b) = board.move_space(direction) {
@@ -189,7 +510,8 @@ impl Board {
     ///
     /// A 9-element array where each position contains the tile number,
     /// with 0 representing the empty space
-    fn into_arr(self) -> [u8; BOARD_AREA as usize] {
+    #[must_use]
+    pub fn into_arr(self) -> [u8; BOARD_AREA as usize] {
         let mut arr = [0; BOARD_AREA as usize];
 
         // For each tile (0-7 representing tiles 1-8)
@@ -206,7 +528,8 @@ impl Board {
     /// Creates a board from a 2D array representation
     ///
     /// This function encodes the array format back into the compact 32-bit representation.
-    /// It assumes that the input array is valid (contains numbers 0-8 with no duplicates).
+    /// It assumes that the input array is valid (contains numbers 0-8 with no duplicates);
+    /// use [`Board::try_from_arr`] for untrusted input.
     ///
     /// # Arguments
     ///
@@ -215,25 +538,76 @@ impl Board {
     /// # Returns
     ///
     /// The encoded `Board` instance
+    ///
+    /// # Panics
+    ///
+    /// Never panics in practice: array positions always fit in a `u8`
+    #[must_use]
     pub fn from_arr(arr: &[u8; BOARD_AREA as usize]) -> Self {
-        let mut board = Board(0);
+        let mut board = Board { tiles: 0, blank: 0 };
 
         for (pos, &val) in arr.iter().enumerate() {
-            if val != 0 {
-                board.set_value(pos.try_into().expect("Should be less than 256"), val - 1);
+            let pos = pos.try_into().expect("Should be less than 256");
+            if val == 0 {
+                board.blank = pos;
+            } else {
+                board.set_value(pos, val - 1);
             }
         }
 
         board
     }
 
+    /// Creates a board from a row-major array, checking that it is valid
+    ///
+    /// Unlike [`Board::from_arr`], which trusts its input, this is meant for
+    /// arrays from outside the program.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BoardError::OutOfRange`] for a value above 8, then
+    /// [`BoardError::BlankCount`] unless exactly one cell is 0, then
+    /// [`BoardError::DuplicateTile`] for the lowest repeated tile
+    pub fn try_from_arr(arr: &[u8; BOARD_AREA as usize]) -> Result<Self, BoardError> {
+        if let Some(&value) = arr.iter().find(|&&value| value >= BOARD_AREA) {
+            return Err(BoardError::OutOfRange { value });
+        }
+
+        let mut counts = [0; BOARD_AREA as usize];
+        for &value in arr {
+            counts[usize::from(value)] += 1;
+        }
+
+        if counts[0] != 1 {
+            return Err(BoardError::BlankCount { count: counts[0] });
+        }
+        if let Some(tile) = (1..BOARD_AREA).find(|&tile| counts[usize::from(tile)] > 1) {
+            return Err(BoardError::DuplicateTile { tile });
+        }
+
+        Ok(Board::from_arr(arr))
+    }
+
+    /// Formats the board as a compact row-major digit string
+    ///
+    /// The empty space is written as `0`, e.g. the solved board is `"123804765"`.
+    /// This is the inverse of the [`FromStr`] implementation.
+    #[must_use]
+    pub fn to_digit_string(self) -> String {
+        self.into_arr()
+            .iter()
+            .map(|&d| char::from(b'0' + d))
+            .collect()
+    }
+
     /// Checks if the board is in the solved state
     ///
     /// # Returns
     ///
     /// `true` if the board is solved (tiles are in numerical order), `false` otherwise
+    #[must_use]
     pub fn is_solved(self) -> bool {
-        self.0 == *SOLVED_BOARD_ENCODED
+        self.tiles == SOLVED.tiles
     }
 
     /// Validates if a movement is possible from a given position
@@ -250,12 +624,23 @@ impl Board {
         match direction {
             Up => (position / BOARD_SIDE) != 0,
             Down => (position / BOARD_SIDE) != BOARD_SIDE - 1,
-            Left => (position % BOARD_SIDE) != 0,
+            Left => !position.is_multiple_of(BOARD_SIDE),
             Right => (position % BOARD_SIDE) != BOARD_SIDE - 1,
         }
     }
 
-    /// Finds the current position of the empty space on the board
+    /// Returns the position of the empty space on the board
+    ///
+    /// The position is cached in the board, so this takes constant time.
+    ///
+    /// # Returns
+    ///
+    /// The position (0-8) of the empty space
+    pub(crate) fn find_space_position(self) -> u8 {
+        self.blank
+    }
+
+    /// Finds the position of the empty space of an encoding
     ///
     /// Since we only store positions for tiles 1-8, the empty space is implicitly
     /// the position that's NOT occupied by any tile.
@@ -284,12 +669,13 @@ impl Board {
     /// # Returns
     ///
     /// The position (0-8) of the empty space
-    fn find_space_position(self) -> u8 {
+    fn locate_blank(tiles: u32) -> u8 {
+        let board = Board { tiles, blank: 0 };
         let mut idx: u32 = 0;
 
         // Build bitmask of occupied positions
         for val in 0..(BOARD_AREA - 1) {
-            let pos = self.get_pos(val);
+            let pos = board.get_pos(val);
             idx |= 1 << pos; // Set bit at position 'pos'
         }
 
@@ -308,10 +694,10 @@ impl Board {
     ///
     /// # Returns
     ///
-    /// `Ok(new_position)` if the move is valid, or an error message if invalid
-    fn calculate_new_position(from: u8, direction: Direction) -> Result<u8, &'static str> {
+    /// `Ok(new_position)` if the move is valid, or [`MoveError::OffBoard`] if invalid
+    fn calculate_new_position(from: u8, direction: Direction) -> Result<u8, MoveError> {
         if !Board::is_valid_movement(from, direction) {
-            return Err("Invalid move: cannot move space in that direction");
+            return Err(MoveError::OffBoard { from, direction });
         }
 
         Ok(match direction {
@@ -322,57 +708,53 @@ impl Board {
         })
     }
 
-    /// Gets the tile value at a specific position
+    /// Gets the tile at a specific position
     ///
-    /// This function searches through all tiles to find which one is located
-    /// at the specified position. It's essentially the inverse of the encoding.
+    /// This is the inverse of the encoding, answered in constant time by
+    /// comparing all eight 4-bit fields with `position` at once.
     ///
     /// ## Algorithm
     ///
-    /// 1. Iterate through all tiles (1-8, represented as 0-7 internally)
-    /// 2. For each tile, extract its encoded position
-    /// 3. If the position matches what we're looking for, return the tile number
-    /// 4. If no tile is found at that position, it means the position is empty
+    /// 1. Repeat `position` in every field and XOR it with the encoding, so
+    ///    the field of the tile at `position` becomes zero
+    /// 2. Subtract one from every field: only a zero field borrows, setting its
+    ///    high bit while its own high bit was clear
+    /// 3. The lowest field flagged this way belongs to the tile (a borrow can
+    ///    only mislead the fields above a zero one)
     ///
     /// ## Example
     ///
-    /// To find what's at position 2:
+    /// To find what's at position 2 of `123456780` (encoded as `1985229328`):
     /// ```text
-    /// Check tile 1: position = bits 0-3   → if == 2, return 0 (tile 1)
-    /// Check tile 2: position = bits 4-7   → if == 2, return 1 (tile 2)  
-    /// Check tile 3: position = bits 8-11  → if == 2, return 2 (tile 3) ✓
+    /// Encoding:        0111_0110_0101_0100_0011_0010_0001_0000
+    /// XOR 0x2222_2222: 0101_0100_0111_0110_0001_0000_0011_0010
+    /// Zero field:                               ^^^^ tile 3
     /// ```
     ///
     /// # Arguments
     ///
-    /// * `p` - The position to query (0-8)
+    /// * `position` - The position to query (0-8)
     ///
     /// # Returns
     ///
-    /// The tile number (0-7 representing tiles 1-8) at the specified position
+    /// `Some(tile)` with the tile number (1-8), or `None` if the position is
+    /// the empty space or lies outside the board
     ///
     /// # Panics
     ///
-    /// Panics if the position doesn't contain a valid tile (i.e., it's the empty space)
-    fn get_value(self, p: u8) -> u8 {
-        let mut target_val = 0;
-        let mut target_pos = 0;
-
-        // Search through all tiles to find which one is at position 'p'
-        for val in 0..(BOARD_AREA - 1) {
-            target_val = val;
-            target_pos = self.get_pos(val);
-            if target_pos == p {
-                break; // Found the tile at position 'p'
-            }
+    /// Never panics in practice: tile numbers always fit in a `u8`
+    #[must_use]
+    pub fn get_tile(self, position: u8) -> Option<u8> {
+        if position >= BOARD_AREA {
+            return None;
         }
 
-        assert!(
-            target_pos == p,
-            "Invalid move: cannot move space in that direction"
-        );
-
-        target_val // Return tile number (0-7)
+        let fields = self.tiles ^ (u32::from(position) * LOW_FIELD_BITS);
+        let zero_fields = fields.wrapping_sub(LOW_FIELD_BITS) & !fields & HIGH_FIELD_BITS;
+        (zero_fields != 0).then(|| {
+            let val = zero_fields.trailing_zeros() / u32::from(TILE_BIT_SIZE);
+            u8::try_from(val).expect("Should be less than 256") + 1
+        })
     }
 
     /// Sets a tile value at a specific position in the compact representation
@@ -412,9 +794,9 @@ impl Board {
         // Position mask at tile's bit field
         let mask = ones << (TILE_BIT_SIZE * val);
         // Clear old position
-        self.0 &= !mask;
+        self.tiles &= !mask;
         // Set new position
-        self.0 |= u32::from(p) << (TILE_BIT_SIZE * val);
+        self.tiles |= u32::from(p) << (TILE_BIT_SIZE * val);
     }
 
     /// Moves the empty space in the specified direction
@@ -428,20 +810,276 @@ impl Board {
     ///
     /// # Returns
     ///
-    /// `Ok(new_board)` if the move is valid, or an error message if the move is invalid
-    pub fn move_space(mut self, direction: Direction) -> Result<Self, &'static str> {
+    /// `Ok(new_board)` if the move is valid, or a [`MoveError`] if the move is invalid
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MoveError::OffBoard`] if the empty space would leave the board,
+    /// and [`MoveError::MissingTile`] if the board encoding is corrupt
+    pub fn move_space(mut self, direction: Direction) -> Result<Self, MoveError> {
         let space_position = self.find_space_position();
         let space_new_position = Self::calculate_new_position(space_position, direction)?;
-        let digit_to_move = self.get_value(space_new_position);
+        let tile_to_move = self
+            .get_tile(space_new_position)
+            .ok_or(MoveError::MissingTile {
+                position: space_new_position,
+            })?;
 
-        self.set_value(space_position, digit_to_move);
+        self.set_value(space_position, tile_to_move - 1);
+        self.blank = space_new_position;
 
         Ok(self)
     }
 
+    /// Every board one move away, with the move of the empty space reaching it
+    ///
+    /// Moves that would take the empty space off the board are skipped, so a
+    /// corner yields two successors, an edge three and the center four, always
+    /// in the order of [`ALL_DIRECTIONS`].
+    pub fn successors(self) -> impl Iterator<Item = (Direction, Board)> {
+        ALL_DIRECTIONS
+            .into_iter()
+            .filter_map(move |direction| Some((direction, self.move_space(direction).ok()?)))
+    }
+
+    /// Applies a sequence of moves of the empty space, in order
+    ///
+    /// # Returns
+    ///
+    /// The board after the last move, which is this board for an empty sequence
+    ///
+    /// # Errors
+    ///
+    /// Returns the first move that fails, with its index in `moves`
+    pub fn apply_moves(self, moves: &[Direction]) -> Result<Board, MoveSequenceError> {
+        moves
+            .iter()
+            .enumerate()
+            .try_fold(self, |board, (index, &direction)| {
+                board
+                    .move_space(direction)
+                    .map_err(|error| MoveSequenceError { index, error })
+            })
+    }
+
+    /// Finds the move of the empty space that turns this board into `next`
+    ///
+    /// # Returns
+    ///
+    /// `Some(direction)` if `next` is one move away, `None` otherwise
+    #[must_use]
+    pub fn direction_to(self, next: Board) -> Option<Direction> {
+        self.successors()
+            .find(|&(_, successor)| successor == next)
+            .map(|(direction, _)| direction)
+    }
+
+    /// Returns the raw 32-bit encoding of the board
+    ///
+    /// The value is stable across runs and platforms, which makes it suitable
+    /// for fingerprinting board sets.
+    #[must_use]
+    pub fn encoded(self) -> u32 {
+        self.tiles
+    }
+
+    /// Rebuilds a board from a value returned by [`Board::encoded`]
+    pub(crate) fn from_encoded(encoded: u32) -> Self {
+        Board {
+            tiles: encoded,
+            blank: Board::locate_blank(encoded),
+        }
+    }
+
+    /// Rebuilds a board from an untrusted encoding, e.g. one read from a file
+    ///
+    /// # Returns
+    ///
+    /// `Some(board)`, or `None` if two tiles share a cell or a tile lies off the board
+    pub(crate) fn try_from_encoded(encoded: u32) -> Option<Self> {
+        let board = Board {
+            tiles: encoded,
+            blank: 0,
+        };
+        let mut occupied = 0_u16;
+        for val in 0..(BOARD_AREA - 1) {
+            let position = board.get_pos(val);
+            if position >= BOARD_AREA || occupied & (1 << position) != 0 {
+                return None;
+            }
+            occupied |= 1 << position;
+        }
+        Some(Board::from_encoded(encoded))
+    }
+
+    /// Number of distinct values returned by [`Board::rank`] (9!)
+    pub const RANK_COUNT: u32 = 362_880;
+
+    /// Dense index of the board among all arrangements of the nine cells
+    ///
+    /// This is the Lehmer code of the row-major cells (blank included), so
+    /// every board, solvable or not, maps to a unique value below
+    /// [`Board::RANK_COUNT`]. It suits flat arrays indexed by board, and
+    /// [`Board::unrank`] is its inverse.
+    ///
+    /// The ranks span all 9! arrangements rather than the 9!/2 of one parity
+    /// class: a solver can be given a goal of either class, and a table over
+    /// both serves every goal without a parity check on each lookup, at the
+    /// cost of half its slots staying unused. [`Board::reachable_rank`] is the
+    /// dense rank over the boards that reach the default goal.
+    ///
+    /// # Returns
+    ///
+    /// The lexicographic rank of the arrangement, e.g. 0 for `012345678`
+    #[must_use]
+    pub fn rank(self) -> u32 {
+        let mut rank = 0;
+        let mut unused: u32 = (1 << BOARD_AREA) - 1;
+
+        // Mixed-radix Horner scheme: the i-th digit has radix 9 - i
+        for (radix, cell) in (1..=u32::from(BOARD_AREA)).rev().zip(self.into_arr()) {
+            let smaller = (unused & ((1 << cell) - 1)).count_ones();
+            rank = rank * radix + smaller;
+            unused &= !(1 << cell);
+        }
+
+        rank
+    }
+
+    /// Rebuilds the board with the given [rank](Board::rank)
+    ///
+    /// # Returns
+    ///
+    /// `Some(board)`, or `None` if `rank` is not below [`Board::RANK_COUNT`]
+    ///
+    /// # Panics
+    ///
+    /// Never panics in practice: cells always fit in a `u8`
+    #[must_use]
+    pub fn unrank(rank: u32) -> Option<Board> {
+        if rank >= Self::RANK_COUNT {
+            return None;
+        }
+
+        // Peel off the mixed-radix digits, least significant (radix 1) first
+        let mut digits = [0; BOARD_AREA as usize];
+        let mut rest = rank;
+        for (digit, radix) in digits.iter_mut().rev().zip(1..=u32::from(BOARD_AREA)) {
+            *digit = rest % radix;
+            rest /= radix;
+        }
+
+        // Each digit picks the smallest cell value not used yet, skipping `digit` of them
+        let mut unused: u32 = (1 << BOARD_AREA) - 1;
+        let cells = digits.map(|digit| {
+            let mut candidates = unused;
+            for _ in 0..digit {
+                candidates &= candidates - 1;
+            }
+            let cell = candidates.trailing_zeros();
+            unused &= !(1 << cell);
+            u8::try_from(cell).expect("Cells are below 9")
+        });
+
+        Some(Board::from_arr(&cells))
+    }
+
+    /// Number of distinct values returned by [`Board::reachable_rank`] (9!/2)
+    pub const REACHABLE_COUNT: u32 = 181_440;
+
+    /// Dense index of the board among the boards that can reach the goal
+    ///
+    /// Unlike [`Board::rank`], only the parity class of the goal is ranked, so
+    /// the values fill `0..REACHABLE_COUNT` and a table indexed by them holds
+    /// no slot for an unreachable board (see [`database`](crate::database) for
+    /// the scheme).
+    ///
+    /// # Returns
+    ///
+    /// `Some(rank)`, or `None` if the board cannot reach the goal
+    ///
+    /// # Panics
+    ///
+    /// Never panics in practice: ranks are below [`Board::REACHABLE_COUNT`]
+    #[must_use]
+    pub fn reachable_rank(self) -> Option<u32> {
+        database::rank(self).map(|rank| u32::try_from(rank).expect("Ranks fit in a u32"))
+    }
+
+    /// Rebuilds the board with the given [reachable rank](Board::reachable_rank)
+    ///
+    /// # Returns
+    ///
+    /// `Some(board)`, or `None` if `rank` is not below [`Board::REACHABLE_COUNT`]
+    #[must_use]
+    pub fn from_reachable_rank(rank: u32) -> Option<Board> {
+        (rank < Self::REACHABLE_COUNT).then(|| database::unrank(rank as usize))
+    }
+
+    /// Inversion parity of the tiles, ignoring the empty space
+    #[must_use]
+    pub fn parity(self) -> Parity {
+        let tiles: Vec<u8> = self.into_arr().into_iter().filter(|&c| c != 0).collect();
+        let inversions: usize = tiles
+            .iter()
+            .enumerate()
+            .map(|(i, &a)| tiles[i + 1..].iter().filter(|&&b| b < a).count())
+            .sum();
+        if inversions.is_multiple_of(2) {
+            Parity::Even
+        } else {
+            Parity::Odd
+        }
+    }
+
+    /// Whether the board can reach the goal, decided by its parity alone
+    ///
+    /// Half of the 9! arrangements have the other parity and can never be
+    /// solved, so boards from outside the program should be checked before
+    /// a search that would otherwise exhaust the reachable states.
+    #[must_use]
+    pub fn is_solvable(self) -> bool {
+        self.can_reach(Board::default())
+    }
+
+    /// Whether the board can reach `goal`, which holds when their parities match
+    #[must_use]
+    pub fn can_reach(self, goal: Board) -> bool {
+        self.parity() == goal.parity()
+    }
+
+    /// The tile swap that makes an unsolvable board solvable and closest to the goal
+    ///
+    /// Exchanging any two tiles flips the parity; the swap chosen leaves the
+    /// lowest Manhattan distance, ties going to the lowest tile numbers.
+    ///
+    /// # Returns
+    ///
+    /// `Some(swap)`, or `None` if the board is already solvable
+    #[must_use]
+    pub fn solvable_swap(self) -> Option<TileSwap> {
+        if self.is_solvable() {
+            return None;
+        }
+
+        let cells = self.into_arr();
+        let position = |tile: u8| cells.iter().position(|&c| c == tile);
+        (1..BOARD_AREA)
+            .flat_map(|a| (a + 1..BOARD_AREA).map(move |b| (a, b)))
+            .filter_map(|(a, b)| {
+                let mut swapped = cells;
+                swapped.swap(position(a)?, position(b)?);
+                Some(TileSwap {
+                    tiles: (a, b),
+                    board: Board::from_arr(&swapped),
+                })
+            })
+            .min_by_key(|swap| swap.board.heuristic_distance_to_solution())
+    }
+
     fn get_pos(self, value: u8) -> u8 {
         let offset = TILE_BIT_SIZE * value;
-        (self.0.unbounded_shr(u32::from(offset)) % (1 << TILE_BIT_SIZE))
+        (self.tiles.unbounded_shr(u32::from(offset)) % (1 << TILE_BIT_SIZE))
             .try_into()
             .expect("TILE_BIT_SIZE should be less than 8")
     }
@@ -473,6 +1111,7 @@ impl Board {
     ///
     /// The total Manhattan distance as a `u8` value, representing how far
     /// the board is from the solved state.
+    #[must_use]
     pub fn heuristic_distance_to_solution(self) -> u8 {
         let solution = Self::default();
         let mut distance = 0;
@@ -484,6 +1123,61 @@ impl Board {
         distance
     }
 
+    /// Sum of the Manhattan distances of every tile between `self` and `other`
+    ///
+    /// An admissible estimate of the moves between two arbitrary boards;
+    /// [`Board::heuristic_distance_to_solution`] is the special case of the goal.
+    #[must_use]
+    pub fn manhattan_distance_to(self, other: Board) -> u8 {
+        (0..(BOARD_AREA - 1))
+            .map(|val| Self::manhattan_distance(other.get_pos(val), self.get_pos(val)))
+            .sum()
+    }
+
+    /// Manhattan distance of the tile on each cell to its goal cell
+    ///
+    /// The values sum to [`Board::heuristic_distance_to_solution`]; the empty
+    /// space contributes 0.
+    ///
+    /// # Returns
+    ///
+    /// One distance per cell, in reading order
+    #[must_use]
+    pub fn manhattan_contributions(self) -> [u8; BOARD_AREA as usize] {
+        self.manhattan_contributions_to(Self::default())
+    }
+
+    /// Manhattan distance of the tile on each cell to its cell on `goal`
+    ///
+    /// # Returns
+    ///
+    /// One distance per cell, in reading order
+    #[must_use]
+    pub fn manhattan_contributions_to(self, goal: Board) -> [u8; BOARD_AREA as usize] {
+        let solution = goal;
+        let mut contributions = [0; BOARD_AREA as usize];
+
+        for val in 0..(BOARD_AREA - 1) {
+            let cell = self.get_pos(val);
+            contributions[cell as usize] = Self::manhattan_distance(solution.get_pos(val), cell);
+        }
+
+        contributions
+    }
+
+    /// Number of tiles outside their goal cell, the empty space not counted
+    ///
+    /// Every misplaced tile needs at least one move, so the count never
+    /// overestimates the distance to the solution, but it never exceeds the
+    /// Manhattan distance either: it is the weaker of the two heuristics.
+    #[must_use]
+    pub fn misplaced_tiles(self) -> u8 {
+        let solution = Self::default();
+        (0..(BOARD_AREA - 1))
+            .map(|val| u8::from(self.get_pos(val) != solution.get_pos(val)))
+            .sum()
+    }
+
     fn manhattan_distance(pos1: u8, pos2: u8) -> u8 {
         let hdis = (pos2 % BOARD_SIDE).abs_diff(pos1 % BOARD_SIDE);
         let vdis = (pos2 / BOARD_SIDE).abs_diff(pos1 / BOARD_SIDE);
@@ -492,34 +1186,111 @@ impl Board {
     }
 }
 
-impl PartialOrd for Board {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
+/// Default implementation creates a solved board state
+impl Default for Board {
+    fn default() -> Self {
+        *SOLVED
     }
 }
 
-impl Ord for Board {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.heuristic_distance_to_solution()
-            .cmp(&other.heuristic_distance_to_solution())
+/// Hashes the encoding alone, which determines the cached empty space
+impl Hash for Board {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.tiles.hash(state);
     }
 }
 
-/// Default implementation creates a solved board state
-impl Default for Board {
-    fn default() -> Self {
-        Board(*SOLVED_BOARD_ENCODED)
+/// Serializes the board in its digit form (see [`Board::to_digit_string`])
+#[cfg(feature = "serde")]
+impl serde::Serialize for Board {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_digit_string())
+    }
+}
+
+/// Deserializes a board from any form its [`FromStr`] implementation accepts
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Board {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let digits = <std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+        digits.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+impl TryFrom<[u8; BOARD_AREA as usize]> for Board {
+    type Error = BoardError;
+
+    fn try_from(arr: [u8; BOARD_AREA as usize]) -> Result<Self, Self::Error> {
+        Board::try_from_arr(&arr)
+    }
+}
+
+/// Parses a board from its row-major digit form
+///
+/// Accepts the nine digits `0-8` (with `0` as the empty space), optionally
+/// separated by whitespace or commas, e.g. `"123804765"` or `"1,2,3,8,0,4,7,6,5"`.
+/// Each digit must appear exactly once.
+impl FromStr for Board {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut arr = [0; BOARD_AREA as usize];
+        let mut len = 0;
+
+        for c in s.chars().filter(|c| !c.is_whitespace() && *c != ',') {
+            let digit = c
+                .to_digit(10)
+                .and_then(|d| u8::try_from(d).ok())
+                .filter(|&d| d < BOARD_AREA)
+                .ok_or("Invalid board: expected digits 0-8")?;
+
+            if len == arr.len() {
+                return Err("Invalid board: expected exactly 9 digits");
+            }
+            arr[len] = digit;
+            len += 1;
+        }
+
+        if len != arr.len() {
+            return Err("Invalid board: expected exactly 9 digits");
+        }
+
+        Board::try_from_arr(&arr)
+            .map_err(|_| "Invalid board: each digit 0-8 must appear exactly once")
     }
 }
 
 /// Display implementation for pretty-printing the board
 ///
 /// Displays the board as a 3x3 grid with numbers 1-8 and empty space
-/// represented by three spaces.
+/// represented by three spaces. With the `cli` feature, tiles in their goal
+/// position are green and misplaced tiles are red.
 impl Display for Board {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let arr = self.into_arr().into_iter();
-        let target = Board::default().into_arr().into_iter();
+        self.display_towards(Board::default()).fmt(f)
+    }
+}
+
+impl Board {
+    /// Displays the board like its [`Display`] implementation, coloring the
+    /// tiles by their cells on `goal` instead of the spiral goal
+    #[must_use]
+    pub fn display_towards(self, goal: Board) -> GoalDisplay {
+        GoalDisplay { board: self, goal }
+    }
+}
+
+/// A board displayed against a goal, made by [`Board::display_towards`]
+#[derive(Clone, Copy, Debug)]
+pub struct GoalDisplay {
+    board: Board,
+    goal: Board,
+}
+
+impl Display for GoalDisplay {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let arr = self.board.into_arr().into_iter();
+        let target = self.goal.into_arr().into_iter();
 
         for (i, (val, is_in_position)) in arr.zip(target).map(|(a, t)| (a, a == t)).enumerate() {
             if i % BOARD_SIDE as usize == 0 && i != 0 {
@@ -527,12 +1298,7 @@ impl Display for Board {
             }
 
             if val != 0 {
-                let s = format!("{val:2} ");
-                if is_in_position {
-                    write!(f, "{}", s.green().bold())?;
-                } else {
-                    write!(f, "{}", s.red())?;
-                }
+                write!(f, "{}", paint_tile(&format!("{val:2} "), is_in_position))?;
             } else {
                 write!(f, "   ")?;
             }
@@ -542,12 +1308,136 @@ impl Display for Board {
     }
 }
 
-/// Board annotated with the number of steps taken to reach it (g-cost).
+/// Colors a tile label: green and bold when in position, red otherwise
+#[cfg(feature = "cli")]
+pub(crate) fn paint_tile(label: &str, is_in_position: bool) -> String {
+    if is_in_position {
+        label.green().bold().to_string()
+    } else {
+        label.red().to_string()
+    }
+}
+
+/// Without the `cli` feature there are no terminal colors, so labels are kept as-is
+#[cfg(not(feature = "cli"))]
+pub(crate) fn paint_tile(label: &str, _is_in_position: bool) -> String {
+    label.to_owned()
+}
+
+/// Board paired with its heuristic distance, computed once
+///
+/// Orders by the heuristic first and by the board encoding second, so boards
+/// with equal estimates stay distinct (as `BTreeSet` members, for example).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ScoredBoard {
+    board: Board,
+    h: u8,
+}
+
+impl ScoredBoard {
+    /// Scores a board with [`Board::heuristic_distance_to_solution`]
+    #[must_use]
+    pub fn new(board: Board) -> Self {
+        Self {
+            board,
+            h: board.heuristic_distance_to_solution(),
+        }
+    }
+
+    /// The scored board
+    #[must_use]
+    pub fn board(self) -> Board {
+        self.board
+    }
+
+    /// The cached heuristic distance
+    #[must_use]
+    pub fn h(self) -> u8 {
+        self.h
+    }
+}
+
+impl From<Board> for ScoredBoard {
+    fn from(board: Board) -> Self {
+        Self::new(board)
+    }
+}
+
+impl PartialOrd for ScoredBoard {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ScoredBoard {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.h, self.board).cmp(&(other.h, other.board))
+    }
+}
+
+/// Board annotated with the cost of the path taken to reach it (g-cost).
 ///
-/// When ordered, it uses `heuristic_distance_to_solution() + steps` which
+/// The cost is the number of moves unless the solver charges moves by tile
+/// (see [`MoveCosts`](crate::cost::MoveCosts)). When ordered, it uses
+/// `heuristic_distance_to_solution() + steps` which
 /// allows a priority queue to behave like A* with an admissible heuristic.
+/// The heuristic is computed once by [`BoardWithSteps::new`] and cached, so
+/// heap comparisons never recompute it. The node also remembers the move of
+/// the empty space that reached it, so expanding it can skip the move back.
+/// With the `serde` feature it is (de)serialized as `[board, cost, h, move]`.
 #[derive(PartialEq, Eq, Default, Clone)]
-pub struct BoardWithSteps(pub Board, pub usize);
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BoardWithSteps(pub Board, pub usize, u8, Option<Direction>);
+
+impl BoardWithSteps {
+    /// Creates a node, computing the board's heuristic distance once
+    ///
+    /// # Arguments
+    ///
+    /// * `board` - The board of the node
+    /// * `steps` - Cost of the moves taken to reach it
+    #[must_use]
+    pub fn new(board: Board, steps: usize) -> Self {
+        Self(board, steps, board.heuristic_distance_to_solution(), None)
+    }
+
+    /// Creates a node whose heuristic distance was computed by another heuristic
+    ///
+    /// # Arguments
+    ///
+    /// * `board` - The board of the node
+    /// * `steps` - Cost of the moves taken to reach it
+    /// * `h` - Estimated cost from the board to the goal
+    #[must_use]
+    pub fn with_h(board: Board, steps: usize, h: u8) -> Self {
+        Self(board, steps, h, None)
+    }
+
+    /// Records that the empty space moved in `direction` to reach this node
+    #[must_use]
+    pub fn reached_by(mut self, direction: Direction) -> Self {
+        self.3 = Some(direction);
+        self
+    }
+
+    /// The move of the empty space that reached this node, `None` for a start node
+    #[must_use]
+    pub fn last_move(&self) -> Option<Direction> {
+        self.3
+    }
+
+    /// The cached heuristic distance of the board (h-cost)
+    #[must_use]
+    pub fn h(&self) -> u8 {
+        self.2
+    }
+
+    /// Estimated total cost through this node, `steps + h` (f-cost)
+    #[must_use]
+    pub fn f(&self) -> usize {
+        self.1 + usize::from(self.2)
+    }
+}
 
 impl PartialOrd for BoardWithSteps {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
@@ -557,7 +1447,134 @@ impl PartialOrd for BoardWithSteps {
 
 impl Ord for BoardWithSteps {
     fn cmp(&self, other: &Self) -> Ordering {
-        (self.0.heuristic_distance_to_solution() as usize + self.1)
-            .cmp(&(other.0.heuristic_distance_to_solution() as usize + other.1))
+        self.f().cmp(&other.f())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use rand::seq::IndexedRandom;
+
+    use super::*;
+
+    #[test]
+    fn rank_and_unrank_are_inverse_over_every_arrangement() {
+        for rank in 0..Board::RANK_COUNT {
+            let board = Board::unrank(rank).unwrap();
+            assert_eq!(board.rank(), rank);
+        }
+        assert_eq!(Board::unrank(Board::RANK_COUNT), None);
+        assert_eq!(Board::from_arr(&[0, 1, 2, 3, 4, 5, 6, 7, 8]).rank(), 0);
+    }
+
+    #[test]
+    fn reachable_ranks_are_dense_over_the_boards_that_reach_the_goal() {
+        for rank in 0..Board::REACHABLE_COUNT {
+            let board = Board::from_reachable_rank(rank).unwrap();
+            assert!(board.is_solvable());
+            assert_eq!(board.reachable_rank(), Some(rank));
+        }
+        assert_eq!(Board::from_reachable_rank(Board::REACHABLE_COUNT), None);
+
+        let unsolvable = Board::from_arr(&[2, 1, 3, 8, 0, 4, 7, 6, 5]);
+        assert_eq!(unsolvable.reachable_rank(), None);
+    }
+
+    #[test]
+    fn moves_keep_the_parity_and_tile_swaps_flip_it() {
+        let goal = Board::default();
+        assert_eq!(goal.parity(), Parity::Odd);
+
+        let solvable = (0..Board::RANK_COUNT)
+            .filter_map(Board::unrank)
+            .filter(|board| board.is_solvable())
+            .count();
+        assert_eq!(solvable, Board::REACHABLE_COUNT as usize);
+
+        for rank in (0..Board::RANK_COUNT).step_by(97) {
+            let board = Board::unrank(rank).unwrap();
+            assert_eq!(board.is_solvable(), board.parity() == goal.parity());
+            for (_, next) in board.successors() {
+                assert_eq!(next.parity(), board.parity());
+            }
+
+            let mut cells = board.into_arr();
+            let tiles: Vec<usize> = (0..cells.len()).filter(|&i| cells[i] != 0).collect();
+            cells.swap(tiles[0], tiles[1]);
+            assert_ne!(Board::from_arr(&cells).parity(), board.parity());
+        }
+    }
+
+    #[test]
+    fn apply_moves_replays_a_walk_and_its_reverse_undoes_it() {
+        let mut rng = StdRng::seed_from_u64(555);
+        let mut board = Board::default();
+        let mut path = vec![board];
+        let mut moves = Vec::new();
+        for _ in 0..200 {
+            let (direction, next) = *board
+                .successors()
+                .collect::<Vec<_>>()
+                .choose(&mut rng)
+                .unwrap();
+            moves.push(direction);
+            path.push(next);
+            board = next;
+        }
+
+        assert_eq!(Board::default().apply_moves(&moves), Ok(board));
+        assert_eq!(Board::default().apply_moves(&[]), Ok(Board::default()));
+        let undo: Vec<Direction> = moves
+            .iter()
+            .rev()
+            .copied()
+            .map(Direction::opposite)
+            .collect();
+        assert_eq!(board.apply_moves(&undo), Ok(Board::default()));
+        for (index, &start) in path.iter().enumerate() {
+            assert_eq!(start.apply_moves(&moves[index..]), Ok(board));
+        }
+    }
+
+    #[test]
+    fn apply_moves_reports_the_first_illegal_move() {
+        // The empty space starts in the centre, so the second `Up` leaves the board
+        let moves = [Left, Up, Up, Down];
+        assert_eq!(
+            Board::default().apply_moves(&moves),
+            Err(MoveSequenceError {
+                index: 2,
+                error: MoveError::OffBoard {
+                    from: 0,
+                    direction: Up,
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn parse_moves_reads_back_formatted_moves() {
+        assert_eq!(format_moves(&ALL_DIRECTIONS), "UDLR");
+        let moves = [Up, Left, Down, Down, Right, Up];
+        assert_eq!(format_moves(&moves), "ULDDRU");
+        assert_eq!(parse_moves(&format_moves(&moves)), Ok(moves.to_vec()));
+        assert_eq!(parse_moves("u, l d\nD r,U"), Ok(moves.to_vec()));
+        assert_eq!(parse_moves(""), Ok(Vec::new()));
+
+        for direction in ALL_DIRECTIONS {
+            assert_eq!(direction.to_string().parse(), Ok(direction));
+            assert_eq!(direction.name().parse(), Ok(direction));
+        }
+    }
+
+    #[test]
+    fn parse_moves_rejects_other_letters() {
+        assert!(parse_moves("ULX").is_err());
+        assert!(parse_moves("UP").is_err());
+        assert!(parse_moves("U-D").is_err());
+        assert!("X".parse::<Direction>().is_err());
+        assert!("".parse::<Direction>().is_err());
     }
 }
@@ -0,0 +1,332 @@
+//! # 15-Puzzle Board Module
+//!
+//! [`Board15`] is the packed 64-bit counterpart of [`Board`](crate::board::Board)
+//! for the 4×4 puzzle. It uses the same scheme: instead of storing "what tile
+//! is at each position", it stores "at what position is each tile", 4 bits per
+//! tile, and the empty space is the one position no tile claims.
+//!
+//! ### Encoding Details
+//!
+//! - Each tile (1-15) gets 4 bits to store its position (0-15)
+//! - Tile 1's position is stored in bits 0-3, tile 15's in bits 56-59
+//! - Bits 60-63 are always zero
+//!
+//! Like `Board`, a `Board15` is `Copy`, hashes as a single integer and moves
+//! the empty space with a few bit operations, which is what 15-puzzle searches
+//! and pattern databases need from their states. It is the
+//! [`Puzzle15`](crate::sized::Puzzle15) that [`sized::solve`](crate::sized::solve)
+//! searches; [`SizedBoard<4>`] converts to and from it without loss and lends
+//! it its parsing and solvability test.
+
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use rand::{Rng, rng, seq::IndexedRandom};
+
+use crate::board::{ALL_DIRECTIONS, Direction, MoveError, paint_tile};
+use crate::sized::{Puzzle, SizedBoard};
+
+use Direction::{Down, Left, Right, Up};
+
+/// The side length of the 15-puzzle (4x4 grid)
+const SIDE: u8 = 4;
+
+/// The total number of positions on the board (16 positions)
+const AREA: u8 = SIDE * SIDE;
+
+/// Number of bits used to represent each tile position
+const TILE_BIT_SIZE: u8 = 4;
+
+/// The solved board: the spiral of [`SizedBoard::goal`]
+#[rustfmt::skip]
+const SOLVED_BOARD: [u8; AREA as usize] = [
+     1,  2,  3,  4,
+    12, 13, 14,  5,
+    11,  0, 15,  6,
+    10,  9,  8,  7,
+];
+
+/// Goal position of each tile, indexed by tile number (the empty space at 0)
+const GOAL_POSITIONS: [u8; AREA as usize] = goal_positions();
+
+/// Inverts [`SOLVED_BOARD`] at compile time
+const fn goal_positions() -> [u8; AREA as usize] {
+    let mut positions = [0; AREA as usize];
+    let mut pos = 0;
+    while pos < AREA {
+        positions[SOLVED_BOARD[pos as usize] as usize] = pos;
+        pos += 1;
+    }
+    positions
+}
+
+/// Represents a 15-puzzle board state
+///
+/// ### Bit Layout
+///
+/// ```text
+/// Bits:  63-60  59-56  55-52  ...  7-4    3-0
+/// Tile:    -     15     14    ...   2      1
+/// Value:   0   pos15  pos14   ... pos2   pos1
+/// ```
+///
+/// Each 4-bit field stores the position (0-15) where that tile is located,
+/// counted row by row from the top-left corner.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Board15(u64);
+
+impl Default for Board15 {
+    fn default() -> Self {
+        Self::from_arr(&SOLVED_BOARD)
+    }
+}
+
+impl Board15 {
+    /// Generates a random board by performing random moves from the solved state
+    #[must_use]
+    pub fn random_with_solution(steps: usize) -> Self {
+        Self::random_with_solution_using(steps, &mut rng())
+    }
+
+    /// Generates a random board, drawing the scramble moves from `rng`
+    ///
+    /// # Panics
+    ///
+    /// Never panics in practice: the direction list is never empty
+    pub fn random_with_solution_using<R: Rng + ?Sized>(steps: usize, rng: &mut R) -> Self {
+        let mut board = Self::default();
+
+        for _ in 0..steps {
+            let direction = *ALL_DIRECTIONS
+                .choose(rng)
+                .expect("This should never happen");
+
+            if let Ok(b) = board.move_space(direction) {
+                board = b;
+            }
+        }
+
+        board
+    }
+
+    /// Converts the compact board representation to a row-major array
+    ///
+    /// # Returns
+    ///
+    /// A 16-element array where each position contains the tile number,
+    /// with 0 representing the empty space
+    #[must_use]
+    pub fn into_arr(self) -> [u8; AREA as usize] {
+        let mut arr = [0; AREA as usize];
+
+        for val in 0..(AREA - 1) {
+            arr[self.get_pos(val) as usize] = val + 1;
+        }
+
+        arr
+    }
+
+    /// Creates a board from a row-major array
+    ///
+    /// Assumes that the array is valid (contains numbers 0-15 with no duplicates).
+    ///
+    /// # Panics
+    ///
+    /// Never panics in practice: array positions always fit in a `u8`
+    #[must_use]
+    pub fn from_arr(arr: &[u8; AREA as usize]) -> Self {
+        let mut board = Board15(0);
+
+        for (pos, &val) in arr.iter().enumerate() {
+            if val != 0 {
+                board.set_value(pos.try_into().expect("Should be less than 256"), val - 1);
+            }
+        }
+
+        board
+    }
+
+    /// Returns the raw 64-bit encoding of the board
+    #[must_use]
+    pub fn encoded(self) -> u64 {
+        self.0
+    }
+
+    /// Checks if the board is in the solved state
+    #[must_use]
+    pub fn is_solved(self) -> bool {
+        self == Self::default()
+    }
+
+    /// Validates if a movement of the empty space is possible from `position`
+    fn is_valid_movement(position: u8, direction: Direction) -> bool {
+        match direction {
+            Up => (position / SIDE) != 0,
+            Down => (position / SIDE) != SIDE - 1,
+            Left => !position.is_multiple_of(SIDE),
+            Right => (position % SIDE) != SIDE - 1,
+        }
+    }
+
+    /// Finds the current position of the empty space on the board
+    ///
+    /// The empty space is the first position no tile's field points at.
+    ///
+    /// # Panics
+    ///
+    /// Never panics in practice: positions always fit in a `u8`
+    #[must_use]
+    pub fn find_space_position(self) -> u8 {
+        let mut idx: u32 = 0;
+
+        for val in 0..(AREA - 1) {
+            idx |= 1 << self.get_pos(val);
+        }
+
+        idx.trailing_ones()
+            .try_into()
+            .expect("Should be less than 256")
+    }
+
+    /// Calculates the new position after moving in a specific direction
+    fn calculate_new_position(from: u8, direction: Direction) -> Result<u8, MoveError> {
+        if !Self::is_valid_movement(from, direction) {
+            return Err(MoveError::OffBoard { from, direction });
+        }
+
+        Ok(match direction {
+            Up => from - SIDE,
+            Down => from + SIDE,
+            Left => from - 1,
+            Right => from + 1,
+        })
+    }
+
+    /// Gets the tile at a specific position
+    ///
+    /// # Returns
+    ///
+    /// `Some(tile)` with the tile number (1-15), or `None` if the position is
+    /// the empty space or lies outside the board
+    #[must_use]
+    pub fn get_tile(self, position: u8) -> Option<u8> {
+        if position >= AREA {
+            return None;
+        }
+
+        (0..(AREA - 1))
+            .find(|&val| self.get_pos(val) == position)
+            .map(|val| val + 1)
+    }
+
+    /// Places tile `val + 1` at position `p`
+    fn set_value(&mut self, p: u8, val: u8) {
+        let ones = (1 << TILE_BIT_SIZE) - 1;
+        let mask = ones << (TILE_BIT_SIZE * val);
+        self.0 &= !mask;
+        self.0 |= u64::from(p) << (TILE_BIT_SIZE * val);
+    }
+
+    /// Position of tile `value + 1`
+    fn get_pos(self, value: u8) -> u8 {
+        let offset = TILE_BIT_SIZE * value;
+        (self.0.unbounded_shr(u32::from(offset)) % (1 << TILE_BIT_SIZE))
+            .try_into()
+            .expect("TILE_BIT_SIZE should be less than 8")
+    }
+
+    /// Moves the empty space in the specified direction
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MoveError::OffBoard`] if the empty space would leave the board,
+    /// and [`MoveError::MissingTile`] if the board encoding is corrupt
+    pub fn move_space(mut self, direction: Direction) -> Result<Self, MoveError> {
+        let space_position = self.find_space_position();
+        let space_new_position = Self::calculate_new_position(space_position, direction)?;
+        let tile_to_move = self
+            .get_tile(space_new_position)
+            .ok_or(MoveError::MissingTile {
+                position: space_new_position,
+            })?;
+
+        self.set_value(space_position, tile_to_move - 1);
+
+        Ok(self)
+    }
+
+    /// Sum of the Manhattan distances of the tiles to their goal positions
+    #[must_use]
+    pub fn heuristic_distance_to_solution(self) -> u8 {
+        (0..(AREA - 1))
+            .map(|val| {
+                let pos = self.get_pos(val);
+                let goal = GOAL_POSITIONS[usize::from(val + 1)];
+                (pos / SIDE).abs_diff(goal / SIDE) + (pos % SIDE).abs_diff(goal % SIDE)
+            })
+            .sum()
+    }
+}
+
+impl From<SizedBoard<4>> for Board15 {
+    fn from(board: SizedBoard<4>) -> Self {
+        let mut arr = [0; AREA as usize];
+        for (cell, &tile) in arr.iter_mut().zip(board.cells().iter().flatten()) {
+            *cell = tile;
+        }
+        Self::from_arr(&arr)
+    }
+}
+
+impl From<Board15> for SizedBoard<4> {
+    fn from(board: Board15) -> Self {
+        let arr = board.into_arr();
+        let cells = std::array::from_fn(|row| std::array::from_fn(|col| arr[row * 4 + col]));
+        Self::from_cells(cells).expect("A board holds every tile once")
+    }
+}
+
+impl Puzzle for Board15 {
+    fn goal() -> Self {
+        Self::default()
+    }
+
+    fn move_space(self, direction: Direction) -> Result<Self, MoveError> {
+        self.move_space(direction)
+    }
+
+    fn manhattan(&self) -> usize {
+        usize::from(self.heuristic_distance_to_solution())
+    }
+
+    fn is_solvable(&self) -> bool {
+        SizedBoard::<4>::from(*self).is_solvable()
+    }
+}
+
+/// Parses the tile numbers in reading order, as [`SizedBoard`] does
+impl FromStr for Board15 {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse::<SizedBoard<4>>().map(Self::from)
+    }
+}
+
+impl Display for Board15 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (val, target)) in self.into_arr().into_iter().zip(SOLVED_BOARD).enumerate() {
+            if i % SIDE as usize == 0 && i != 0 {
+                writeln!(f)?;
+            }
+
+            if val != 0 {
+                write!(f, "{}", paint_tile(&format!("{val:2} "), val == target))?;
+            } else {
+                write!(f, "   ")?;
+            }
+        }
+
+        Ok(())
+    }
+}
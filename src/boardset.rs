@@ -0,0 +1,554 @@
+//! # Board Set Module
+//!
+//! A canonical file format for lists of boards, shared by `o8 generate`,
+//! `o8 solve --file` and `o8 benchmark --boards`, so the exact instances of a
+//! run can be saved, edited by hand and fed back in.
+//!
+//! ## Text format
+//!
+//! One board per line in digit form, optionally followed by `key=value`
+//! metadata. Lines starting with `#` are comments, except `#!` lines before
+//! the first board, which hold metadata of the whole set:
+//!
+//! ```text
+//! # o8 board set
+//! #! seed=42
+//! 867254301 seed=42 distance=30 difficulty=expert
+//! 123804765
+//! ```
+//!
+//! Board metadata keys are `seed`, `distance` and `difficulty`; the only set
+//! key is `seed`. Parsing is strict: unknown or repeated keys, malformed
+//! values and a `difficulty` that contradicts `distance` are errors reported
+//! with their line number.
+//!
+//! ## Built-in sets
+//!
+//! Named sets are embedded in the crate and selected with `builtin:NAME`
+//! wherever a board set file is accepted:
+//!
+//! - `builtin:hard`: all 148 boards at the maximum distance from the goal
+//!   (30 moves, God's number for the spiral goal), the standard stress cases.
+//!
+//! ## JSON format
+//!
+//! With the `serde` feature, the same data can be stored as JSON (chosen by
+//! the `.json` extension):
+//!
+//! ```text
+//! {"format":"o8-boards","version":1,"seed":42,
+//!  "boards":[{"board":"867254301","seed":42,"distance":30,"difficulty":"expert"}]}
+//! ```
+
+use std::fmt::{self, Display, Write as _};
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::board::Board;
+use crate::database::Difficulty;
+
+/// Value of the `format` field of JSON board sets
+#[cfg(feature = "serde")]
+const JSON_FORMAT: &str = "o8-boards";
+
+/// Version of the JSON board set format
+#[cfg(feature = "serde")]
+const JSON_VERSION: u32 = 1;
+
+/// Prefix selecting a built-in set instead of a file
+const BUILTIN_PREFIX: &str = "builtin:";
+
+/// Built-in board sets, by name, in the text format
+const BUILTIN_SETS: [(&str, &str); 1] = [("hard", include_str!("boardsets/hard.txt"))];
+
+/// One board of a set, with its optional metadata
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BoardEntry {
+    /// The board itself
+    pub board: Board,
+    /// Seed the board was generated with, if known
+    pub seed: Option<u64>,
+    /// Exact number of moves to the goal, if known
+    pub distance: Option<u8>,
+    /// Difficulty grade, if known
+    pub difficulty: Option<Difficulty>,
+}
+
+impl BoardEntry {
+    /// Creates an entry without metadata
+    #[must_use]
+    pub fn new(board: Board) -> Self {
+        Self {
+            board,
+            seed: None,
+            distance: None,
+            difficulty: None,
+        }
+    }
+
+    /// Checks that `difficulty` agrees with `distance` when both are present
+    fn validate(&self) -> Result<(), String> {
+        match (self.distance, self.difficulty) {
+            (Some(distance), Some(difficulty))
+                if Difficulty::from_distance(distance) != difficulty =>
+            {
+                Err(format!(
+                    "difficulty `{}` does not match distance {distance}",
+                    difficulty.name()
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// An ordered list of boards with optional metadata
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BoardSet {
+    /// Seed the whole set was generated with, if known
+    pub seed: Option<u64>,
+    /// The boards, in order
+    pub entries: Vec<BoardEntry>,
+}
+
+/// Encoding of a board set file
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BoardSetFormat {
+    /// One board per line (see the module documentation)
+    Text,
+    /// A JSON document (requires the `serde` feature)
+    Json,
+}
+
+impl BoardSetFormat {
+    /// Picks the format from a file extension: `.json` is JSON, anything else is text
+    #[must_use]
+    pub fn from_path(path: &Path) -> Self {
+        if path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+        {
+            BoardSetFormat::Json
+        } else {
+            BoardSetFormat::Text
+        }
+    }
+}
+
+/// Error raised while parsing a board set
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BoardSetError {
+    /// 1-based line of the error, when known
+    pub line: Option<usize>,
+    /// Description of the problem
+    pub message: String,
+}
+
+impl BoardSetError {
+    /// Creates an error at a known line
+    fn at(line: usize, message: impl Into<String>) -> Self {
+        Self {
+            line: Some(line),
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for BoardSetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {line}: {}", self.message),
+            None => f.write_str(&self.message),
+        }
+    }
+}
+
+impl std::error::Error for BoardSetError {}
+
+impl From<BoardSetError> for io::Error {
+    fn from(error: BoardSetError) -> Self {
+        io::Error::new(io::ErrorKind::InvalidData, error)
+    }
+}
+
+impl BoardSet {
+    /// Creates a set from plain boards
+    ///
+    /// # Arguments
+    ///
+    /// * `boards` - The boards, in order
+    /// * `seed` - Seed the boards were generated with, if any
+    #[must_use]
+    pub fn from_boards(boards: &[Board], seed: Option<u64>) -> Self {
+        Self {
+            seed,
+            entries: boards.iter().copied().map(BoardEntry::new).collect(),
+        }
+    }
+
+    /// The boards of the set, in order, without their metadata
+    #[must_use]
+    pub fn boards(&self) -> Vec<Board> {
+        self.entries.iter().map(|entry| entry.board).collect()
+    }
+
+    /// Parses the text format
+    ///
+    /// # Errors
+    ///
+    /// Returns the first problem found, with its line number
+    pub fn parse_text(text: &str) -> Result<Self, BoardSetError> {
+        let mut set = BoardSet::default();
+
+        for (index, raw) in text.lines().enumerate() {
+            let line = index + 1;
+            let content = raw.trim();
+
+            if let Some(header) = content.strip_prefix("#!") {
+                if !set.entries.is_empty() {
+                    return Err(BoardSetError::at(
+                        line,
+                        "set metadata (`#!`) must come before the first board",
+                    ));
+                }
+                for (key, value) in fields(header, line)? {
+                    match key {
+                        "seed" if set.seed.is_none() => set.seed = Some(parse_value(value, line)?),
+                        "seed" => return Err(BoardSetError::at(line, "repeated key `seed`")),
+                        _ => {
+                            return Err(BoardSetError::at(
+                                line,
+                                format!("unknown set key `{key}` (expected seed)"),
+                            ));
+                        }
+                    }
+                }
+                continue;
+            }
+            if content.is_empty() || content.starts_with('#') {
+                continue;
+            }
+
+            let (board, metadata) = content
+                .split_once(char::is_whitespace)
+                .unwrap_or((content, ""));
+            let board = board.parse().map_err(|e| BoardSetError::at(line, e))?;
+            let mut entry = BoardEntry::new(board);
+
+            for (key, value) in fields(metadata, line)? {
+                let repeated = match key {
+                    "seed" => entry.seed.replace(parse_value(value, line)?).is_some(),
+                    "distance" => entry.distance.replace(parse_value(value, line)?).is_some(),
+                    "difficulty" => entry
+                        .difficulty
+                        .replace(value.parse().map_err(|e| BoardSetError::at(line, e))?)
+                        .is_some(),
+                    _ => {
+                        return Err(BoardSetError::at(
+                            line,
+                            format!(
+                                "unknown board key `{key}` (expected seed, distance or difficulty)"
+                            ),
+                        ));
+                    }
+                };
+                if repeated {
+                    return Err(BoardSetError::at(line, format!("repeated key `{key}`")));
+                }
+            }
+            entry.validate().map_err(|e| BoardSetError::at(line, e))?;
+            set.entries.push(entry);
+        }
+
+        Ok(set)
+    }
+
+    /// Renders the text format
+    #[must_use]
+    pub fn to_text(&self) -> String {
+        let mut text = String::from("# o8 board set\n");
+        if let Some(seed) = self.seed {
+            let _ = writeln!(text, "#! seed={seed}");
+        }
+        for entry in &self.entries {
+            text.push_str(&entry.board.to_digit_string());
+            if let Some(seed) = entry.seed {
+                let _ = write!(text, " seed={seed}");
+            }
+            if let Some(distance) = entry.distance {
+                let _ = write!(text, " distance={distance}");
+            }
+            if let Some(difficulty) = entry.difficulty {
+                let _ = write!(text, " difficulty={}", difficulty.name());
+            }
+            text.push('\n');
+        }
+        text
+    }
+
+    /// Parses the JSON format
+    ///
+    /// # Errors
+    ///
+    /// Returns the first problem found: syntax errors name their line and
+    /// column, invalid entries their index in `boards`
+    #[cfg(feature = "serde")]
+    pub fn parse_json(text: &str) -> Result<Self, BoardSetError> {
+        let raw: json::RawSet = serde_json::from_str(text).map_err(|e| BoardSetError {
+            line: None,
+            message: e.to_string(),
+        })?;
+        raw.validate()
+    }
+
+    /// Renders the JSON format
+    #[cfg(feature = "serde")]
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        let raw = json::RawSet::from(self);
+        let mut text = serde_json::to_string_pretty(&raw).unwrap_or_default();
+        text.push('\n');
+        text
+    }
+}
+
+/// Splits `key=value` pairs separated by whitespace
+fn fields(text: &str, line: usize) -> Result<Vec<(&str, &str)>, BoardSetError> {
+    text.split_whitespace()
+        .map(|field| {
+            field
+                .split_once('=')
+                .filter(|(key, value)| !key.is_empty() && !value.is_empty())
+                .ok_or_else(|| {
+                    BoardSetError::at(line, format!("expected `key=value`, found `{field}`"))
+                })
+        })
+        .collect()
+}
+
+/// Parses a numeric metadata value
+fn parse_value<T: std::str::FromStr>(value: &str, line: usize) -> Result<T, BoardSetError> {
+    value
+        .parse()
+        .map_err(|_| BoardSetError::at(line, format!("invalid number `{value}`")))
+}
+
+/// Names of the built-in board sets
+pub fn builtin_names() -> impl Iterator<Item = &'static str> {
+    BUILTIN_SETS.iter().map(|(name, _)| *name)
+}
+
+/// Loads a built-in board set by name
+///
+/// # Returns
+///
+/// `Some(set)` for a known name, `None` otherwise
+///
+/// # Panics
+///
+/// Never panics in practice: the embedded sets are valid
+#[must_use]
+pub fn builtin_board_set(name: &str) -> Option<BoardSet> {
+    BUILTIN_SETS
+        .iter()
+        .find(|(builtin, _)| *builtin == name)
+        .map(|(_, text)| BoardSet::parse_text(text).expect("built-in board sets are valid"))
+}
+
+/// Where a board set comes from: a file or a built-in set
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BoardSource {
+    /// A board set file, in the format picked by its extension
+    File(PathBuf),
+    /// A built-in set, written `builtin:NAME`
+    Builtin(&'static str),
+}
+
+impl BoardSource {
+    /// Loads the board set
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is not a valid board set
+    pub fn load(&self) -> io::Result<BoardSet> {
+        match self {
+            BoardSource::File(path) => read_board_set(path),
+            BoardSource::Builtin(name) => builtin_board_set(name)
+                .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "unknown built-in set")),
+        }
+    }
+}
+
+impl FromStr for BoardSource {
+    type Err = &'static str;
+
+    /// Parses `builtin:NAME` as a built-in set and anything else as a file path
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.strip_prefix(BUILTIN_PREFIX) {
+            Some(name) => builtin_names()
+                .find(|builtin| *builtin == name)
+                .map(BoardSource::Builtin)
+                .ok_or("Unknown built-in board set: expected builtin:hard"),
+            None => Ok(BoardSource::File(PathBuf::from(s))),
+        }
+    }
+}
+
+impl Display for BoardSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BoardSource::File(path) => write!(f, "{}", path.display()),
+            BoardSource::Builtin(name) => write!(f, "{BUILTIN_PREFIX}{name}"),
+        }
+    }
+}
+
+/// Reads a board set, picking the format from the file extension
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or is not a valid board set;
+/// parse errors are reported as [`io::ErrorKind::InvalidData`] with the
+/// offending line in the message
+pub fn read_board_set(path: &Path) -> io::Result<BoardSet> {
+    let text = fs::read_to_string(path)?;
+    match BoardSetFormat::from_path(path) {
+        BoardSetFormat::Text => Ok(BoardSet::parse_text(&text)?),
+        #[cfg(feature = "serde")]
+        BoardSetFormat::Json => Ok(BoardSet::parse_json(&text)?),
+        #[cfg(not(feature = "serde"))]
+        BoardSetFormat::Json => Err(json_unsupported()),
+    }
+}
+
+/// Writes a board set, picking the format from the file extension
+///
+/// # Errors
+///
+/// Returns any I/O error raised while writing the file
+pub fn write_board_set(path: &Path, set: &BoardSet) -> io::Result<()> {
+    let text = match BoardSetFormat::from_path(path) {
+        BoardSetFormat::Text => set.to_text(),
+        #[cfg(feature = "serde")]
+        BoardSetFormat::Json => set.to_json(),
+        #[cfg(not(feature = "serde"))]
+        BoardSetFormat::Json => return Err(json_unsupported()),
+    };
+    let mut file = BufWriter::new(File::create(path)?);
+    file.write_all(text.as_bytes())?;
+    file.flush()
+}
+
+/// Error returned for JSON board sets when the `serde` feature is disabled
+#[cfg(not(feature = "serde"))]
+fn json_unsupported() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        "JSON board sets require the `serde` feature",
+    )
+}
+
+/// Serde shapes of the JSON format
+#[cfg(feature = "serde")]
+mod json {
+    use serde::{Deserialize, Serialize};
+
+    use super::{BoardEntry, BoardSet, BoardSetError, JSON_FORMAT, JSON_VERSION};
+    use crate::database::Difficulty;
+
+    /// A whole JSON board set
+    #[derive(Serialize, Deserialize)]
+    #[serde(deny_unknown_fields)]
+    pub(super) struct RawSet {
+        format: String,
+        version: u32,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        seed: Option<u64>,
+        boards: Vec<RawEntry>,
+    }
+
+    /// One board of a JSON board set
+    #[derive(Serialize, Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct RawEntry {
+        board: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        seed: Option<u64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        distance: Option<u8>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        difficulty: Option<String>,
+    }
+
+    impl RawSet {
+        /// Checks the header and every entry, naming the offending board on error
+        pub(super) fn validate(self) -> Result<BoardSet, BoardSetError> {
+            let error = |message: String| BoardSetError {
+                line: None,
+                message,
+            };
+            if self.format != JSON_FORMAT {
+                return Err(error(format!(
+                    "expected format `{JSON_FORMAT}`, found `{}`",
+                    self.format
+                )));
+            }
+            if self.version != JSON_VERSION {
+                return Err(error(format!(
+                    "unsupported board set version {}",
+                    self.version
+                )));
+            }
+
+            let entries = self
+                .boards
+                .into_iter()
+                .enumerate()
+                .map(|(index, raw)| {
+                    let context = |e: &str| error(format!("boards[{index}]: {e}"));
+                    let difficulty = raw
+                        .difficulty
+                        .as_deref()
+                        .map(str::parse::<Difficulty>)
+                        .transpose()
+                        .map_err(context)?;
+                    let entry = BoardEntry {
+                        board: raw.board.parse().map_err(context)?,
+                        seed: raw.seed,
+                        distance: raw.distance,
+                        difficulty,
+                    };
+                    entry.validate().map_err(|e| context(&e))?;
+                    Ok(entry)
+                })
+                .collect::<Result<_, _>>()?;
+
+            Ok(BoardSet {
+                seed: self.seed,
+                entries,
+            })
+        }
+    }
+
+    impl From<&BoardSet> for RawSet {
+        fn from(set: &BoardSet) -> Self {
+            Self {
+                format: JSON_FORMAT.to_owned(),
+                version: JSON_VERSION,
+                seed: set.seed,
+                boards: set
+                    .entries
+                    .iter()
+                    .map(|entry| RawEntry {
+                        board: entry.board.to_digit_string(),
+                        seed: entry.seed,
+                        distance: entry.distance,
+                        difficulty: entry.difficulty.map(|d| d.name().to_owned()),
+                    })
+                    .collect(),
+            }
+        }
+    }
+}
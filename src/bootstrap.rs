@@ -0,0 +1,272 @@
+//! # Bootstrap Module
+//!
+//! Learns a heuristic by bootstrapping (Jabbari Arfaee, Zilles and Holte
+//! 2011): solve random boards with the current heuristic, record the cost
+//! observed from every board on each solution, and solve harder boards with
+//! the improved heuristic in the next iteration. Easy boards are solved
+//! quickly even by a weak heuristic, and what they teach makes the harder
+//! ones affordable.
+//!
+//! The learned [`LearnedHeuristic`] is a lookup table indexed by two
+//! features of a board, its Manhattan distance and its number of misplaced
+//! tiles. Each cell keeps the lowest cost observed for a board with those
+//! features, and the estimate is never below the Manhattan distance. The
+//! solutions that feed the table are searched with the heuristic learned so
+//! far and are only upper bounds on the exact costs, and the boards seen are
+//! a sample of each cell, so the learned heuristic is not guaranteed to be
+//! admissible: it trades a little solution length for far fewer expansions.
+//!
+//! ## On-disk format
+//!
+//! ```text
+//! magic "O8LH" | version u8 | checksum u64 LE | rows u8 | columns u8 | entries
+//! ```
+//!
+//! One byte per cell, row by Manhattan distance, `u8::MAX` for cells never
+//! observed; the checksum is the FNV-1a hash of everything after it.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::board::{BOARD_AREA, BOARD_SIDE, Board};
+use crate::heuristic::Heuristic;
+use crate::persist::{Format, read_checked, write_atomic};
+use crate::search_strategies::HeuristicSearchStrategy;
+use crate::solver::Solver;
+
+/// Header of a learned heuristic file
+const FORMAT: Format = Format {
+    magic: *b"O8LH",
+    version: 2,
+    name: "learned heuristic",
+};
+
+/// Marker for cells without an observed cost
+const UNKNOWN: u8 = u8::MAX;
+
+/// Number of tiles, the empty space not counted
+const TILES: u8 = BOARD_AREA - 1;
+
+/// One row per Manhattan distance, which is at most the largest distance of every tile
+const ROWS: u8 = TILES * 2 * (BOARD_SIDE - 1) + 1;
+
+/// One column per number of misplaced tiles
+const COLUMNS: u8 = TILES + 1;
+
+/// Number of cells of the table
+const CELLS: usize = ROWS as usize * COLUMNS as usize;
+
+/// Iterations of [`bootstrap`] unless told otherwise
+pub const DEFAULT_ITERATIONS: usize = 8;
+
+/// Boards solved per iteration unless told otherwise
+pub const DEFAULT_BOARDS: usize = 200;
+
+/// Scramble steps of the first iteration's boards unless told otherwise
+pub const DEFAULT_START_STEPS: usize = 10;
+
+/// Scramble steps added per iteration unless told otherwise
+pub const DEFAULT_STEP_INCREMENT: usize = 10;
+
+/// Nodes one solve may explore unless told otherwise
+pub const DEFAULT_NODE_LIMIT: usize = 20_000;
+
+/// A heuristic learned from observed solution costs
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LearnedHeuristic {
+    /// Lowest observed cost per cell, indexed by [`cell`]
+    costs: Vec<u8>,
+}
+
+impl Default for LearnedHeuristic {
+    fn default() -> Self {
+        Self {
+            costs: vec![UNKNOWN; CELLS],
+        }
+    }
+}
+
+impl LearnedHeuristic {
+    /// A table without observations, which estimates the Manhattan distance
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of cells with an observed cost
+    #[must_use]
+    pub fn known_cells(&self) -> usize {
+        self.costs.iter().filter(|&&cost| cost != UNKNOWN).count()
+    }
+
+    /// Records that `board` was `cost` moves from the goal
+    pub fn record(&mut self, board: Board, cost: usize) {
+        let cost = u8::try_from(cost).unwrap_or(UNKNOWN - 1);
+        let known = &mut self.costs[cell(board)];
+        *known = (*known).min(cost);
+    }
+
+    /// Records the cost to the goal of every board of a solution path
+    pub fn record_path(&mut self, path: &[Board]) {
+        for (index, &board) in path.iter().enumerate() {
+            self.record(board, path.len() - 1 - index);
+        }
+    }
+
+    /// Estimated moves from `board` to the goal
+    ///
+    /// The lowest cost observed for boards with the same features, or the
+    /// Manhattan distance if it is higher or nothing was observed.
+    #[must_use]
+    pub fn cost(&self, board: Board) -> u8 {
+        let manhattan = board.heuristic_distance_to_solution();
+        match self.costs[cell(board)] {
+            UNKNOWN => manhattan,
+            learned => learned.max(manhattan),
+        }
+    }
+
+    /// Opens a table previously written by [`LearnedHeuristic::save`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read, is not a valid table or
+    /// fails its integrity check
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let invalid = |msg: &str| io::Error::new(io::ErrorKind::InvalidData, msg.to_owned());
+        let bytes = fs::read(path)?;
+
+        let [rows, columns, costs @ ..] = read_checked(&bytes, &FORMAT)? else {
+            return Err(invalid("truncated learned heuristic"));
+        };
+        if *rows != ROWS || *columns != COLUMNS {
+            return Err(invalid("learned heuristic is for another board size"));
+        }
+        if costs.len() != CELLS {
+            return Err(invalid("corrupt learned heuristic"));
+        }
+        Ok(Self {
+            costs: costs.to_vec(),
+        })
+    }
+
+    /// Writes the table to `path`, replacing any previous file atomically
+    ///
+    /// # Errors
+    ///
+    /// Returns any I/O error raised while writing the file
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut body = vec![ROWS, COLUMNS];
+        body.extend_from_slice(&self.costs);
+        write_atomic(path, &FORMAT, &body)
+    }
+}
+
+impl Heuristic for LearnedHeuristic {
+    fn estimate(&self, board: Board) -> u32 {
+        u32::from(self.cost(board))
+    }
+}
+
+/// Index of the table cell of `board`
+fn cell(board: Board) -> usize {
+    usize::from(board.heuristic_distance_to_solution()) * usize::from(COLUMNS)
+        + usize::from(board.misplaced_tiles())
+}
+
+/// Settings of a [`bootstrap`] run
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BootstrapConfig {
+    /// Number of rounds of solving and learning
+    pub iterations: usize,
+    /// Random boards solved per iteration
+    pub boards: usize,
+    /// Scramble steps of the boards of the first iteration
+    pub start_steps: usize,
+    /// Scramble steps added in each later iteration
+    pub step_increment: usize,
+    /// Nodes one solve may explore before the board is skipped
+    pub node_limit: usize,
+    /// Seed of the boards, which only depend on it and their iteration
+    pub seed: u64,
+}
+
+impl Default for BootstrapConfig {
+    fn default() -> Self {
+        Self {
+            iterations: DEFAULT_ITERATIONS,
+            boards: DEFAULT_BOARDS,
+            start_steps: DEFAULT_START_STEPS,
+            step_increment: DEFAULT_STEP_INCREMENT,
+            node_limit: DEFAULT_NODE_LIMIT,
+            seed: 0,
+        }
+    }
+}
+
+/// What one iteration of [`bootstrap`] solved and learned
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BootstrapIteration {
+    /// Scramble steps of the iteration's boards
+    pub scramble_steps: usize,
+    /// Boards solved within the node limit
+    pub solved: usize,
+    /// Boards attempted
+    pub boards: usize,
+    /// Nodes explored by all the solves, the abandoned ones included
+    pub nodes_explored: usize,
+    /// Table cells with an observed cost after the iteration
+    pub known_cells: usize,
+}
+
+/// Learns a heuristic from progressively harder random boards
+///
+/// Every iteration solves `config.boards` boards scrambled a further
+/// `config.step_increment` steps with the heuristic search guided by the
+/// table learned so far, and adds the costs along each solution found
+/// within the node limit to the table. `observer` is called after each
+/// iteration.
+#[must_use]
+pub fn bootstrap(
+    config: &BootstrapConfig,
+    mut observer: impl FnMut(&BootstrapIteration),
+) -> (LearnedHeuristic, Vec<BootstrapIteration>) {
+    let mut learned = LearnedHeuristic::new();
+    let mut iterations = Vec::with_capacity(config.iterations);
+
+    for iteration in 0..config.iterations {
+        let scramble_steps = config.start_steps + iteration * config.step_increment;
+        let mut solver = Solver::builder(HeuristicSearchStrategy::default())
+            .node_limit(config.node_limit)
+            .heuristic(Arc::new(learned.clone()))
+            .build();
+        let mut next = learned.clone();
+        let mut report = BootstrapIteration {
+            scramble_steps,
+            solved: 0,
+            boards: config.boards,
+            nodes_explored: 0,
+            known_cells: 0,
+        };
+
+        for index in 0..config.boards {
+            let index = (iteration * config.boards + index) as u64;
+            let board = Board::random_in_set(config.seed, index, scramble_steps);
+            solver.reset();
+            let found = solver.solve(board).is_some();
+            report.nodes_explored += solver.get_solution_stats().nodes_explored;
+            if found {
+                report.solved += 1;
+                next.record_path(&solver.step_by_step_solution());
+            }
+        }
+
+        learned = next;
+        report.known_cells = learned.known_cells();
+        observer(&report);
+        iterations.push(report);
+    }
+    (learned, iterations)
+}
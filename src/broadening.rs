@@ -0,0 +1,141 @@
+//! # Broadening Module
+//!
+//! Iterative broadening: a depth-first search that only follows the `b` most
+//! promising successors of every node, ordered by Manhattan distance, and
+//! restarts with `b + 1` whenever a pass fails. The first pass is a greedy
+//! descent; the last one considers every successor and is a plain
+//! depth-limited DFS.
+//!
+//! Like DFS it only keeps the current path in memory, and no closed list, so
+//! memory stays bounded by the depth limit. Only boards already on the path are
+//! skipped as duplicates, which is why the depth limit is needed to stop. The
+//! solutions are not optimal, but narrow passes usually find one after a small
+//! fraction of the nodes an unrestricted search expands.
+
+use crate::time::Instant;
+
+use crate::board::{ALL_DIRECTIONS, Board};
+use crate::database;
+use crate::stats::Stats;
+
+/// Depth limit used by [`solve`]: the diameter of the 3x3 state space
+///
+/// Every solvable board is at most 30 moves from the goal, so a full-breadth
+/// pass within this limit never misses a solution.
+pub const DEFAULT_MAX_DEPTH: usize = 30;
+
+/// A solution found by iterative broadening
+#[derive(Clone, Debug)]
+pub struct BroadeningSolution {
+    /// Every board from the start to the goal
+    pub path: Vec<Board>,
+    /// Number of successors per node the successful pass followed
+    pub breadth: usize,
+    /// Work done by every pass, including the failed ones
+    pub stats: Stats,
+}
+
+/// State of the depth-first passes
+struct Broadening {
+    /// Boards from the start to the node being expanded
+    path: Vec<Board>,
+    /// Number of successors followed per node in the current pass
+    breadth: usize,
+    /// Deepest depth the passes may reach
+    max_depth: usize,
+    /// Work accumulated over all passes
+    stats: Stats,
+}
+
+impl Broadening {
+    /// Searches below the last board of the path, returning whether the goal was reached
+    fn descend(&mut self) -> bool {
+        let board = *self.path.last().expect("The path always holds the start");
+        let depth = self.path.len() - 1;
+        self.stats.nodes_explored += 1;
+        self.stats.max_depth_reached = self.stats.max_depth_reached.max(depth);
+
+        if board.is_solved() {
+            return true;
+        }
+        if depth == self.max_depth {
+            return false;
+        }
+
+        let mut children = Vec::with_capacity(ALL_DIRECTIONS.len());
+        for direction in ALL_DIRECTIONS {
+            let Ok(child) = board.move_space(direction) else {
+                continue;
+            };
+            self.stats.generated_nodes += 1;
+            if self.path.contains(&child) {
+                self.stats.duplicates_pruned += 1;
+            } else {
+                children.push(child);
+            }
+        }
+        children.sort_by_key(|child| child.heuristic_distance_to_solution());
+        children.truncate(self.breadth);
+
+        for child in children {
+            self.stats.enqueued_nodes += 1;
+            self.path.push(child);
+            self.stats.max_frontier = self.stats.max_frontier.max(self.path.len());
+            if self.descend() {
+                return true;
+            }
+            self.path.pop();
+        }
+        false
+    }
+}
+
+/// Solves a board by iterative broadening within [`DEFAULT_MAX_DEPTH`] moves
+///
+/// # Returns
+///
+/// `Some(solution)` with the path of the first successful pass, or `None` if
+/// the board cannot reach the goal
+#[must_use]
+pub fn solve(board: Board) -> Option<BroadeningSolution> {
+    solve_within(board, DEFAULT_MAX_DEPTH)
+}
+
+/// Solves a board by iterative broadening, never descending past `max_depth` moves
+///
+/// Passes follow 1, 2, 3 and finally all 4 successors of every node. The
+/// reported `max_frontier` is the longest path held, the only nodes kept in memory.
+///
+/// # Returns
+///
+/// `Some(solution)` with the path of the first successful pass, or `None` if
+/// even the full-breadth pass finds no solution within `max_depth` moves
+#[must_use]
+pub fn solve_within(board: Board, max_depth: usize) -> Option<BroadeningSolution> {
+    let started = Instant::now();
+    // A board of the wrong parity would make every pass exhaust its whole tree
+    database::rank(board)?;
+
+    let mut search = Broadening {
+        path: Vec::with_capacity(max_depth + 1),
+        breadth: 0,
+        max_depth,
+        stats: Stats::default(),
+    };
+    for breadth in 1..=ALL_DIRECTIONS.len() {
+        search.breadth = breadth;
+        search.path.clear();
+        search.path.push(board);
+        if search.descend() {
+            search.stats.solution_moves = search.path.len() - 1;
+            search.stats.solution_cost = search.stats.solution_moves;
+            search.stats.duration_ms = started.elapsed().as_millis();
+            return Some(BroadeningSolution {
+                path: search.path,
+                breadth,
+                stats: search.stats,
+            });
+        }
+    }
+    None
+}
@@ -0,0 +1,328 @@
+//! # Checkpoint Module
+//!
+//! Snapshots of an in-progress search that can be written to disk and resumed
+//! later, possibly by another process. A [`SolverCheckpoint`] holds everything
+//! the [`Solver`](crate::solver::Solver) needs to continue exactly where it
+//! stopped: the frontier, the closed list, the parent and depth of every
+//! reached board and all the counters behind [`Stats`].
+//!
+//! Checkpoints are taken between two expansions, either on demand with
+//! `Solver::checkpoint` or periodically through
+//! `SolverBuilder::checkpoint_every`, and resumed with `Solver::resume`. The
+//! file layout is:
+//!
+//! ```text
+//! magic "O8CK" | version u8 | checksum u64 LE | body
+//! ```
+//!
+//! where the body holds the strategy name, the start board, the counters and
+//! then the frontier, explored, depth and parent lists, each prefixed with its
+//! length. Integers are little-endian and boards are stored as their 32-bit
+//! encoding. The checksum is the FNV-1a hash of the body, so a file truncated
+//! by a crash mid-write is rejected rather than resumed.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::board::{BOARD_AREA, Board};
+use crate::persist::{Format, read_checked, write_atomic};
+use crate::stats::Stats;
+
+/// Header of a checkpoint file
+const FORMAT: Format = Format {
+    magic: *b"O8CK",
+    version: 1,
+    name: "checkpoint",
+};
+
+/// Number of cells whose blank counts are stored
+const CELLS: usize = BOARD_AREA as usize;
+
+/// State of a search between two expansions
+#[derive(Clone, Debug, Default)]
+pub struct SolverCheckpoint {
+    /// Name of the strategy that produced the checkpoint, set by the caller
+    /// (empty when unknown); resuming with another strategy mixes two frontier orders
+    pub strategy: String,
+    /// Board the search started from
+    pub start: Board,
+    /// Time spent searching before the checkpoint
+    pub elapsed: Duration,
+    /// Counters of the search so far; the solution lengths and duration are not used
+    pub stats: Stats,
+    /// Number of successors dropped for lying past the depth limit
+    pub depth_pruned: usize,
+    /// Number of expanded nodes with the empty space at each cell
+    pub blank_counts: [usize; CELLS],
+    /// Lowest heuristic value expanded so far
+    pub best_h: Option<u8>,
+    /// Length of the heuristic plateau in progress
+    pub plateau_len: usize,
+    /// Frontier nodes and their depths, in the order they are enqueued again
+    pub frontier: Vec<(Board, usize)>,
+    /// Boards already expanded
+    pub explored: Vec<Board>,
+    /// Depth of every reached board
+    pub depths: Vec<(Board, usize)>,
+    /// Parent of every reached board except the start
+    pub parents: Vec<(Board, Board)>,
+}
+
+impl SolverCheckpoint {
+    /// Writes the checkpoint to `path`
+    ///
+    /// The file is replaced atomically, so an interrupted write leaves the
+    /// previous checkpoint intact.
+    ///
+    /// # Errors
+    ///
+    /// Returns any I/O error raised while writing the file
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        write_atomic(path, &FORMAT, &self.encode())
+    }
+
+    /// Reads a checkpoint written by [`SolverCheckpoint::save`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read, is not a checkpoint or
+    /// fails its integrity check
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let bytes = fs::read(path)?;
+        Self::decode(&mut Decoder(read_checked(&bytes, &FORMAT)?))
+    }
+
+    /// Serializes everything after the header
+    fn encode(&self) -> Vec<u8> {
+        let mut out = Encoder(Vec::new());
+        out.len(self.strategy.len());
+        out.0.extend_from_slice(self.strategy.as_bytes());
+        out.board(self.start);
+        out.u64(u64::try_from(self.elapsed.as_millis()).unwrap_or(u64::MAX));
+        for counter in [
+            self.stats.generated_nodes,
+            self.stats.enqueued_nodes,
+            self.stats.duplicates_pruned,
+            self.stats.capacity_pruned,
+            self.stats.max_frontier,
+            self.stats.max_depth_reached,
+            self.stats.plateaus,
+            self.stats.plateau_expansions,
+            self.stats.longest_plateau,
+            self.depth_pruned,
+            self.plateau_len,
+        ]
+        .into_iter()
+        .chain(self.blank_counts)
+        {
+            out.len(counter);
+        }
+        out.0.push(self.best_h.unwrap_or(u8::MAX));
+
+        out.len(self.frontier.len());
+        for &(board, depth) in &self.frontier {
+            out.board(board);
+            out.len(depth);
+        }
+        out.len(self.explored.len());
+        for &board in &self.explored {
+            out.board(board);
+        }
+        out.len(self.depths.len());
+        for &(board, depth) in &self.depths {
+            out.board(board);
+            out.len(depth);
+        }
+        out.len(self.parents.len());
+        for &(child, parent) in &self.parents {
+            out.board(child);
+            out.board(parent);
+        }
+        out.0
+    }
+
+    /// Parses everything after the header
+    fn decode(input: &mut Decoder) -> io::Result<Self> {
+        let strategy_len = input.len()?;
+        let strategy = String::from_utf8(input.take(strategy_len)?.to_vec())
+            .map_err(|_| invalid("corrupt checkpoint"))?;
+        let start = input.board()?;
+        let elapsed = Duration::from_millis(input.u64()?);
+
+        let mut counters = [0; 11];
+        for counter in &mut counters {
+            *counter = input.len()?;
+        }
+        let mut blank_counts = [0; CELLS];
+        for count in &mut blank_counts {
+            *count = input.len()?;
+        }
+        let [
+            generated_nodes,
+            enqueued_nodes,
+            duplicates_pruned,
+            capacity_pruned,
+            max_frontier,
+            max_depth_reached,
+            plateaus,
+            plateau_expansions,
+            longest_plateau,
+            depth_pruned,
+            plateau_len,
+        ] = counters;
+        let best_h = Some(input.take(1)?[0]).filter(|&h| h != u8::MAX);
+
+        let frontier = input.list(|input| Ok((input.board()?, input.len()?)))?;
+        let explored = input.list(Decoder::board)?;
+        let depths = input.list(|input| Ok((input.board()?, input.len()?)))?;
+        let parents = input.list(|input| Ok((input.board()?, input.board()?)))?;
+        if !input.0.is_empty() {
+            return Err(invalid("corrupt checkpoint"));
+        }
+
+        Ok(Self {
+            strategy,
+            start,
+            elapsed,
+            stats: Stats {
+                nodes_explored: explored.len(),
+                generated_nodes,
+                enqueued_nodes,
+                duplicates_pruned,
+                capacity_pruned,
+                max_frontier,
+                max_depth_reached,
+                plateaus,
+                plateau_expansions,
+                longest_plateau,
+                ..Stats::default()
+            },
+            depth_pruned,
+            blank_counts,
+            best_h,
+            plateau_len,
+            frontier,
+            explored,
+            depths,
+            parents,
+        })
+    }
+}
+
+/// An `InvalidData` error with the given message
+fn invalid(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.to_owned())
+}
+
+/// Little-endian writer of the checkpoint body
+struct Encoder(Vec<u8>);
+
+impl Encoder {
+    fn u64(&mut self, value: u64) {
+        self.0.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn len(&mut self, value: usize) {
+        self.u64(value as u64);
+    }
+
+    fn board(&mut self, board: Board) {
+        self.0.extend_from_slice(&board.encoded().to_le_bytes());
+    }
+}
+
+/// Little-endian reader of the checkpoint body, failing on truncated input
+struct Decoder<'a>(&'a [u8]);
+
+impl<'a> Decoder<'a> {
+    fn take(&mut self, count: usize) -> io::Result<&'a [u8]> {
+        let (taken, rest) = self
+            .0
+            .split_at_checked(count)
+            .ok_or_else(|| invalid("truncated checkpoint"))?;
+        self.0 = rest;
+        Ok(taken)
+    }
+
+    fn u64(&mut self) -> io::Result<u64> {
+        let bytes = self.take(8)?;
+        Ok(u64::from_le_bytes(bytes.try_into().expect("took 8 bytes")))
+    }
+
+    fn len(&mut self) -> io::Result<usize> {
+        usize::try_from(self.u64()?).map_err(|_| invalid("corrupt checkpoint"))
+    }
+
+    fn board(&mut self) -> io::Result<Board> {
+        let bytes = self.take(4)?;
+        Board::try_from_encoded(u32::from_le_bytes(bytes.try_into().expect("took 4 bytes")))
+            .ok_or_else(|| invalid("corrupt checkpoint"))
+    }
+
+    /// Reads a length-prefixed list of items
+    fn list<T>(&mut self, item: impl Fn(&mut Self) -> io::Result<T>) -> io::Result<Vec<T>> {
+        let len = self.len()?;
+        // Every item takes at least 4 bytes, so a corrupt length cannot over-allocate
+        let mut items = Vec::with_capacity(len.min(self.0.len() / 4));
+        for _ in 0..len {
+            items.push(item(self)?);
+        }
+        Ok(items)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use super::*;
+    use crate::scramble::Walk;
+    use crate::search_strategies::SimpleSearchStrategy;
+    use crate::solver::{ExplorerStrategy, SolveError, Solver};
+
+    #[test]
+    fn resumed_searches_finish_like_uninterrupted_ones() {
+        let dir = std::env::temp_dir().join(format!("o8-checkpoint-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("search.ckpt");
+        let bfs = || SimpleSearchStrategy::new(ExplorerStrategy::Bfs);
+        let board = Walk::SelfAvoiding.scramble(18, &mut StdRng::seed_from_u64(498));
+
+        let mut uninterrupted = Solver::new(bfs());
+        uninterrupted.try_solve(board).unwrap();
+
+        let taken = Arc::new(Mutex::new(0));
+        let counter = Arc::clone(&taken);
+        let mut interrupted = Solver::builder(bfs())
+            .node_limit(300)
+            .checkpoint_every(100, Arc::new(move |_| *counter.lock().unwrap() += 1))
+            .build();
+        assert_eq!(interrupted.try_solve(board), Err(SolveError::LimitReached));
+        assert_eq!(*taken.lock().unwrap(), 3);
+        interrupted.checkpoint().unwrap().save(&path).unwrap();
+
+        let checkpoint = SolverCheckpoint::open(&path).unwrap();
+        assert_eq!(checkpoint.start, board);
+        assert_eq!(checkpoint.explored.len(), 300);
+        let mut resumed = Solver::new(bfs());
+        resumed.resume(&checkpoint).unwrap();
+        assert_eq!(
+            resumed.step_by_step_solution(),
+            uninterrupted.step_by_step_solution()
+        );
+        assert_eq!(
+            resumed.get_solution_stats().nodes_explored,
+            uninterrupted.get_solution_stats().nodes_explored
+        );
+
+        let mut bytes = fs::read(&path).unwrap();
+        *bytes.last_mut().unwrap() ^= 1;
+        fs::write(&path, bytes).unwrap();
+        assert!(SolverCheckpoint::open(&path).is_err());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,19 @@
+//! # Checksum Module
+//!
+//! Stable, dependency-free hashing for fingerprints and file integrity checks.
+
+/// FNV-1a 64-bit offset basis
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// FNV-1a 64-bit prime
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Computes the 64-bit FNV-1a hash of a byte sequence
+///
+/// Unlike `std`'s randomly seeded hasher, the value only depends on the bytes,
+/// never on the platform or the process, so it can be stored and compared later.
+pub(crate) fn fnv1a(bytes: impl IntoIterator<Item = u8>) -> u64 {
+    bytes.into_iter().fold(FNV_OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+    })
+}
@@ -0,0 +1,1168 @@
+//! # Commands Module
+//!
+//! The work behind the subcommands of the `o8` binary, which only parses its
+//! arguments and calls in here: solving random boards and board set files
+//! with any registered strategy, writing their exports and checkpoints,
+//! generating board sets, building the on-disk tables and running the
+//! self-test.
+//!
+//! Commands report failures as a [`CommandError`] instead of exiting, and
+//! leave the formatting of their output to [`report`](crate::report).
+
+use crate::time::Instant;
+use std::fmt::{self, Display};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use rand::{Rng, rng};
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+use crate::adversarial::{self, AdversarialConfig};
+use crate::benchmark::generate_boards;
+use crate::board::{Board, Direction, board_seed};
+use crate::boardset::{BoardEntry, BoardSet, BoardSource, write_board_set};
+use crate::bootstrap::{self, BootstrapConfig};
+use crate::checkpoint::SolverCheckpoint;
+use crate::convention::{GoalConvention, Translation};
+use crate::database::{self, StateSpaceDatabase, default_data_dir};
+use crate::dfbnb;
+use crate::doctor;
+use crate::external::{ExternalBfs, IoStats};
+use crate::goal_cache::GoalCache;
+use crate::heuristic::LoadHeuristicError;
+use crate::human;
+use crate::kbest;
+use crate::overlap::{Overlap, RankSet};
+use crate::pdb::{self, Pattern, PatternDatabase};
+use crate::portfolio;
+use crate::registry::{StrategyConfig, StrategyEntry};
+use crate::render;
+use crate::report::{
+    describe_unsolvable, moves_summary, print_adversarial_instances, print_cache_size,
+    print_cache_use, print_doctor_report, print_io_stats, print_overlap, print_solution_steps,
+    print_translation, pruned_summary,
+};
+use crate::schema::{self, OutputFormat, Solution};
+use crate::scramble::{Scramble, ScrambleModel};
+use crate::sized::{self, Puzzle};
+use crate::solver::CheckpointObserver;
+use crate::stats::{
+    Stats, StatsSummary, print_blank_heatmap, print_comparison_table, print_run_stats,
+};
+use crate::visualize::{self, SearchRecorder};
+
+/// Name of the external-memory breadth-first search in reports
+pub const EXTERNAL_BFS_NAME: &str = "External BFS";
+
+/// Name shown for solves through a goal cache file
+pub const GOAL_CACHE_NAME: &str = "Goal cache";
+
+/// Name of the search for the shortest distinct solutions in reports
+pub const K_BEST_NAME: &str = "K-best A*";
+
+/// Why a command stopped before finishing
+#[derive(Debug)]
+pub enum CommandError {
+    /// The arguments cannot be honoured together, e.g. a goal for a strategy
+    /// that only searches for the spiral goal
+    Usage(String),
+    /// A search, a file or a check failed
+    Failed(String),
+}
+
+impl CommandError {
+    /// Exit status of the binary for the error: 2 for usage errors, 1 otherwise
+    #[must_use]
+    pub fn exit_status(&self) -> i32 {
+        match self {
+            CommandError::Usage(_) => 2,
+            CommandError::Failed(_) => 1,
+        }
+    }
+}
+
+impl Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CommandError::Usage(message) | CommandError::Failed(message) => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for CommandError {}
+
+impl From<LoadHeuristicError> for CommandError {
+    fn from(error: LoadHeuristicError) -> Self {
+        match error {
+            LoadHeuristicError::Overlapping(_) => CommandError::Usage(error.to_string()),
+            _ => CommandError::Failed(error.to_string()),
+        }
+    }
+}
+
+/// Turns an error into a [`CommandError::Failed`] whose message starts with `context`
+fn failed<E: Display>(context: &str) -> impl FnOnce(E) -> CommandError + '_ {
+    move |error| CommandError::Failed(format!("{context}: {error}"))
+}
+
+/// Loads a board set
+///
+/// # Errors
+///
+/// Returns a usage error with the parse error, and its line, if the set is invalid.
+pub fn load_board_set(source: &BoardSource) -> Result<BoardSet, CommandError> {
+    source
+        .load()
+        .map_err(|error| CommandError::Usage(format!("Invalid board set {source}: {error}")))
+}
+
+/// Optional files written for a solve, and extra detail in its step listing
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SolutionExports<'a> {
+    /// Print each step's per-tile Manhattan distances next to the board
+    pub explain_heuristic: bool,
+    /// Measure the compressed length of the solution
+    pub compress: bool,
+    /// Destination of the animated GIF
+    pub gif: Option<&'a Path>,
+    /// Destination of the SVG grid
+    pub svg: Option<&'a Path>,
+    /// Destination of the search visualization
+    pub visualize: Option<&'a Path>,
+    /// Destination of the frontier timeline CSV
+    pub frontier_timeline: Option<&'a Path>,
+    /// Number of explored nodes between two recorded frames
+    pub visualize_interval: usize,
+}
+
+impl SolutionExports<'_> {
+    /// The `stats` of `solution`, with its compressed length measured if requested
+    fn measure(self, solution: &[Board], mut stats: Stats) -> Stats {
+        if self.compress {
+            stats.record_compression(solution);
+        }
+        stats
+    }
+
+    /// Write every requested picture of a solution path
+    fn write(self, solution: &[Board]) -> Result<(), CommandError> {
+        if let Some(path) = self.gif {
+            render::write_gif(path, solution).map_err(failed("Failed to write GIF"))?;
+        }
+        if let Some(path) = self.svg {
+            render::write_svg(path, solution).map_err(failed("Failed to write SVG"))?;
+        }
+        Ok(())
+    }
+
+    /// Print where the files were written
+    fn print_paths(self) {
+        if let Some(path) = self.gif {
+            println!("Animation written to {}", path.display());
+        }
+        if let Some(path) = self.svg {
+            println!("Solution grid written to {}", path.display());
+        }
+        if let Some(path) = self.visualize {
+            println!("Search visualization written to {}", path.display());
+        }
+        if let Some(path) = self.frontier_timeline {
+            println!("Frontier timeline written to {}", path.display());
+        }
+    }
+
+    /// Whether the search itself has to be recorded
+    fn records_search(self) -> bool {
+        self.visualize.is_some() || self.frontier_timeline.is_some()
+    }
+
+    /// Drop the search recordings, with a warning, for a solver called `name`
+    /// that is not a single tree search
+    fn skip_recording(&mut self, name: &str) {
+        if self.visualize.take().is_some() {
+            eprintln!("--visualize records tree searches only; ignored for {name}");
+        }
+        if self.frontier_timeline.take().is_some() {
+            eprintln!("--frontier-timeline records tree searches only; ignored for {name}");
+        }
+    }
+}
+
+/// Print a solution as a versioned document in `format` on standard output
+fn write_solution(
+    format: OutputFormat,
+    algorithm: &str,
+    path: &[Board],
+    stats: Stats,
+) -> Result<(), CommandError> {
+    let document = Solution::new(algorithm, path, stats);
+    schema::write_document(io::stdout().lock(), format, &document)
+        .map_err(failed("Failed to write solution"))
+}
+
+/// Race the portfolio's strategies on one board, then print the winner's
+/// solution and how far every entrant got
+fn solve_portfolio(
+    board: Board,
+    strategy: StrategyEntry,
+    limits: &StrategyConfig,
+    output: Option<OutputFormat>,
+    mut exports: SolutionExports,
+) -> Result<(), CommandError> {
+    exports.skip_recording(strategy.label);
+
+    let solution = portfolio::solve(board, limits).ok_or_else(|| {
+        CommandError::Failed("No entrant of the portfolio found a solution".to_owned())
+    })?;
+    exports.write(&solution.path)?;
+    let stats = exports.measure(&solution.path, solution.stats);
+
+    if let Some(format) = output {
+        let algorithm = format!("{} ({})", strategy.label, solution.winner);
+        return write_solution(format, &algorithm, &solution.path, stats);
+    }
+
+    print_solution_steps(&solution.path, exports.explain_heuristic);
+    println!("\n{} won the race", solution.winner);
+    for entrant in &solution.entrants {
+        let outcome = if entrant.label == solution.winner {
+            "won"
+        } else if entrant.finished {
+            "also finished"
+        } else {
+            "cancelled"
+        };
+        println!(
+            "  {}: {outcome} after {} explored nodes, depth {}, {} ms",
+            entrant.label,
+            entrant.stats.nodes_explored,
+            entrant.stats.max_depth_reached,
+            entrant.stats.duration_ms
+        );
+    }
+    print_run_stats(&stats);
+    exports.print_paths();
+    Ok(())
+}
+
+/// Solve a single board and print the path and per-step heuristic
+///
+/// With `output` set, the solution is printed as a versioned [`Solution`] document instead.
+/// The requested `exports` are written in either case.
+fn solve_one(
+    board: Board,
+    strategy: StrategyEntry,
+    limits: &StrategyConfig,
+    output: Option<OutputFormat>,
+    mut exports: SolutionExports,
+) -> Result<(), CommandError> {
+    let tree_search = strategy.is_tree_search();
+    if !tree_search {
+        let name = strategy.label;
+        exports.skip_recording(name);
+        if limits.max_cost.is_some() {
+            eprintln!("--max-cost bounds tree searches only; ignored for {name}");
+        }
+        if !limits.move_costs.is_unit() {
+            eprintln!("--move-cost charges tree searches only; ignored for {name}");
+        }
+    }
+
+    let recorder = SearchRecorder::new();
+    let config = StrategyConfig {
+        progress: exports
+            .records_search()
+            .then(|| (exports.visualize_interval, recorder.observer())),
+        ..limits.clone()
+    };
+    let mut solver = strategy.build(&config);
+
+    solver
+        .try_solve(board)
+        .map_err(|error| CommandError::Failed(error.to_string()))?;
+    let solution = solver.step_by_step_solution();
+    exports.write(&solution)?;
+    if let Some(path) = exports.visualize {
+        let title = format!("{} search", strategy.label);
+        visualize::write_html(path, &recorder.frames(), &title)
+            .map_err(failed("Failed to write visualization"))?;
+    }
+    if let Some(path) = exports.frontier_timeline {
+        visualize::write_timeline_csv(path, &recorder.frames())
+            .map_err(failed("Failed to write frontier timeline"))?;
+    }
+
+    let stats = exports.measure(&solution, solver.get_solution_stats());
+    if let Some(format) = output {
+        return write_solution(format, strategy.label, &solution, stats);
+    }
+
+    print_solution_steps(&solution, exports.explain_heuristic);
+    print_run_stats(&stats);
+    if let Some(max_cost) = limits.max_cost.filter(|_| tree_search) {
+        println!(
+            "\nFound a solution of {} moves within the cost bound of {max_cost}",
+            stats.solution_moves
+        );
+    }
+    if tree_search {
+        print_blank_heatmap(strategy.label, &solver.blank_heatmap());
+    }
+    exports.print_paths();
+    Ok(())
+}
+
+/// Solve a single board with the external-memory BFS and print the path and disk I/O
+///
+/// With `output` set, the solution is printed as a versioned [`Solution`] document instead.
+fn solve_external(
+    board: Board,
+    scratch_dir: &Path,
+    output: Option<OutputFormat>,
+    exports: SolutionExports,
+) -> Result<(), CommandError> {
+    let solution = ExternalBfs::new(scratch_dir)
+        .solve(board)
+        .map_err(failed("External-memory search failed"))?
+        .ok_or_else(|| CommandError::Failed("No solution found".to_owned()))?;
+    exports.write(&solution.path)?;
+    let stats = exports.measure(&solution.path, solution.stats);
+
+    if let Some(format) = output {
+        return write_solution(format, EXTERNAL_BFS_NAME, &solution.path, stats);
+    }
+
+    print_solution_steps(&solution.path, exports.explain_heuristic);
+    print_run_stats(&stats);
+    print_io_stats(&solution.io);
+    exports.print_paths();
+    Ok(())
+}
+
+/// Open a goal cache file
+fn open_goal_cache(path: &Path) -> Result<GoalCache, CommandError> {
+    GoalCache::open(path).map_err(failed(&format!(
+        "Cannot open goal cache {}",
+        path.display()
+    )))
+}
+
+/// Solve a single board through a goal cache file and print the path and cache use
+///
+/// With `output` set, the solution is printed as a versioned [`Solution`] document instead.
+fn solve_cached(
+    board: Board,
+    cache_path: &Path,
+    output: Option<OutputFormat>,
+    exports: SolutionExports,
+) -> Result<(), CommandError> {
+    let mut cache = open_goal_cache(cache_path)?;
+    let solution = cache
+        .solve(board)
+        .ok_or_else(|| CommandError::Failed("No solution found".to_owned()))?;
+    cache
+        .flush()
+        .map_err(failed("Failed to write goal cache"))?;
+    exports.write(&solution.path)?;
+    let stats = exports.measure(&solution.path, solution.stats);
+
+    if let Some(format) = output {
+        return write_solution(format, GOAL_CACHE_NAME, &solution.path, stats);
+    }
+
+    print_solution_steps(&solution.path, exports.explain_heuristic);
+    print_run_stats(&stats);
+    print_cache_use(&solution, &cache);
+    exports.print_paths();
+    Ok(())
+}
+
+/// Solve a single board with the human-style layered solver and print its plan
+///
+/// With `output` set, the solution is printed as a versioned [`Solution`] document instead.
+/// The requested `exports` are written in either case, except for the search
+/// visualization and frontier timeline: the layered solver has no single
+/// search to record.
+fn solve_human(
+    board: Board,
+    strategy: StrategyEntry,
+    output: Option<OutputFormat>,
+    mut exports: SolutionExports,
+) -> Result<(), CommandError> {
+    exports.skip_recording("the human solver");
+
+    let solution =
+        human::solve(board).ok_or_else(|| CommandError::Failed("No solution found".to_owned()))?;
+    let path = solution.path();
+    exports.write(&path)?;
+    let stats = exports.measure(&path, solution.stats);
+
+    if let Some(format) = output {
+        return write_solution(format, strategy.label, &path, stats);
+    }
+
+    println!("\nStart\n\n{board}");
+    for (idx, stage) in solution.stages.iter().enumerate() {
+        let Some(after) = stage.boards.last() else {
+            println!("Stage {}: {} (already done)\n", idx + 1, stage.description);
+            continue;
+        };
+        let moves: Vec<&str> = stage.moves.iter().map(|d| d.name()).collect();
+        println!(
+            "Stage {}: {} ({} moves: {})",
+            idx + 1,
+            stage.description,
+            moves.len(),
+            moves.join(" ")
+        );
+        println!("{after}");
+    }
+
+    print_run_stats(&stats);
+    exports.print_paths();
+    Ok(())
+}
+
+/// Solve a single board with depth-first branch-and-bound and print its incumbents
+///
+/// With `output` set, the solution is printed as a versioned [`Solution`] document instead.
+/// The requested `exports` are written in either case, except for the search
+/// visualization and frontier timeline, which record tree searches only.
+fn solve_dfbnb(
+    board: Board,
+    strategy: StrategyEntry,
+    output: Option<OutputFormat>,
+    mut exports: SolutionExports,
+) -> Result<(), CommandError> {
+    exports.skip_recording(strategy.label);
+
+    let solution =
+        dfbnb::solve(board).ok_or_else(|| CommandError::Failed("No solution found".to_owned()))?;
+    exports.write(&solution.path)?;
+    let stats = exports.measure(&solution.path, solution.stats);
+
+    if let Some(format) = output {
+        return write_solution(format, strategy.label, &solution.path, stats);
+    }
+
+    print_solution_steps(&solution.path, exports.explain_heuristic);
+    println!("\nIncumbents (the last one is optimal):");
+    for incumbent in &solution.incumbents {
+        println!(
+            "  {:>4} moves after {} explored nodes",
+            incumbent.moves, incumbent.nodes_explored
+        );
+    }
+    print_run_stats(&stats);
+    exports.print_paths();
+    Ok(())
+}
+
+/// Solve a single board for its `k` shortest distinct solutions and print their moves
+///
+/// The first solution is printed step by step and written to the requested
+/// `exports`; the rest are listed as moves. With `output` set, every solution is
+/// printed as a versioned [`Solution`] document instead.
+fn solve_k_best(
+    board: Board,
+    k: usize,
+    output: Option<OutputFormat>,
+    exports: SolutionExports,
+) -> Result<(), CommandError> {
+    let solutions = kbest::solve(board, k)
+        .filter(|found| !found.paths.is_empty())
+        .ok_or_else(|| CommandError::Failed("No solution found".to_owned()))?;
+    exports.write(&solutions.paths[0])?;
+
+    if let Some(format) = output {
+        let documents: Vec<Solution> = solutions
+            .paths
+            .iter()
+            .map(|path| {
+                let stats = Stats {
+                    solution_moves: path.len() - 1,
+                    solution_cost: path.len() - 1,
+                    ..solutions.stats
+                };
+                Solution::new(K_BEST_NAME, path, exports.measure(path, stats))
+            })
+            .collect();
+        return schema::write_document(io::stdout().lock(), format, &documents)
+            .map_err(failed("Failed to write solutions"));
+    }
+
+    print_solution_steps(&solutions.paths[0], exports.explain_heuristic);
+    println!("\n{} shortest solutions:", solutions.paths.len());
+    for (idx, path) in solutions.paths.iter().enumerate() {
+        let moves: Vec<&str> = path
+            .windows(2)
+            .filter_map(|pair| pair[0].direction_to(pair[1]))
+            .map(Direction::name)
+            .collect();
+        println!("  #{} {} moves: {}", idx + 1, moves.len(), moves.join(" "));
+    }
+    print_run_stats(&exports.measure(&solutions.paths[0], solutions.stats));
+    exports.print_paths();
+    Ok(())
+}
+
+/// Solve a single random puzzle board and display the solution steps
+///
+/// With `external` set, the board is solved with the external-memory BFS
+/// using that scratch directory; with `cache` set, through that goal cache
+/// file; with `num_solutions` set, the shortest distinct solutions are
+/// searched for instead.
+///
+/// # Errors
+///
+/// Returns a usage error if `strategy` cannot search for the goal of
+/// `limits`, and an error if no solution is found or an export cannot be written.
+#[allow(clippy::too_many_arguments)]
+pub fn solve_random(
+    scramble: &Scramble,
+    strategy: StrategyEntry,
+    limits: &StrategyConfig,
+    external: Option<&Path>,
+    cache: Option<&Path>,
+    num_solutions: Option<usize>,
+    output: Option<OutputFormat>,
+    exports: SolutionExports,
+) -> Result<(), CommandError> {
+    require_tree_search_for_goal(strategy, limits)?;
+    let board = scramble
+        .model
+        .scramble_from(limits.goal, scramble.steps, &mut rng());
+    if output.is_none() {
+        let name = match (external, cache, num_solutions) {
+            (Some(_), _, _) => EXTERNAL_BFS_NAME,
+            (None, Some(_), _) => GOAL_CACHE_NAME,
+            (None, None, Some(_)) => K_BEST_NAME,
+            (None, None, None) => strategy.label,
+        };
+        println!("Solving a random board ({scramble}) using {name}...");
+    }
+
+    if let Some(k) = num_solutions {
+        return solve_k_best(board, k, output, exports);
+    }
+    if let Some(scratch_dir) = external {
+        return solve_external(board, scratch_dir, output, exports);
+    }
+    if let Some(cache_path) = cache {
+        return solve_cached(board, cache_path, output, exports);
+    }
+
+    match strategy.name {
+        "human" => solve_human(board, strategy, output, exports),
+        "dfbnb" => solve_dfbnb(board, strategy, output, exports),
+        "portfolio" => solve_portfolio(board, strategy, limits, output, exports),
+        _ => solve_one(board, strategy, limits, output, exports),
+    }
+}
+
+/// Solve a random board other than 3×3 optimally with IDA* and print the path
+///
+/// Such boards do not fit the packed [`Board`] the solver searches, so the
+/// search options of solve-random do not apply to them. `steps` is the
+/// number of scramble moves that produced `board`.
+///
+/// # Errors
+///
+/// Returns an error if the board cannot reach the goal.
+pub fn solve_random_sized<P: Puzzle + Display>(
+    board: P,
+    (rows, cols): (u8, u8),
+    steps: usize,
+) -> Result<(), CommandError> {
+    println!("Solving a random {rows}×{cols} board ({steps} scramble moves) using IDA*...");
+
+    let solution = sized::solve(board)
+        .ok_or_else(|| CommandError::Failed("The board cannot reach the goal".to_owned()))?;
+    let moves = solution.path.len() - 1;
+    println!("\nSolution path ({moves} steps)\n");
+    for (idx, step) in solution.path.iter().enumerate() {
+        println!("Step {idx}/{moves} h(n): {}", step.manhattan());
+        println!("{step}\n");
+    }
+    print_run_stats(&solution.stats);
+    Ok(())
+}
+
+/// Write a board set to `file`, or print it as text without one
+fn write_or_print_set(
+    set: &BoardSet,
+    file: Option<&Path>,
+    description: &str,
+) -> Result<(), CommandError> {
+    match file {
+        Some(path) => {
+            write_board_set(path, set).map_err(failed("Failed to write board set"))?;
+            println!(
+                "{} boards ({description}) written to {}",
+                set.entries.len(),
+                path.display()
+            );
+        }
+        None => print!("{}", set.to_text()),
+    }
+    Ok(())
+}
+
+/// Generate a board set with one seed per board and exact distances
+///
+/// Each board is scrambled with its own seed, derived from the set seed and
+/// its index, so a single line of the file can be reproduced on its own and
+/// the set is generated in parallel. `benchmark --seed` produces the same boards.
+/// Without a `seed`, a random one is drawn and reported.
+///
+/// # Errors
+///
+/// Returns an error if `file` cannot be written.
+pub fn generate(
+    count: usize,
+    scramble: &Scramble,
+    seed: Option<u64>,
+    file: Option<&Path>,
+) -> Result<(), CommandError> {
+    let seed = seed.unwrap_or_else(|| rng().random());
+    let database = StateSpaceDatabase::global();
+
+    let entries = (0..count as u64)
+        .into_par_iter()
+        .map(|index| {
+            let board = scramble.model.board_in_set(seed, index, scramble.steps);
+            BoardEntry {
+                seed: Some(board_seed(seed, index)),
+                distance: database.distance(board),
+                difficulty: database.difficulty(board),
+                ..BoardEntry::new(board)
+            }
+        })
+        .collect();
+    let set = BoardSet {
+        seed: Some(seed),
+        entries,
+    };
+
+    write_or_print_set(&set, file, &format!("{scramble}, seed {seed}"))
+}
+
+/// Generate the board set of every board at the maximum distance from the goal
+///
+/// The boards are read off the exact-distance table in rank order, so the
+/// set is the same on every run and needs no seed.
+///
+/// # Errors
+///
+/// Returns an error if `file` cannot be written.
+pub fn generate_hardest(file: Option<&Path>) -> Result<(), CommandError> {
+    let database = StateSpaceDatabase::global();
+    let distance = database.max_distance();
+
+    let entries: Vec<BoardEntry> = database
+        .boards_at(distance)
+        .map(|board| BoardEntry {
+            distance: Some(distance),
+            difficulty: database.difficulty(board),
+            ..BoardEntry::new(board)
+        })
+        .collect();
+    let set = BoardSet {
+        seed: None,
+        entries,
+    };
+
+    write_or_print_set(&set, file, &format!("{distance} moves from the goal"))
+}
+
+/// Solve one board with `strategy` and return its path and statistics
+///
+/// The strategies without a frontier, such as the human one, ignore the `limits`.
+///
+/// With `checkpoint` set, the search continues from it instead of starting over.
+fn solve_path(
+    board: Board,
+    strategy: StrategyEntry,
+    limits: &StrategyConfig,
+    checkpoint: Option<&SolverCheckpoint>,
+) -> Option<(Vec<Board>, Stats)> {
+    let mut solver = strategy.build(limits);
+    match checkpoint {
+        Some(checkpoint) => solver.resume(checkpoint).ok()?,
+        None => solver.solve(board)?,
+    };
+    Some((solver.step_by_step_solution(), solver.get_solution_stats()))
+}
+
+/// Fail unless `strategy` can search for the goal of `limits`
+///
+/// Only the generic solver takes a goal; the other strategies would solve
+/// towards the spiral goal instead.
+fn require_tree_search_for_goal(
+    strategy: StrategyEntry,
+    limits: &StrategyConfig,
+) -> Result<(), CommandError> {
+    if !limits.goal.is_solved() && !strategy.is_tree_search() {
+        return Err(CommandError::Usage(format!(
+            "--goal and --goal-layout are supported by DFS, BFS, UCS, the heuristic search and focal search only, not by {}",
+            strategy.label
+        )));
+    }
+    Ok(())
+}
+
+/// Checkpoint observer that overwrites `path` with every checkpoint of a `strategy` search
+///
+/// Warns that the checkpoints are ignored if `strategy` is not a tree search.
+///
+/// # Panics
+///
+/// The observer panics if a checkpoint cannot be written.
+#[must_use]
+pub fn checkpoint_saver(strategy: StrategyEntry, path: PathBuf) -> CheckpointObserver {
+    if !strategy.is_tree_search() {
+        eprintln!(
+            "--checkpoint-every checkpoints tree searches only; ignored for {}",
+            strategy.label
+        );
+    }
+    Arc::new(move |checkpoint: &SolverCheckpoint| {
+        let checkpoint = SolverCheckpoint {
+            strategy: strategy.label.to_owned(),
+            ..checkpoint.clone()
+        };
+        checkpoint.save(&path).expect("Failed to write checkpoint");
+    })
+}
+
+/// Read a checkpoint to resume with `strategy`
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or was written by another strategy.
+pub fn open_checkpoint(
+    path: &Path,
+    strategy: StrategyEntry,
+) -> Result<SolverCheckpoint, CommandError> {
+    let checkpoint = SolverCheckpoint::open(path)
+        .map_err(failed(&format!("Cannot resume from {}", path.display())))?;
+    if checkpoint.strategy != strategy.label {
+        return Err(CommandError::Failed(format!(
+            "Cannot resume from {}: it was written by {}, not {}",
+            path.display(),
+            checkpoint.strategy,
+            strategy.label
+        )));
+    }
+    Ok(checkpoint)
+}
+
+/// Index in `set` of the board `checkpoint` was taken on
+fn checkpoint_index(
+    set: &BoardSet,
+    source: &BoardSource,
+    checkpoint: &SolverCheckpoint,
+) -> Result<usize, CommandError> {
+    set.entries
+        .iter()
+        .position(|entry| entry.board == checkpoint.start)
+        .ok_or_else(|| {
+            CommandError::Failed(format!(
+                "Cannot resume: the checkpoint's board {} is not in {source}",
+                checkpoint.start.to_digit_string()
+            ))
+        })
+}
+
+/// Write a solution as `<rank>.json` in `out_dir`, named by the rank of its start board
+fn write_sidecar(
+    out_dir: &Path,
+    algorithm: &str,
+    path: &[Board],
+    stats: Stats,
+) -> Result<(), CommandError> {
+    let start = path.first().copied().unwrap_or_default();
+    let file = out_dir.join(format!("{}.json", start.rank()));
+    let writer = io::BufWriter::new(
+        fs::File::create(&file).map_err(failed("Failed to create solution file"))?,
+    );
+    schema::write_document(
+        writer,
+        OutputFormat::Json,
+        &Solution::new(algorithm, path, stats),
+    )
+    .map_err(failed("Failed to write solution file"))
+}
+
+/// Where the boards of [`solve_file`] are solved instead of by the chosen strategy,
+/// and where their solutions go
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FileSolveOptions<'a> {
+    /// Solve every board with the external-memory BFS, using this scratch directory
+    pub external: Option<&'a Path>,
+    /// Solve every board through this goal cache file, which the boards share
+    pub cache: Option<&'a Path>,
+    /// Write every solution to this directory as soon as it is found
+    pub out_dir: Option<&'a Path>,
+    /// Measure the compressed length of every solution
+    pub compress: bool,
+}
+
+/// Solve every board of a board set file and print one line per board and a summary
+///
+/// With `options.external` set, the total disk I/O is printed; with
+/// `options.cache` set, the cache use of every board. Solutions written to
+/// `options.out_dir` are JSON solution documents named by the rank of their
+/// board. With `resume` set, the boards before the checkpoint's board are
+/// skipped and its search is continued from the checkpoint.
+///
+/// # Errors
+///
+/// Returns a usage error if the set is invalid or `strategy` cannot search
+/// for the goal of `limits`, and an error if the checkpoint's board is not in
+/// the set or a file cannot be written.
+#[allow(clippy::too_many_lines)]
+pub fn solve_file(
+    source: &BoardSource,
+    strategy: StrategyEntry,
+    limits: &StrategyConfig,
+    options: FileSolveOptions,
+    resume: Option<&SolverCheckpoint>,
+) -> Result<(), CommandError> {
+    let FileSolveOptions {
+        external,
+        cache,
+        out_dir,
+        compress,
+    } = options;
+    require_tree_search_for_goal(strategy, limits)?;
+    let set = load_board_set(source)?;
+    let resume_index = resume
+        .map(|checkpoint| checkpoint_index(&set, source, checkpoint))
+        .transpose()?;
+    let mut cache = cache.map(open_goal_cache).transpose()?;
+    if let Some(out_dir) = out_dir {
+        fs::create_dir_all(out_dir).map_err(failed("Failed to create output directory"))?;
+    }
+    let name = match (external, &cache) {
+        (Some(_), _) => EXTERNAL_BFS_NAME,
+        (None, Some(_)) => GOAL_CACHE_NAME,
+        (None, None) => strategy.label,
+    };
+    println!(
+        "Solving {} boards from {source} using {name}...\n",
+        set.entries.len()
+    );
+
+    let mut runs = Vec::with_capacity(set.entries.len());
+    let mut io_total = IoStats::default();
+    for (idx, entry) in set.entries.iter().enumerate() {
+        let digits = entry.board.to_digit_string();
+        if resume_index.is_some_and(|resume_index| idx < resume_index) {
+            println!(
+                "#{} {digits} skipped (solved before the checkpoint)",
+                idx + 1
+            );
+            continue;
+        }
+        let checkpoint = resume.filter(|_| resume_index == Some(idx));
+        if let Some(checkpoint) = checkpoint {
+            println!(
+                "#{} {digits} resuming after {} explored nodes",
+                idx + 1,
+                checkpoint.explored.len()
+            );
+        }
+        if !entry.board.can_reach(limits.goal) {
+            println!(
+                "#{} {digits} {}",
+                idx + 1,
+                describe_unsolvable(entry.board, limits.goal)
+            );
+            continue;
+        }
+        let mut cache_use = String::new();
+        let solved = match (external, &mut cache) {
+            (Some(scratch_dir), _) => ExternalBfs::new(scratch_dir)
+                .solve(entry.board)
+                .map_err(failed("External-memory search failed"))?
+                .map(|solution| {
+                    io_total += solution.io;
+                    (solution.path, solution.stats)
+                }),
+            (None, Some(cache)) => cache.solve(entry.board).map(|solution| {
+                cache_use = if solution.was_lookup() {
+                    " cache: lookup".to_owned()
+                } else {
+                    format!(" cache: +{} layers", solution.layers_expanded)
+                };
+                (solution.path, solution.stats)
+            }),
+            (None, None) => solve_path(entry.board, strategy, limits, checkpoint),
+        };
+        let Some((path, mut stats)) = solved else {
+            let bound = limits
+                .max_cost
+                .map(|max_cost| format!(" within the cost bound of {max_cost} moves"))
+                .unwrap_or_default();
+            println!("#{} {digits} no solution found{bound}", idx + 1);
+            continue;
+        };
+        if compress {
+            stats.record_compression(&path);
+        }
+        if let Some(out_dir) = out_dir {
+            write_sidecar(out_dir, name, &path, stats)?;
+        }
+        let optimal = entry
+            .distance
+            .filter(|_| limits.goal.is_solved())
+            .map(|distance| format!(" (optimal {distance})"))
+            .unwrap_or_default();
+        let pruned = pruned_summary(limits, &stats);
+        println!(
+            "#{} {digits} moves: {}{optimal} nodes: {}{pruned}{cache_use} time: {} ms",
+            idx + 1,
+            moves_summary(limits, &stats),
+            stats.nodes_explored,
+            stats.duration_ms
+        );
+        runs.push(stats);
+    }
+
+    if runs.is_empty() {
+        return Ok(());
+    }
+    let summary: StatsSummary = runs.as_slice().into();
+    print_comparison_table(&[(name, &summary)]);
+    if external.is_some() {
+        print_io_stats(&io_total);
+    }
+    if let Some(cache) = &mut cache {
+        cache
+            .flush()
+            .map_err(failed("Failed to write goal cache"))?;
+        print_cache_size(cache);
+    }
+    Ok(())
+}
+
+/// Hill-climb towards the boards that make `strategy` work hardest and print them
+///
+/// # Errors
+///
+/// Returns a usage error if `strategy` is not a single tree search.
+pub fn adversarial(
+    strategy: StrategyEntry,
+    config: &AdversarialConfig,
+) -> Result<(), CommandError> {
+    if !strategy.is_tree_search() {
+        return Err(CommandError::Usage(format!(
+            "{} is not a single tree search, so it has no adversarial boards",
+            strategy.label
+        )));
+    }
+
+    println!(
+        "Searching adversarial boards for {} ({} restarts x {} probes, objective: {:?})...",
+        strategy.label, config.restarts, config.iterations, config.objective
+    );
+
+    let instances = adversarial::generate(&strategy, config);
+
+    print_adversarial_instances(&instances, strategy.label, config.objective);
+    Ok(())
+}
+
+/// Solve a board and collect the states the search expanded
+///
+/// # Returns
+///
+/// `None` for the strategies that are not single tree searches
+fn explored_set(board: Board, strategy: StrategyEntry) -> Option<RankSet> {
+    let mut solver = strategy.build(&StrategyConfig::default());
+    if !solver.is_tree_search() {
+        return None;
+    }
+    solver.solve(board);
+    Some(solver.explored_boards().into_iter().collect())
+}
+
+/// Print how much the explored sets of two strategies overlap on one board
+///
+/// # Errors
+///
+/// Returns a usage error if either strategy is not a single tree search.
+pub fn overlap(
+    board: Board,
+    first: StrategyEntry,
+    second: StrategyEntry,
+) -> Result<(), CommandError> {
+    let (Some(first_set), Some(second_set)) =
+        (explored_set(board, first), explored_set(board, second))
+    else {
+        return Err(CommandError::Usage(
+            "Only DFS, BFS, UCS and Heuristic are single tree searches with an explored set"
+                .to_owned(),
+        ));
+    };
+
+    let overlap = Overlap::between(&first_set, &second_set);
+    print_overlap(board, (first.label, second.label), &overlap);
+    Ok(())
+}
+
+/// Print a board translated from one goal convention to another
+///
+/// # Errors
+///
+/// Returns an error if the conventions describe different puzzles.
+pub fn translate(
+    board: Board,
+    from: GoalConvention,
+    to: GoalConvention,
+) -> Result<(), CommandError> {
+    let translation = Translation::between(from, to)
+        .map_err(failed(&format!("Cannot translate from {from} to {to}")))?;
+    print_translation((board, from), (translation.board(board), to));
+    Ok(())
+}
+
+/// Run the self-test on `samples` random boards of `scramble_steps` moves
+///
+/// Without a `seed`, a random one is drawn and reported.
+///
+/// # Errors
+///
+/// Returns an error if a check fails.
+pub fn doctor(
+    samples: usize,
+    scramble_steps: usize,
+    seed: Option<u64>,
+) -> Result<(), CommandError> {
+    let seed = seed.unwrap_or_else(|| rng().random());
+    println!(
+        "Checking every strategy on {samples} boards ({scramble_steps} scramble moves, seed {seed})...\n"
+    );
+
+    let scramble = Scramble {
+        steps: scramble_steps,
+        model: ScrambleModel::default(),
+    };
+    let boards = generate_boards(seed, 0..samples, &scramble);
+    let report = doctor::run(&boards, StateSpaceDatabase::global());
+    print_doctor_report(&report);
+    if report.passed() {
+        Ok(())
+    } else {
+        Err(CommandError::Failed(format!(
+            "{} checks failed",
+            report.failures()
+        )))
+    }
+}
+
+/// The directory to write tables to: `dir`, or the data directory without one
+fn data_dir(dir: Option<PathBuf>) -> Result<PathBuf, CommandError> {
+    let dir = dir
+        .or_else(default_data_dir)
+        .ok_or_else(|| CommandError::Failed("No data directory found; pass --dir".to_owned()))?;
+    fs::create_dir_all(&dir).map_err(failed("Failed to create data directory"))?;
+    Ok(dir)
+}
+
+/// Build the requested pattern databases and write them to `dir`, or the data directory
+///
+/// # Errors
+///
+/// Returns a usage error if two patterns share tiles, and an error if a
+/// database cannot be written.
+pub fn pdb_build(patterns: &[Pattern], dir: Option<PathBuf>) -> Result<(), CommandError> {
+    for (i, pattern) in patterns.iter().enumerate() {
+        if let Some(other) = patterns[..i].iter().find(|p| !p.is_disjoint(pattern)) {
+            return Err(CommandError::Usage(format!(
+                "Patterns {other} and {pattern} share tiles, so their costs cannot be added"
+            )));
+        }
+    }
+
+    let dir = data_dir(dir)?;
+    for pattern in patterns {
+        println!("Building pattern database {pattern}...");
+        let database = PatternDatabase::build(pattern);
+        let path = pdb::cache_path(&dir, pattern);
+        database
+            .save(&path)
+            .map_err(failed("Failed to write pattern database"))?;
+        println!(
+            "  {} entries, {} bytes, max cost {} -> {}",
+            database.len(),
+            database.size_in_bytes(),
+            database.max_cost(),
+            path.display()
+        );
+    }
+    Ok(())
+}
+
+/// Learn a heuristic table by bootstrapping and write it to `file`
+///
+/// Prints one line per round with the boards solved within the node limit
+/// and the table cells learned so far.
+///
+/// # Errors
+///
+/// Returns an error if the table cannot be written.
+pub fn learn_heuristic(file: &Path, config: &BootstrapConfig) -> Result<(), CommandError> {
+    println!(
+        "Bootstrapping a heuristic from {} rounds of {} boards (seed {})...",
+        config.iterations, config.boards, config.seed
+    );
+    let (learned, _) = bootstrap::bootstrap(config, |round| {
+        println!(
+            "  {} steps: solved {}/{}, {} nodes explored, {} cells learned",
+            round.scramble_steps,
+            round.solved,
+            round.boards,
+            round.nodes_explored,
+            round.known_cells
+        );
+    });
+    learned
+        .save(file)
+        .map_err(failed("Failed to write learned heuristic"))?;
+    println!("Wrote {}", file.display());
+    Ok(())
+}
+
+/// Build the exact-distance table with a backward BFS and write it to `dir`, or
+/// the data directory
+///
+/// The oracle strategy, `lookup` and the optimality checks read it from there
+/// instead of building it again.
+///
+/// # Errors
+///
+/// Returns an error if the table cannot be written.
+pub fn oracle_build(dir: Option<PathBuf>) -> Result<(), CommandError> {
+    let dir = data_dir(dir)?;
+
+    println!("Building the distance table by backward breadth-first search from the goal...");
+    let started = Instant::now();
+    let table = StateSpaceDatabase::build();
+    let elapsed = started.elapsed();
+    let path = database::cache_path(&dir);
+    table
+        .save(&path)
+        .map_err(failed("Failed to write distance table"))?;
+    println!(
+        "  {} boards, max distance {}, built in {} ms -> {}",
+        database::STATE_COUNT,
+        table.max_distance(),
+        elapsed.as_millis(),
+        path.display()
+    );
+    Ok(())
+}
@@ -0,0 +1,176 @@
+//! # Compress Module
+//!
+//! Post-processing of non-optimal solutions. A depth-first search returns
+//! whatever path it stumbled on first, often thousands of moves long, most of
+//! it detours that wander around and come back close to where they left.
+//! [`compress`] cuts those detours: from every board of the path it searches
+//! the boards within [`COMPRESSION_RADIUS`] moves, and if one of them appears
+//! later on the path by a longer route, it splices in the short route instead.
+//! Passes repeat until one saves nothing, which gives the canonical form of a
+//! solution: it never visits a board twice and no segment of it can be
+//! replaced by a shorter one within the radius.
+//!
+//! Compression only looks at short windows, so the compressed path is usually
+//! still longer than an optimal one. Optimal paths come back unchanged.
+
+use std::collections::{HashMap, VecDeque};
+
+use crate::board::{ALL_DIRECTIONS, Board};
+
+/// Farthest distance, in moves, a shortcut may cover
+///
+/// Every cycle of the 3x3 puzzle is at least 12 moves long, so this radius is
+/// enough to replace the longer half of any smallest cycle by its shorter half.
+pub const COMPRESSION_RADIUS: usize = 6;
+
+/// Removes detours from a solution path, keeping its start and end
+///
+/// # Arguments
+///
+/// * `path` - Boards from the start to the goal, each one move from the previous
+///
+/// # Returns
+///
+/// A path with the same start and end, each board one move from the previous,
+/// that is never longer than `path`
+#[must_use]
+pub fn compress(path: &[Board]) -> Vec<Board> {
+    let mut compressed = path.to_vec();
+    loop {
+        let shorter = compress_pass(&compressed);
+        if shorter.len() == compressed.len() {
+            return compressed;
+        }
+        compressed = shorter;
+    }
+}
+
+/// Number of moves of the compressed form of a solution path
+///
+/// # Arguments
+///
+/// * `path` - Boards from the start to the goal, each one move from the previous
+#[must_use]
+pub fn compressed_moves(path: &[Board]) -> usize {
+    compress(path).len().saturating_sub(1)
+}
+
+/// Walks the path once, taking the shortcut that saves the most moves from every board
+fn compress_pass(path: &[Board]) -> Vec<Board> {
+    let Some(&start) = path.first() else {
+        return Vec::new();
+    };
+    let last_index: HashMap<Board, usize> = path
+        .iter()
+        .enumerate()
+        .map(|(index, &board)| (board, index))
+        .collect();
+
+    let mut compressed = vec![start];
+    let mut index = last_index[&start];
+    while index + 1 < path.len() {
+        let parents = neighborhood(path[index]);
+        let (target, _) = parents
+            .iter()
+            .filter_map(|(board, &(_, distance))| {
+                let later = *last_index.get(board)?;
+                (later > index).then(|| (later, later - index - distance))
+            })
+            .max_by_key(|&(later, saved)| (saved, later))
+            .unwrap_or((index + 1, 0));
+
+        let mut segment = Vec::new();
+        let mut board = path[target];
+        while board != path[index] {
+            segment.push(board);
+            board = parents[&board].0;
+        }
+        compressed.extend(segment.into_iter().rev());
+        index = target;
+    }
+    compressed
+}
+
+/// Boards within [`COMPRESSION_RADIUS`] moves of `center`, each with its
+/// parent on a shortest route from `center` and its distance
+fn neighborhood(center: Board) -> HashMap<Board, (Board, usize)> {
+    let mut parents = HashMap::from([(center, (center, 0))]);
+    let mut queue = VecDeque::from([(center, 0)]);
+    while let Some((board, distance)) = queue.pop_front() {
+        if distance == COMPRESSION_RADIUS {
+            continue;
+        }
+        for direction in ALL_DIRECTIONS {
+            let Ok(next) = board.move_space(direction) else {
+                continue;
+            };
+            parents.entry(next).or_insert_with(|| {
+                queue.push_back((next, distance + 1));
+                (board, distance + 1)
+            });
+        }
+    }
+    parents
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::seq::IndexedRandom;
+    use rand::{Rng, SeedableRng};
+
+    use super::*;
+    use crate::board::Direction;
+
+    /// A random walk of `steps` moves from the goal, reversed so it ends there
+    fn detour_path(steps: usize, rng: &mut impl Rng) -> Vec<Board> {
+        let mut path = vec![Board::default()];
+        for _ in 0..steps {
+            let board = *path.last().expect("Never empty");
+            let successors: Vec<Board> = board.successors().map(|(_, next)| next).collect();
+            path.push(*successors.choose(rng).expect("Every board has a move"));
+        }
+        path.reverse();
+        path
+    }
+
+    fn assert_walk(path: &[Board]) {
+        for pair in path.windows(2) {
+            assert!(pair[0].successors().any(|(_, next)| next == pair[1]));
+        }
+    }
+
+    #[test]
+    fn never_lengthens_a_path_and_still_reaches_the_goal() {
+        let mut rng = StdRng::seed_from_u64(492);
+        for steps in [0, 1, 2, 12, 50, 200, 1000] {
+            let path = detour_path(steps, &mut rng);
+            let compressed = compress(&path);
+            assert!(compressed.len() <= path.len());
+            assert_eq!(compressed.first(), path.first());
+            assert!(compressed.last().is_some_and(|last| last.is_solved()));
+            assert_walk(&compressed);
+            assert_eq!(compressed_moves(&path), compressed.len() - 1);
+        }
+    }
+
+    #[test]
+    fn cuts_a_move_and_its_undo() {
+        let start = Board::default();
+        let away = start.successors().next().expect("The goal has moves").1;
+        assert_eq!(compress(&[start, away, start]), [start]);
+    }
+
+    #[test]
+    fn leaves_a_shortest_path_unchanged() {
+        let mut path = vec![Board::default()];
+        for direction in [Direction::Up, Direction::Left, Direction::Down] {
+            let next = path[path.len() - 1]
+                .move_space(direction)
+                .expect("Legal move");
+            path.push(next);
+        }
+        path.reverse();
+        assert_eq!(compress(&path), path);
+    }
+}
@@ -0,0 +1,188 @@
+//! # Convention Module
+//!
+//! Translates boards and solutions between goal conventions. This crate
+//! solves towards the spiral goal `123804765`, while many solvers and
+//! published tables use a row-major goal such as `123456780` or `012345678`.
+//!
+//! Two conventions are equivalent when a symmetry of the square moves the
+//! empty cell of one goal onto the empty cell of the other. The board is then
+//! rotated or reflected by that symmetry and its tiles relabeled, which maps
+//! every position and every move of one puzzle onto the other: distances are
+//! equal and solutions translate step by step.
+//!
+//! Goals whose empty cells lie in different orbits of the square (center,
+//! corners, edges) are different puzzles, not relabelings of one another:
+//! the spiral goal keeps the empty space in the center and its hardest boards
+//! are 30 moves away, while the corner goals need up to 31 moves. Translating
+//! between them is refused rather than producing numbers that look
+//! comparable but are not.
+
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+#[cfg(feature = "cli")]
+use clap::ValueEnum;
+
+use crate::board::{BOARD_AREA, BOARD_SIDE, Board, Direction};
+
+/// A goal arrangement used by some solver or table
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(ValueEnum))]
+pub enum GoalConvention {
+    /// `123804765`: tiles in a clockwise spiral around a central empty space (this crate)
+    #[default]
+    Spiral,
+    /// `123456780`: tiles in reading order, empty space in the bottom-right corner
+    RowMajor,
+    /// `012345678`: empty space in the top-left corner, tiles in reading order after it
+    BlankFirst,
+}
+
+impl GoalConvention {
+    /// Every supported convention
+    pub const ALL: [GoalConvention; 3] = [
+        GoalConvention::Spiral,
+        GoalConvention::RowMajor,
+        GoalConvention::BlankFirst,
+    ];
+
+    /// Lower-case name of the convention (`"spiral"`, `"row-major"`, `"blank-first"`)
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            GoalConvention::Spiral => "spiral",
+            GoalConvention::RowMajor => "row-major",
+            GoalConvention::BlankFirst => "blank-first",
+        }
+    }
+
+    /// Goal cells in row-major order, with 0 for the empty space
+    #[must_use]
+    pub fn goal(self) -> [u8; BOARD_AREA as usize] {
+        match self {
+            GoalConvention::Spiral => [1, 2, 3, 8, 0, 4, 7, 6, 5],
+            GoalConvention::RowMajor => [1, 2, 3, 4, 5, 6, 7, 8, 0],
+            GoalConvention::BlankFirst => [0, 1, 2, 3, 4, 5, 6, 7, 8],
+        }
+    }
+}
+
+impl Display for GoalConvention {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for GoalConvention {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        GoalConvention::ALL
+            .into_iter()
+            .find(|convention| convention.name() == s.trim())
+            .ok_or("Invalid goal convention: expected spiral, row-major or blank-first")
+    }
+}
+
+/// A symmetry of the square, as a map of (row, column)
+type Symmetry = fn(u8, u8) -> (u8, u8);
+
+/// The eight symmetries of the square
+const SYMMETRIES: [Symmetry; 8] = [
+    |r, c| (r, c),
+    |r, c| (c, BOARD_SIDE - 1 - r),
+    |r, c| (BOARD_SIDE - 1 - r, BOARD_SIDE - 1 - c),
+    |r, c| (BOARD_SIDE - 1 - c, r),
+    |r, c| (r, BOARD_SIDE - 1 - c),
+    |r, c| (BOARD_SIDE - 1 - r, c),
+    |r, c| (c, r),
+    |r, c| (BOARD_SIDE - 1 - c, BOARD_SIDE - 1 - r),
+];
+
+/// A relabeling of positions and tiles that maps one goal convention onto another
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Translation {
+    /// Cell each cell of the source board moves to
+    cells: [u8; BOARD_AREA as usize],
+    /// New label of each tile, indexed by its old label (0 stays 0)
+    labels: [u8; BOARD_AREA as usize],
+}
+
+impl Translation {
+    /// Finds the translation from `from` to `to`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no symmetry of the square maps the empty cell of
+    /// one goal onto the other, i.e. the conventions describe different puzzles
+    ///
+    /// # Panics
+    ///
+    /// Never panics in practice: every goal has an empty space
+    pub fn between(from: GoalConvention, to: GoalConvention) -> Result<Self, &'static str> {
+        let (source, target) = (from.goal(), to.goal());
+        let blank = |goal: &[u8; BOARD_AREA as usize]| {
+            goal.iter()
+                .position(|&c| c == 0)
+                .expect("Every goal has an empty space")
+        };
+        let (source_blank, target_blank) = (blank(&source), blank(&target));
+
+        let symmetry = SYMMETRIES
+            .into_iter()
+            .find(|symmetry| map_cell(*symmetry, source_blank) == target_blank)
+            .ok_or("Goal conventions with the empty space in different kinds of cells are different puzzles")?;
+
+        let mut cells = [0; BOARD_AREA as usize];
+        let mut labels = [0; BOARD_AREA as usize];
+        for (cell, &tile) in source.iter().enumerate() {
+            let moved = map_cell(symmetry, cell);
+            cells[cell] = u8::try_from(moved).expect("Cells fit in a u8");
+            labels[usize::from(tile)] = target[moved];
+        }
+        Ok(Self { cells, labels })
+    }
+
+    /// Translates a board
+    ///
+    /// The result is meant to be solved towards the target goal, so it is only
+    /// [`Board::is_solved`] when the target is the spiral goal.
+    #[must_use]
+    pub fn board(&self, board: Board) -> Board {
+        let mut arr = [0; BOARD_AREA as usize];
+        for (cell, tile) in board.into_arr().into_iter().enumerate() {
+            arr[usize::from(self.cells[cell])] = self.labels[usize::from(tile)];
+        }
+        Board::from_arr(&arr)
+    }
+
+    /// Translates every board of a solution path
+    #[must_use]
+    pub fn path(&self, path: &[Board]) -> Vec<Board> {
+        path.iter().map(|&board| self.board(board)).collect()
+    }
+
+    /// Translates a move, so move lists can be compared without replaying boards
+    ///
+    /// # Panics
+    ///
+    /// Never panics in practice: symmetries keep the center cell, from which
+    /// every move is legal
+    #[must_use]
+    pub fn direction(&self, direction: Direction) -> Direction {
+        let center = Board::default();
+        let moved = center
+            .move_space(direction)
+            .expect("Every move is legal from the center");
+        self.board(center)
+            .direction_to(self.board(moved))
+            .expect("Symmetries preserve adjacency")
+    }
+}
+
+/// Applies a symmetry to a row-major cell index
+fn map_cell(symmetry: Symmetry, cell: usize) -> usize {
+    let cell = u8::try_from(cell).expect("Cells fit in a u8");
+    let (row, col) = symmetry(cell / BOARD_SIDE, cell % BOARD_SIDE);
+    usize::from(row * BOARD_SIDE + col)
+}
@@ -0,0 +1,159 @@
+//! # Move Cost Module
+//!
+//! The cost of a move is one by default, so the cheapest solution is the
+//! shortest. [`MoveCosts`] instead charges each tile its own cost whenever it
+//! slides, as in the weighted sliding-tile puzzles of the search literature,
+//! where moving tile `k` usually costs `k`. The generic
+//! [`Solver`](crate::solver::Solver) then counts the g-cost of its nodes in
+//! these units, so uniform-cost and heuristic searches return the cheapest
+//! solution rather than the shortest one.
+//!
+//! Every cost is at least one, so a heuristic that never overestimates the
+//! number of moves never overestimates the cost either. The
+//! [weighted Manhattan distance](MoveCosts::weighted_manhattan) charges each
+//! tile's distance at its cost and is the tighter estimate.
+
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use crate::board::{BOARD_AREA, Board};
+
+/// Number of tiles, the empty space not counted
+const TILES: usize = BOARD_AREA as usize - 1;
+
+/// Cost of sliding each tile one cell
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MoveCosts {
+    /// `costs[k - 1]`: cost of moving tile `k`
+    costs: [usize; TILES],
+}
+
+impl Default for MoveCosts {
+    fn default() -> Self {
+        Self::unit()
+    }
+}
+
+impl MoveCosts {
+    /// Every move costs one, so the cost of a solution is its length
+    #[must_use]
+    pub fn unit() -> Self {
+        Self { costs: [1; TILES] }
+    }
+
+    /// Moving tile `k` costs `k`
+    #[must_use]
+    pub fn tile_number() -> Self {
+        Self {
+            costs: std::array::from_fn(|index| index + 1),
+        }
+    }
+
+    /// Moving tile `k` costs `costs[k - 1]`
+    ///
+    /// # Errors
+    ///
+    /// Fails if a cost is zero, which would make free moves and heuristics
+    /// counted in moves inadmissible
+    pub fn from_costs(costs: [usize; TILES]) -> Result<Self, &'static str> {
+        if costs.contains(&0) {
+            return Err("Invalid move costs: every cost must be at least 1");
+        }
+        Ok(Self { costs })
+    }
+
+    /// Whether every move costs one
+    #[must_use]
+    pub fn is_unit(&self) -> bool {
+        self.costs == [1; TILES]
+    }
+
+    /// Cost of moving `tile` (1-8)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tile` is not between 1 and 8
+    #[must_use]
+    pub fn cost(&self, tile: u8) -> usize {
+        self.costs[usize::from(tile) - 1]
+    }
+
+    /// Cost of the move from `board` to `next`, its neighbor
+    ///
+    /// The moved tile is the one `next` holds where `board` had the empty space.
+    #[must_use]
+    pub fn move_cost(&self, board: Board, next: Board) -> usize {
+        next.get_tile(board.find_space_position())
+            .map_or(0, |tile| self.cost(tile))
+    }
+
+    /// Total cost of the moves along `path`
+    #[must_use]
+    pub fn path_cost(&self, path: &[Board]) -> usize {
+        path.windows(2)
+            .map(|pair| self.move_cost(pair[0], pair[1]))
+            .sum()
+    }
+
+    /// Sum of each tile's Manhattan distance times its cost, capped to `u8::MAX`
+    ///
+    /// Every tile has to slide at least its Manhattan distance, paying its
+    /// cost each time, so the estimate is admissible; under unit costs it is
+    /// [`Board::heuristic_distance_to_solution`].
+    #[must_use]
+    pub fn weighted_manhattan(&self, board: Board) -> u8 {
+        self.weighted_manhattan_to(board, Board::default())
+    }
+
+    /// The [weighted Manhattan distance](MoveCosts::weighted_manhattan) from `board` to `goal`
+    #[must_use]
+    pub fn weighted_manhattan_to(&self, board: Board, goal: Board) -> u8 {
+        let total: usize = board
+            .manhattan_contributions_to(goal)
+            .into_iter()
+            .zip(0..BOARD_AREA)
+            .filter_map(|(distance, cell)| {
+                let tile = board.get_tile(cell)?;
+                Some(usize::from(distance) * self.cost(tile))
+            })
+            .sum();
+        u8::try_from(total).unwrap_or(u8::MAX)
+    }
+}
+
+impl Display for MoveCosts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_unit() {
+            return f.write_str("unit");
+        }
+        if *self == Self::tile_number() {
+            return f.write_str("tile");
+        }
+        let costs: Vec<String> = self.costs.iter().map(ToString::to_string).collect();
+        f.write_str(&costs.join(","))
+    }
+}
+
+/// Parses `unit`, `tile`, or the costs of tiles 1 to 8 separated by commas
+impl FromStr for MoveCosts {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "unit" => Ok(Self::unit()),
+            "tile" => Ok(Self::tile_number()),
+            table => {
+                let costs: Vec<usize> = table
+                    .split(',')
+                    .map(|cost| cost.trim().parse())
+                    .collect::<Result<_, _>>()
+                    .map_err(
+                        |_| "Invalid move costs: expected unit, tile or 8 comma-separated costs",
+                    )?;
+                let costs = <[usize; TILES]>::try_from(costs)
+                    .map_err(|_| "Invalid move costs: expected one cost for each of the 8 tiles")?;
+                Self::from_costs(costs)
+            }
+        }
+    }
+}
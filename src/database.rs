@@ -0,0 +1,378 @@
+//! # Database Module
+//!
+//! A complete table of exact distances to the goal for every reachable 3x3
+//! board. The table is built with a single backward breadth-first search from
+//! the solved board over all 181,440 reachable states, and stored as one byte
+//! per state (about 180 KB) indexed by a compact rank of the board.
+//!
+//! Building takes a fraction of a second, but the table is cached on disk
+//! under a data directory (see [`default_data_dir`]) so later processes only
+//! read it back. With the table in hand, exact distances, perfect hints,
+//! optimality audits and difficulty grades are all O(1) lookups.
+//!
+//! ## Ranking
+//!
+//! A board is ranked as `blank_position * 20160 + lehmer(tiles) / 2`, where
+//! `lehmer(tiles)` is the Lehmer code of the eight tiles in reading order.
+//! On a 3x3 board every reachable state has the same inversion parity as the
+//! goal, which fixes the last significant digit of the Lehmer code, so halving
+//! it loses nothing and the ranks are dense in `0..181440`.
+
+use std::collections::VecDeque;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use crate::board::{ALL_DIRECTIONS, Board, Direction, Parity};
+use crate::persist::{Format, read_checked, write_atomic};
+
+/// Number of states reachable from the goal on a 3x3 board (9! / 2)
+pub const STATE_COUNT: usize = 181_440;
+
+/// Number of tile arrangements per blank position (8! / 2)
+const ARRANGEMENTS: usize = 20_160;
+
+/// Marker for states not reached (yet) by the backward search
+const UNKNOWN: u8 = u8::MAX;
+
+/// Header of a database file
+const FORMAT: Format = Format {
+    magic: *b"O8SS",
+    version: 2,
+    name: "state-space database",
+};
+
+/// File name of the cached table inside the data directory
+const FILE_NAME: &str = "distances-v2.bin";
+
+/// Coarse difficulty grade of a board, based on its exact distance
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Difficulty {
+    /// At most 10 moves from the goal
+    Easy,
+    /// 11 to 20 moves from the goal
+    Medium,
+    /// 21 to 26 moves from the goal
+    Hard,
+    /// More than 26 moves from the goal
+    Expert,
+}
+
+impl Difficulty {
+    /// Grades a board from its exact distance to the goal
+    #[must_use]
+    pub fn from_distance(distance: u8) -> Self {
+        match distance {
+            0..=10 => Difficulty::Easy,
+            11..=20 => Difficulty::Medium,
+            21..=26 => Difficulty::Hard,
+            _ => Difficulty::Expert,
+        }
+    }
+
+    /// Lower-case name of the grade (`"easy"`, `"medium"`, `"hard"`, `"expert"`)
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Medium => "medium",
+            Difficulty::Hard => "hard",
+            Difficulty::Expert => "expert",
+        }
+    }
+}
+
+impl FromStr for Difficulty {
+    type Err = &'static str;
+
+    /// Parses a grade from its lower-case name (see [`Difficulty::name`])
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "easy" => Ok(Difficulty::Easy),
+            "medium" => Ok(Difficulty::Medium),
+            "hard" => Ok(Difficulty::Hard),
+            "expert" => Ok(Difficulty::Expert),
+            _ => Err("Invalid difficulty: expected easy, medium, hard or expert"),
+        }
+    }
+}
+
+/// Exact distance to the goal of every reachable board
+#[derive(Clone)]
+pub struct StateSpaceDatabase {
+    /// Distance of each state, indexed by rank
+    distances: Box<[u8]>,
+}
+
+impl StateSpaceDatabase {
+    /// Builds the table with a backward breadth-first search from the goal
+    ///
+    /// # Panics
+    ///
+    /// Never panics in practice: every board reached from the goal is ranked
+    #[must_use]
+    pub fn build() -> Self {
+        let mut distances = vec![UNKNOWN; STATE_COUNT].into_boxed_slice();
+        let mut queue = VecDeque::with_capacity(STATE_COUNT);
+
+        let goal = Board::default();
+        distances[rank(goal).expect("The goal is always reachable")] = 0;
+        queue.push_back((goal, 0u8));
+
+        while let Some((board, distance)) = queue.pop_front() {
+            for direction in ALL_DIRECTIONS {
+                let Ok(next) = board.move_space(direction) else {
+                    continue;
+                };
+                let index = rank(next).expect("Moves preserve solvability");
+                if distances[index] == UNKNOWN {
+                    distances[index] = distance + 1;
+                    queue.push_back((next, distance + 1));
+                }
+            }
+        }
+
+        Self { distances }
+    }
+
+    /// Loads the cached table from `dir`, building and caching it if needed
+    ///
+    /// A missing or corrupt cache file is rebuilt. Writing the cache is best
+    /// effort: if the directory is not writable, the freshly built table is
+    /// still returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - Data directory holding the cache file
+    #[must_use]
+    pub fn open_or_build(dir: &Path) -> Self {
+        let path = cache_path(dir);
+        if let Ok(database) = Self::load(&path) {
+            return database;
+        }
+
+        let database = Self::build();
+        let _ = fs::create_dir_all(dir).and_then(|()| database.save(&path));
+        database
+    }
+
+    /// Returns a process-wide table, loaded from [`default_data_dir`] on first use
+    ///
+    /// If no data directory can be determined, the table is built in memory.
+    pub fn global() -> &'static Self {
+        static DATABASE: OnceLock<StateSpaceDatabase> = OnceLock::new();
+        DATABASE.get_or_init(|| match default_data_dir() {
+            Some(dir) => Self::open_or_build(&dir),
+            None => Self::build(),
+        })
+    }
+
+    /// Reads a table previously written by [`StateSpaceDatabase::save`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is not a valid table
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let raw = fs::read(path)?;
+        let distances = read_checked(&raw, &FORMAT)?;
+        if distances.len() != STATE_COUNT || distances.contains(&UNKNOWN) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "corrupt state-space database",
+            ));
+        }
+
+        Ok(Self {
+            distances: distances.into(),
+        })
+    }
+
+    /// Writes the table to `path`, replacing any previous file atomically
+    ///
+    /// # Errors
+    ///
+    /// Returns any I/O error raised while writing the file
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        write_atomic(path, &FORMAT, &self.distances)
+    }
+
+    /// Exact number of moves from `board` to the goal
+    ///
+    /// # Returns
+    ///
+    /// `Some(distance)`, or `None` if the board cannot reach the goal
+    #[must_use]
+    pub fn distance(&self, board: Board) -> Option<u8> {
+        rank(board).map(|index| self.distances[index])
+    }
+
+    /// An optimal next move of the empty space
+    ///
+    /// # Returns
+    ///
+    /// `Some(direction)`, or `None` if the board is solved or unsolvable
+    #[must_use]
+    pub fn best_move(&self, board: Board) -> Option<Direction> {
+        let distance = self.distance(board)?;
+        ALL_DIRECTIONS.into_iter().find(|&direction| {
+            board
+                .move_space(direction)
+                .is_ok_and(|next| self.distance(next) == distance.checked_sub(1))
+        })
+    }
+
+    /// An optimal solution, found by following [`StateSpaceDatabase::best_move`] to the goal
+    ///
+    /// # Returns
+    ///
+    /// `Some(path)` with every board from `board` to the goal, or `None` if
+    /// the board cannot reach the goal
+    #[must_use]
+    pub fn solution(&self, board: Board) -> Option<Vec<Board>> {
+        let distance = self.distance(board)?;
+        let mut path = Vec::with_capacity(usize::from(distance) + 1);
+        path.push(board);
+        let mut current = board;
+        while let Some(next) = self
+            .best_move(current)
+            .and_then(|direction| current.move_space(direction).ok())
+        {
+            path.push(next);
+            current = next;
+        }
+        Some(path)
+    }
+
+    /// Checks that a solution path is valid and as short as possible
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Boards from start to goal, as returned by `Solver::step_by_step_solution`
+    ///
+    /// # Returns
+    ///
+    /// `true` if every step is a single move, the path ends at the goal and
+    /// its length equals the exact distance of the start
+    #[must_use]
+    pub fn is_optimal(&self, path: &[Board]) -> bool {
+        let (Some(&start), Some(&end)) = (path.first(), path.last()) else {
+            return false;
+        };
+
+        end.is_solved()
+            && path
+                .windows(2)
+                .all(|pair| pair[0].direction_to(pair[1]).is_some())
+            && self.distance(start).map(usize::from) == Some(path.len() - 1)
+    }
+
+    /// Difficulty grade of a board
+    ///
+    /// # Returns
+    ///
+    /// `Some(grade)`, or `None` if the board cannot reach the goal
+    #[must_use]
+    pub fn difficulty(&self, board: Board) -> Option<Difficulty> {
+        self.distance(board).map(Difficulty::from_distance)
+    }
+
+    /// Number of states at each distance from the goal, indexed by distance
+    #[must_use]
+    pub fn histogram(&self) -> Vec<usize> {
+        let mut counts = vec![0; usize::from(self.max_distance()) + 1];
+        for &distance in &self.distances {
+            counts[usize::from(distance)] += 1;
+        }
+        counts
+    }
+
+    /// Every board exactly `distance` moves from the goal, in rank order
+    pub fn boards_at(&self, distance: u8) -> impl Iterator<Item = Board> + '_ {
+        self.distances
+            .iter()
+            .enumerate()
+            .filter(move |&(_, &d)| d == distance)
+            .map(|(index, _)| unrank(index))
+    }
+
+    /// Largest distance of any state (the puzzle's God's number)
+    #[must_use]
+    pub fn max_distance(&self) -> u8 {
+        self.distances.iter().copied().max().unwrap_or(0)
+    }
+}
+
+/// Path of the cached table inside the data directory `dir`
+#[must_use]
+pub fn cache_path(dir: &Path) -> PathBuf {
+    dir.join(FILE_NAME)
+}
+
+/// Directory where cached tables are stored
+///
+/// Uses `$O8_DATA_DIR` if set, otherwise the platform data directory
+/// (`$XDG_DATA_HOME/o8`, `~/.local/share/o8` or `%LOCALAPPDATA%\o8`).
+#[must_use]
+pub fn default_data_dir() -> Option<PathBuf> {
+    let var = |name: &str| std::env::var_os(name).filter(|v| !v.is_empty());
+
+    var("O8_DATA_DIR").map(PathBuf::from).or_else(|| {
+        var("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .or_else(|| var("HOME").map(|home| Path::new(&home).join(".local/share")))
+            .or_else(|| var("LOCALAPPDATA").map(PathBuf::from))
+            .map(|base| base.join("o8"))
+    })
+}
+
+/// Computes the dense rank of a board (see the module documentation)
+///
+/// # Returns
+///
+/// `Some(rank)` in `0..STATE_COUNT`, or `None` if the board cannot reach the goal
+pub(crate) fn rank(board: Board) -> Option<usize> {
+    let cells = board.into_arr();
+    let blank = cells.iter().position(|&c| c == 0)?;
+
+    let mut code = 0;
+    let mut inversions = 0;
+    let mut tiles = cells.iter().filter(|&&c| c != 0);
+    for radix in (1..=8).rev() {
+        let &tile = tiles.next()?;
+        let smaller = tiles.clone().filter(|&&t| t < tile).count();
+        code = code * radix + smaller;
+        inversions += smaller;
+    }
+
+    (inversions % 2 == goal_parity()).then_some(blank * ARRANGEMENTS + code / 2)
+}
+
+/// Rebuilds the board with the given dense rank (the inverse of [`rank`])
+pub(crate) fn unrank(index: usize) -> Board {
+    let (blank, mut code) = (index / ARRANGEMENTS, index % ARRANGEMENTS);
+
+    // Recover the six significant Lehmer digits; the seventh is fixed by the
+    // goal parity and the eighth is always zero
+    let mut digits = [0; 8];
+    for i in (0..6).rev() {
+        digits[i] = code % (8 - i);
+        code /= 8 - i;
+    }
+    digits[6] = (goal_parity() + digits.iter().sum::<usize>()) % 2;
+
+    let mut remaining: Vec<u8> = (1..=8).collect();
+    let mut cells: Vec<u8> = digits.iter().map(|&d| remaining.remove(d)).collect();
+    cells.insert(blank, 0);
+
+    let mut arr = [0; 9];
+    arr.copy_from_slice(&cells);
+    Board::from_arr(&arr)
+}
+
+/// Inversion parity of the goal's tiles, shared by every reachable board, as 0 or 1
+fn goal_parity() -> usize {
+    static PARITY: OnceLock<usize> = OnceLock::new();
+    *PARITY.get_or_init(|| usize::from(Board::default().parity() == Parity::Odd))
+}
@@ -0,0 +1,173 @@
+//! # Branch-and-Bound Module
+//!
+//! Depth-first branch-and-bound: a depth-first search that keeps the best
+//! solution found so far, the incumbent, and prunes every node whose f-value
+//! `g + h` is no lower than the incumbent's length, since with an admissible
+//! heuristic nothing below it can be shorter. Each solution it reaches
+//! becomes the new incumbent and tightens the bound, and once the tree is
+//! exhausted the incumbent is optimal.
+//!
+//! Like IDA*, the search only remembers the path it is on, which also serves
+//! to skip moves back onto it; unlike IDA*, it has a solution from its first
+//! descent on, so it can be stopped at any time. Children are visited lowest
+//! `h` first, so the first descent is greedy and quick. Each incumbent is
+//! also cut down to its detour-free form (see
+//! [`compress`](crate::compress::compress)), which is a solution of its own
+//! and makes the bound tighter sooner.
+
+use crate::time::Instant;
+use std::cmp::Reverse;
+use std::collections::HashSet;
+
+use crate::board::{ALL_DIRECTIONS, Board};
+use crate::compress::compress;
+use crate::stats::Stats;
+
+/// A solution that was the best one known at some point of the search
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Incumbent {
+    /// Length of the solution in moves
+    pub moves: usize,
+    /// Nodes explored when it was found
+    pub nodes_explored: usize,
+}
+
+/// A solution found by depth-first branch-and-bound
+#[derive(Clone, Debug)]
+pub struct DfbnbSolution {
+    /// Every board from the start to the goal
+    pub path: Vec<Board>,
+    /// Every incumbent in the order found, ending with the optimal one
+    pub incumbents: Vec<Incumbent>,
+    /// Work of the whole search; `max_frontier` counts the longest path held
+    pub stats: Stats,
+}
+
+/// A board on the current path and its children left to visit, the next one last
+struct Frame {
+    board: Board,
+    children: Vec<(usize, Board)>,
+}
+
+/// State of the search
+struct BranchAndBound {
+    /// Boards of the current path, from the start
+    path: Vec<Frame>,
+    /// The same boards, to skip moves back onto the path
+    on_path: HashSet<Board>,
+    /// Best solution found so far, empty before the first
+    best: Vec<Board>,
+    incumbents: Vec<Incumbent>,
+    stats: Stats,
+}
+
+impl BranchAndBound {
+    /// Length of the incumbent, the bound every node's f-value must stay below
+    fn bound(&self) -> usize {
+        self.best.len().checked_sub(1).unwrap_or(usize::MAX)
+    }
+
+    /// Puts `board` on the path with its children that are not already on it
+    fn push(&mut self, board: Board) {
+        self.stats.nodes_explored += 1;
+        let mut children = Vec::with_capacity(ALL_DIRECTIONS.len());
+        for direction in ALL_DIRECTIONS {
+            let Ok(child) = board.move_space(direction) else {
+                continue;
+            };
+            self.stats.generated_nodes += 1;
+            if self.on_path.contains(&child) {
+                self.stats.duplicates_pruned += 1;
+                continue;
+            }
+            children.push((usize::from(child.heuristic_distance_to_solution()), child));
+        }
+        // Stable, so children with equal estimates keep the fixed move order
+        children.sort_by_key(|&(h, _)| Reverse(h));
+        self.stats.enqueued_nodes += children.len();
+
+        self.on_path.insert(board);
+        self.path.push(Frame { board, children });
+        self.stats.max_frontier = self.stats.max_frontier.max(self.path.len());
+        self.stats.max_depth_reached = self.stats.max_depth_reached.max(self.path.len() - 1);
+    }
+
+    /// Makes the path to `goal` the incumbent, cut down to its detour-free form
+    fn improve(&mut self, goal: Board) {
+        let mut solution: Vec<Board> = self.path.iter().map(|frame| frame.board).collect();
+        solution.push(goal);
+        self.best = compress(&solution);
+        self.incumbents.push(Incumbent {
+            moves: self.best.len() - 1,
+            nodes_explored: self.stats.nodes_explored,
+        });
+    }
+
+    /// Visits the next child on the path, or backtracks when none is left
+    ///
+    /// # Returns
+    ///
+    /// `false` once the whole tree below the bound has been searched
+    fn step(&mut self) -> bool {
+        let depth = self.path.len();
+        let Some(frame) = self.path.last_mut() else {
+            return false;
+        };
+        let Some((h, child)) = frame.children.pop() else {
+            let board = frame.board;
+            self.path.pop();
+            self.on_path.remove(&board);
+            return true;
+        };
+
+        if depth + h >= self.bound() {
+            self.stats.bound_pruned += 1;
+        } else if child.is_solved() {
+            self.improve(child);
+        } else {
+            self.push(child);
+        }
+        true
+    }
+}
+
+/// Solves a board optimally with depth-first branch-and-bound
+///
+/// # Returns
+///
+/// `Some(solution)`, or `None` if the board cannot reach the goal
+#[must_use]
+pub fn solve(board: Board) -> Option<DfbnbSolution> {
+    let started = Instant::now();
+    if !board.is_solvable() {
+        return None;
+    }
+
+    let mut search = BranchAndBound {
+        path: Vec::new(),
+        on_path: HashSet::new(),
+        best: Vec::new(),
+        incumbents: Vec::new(),
+        stats: Stats::default(),
+    };
+    if board.is_solved() {
+        search.best.push(board);
+        search.incumbents.push(Incumbent {
+            moves: 0,
+            nodes_explored: 0,
+        });
+    } else {
+        search.push(board);
+        while search.step() {}
+    }
+
+    let mut stats = search.stats;
+    stats.solution_moves = search.best.len() - 1;
+    stats.solution_cost = stats.solution_moves;
+    stats.duration_ms = started.elapsed().as_millis();
+    Some(DfbnbSolution {
+        path: search.best,
+        incumbents: search.incumbents,
+        stats,
+    })
+}
@@ -0,0 +1,314 @@
+//! # Doctor Module
+//!
+//! A self-test that runs at runtime instead of at build time: every strategy
+//! solves a sample of boards, each solution is verified by replaying its
+//! moves, strategies that promise optimal solutions are compared against the
+//! exact-distance table, and heuristics are spot-checked for admissibility.
+//!
+//! [`run`] checks the built-in strategies and the Manhattan and walking
+//! distance heuristics.
+//! Custom strategies and heuristics can be checked with [`check_strategy`]
+//! and [`check_admissible`], and their results added to a [`DoctorReport`].
+
+use std::fmt::{self, Display};
+
+use crate::board::{Board, BoardWithSteps};
+use crate::database::StateSpaceDatabase;
+use crate::human;
+use crate::search_strategies::{HeuristicSearchStrategy, SearchStrategy, SimpleSearchStrategy};
+use crate::solver::{ExplorerStrategy, Solver};
+use crate::walking_distance;
+
+/// Number of boards per distance used by the admissibility spot-checks
+pub const ADMISSIBILITY_SAMPLES_PER_DISTANCE: usize = 16;
+
+/// Outcome of a single check
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CheckResult {
+    /// What was checked, e.g. `"BFS solutions are optimal"`
+    pub name: String,
+    /// Whether the check passed
+    pub passed: bool,
+    /// Summary on success, or the first offending board on failure
+    pub detail: String,
+}
+
+impl CheckResult {
+    /// Builds a check result from the first failure found, if any
+    fn from_failure(name: String, failure: Option<String>, success: String) -> Self {
+        match failure {
+            Some(detail) => Self {
+                name,
+                passed: false,
+                detail,
+            },
+            None => Self {
+                name,
+                passed: true,
+                detail: success,
+            },
+        }
+    }
+}
+
+impl Display for CheckResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let status = if self.passed { "PASS" } else { "FAIL" };
+        write!(f, "[{status}] {}: {}", self.name, self.detail)
+    }
+}
+
+/// Results of every check run by the doctor
+#[derive(Clone, Debug, Default)]
+pub struct DoctorReport {
+    /// Checks in the order they were run
+    pub checks: Vec<CheckResult>,
+}
+
+impl DoctorReport {
+    /// Whether every check passed
+    #[must_use]
+    pub fn passed(&self) -> bool {
+        self.checks.iter().all(|check| check.passed)
+    }
+
+    /// Number of checks that failed
+    #[must_use]
+    pub fn failures(&self) -> usize {
+        self.checks.iter().filter(|check| !check.passed).count()
+    }
+}
+
+/// A strategy to check: how to call it and what it promises
+pub struct Candidate<'a> {
+    /// Name shown in the report
+    pub name: &'a str,
+    /// Whether the strategy promises shortest solutions
+    pub optimal: bool,
+    /// Solves a board and returns the path from it to the goal
+    pub solve: &'a dyn Fn(Board) -> Option<Vec<Board>>,
+}
+
+/// Replays a solution path and explains the first problem found
+///
+/// # Arguments
+///
+/// * `start` - The board that was solved
+/// * `path` - Boards from start to goal, as returned by `Solver::step_by_step_solution`
+///
+/// # Errors
+///
+/// Returns a description of the problem if the path does not start at
+/// `start`, contains a step that is not a single move or does not end solved
+pub fn verify_path(start: Board, path: &[Board]) -> Result<(), String> {
+    if path.first() != Some(&start) {
+        return Err(format!(
+            "path of {} starts elsewhere",
+            start.to_digit_string()
+        ));
+    }
+    if let Some(step) = path
+        .windows(2)
+        .position(|pair| pair[0].direction_to(pair[1]).is_none())
+    {
+        return Err(format!(
+            "path of {} has an illegal move at step {}",
+            start.to_digit_string(),
+            step + 1
+        ));
+    }
+    if !path.last().is_some_and(|board| board.is_solved()) {
+        return Err(format!(
+            "path of {} does not end at the goal",
+            start.to_digit_string()
+        ));
+    }
+    Ok(())
+}
+
+/// Checks that a strategy solves every board, with valid (and optimal, if promised) paths
+///
+/// # Arguments
+///
+/// * `candidate` - The strategy to check
+/// * `boards` - Solvable boards to solve
+/// * `database` - Exact distances used by the optimality check
+///
+/// # Returns
+///
+/// Two results (solved and replayed, then optimal) for optimal strategies,
+/// one otherwise
+#[must_use]
+pub fn check_strategy(
+    candidate: &Candidate,
+    boards: &[Board],
+    database: &StateSpaceDatabase,
+) -> Vec<CheckResult> {
+    let mut replay_failure = None;
+    let mut optimal_failure = None;
+
+    for &board in boards {
+        let Some(path) = (candidate.solve)(board) else {
+            replay_failure.get_or_insert_with(|| {
+                format!("no solution found for {}", board.to_digit_string())
+            });
+            continue;
+        };
+        if let Err(problem) = verify_path(board, &path) {
+            replay_failure.get_or_insert(problem);
+            continue;
+        }
+        if candidate.optimal && !database.is_optimal(&path) {
+            optimal_failure.get_or_insert_with(|| {
+                format!(
+                    "{} solved in {} moves, exact distance is {}",
+                    board.to_digit_string(),
+                    path.len() - 1,
+                    database.distance(board).unwrap_or_default()
+                )
+            });
+        }
+    }
+
+    let mut results = vec![CheckResult::from_failure(
+        format!("{} solves and replays", candidate.name),
+        replay_failure,
+        format!("{} boards", boards.len()),
+    )];
+    if candidate.optimal {
+        results.push(CheckResult::from_failure(
+            format!("{} solutions are optimal", candidate.name),
+            optimal_failure,
+            format!("{} boards match the exact-distance table", boards.len()),
+        ));
+    }
+    results
+}
+
+/// Spot-checks that a heuristic never overestimates the distance to the goal
+///
+/// Besides `boards`, the check samples [`ADMISSIBILITY_SAMPLES_PER_DISTANCE`]
+/// boards at every distance of the table, so the hardest boards are covered.
+///
+/// # Arguments
+///
+/// * `name` - Name of the heuristic shown in the report
+/// * `heuristic` - Estimated number of moves to the goal
+/// * `boards` - Additional boards to check
+/// * `database` - Exact distances to compare against
+#[must_use]
+pub fn check_admissible(
+    name: &str,
+    heuristic: &dyn Fn(Board) -> usize,
+    boards: &[Board],
+    database: &StateSpaceDatabase,
+) -> CheckResult {
+    let spot_checks = (0..=database.max_distance()).flat_map(|distance| {
+        database
+            .boards_at(distance)
+            .take(ADMISSIBILITY_SAMPLES_PER_DISTANCE)
+    });
+
+    let mut checked = 0;
+    let mut failure = None;
+    for board in boards.iter().copied().chain(spot_checks) {
+        let Some(distance) = database.distance(board) else {
+            continue;
+        };
+        checked += 1;
+        let estimate = heuristic(board);
+        if estimate > usize::from(distance) {
+            failure = Some(format!(
+                "{} is estimated at {estimate} moves, exact distance is {distance}",
+                board.to_digit_string()
+            ));
+            break;
+        }
+    }
+
+    CheckResult::from_failure(
+        format!("{name} heuristic is admissible"),
+        failure,
+        format!("{checked} boards never overestimated"),
+    )
+}
+
+/// Solves a board with a tree search and returns its path
+fn search_path<T>(board: Board, mut solver: Solver<T>) -> Option<Vec<Board>>
+where
+    T: SearchStrategy<BoardWithSteps> + Default + Clone,
+{
+    solver.solve(board)?;
+    Some(solver.step_by_step_solution())
+}
+
+/// Runs every built-in check on a sample of boards
+///
+/// DFS, BFS, the heuristic search and the human strategy solve every board,
+/// BFS and the heuristic search must match the exact distances, and the
+/// Manhattan and walking distance heuristics are spot-checked for admissibility.
+///
+/// # Arguments
+///
+/// * `boards` - Solvable boards to run the strategies on
+/// * `database` - Exact distances to compare against
+#[must_use]
+pub fn run(boards: &[Board], database: &StateSpaceDatabase) -> DoctorReport {
+    let dfs = |board| {
+        search_path(
+            board,
+            Solver::new(SimpleSearchStrategy::new(ExplorerStrategy::Dfs)),
+        )
+    };
+    let bfs = |board| {
+        search_path(
+            board,
+            Solver::new(SimpleSearchStrategy::new(ExplorerStrategy::Bfs)),
+        )
+    };
+    let heuristic = |board| search_path(board, Solver::new(HeuristicSearchStrategy::default()));
+    let human = |board| human::solve(board).map(|solution| solution.path());
+
+    let candidates = [
+        Candidate {
+            name: "DFS",
+            optimal: false,
+            solve: &dfs,
+        },
+        Candidate {
+            name: "BFS",
+            optimal: true,
+            solve: &bfs,
+        },
+        Candidate {
+            name: "Heuristic",
+            optimal: true,
+            solve: &heuristic,
+        },
+        Candidate {
+            name: "Human",
+            optimal: false,
+            solve: &human,
+        },
+    ];
+
+    let mut report = DoctorReport::default();
+    for candidate in &candidates {
+        report
+            .checks
+            .extend(check_strategy(candidate, boards, database));
+    }
+    report.checks.push(check_admissible(
+        "Manhattan",
+        &|board: Board| usize::from(board.heuristic_distance_to_solution()),
+        boards,
+        database,
+    ));
+    report.checks.push(check_admissible(
+        "Walking distance",
+        &|board: Board| usize::from(walking_distance::cost(board)),
+        boards,
+        database,
+    ));
+    report
+}
@@ -0,0 +1,113 @@
+//! # Enforced Hill Climbing Module
+//!
+//! Enforced hill climbing (Hoffmann and Nebel 2001, the search of the FF
+//! planner): from the current board, a breadth-first lookahead runs until it
+//! reaches a board with a strictly lower Manhattan distance. The climber
+//! commits to the path to that board, forgets the lookahead and starts the
+//! next one from there, until the goal is reached.
+//!
+//! Each lookahead only remembers the boards it has seen itself, so the search
+//! is fast and light on memory. The goal has the lowest distance of all and
+//! every lookahead can reach it, so the climber never gets stuck on the
+//! 8-puzzle; the solution is far from optimal though, as every lookahead
+//! takes the first improvement rather than the best one. The lookaheads and
+//! the largest of them are counted in [`Stats::lookaheads`] and
+//! [`Stats::longest_lookahead`].
+
+use crate::time::Instant;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, VecDeque};
+
+use crate::board::{ALL_DIRECTIONS, Board};
+use crate::stats::Stats;
+
+/// A solution found by enforced hill climbing
+#[derive(Clone, Debug)]
+pub struct EhcSolution {
+    /// Every board from the start to the goal
+    pub path: Vec<Board>,
+    /// Boards expanded by each breadth-first lookahead, in order
+    pub lookaheads: Vec<usize>,
+    /// Stats of the climb, with the number of lookaheads and the size of the
+    /// largest one
+    pub stats: Stats,
+}
+
+/// Breadth-first search from `start` to the nearest board with a lower `h`
+///
+/// # Returns
+///
+/// The boards from `start` to that board, and the number of boards expanded
+fn look_ahead(start: Board, stats: &mut Stats) -> Option<(Vec<Board>, usize)> {
+    let h = start.heuristic_distance_to_solution();
+    let mut parents = HashMap::from([(start, None)]);
+    let mut queue = VecDeque::from([start]);
+    let mut expanded = 0;
+
+    while let Some(board) = queue.pop_front() {
+        expanded += 1;
+        stats.nodes_explored += 1;
+        for direction in ALL_DIRECTIONS {
+            let Ok(child) = board.move_space(direction) else {
+                continue;
+            };
+            stats.generated_nodes += 1;
+            let Entry::Vacant(entry) = parents.entry(child) else {
+                stats.duplicates_pruned += 1;
+                continue;
+            };
+            entry.insert(Some(board));
+            if child.heuristic_distance_to_solution() < h {
+                let mut path = vec![child];
+                let mut current = child;
+                while let Some(&Some(parent)) = parents.get(&current) {
+                    path.push(parent);
+                    current = parent;
+                }
+                path.reverse();
+                return Some((path, expanded));
+            }
+            queue.push_back(child);
+            stats.enqueued_nodes += 1;
+        }
+        stats.max_frontier = stats.max_frontier.max(queue.len());
+    }
+    None
+}
+
+/// Solves a board with enforced hill climbing
+///
+/// # Returns
+///
+/// `Some(solution)`, or `None` if the board cannot reach the goal
+#[must_use]
+pub fn solve(board: Board) -> Option<EhcSolution> {
+    let started = Instant::now();
+    if !board.is_solvable() {
+        return None;
+    }
+
+    let mut stats = Stats::default();
+    let mut path = vec![board];
+    let mut lookaheads = Vec::new();
+    let mut current = board;
+    while !current.is_solved() {
+        let (steps, expanded) = look_ahead(current, &mut stats)?;
+        stats.lookaheads += 1;
+        stats.longest_lookahead = stats.longest_lookahead.max(expanded);
+        lookaheads.push(expanded);
+        current = steps[steps.len() - 1];
+        path.extend_from_slice(&steps[1..]);
+    }
+
+    stats.solution_moves = path.len() - 1;
+
+    stats.solution_cost = stats.solution_moves;
+    stats.max_depth_reached = stats.solution_moves;
+    stats.duration_ms = started.elapsed().as_millis();
+    Some(EhcSolution {
+        path,
+        lookaheads,
+        stats,
+    })
+}
@@ -0,0 +1,98 @@
+//! # Explore Module
+//!
+//! Bounded breadth-first exploration of the component reachable from a board,
+//! counting the distinct states first reached at each depth. The layer sizes
+//! show the effective branching factor of the puzzle shrinking below the
+//! number of legal moves as duplicates pile up, and a full exploration from
+//! any board must find all 181,440 states of its parity, which makes it a
+//! quick check of the move generator.
+
+use crate::board::{ALL_DIRECTIONS, Board};
+use crate::overlap::RankSet;
+
+/// States reached by a bounded breadth-first exploration, layer by layer
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Exploration {
+    /// Number of distinct states first reached at each depth, starting with
+    /// the start board alone at depth 0
+    pub layers: Vec<usize>,
+    /// Whether the exploration stopped at the depth or node limit before the
+    /// component ran out of new states
+    pub truncated: bool,
+}
+
+impl Exploration {
+    /// Number of distinct states within `depth` moves of the start
+    #[must_use]
+    pub fn reachable_within(&self, depth: usize) -> usize {
+        self.layers.iter().take(depth + 1).sum()
+    }
+
+    /// Number of distinct states found in total
+    #[must_use]
+    pub fn reachable(&self) -> usize {
+        self.layers.iter().sum()
+    }
+
+    /// Ratio between the sizes of the layer at `depth` and the one before it
+    ///
+    /// # Returns
+    ///
+    /// `None` at depth 0 and past the last layer
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn branching_factor(&self, depth: usize) -> Option<f64> {
+        let previous = *self.layers.get(depth.checked_sub(1)?)?;
+        let layer = *self.layers.get(depth)?;
+        Some(layer as f64 / previous as f64)
+    }
+}
+
+/// Explores the states reachable from `start` in breadth-first order
+///
+/// Only complete layers are kept: the first layer that would go past
+/// `max_nodes` distinct states in total, or lie deeper than `max_depth`, is
+/// left out and the exploration is marked as truncated.
+///
+/// # Arguments
+///
+/// * `start` - The board to explore from
+/// * `max_depth` - Deepest layer to explore (`None` for no limit)
+/// * `max_nodes` - Largest number of distinct states to keep
+#[must_use]
+pub fn explore(start: Board, max_depth: Option<usize>, max_nodes: usize) -> Exploration {
+    let mut seen = RankSet::new();
+    seen.insert(start);
+    let mut layer = vec![start];
+    let mut exploration = Exploration {
+        layers: vec![1],
+        truncated: false,
+    };
+
+    while !layer.is_empty() {
+        if max_depth.is_some_and(|max_depth| exploration.layers.len() > max_depth) {
+            exploration.truncated = true;
+            break;
+        }
+
+        let next: Vec<Board> = layer
+            .iter()
+            .flat_map(|&board| {
+                ALL_DIRECTIONS
+                    .into_iter()
+                    .filter_map(move |direction| board.move_space(direction).ok())
+            })
+            .filter(|&next| seen.insert(next))
+            .collect();
+        if next.is_empty() {
+            break;
+        }
+        if seen.len() > max_nodes {
+            exploration.truncated = true;
+            break;
+        }
+        exploration.layers.push(next.len());
+        layer = next;
+    }
+    exploration
+}
@@ -0,0 +1,162 @@
+//! # Export Module
+//!
+//! Writes benchmark results to disk as versioned JSON or `MessagePack` (see
+//! [`crate::schema`]) so they can be archived, shared and re-loaded later.
+//! Every export starts with a [`Manifest`] describing exactly how the numbers
+//! were produced (crate version, git revision, CLI arguments, seed, a
+//! fingerprint of the board set, thread count and host), which is what makes
+//! published results reproducible. The board set itself is stored next to the
+//! results, so any single board can be re-examined later.
+//! Requires the `serde` feature.
+
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::board::Board;
+use crate::checksum::fnv1a;
+use crate::schema::{
+    BenchmarkResult, OutputFormat, RunRecord, SCHEMA_VERSION, SchemaError, read_versioned,
+    write_document,
+};
+use crate::stats::{GenerationStats, StatsSummary};
+
+/// Everything needed to reproduce a benchmark run
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Manifest {
+    /// Version of this crate that produced the results
+    pub crate_version: String,
+    /// Git revision the binary was built from, when known
+    pub git_hash: Option<String>,
+    /// Command-line arguments of the run, including the program name
+    pub cli_args: Vec<String>,
+    /// Seed used to generate the board set
+    pub seed: u64,
+    /// Fingerprint of the generated board set as 16 hex digits (see [`board_set_hash`])
+    pub board_set_hash: String,
+    /// Number of worker threads used to solve the boards
+    pub threads: usize,
+    /// Name of the machine the benchmark ran on
+    pub hostname: String,
+    /// Generation time and initial-distance mix of the board set, when recorded
+    pub generation: Option<GenerationStats>,
+}
+
+impl Manifest {
+    /// Captures the manifest of the current process
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - Seed used to generate `boards`
+    /// * `boards` - The board set every strategy was run on
+    /// * `threads` - Number of worker threads used to solve the boards
+    #[must_use]
+    pub fn capture(seed: u64, boards: &[Board], threads: usize) -> Self {
+        Self {
+            crate_version: env!("CARGO_PKG_VERSION").to_owned(),
+            git_hash: option_env!("O8_GIT_HASH").map(str::to_owned),
+            cli_args: std::env::args().collect(),
+            seed,
+            board_set_hash: format!("{:016x}", board_set_hash(boards)),
+            threads,
+            hostname: hostname(),
+            generation: None,
+        }
+    }
+}
+
+/// Computes a stable fingerprint of an ordered board set
+///
+/// Uses FNV-1a over the little-endian board encodings, so the value only
+/// depends on the boards and their order, never on the platform or the
+/// process (unlike `std`'s randomly seeded hasher).
+#[must_use]
+pub fn board_set_hash(boards: &[Board]) -> u64 {
+    fnv1a(boards.iter().flat_map(|b| b.encoded().to_le_bytes()))
+}
+
+/// Best-effort lookup of the machine's hostname
+fn hostname() -> String {
+    std::env::var("HOSTNAME")
+        .or_else(|_| std::env::var("COMPUTERNAME"))
+        .ok()
+        .or_else(|| fs::read_to_string("/etc/hostname").ok())
+        .map(|h| h.trim().to_owned())
+        .filter(|h| !h.is_empty())
+        .unwrap_or_else(|| "unknown".to_owned())
+}
+
+/// Writes the manifest and per-strategy summaries as a JSON document
+///
+/// # Arguments
+///
+/// * `path` - Destination file, overwritten if it exists
+/// * `manifest` - Reproducibility information for the run
+/// * `boards` - The board set every strategy was run on, in order
+/// * `results` - Strategy names paired with their aggregated statistics
+/// * `runs` - Every individual run behind the summaries
+///
+/// # Errors
+///
+/// Returns any I/O or serialization error raised while writing the file
+pub fn write_benchmark_json(
+    path: &Path,
+    manifest: &Manifest,
+    boards: &[Board],
+    results: &[(&str, &StatsSummary)],
+    runs: &[RunRecord],
+) -> io::Result<()> {
+    write_benchmark(path, OutputFormat::Json, manifest, boards, results, runs)
+}
+
+/// Writes the manifest and per-strategy summaries in the given encoding
+///
+/// # Arguments
+///
+/// * `path` - Destination file, overwritten if it exists
+/// * `format` - Encoding of the file
+/// * `manifest` - Reproducibility information for the run
+/// * `boards` - The board set every strategy was run on, in order
+/// * `results` - Strategy names paired with their aggregated statistics
+/// * `runs` - Every individual run behind the summaries
+///
+/// # Errors
+///
+/// Returns any I/O or serialization error raised while writing the file
+pub fn write_benchmark(
+    path: &Path,
+    format: OutputFormat,
+    manifest: &Manifest,
+    boards: &[Board],
+    results: &[(&str, &StatsSummary)],
+    runs: &[RunRecord],
+) -> io::Result<()> {
+    let export = BenchmarkResult {
+        schema_version: SCHEMA_VERSION,
+        manifest: manifest.clone(),
+        boards: boards.iter().map(|b| b.to_digit_string()).collect(),
+        results: results
+            .iter()
+            .map(|(name, summary)| ((*name).to_owned(), (*summary).clone()))
+            .collect(),
+        runs: runs.to_vec(),
+    };
+
+    write_document(BufWriter::new(File::create(path)?), format, &export)
+}
+
+/// Loads a benchmark export previously written by [`write_benchmark_json`]
+///
+/// Exports from older versions of this crate load as long as their schema
+/// version is supported (see [`crate::schema`]).
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read, is not a valid export, or was
+/// written with a newer schema version
+pub fn read_benchmark_json(path: &Path) -> Result<BenchmarkResult, SchemaError> {
+    read_versioned(BufReader::new(File::open(path)?))
+}
@@ -0,0 +1,446 @@
+//! # External Module
+//!
+//! Breadth-first search whose frontier and closed list live on disk instead
+//! of in memory, for searches whose visited states do not fit in RAM.
+//!
+//! The search uses delayed duplicate detection. Every depth layer is a file
+//! of board encodings in ascending order. The successors of a layer are
+//! buffered in memory up to a run capacity, then sorted and written out as
+//! runs. The runs are merged into the next layer; during the merge,
+//! duplicates are dropped, as are the boards of the two previous layers. In
+//! the puzzle's undirected move graph, the successors of layer `d` can only
+//! lie in layers `d - 1`, `d` or `d + 1`, so no older layer needs to be
+//! consulted.
+//!
+//! Once the goal appears in a layer, the solution is rebuilt backwards by
+//! scanning each earlier layer for a predecessor of the board found so far.
+//! Only the layer files themselves are kept; the parents of boards are never
+//! stored.
+
+use crate::time::Instant;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::ops::AddAssign;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::board::{ALL_DIRECTIONS, Board};
+use crate::stats::Stats;
+
+/// Default number of boards buffered before a sorted run is written (4 MiB)
+pub const DEFAULT_RUN_CAPACITY: usize = 1 << 20;
+
+/// Size of one board encoding on disk
+const RECORD_BYTES: u64 = 4;
+
+/// Disk traffic of an external-memory search
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct IoStats {
+    /// Bytes written to layer and run files
+    pub bytes_written: u64,
+    /// Bytes read back from layer and run files
+    pub bytes_read: u64,
+    /// Number of layer and run files written
+    pub files_written: usize,
+    /// Number of sorted runs written while expanding layers
+    pub sorted_runs: usize,
+}
+
+impl AddAssign for IoStats {
+    fn add_assign(&mut self, other: Self) {
+        self.bytes_written += other.bytes_written;
+        self.bytes_read += other.bytes_read;
+        self.files_written += other.files_written;
+        self.sorted_runs += other.sorted_runs;
+    }
+}
+
+/// Result of a successful external-memory search
+#[derive(Clone, Debug)]
+pub struct ExternalSolution {
+    /// Boards from the start to the goal
+    pub path: Vec<Board>,
+    /// Search statistics; the frontier size is the size of the largest layer
+    pub stats: Stats,
+    /// Disk traffic of the search
+    pub io: IoStats,
+}
+
+/// Breadth-first search with its frontier and closed list on disk
+#[derive(Clone, Debug)]
+pub struct ExternalBfs {
+    /// Directory in which the search creates its private working directory
+    scratch_dir: PathBuf,
+    /// Number of successors buffered in memory before a sorted run is written
+    run_capacity: usize,
+}
+
+impl ExternalBfs {
+    /// Creates a search that writes its files below `scratch_dir`
+    ///
+    /// # Arguments
+    ///
+    /// * `scratch_dir` - Directory for temporary layer and run files
+    #[must_use]
+    pub fn new(scratch_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            scratch_dir: scratch_dir.into(),
+            run_capacity: DEFAULT_RUN_CAPACITY,
+        }
+    }
+
+    /// Sets how many successors are buffered in memory before a sorted run is written
+    ///
+    /// This bounds the memory of the search: the capacity plus one read
+    /// buffer per run and layer file.
+    #[must_use]
+    pub fn with_run_capacity(mut self, boards: usize) -> Self {
+        self.run_capacity = boards.max(1);
+        self
+    }
+
+    /// Finds a shortest solution of `start`
+    ///
+    /// The working files are removed when the search ends, whether or not it
+    /// succeeds.
+    ///
+    /// # Returns
+    ///
+    /// `Some(solution)`, or `None` if the goal cannot be reached from `start`
+    ///
+    /// # Errors
+    ///
+    /// Returns any I/O error raised while creating, writing or reading the
+    /// working files
+    pub fn solve(&self, start: Board) -> io::Result<Option<ExternalSolution>> {
+        let started = Instant::now();
+        let workspace = Workspace::create(&self.scratch_dir)?;
+        let mut io_stats = IoStats::default();
+        let mut stats = Stats::default();
+
+        let first = workspace.file("layer-0");
+        let first_len = write_records(&first, [start.encoded()], &mut io_stats)?;
+        let mut layers = vec![(first, first_len)];
+        let mut found = start.is_solved();
+
+        while !found {
+            let depth = layers.len() - 1;
+            let (current, current_len) = &layers[depth];
+            stats.nodes_explored += current_len;
+            stats.max_frontier = stats.max_frontier.max(*current_len);
+
+            let runs = self.expand(&workspace, depth, current, &mut stats, &mut io_stats)?;
+            let next = workspace.file(&format!("layer-{}", depth + 1));
+            let previous = depth.checked_sub(1).map(|d| layers[d].0.as_path());
+            let (next_len, has_goal) = merge_layer(&next, &runs, previous, current, &mut io_stats)?;
+            for run in &runs {
+                fs::remove_file(run)?;
+            }
+
+            if next_len == 0 {
+                return Ok(None);
+            }
+            stats.enqueued_nodes += next_len;
+            layers.push((next, next_len));
+            found = has_goal;
+        }
+
+        let path = reconstruct(&layers, &mut io_stats)?;
+        stats.solution_moves = path.len() - 1;
+        stats.solution_cost = stats.solution_moves;
+        stats.max_depth_reached = layers.len() - 1;
+        stats.duplicates_pruned = stats.generated_nodes - stats.enqueued_nodes;
+        stats.duration_ms = started.elapsed().as_millis();
+        Ok(Some(ExternalSolution {
+            path,
+            stats,
+            io: io_stats,
+        }))
+    }
+
+    /// Writes the successors of one layer as sorted, deduplicated runs
+    ///
+    /// # Returns
+    ///
+    /// The paths of the runs, in the order they were written
+    fn expand(
+        &self,
+        workspace: &Workspace,
+        depth: usize,
+        layer: &Path,
+        stats: &mut Stats,
+        io_stats: &mut IoStats,
+    ) -> io::Result<Vec<PathBuf>> {
+        let mut reader = RecordReader::open(layer)?;
+        let mut buffer = Vec::with_capacity(self.run_capacity.min(DEFAULT_RUN_CAPACITY));
+        let mut runs = Vec::new();
+
+        while let Some(encoded) = reader.next_record()? {
+            let board = Board::from_encoded(encoded);
+            for direction in ALL_DIRECTIONS {
+                if let Ok(next) = board.move_space(direction) {
+                    stats.generated_nodes += 1;
+                    buffer.push(next.encoded());
+                }
+            }
+            if buffer.len() >= self.run_capacity {
+                runs.push(write_run(
+                    workspace,
+                    depth,
+                    runs.len(),
+                    &mut buffer,
+                    io_stats,
+                )?);
+            }
+        }
+        if !buffer.is_empty() {
+            runs.push(write_run(
+                workspace,
+                depth,
+                runs.len(),
+                &mut buffer,
+                io_stats,
+            )?);
+        }
+
+        io_stats.bytes_read += reader.bytes_read;
+        Ok(runs)
+    }
+}
+
+/// Private working directory of one search, removed when dropped
+struct Workspace {
+    dir: PathBuf,
+}
+
+impl Workspace {
+    /// Creates a fresh directory below `scratch_dir`
+    fn create(scratch_dir: &Path) -> io::Result<Self> {
+        static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        let dir = scratch_dir.join(format!("o8-external-{}-{id}", std::process::id()));
+        fs::create_dir_all(&dir)?;
+        Ok(Self { dir })
+    }
+
+    /// Path of a working file
+    fn file(&self, name: &str) -> PathBuf {
+        self.dir.join(name)
+    }
+}
+
+impl Drop for Workspace {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
+
+/// Sequential reader of board encodings that counts the bytes it reads
+struct RecordReader {
+    reader: BufReader<File>,
+    bytes_read: u64,
+}
+
+impl RecordReader {
+    /// Opens a layer or run file
+    fn open(path: &Path) -> io::Result<Self> {
+        Ok(Self {
+            reader: BufReader::new(File::open(path)?),
+            bytes_read: 0,
+        })
+    }
+
+    /// Reads the next encoding, or `None` at the end of the file
+    fn next_record(&mut self) -> io::Result<Option<u32>> {
+        let mut bytes = [0; size_of::<u32>()];
+        match self.reader.read_exact(&mut bytes) {
+            Ok(()) => {
+                self.bytes_read += RECORD_BYTES;
+                Ok(Some(u32::from_le_bytes(bytes)))
+            }
+            Err(error) if error.kind() == io::ErrorKind::UnexpectedEof => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Advances past every encoding below `value`
+    ///
+    /// # Returns
+    ///
+    /// `true` if the file contains `value`
+    fn skip_to(&mut self, head: &mut Option<u32>, value: u32) -> io::Result<bool> {
+        while head.is_some_and(|h| h < value) {
+            *head = self.next_record()?;
+        }
+        Ok(*head == Some(value))
+    }
+}
+
+/// Writes encodings to a new file
+///
+/// # Returns
+///
+/// The number of encodings written
+fn write_records(
+    path: &Path,
+    records: impl IntoIterator<Item = u32>,
+    io_stats: &mut IoStats,
+) -> io::Result<usize> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    let mut count = 0;
+    for record in records {
+        writer.write_all(&record.to_le_bytes())?;
+        count += 1;
+    }
+    writer.flush()?;
+
+    io_stats.bytes_written += count as u64 * RECORD_BYTES;
+    io_stats.files_written += 1;
+    Ok(count)
+}
+
+/// Sorts and deduplicates the buffered successors and writes them as a run
+fn write_run(
+    workspace: &Workspace,
+    depth: usize,
+    index: usize,
+    buffer: &mut Vec<u32>,
+    io_stats: &mut IoStats,
+) -> io::Result<PathBuf> {
+    buffer.sort_unstable();
+    buffer.dedup();
+    let path = workspace.file(&format!("run-{depth}-{index}"));
+    write_records(&path, buffer.drain(..), io_stats)?;
+    io_stats.sorted_runs += 1;
+    Ok(path)
+}
+
+/// Merges sorted runs into the next layer, dropping boards of the two previous layers
+///
+/// # Returns
+///
+/// The size of the new layer and whether it contains the goal
+fn merge_layer(
+    next: &Path,
+    runs: &[PathBuf],
+    previous: Option<&Path>,
+    current: &Path,
+    io_stats: &mut IoStats,
+) -> io::Result<(usize, bool)> {
+    let goal = Board::default().encoded();
+    let mut readers = runs
+        .iter()
+        .map(|run| RecordReader::open(run))
+        .collect::<io::Result<Vec<_>>>()?;
+    let mut heap = BinaryHeap::with_capacity(readers.len());
+    for (index, reader) in readers.iter_mut().enumerate() {
+        if let Some(value) = reader.next_record()? {
+            heap.push(Reverse((value, index)));
+        }
+    }
+
+    let mut older_layers = Vec::with_capacity(2);
+    for path in previous.into_iter().chain([current]) {
+        let mut reader = RecordReader::open(path)?;
+        let first = reader.next_record()?;
+        older_layers.push((reader, first));
+    }
+
+    let mut writer = BufWriter::new(File::create(next)?);
+    let (mut len, mut has_goal, mut last) = (0, false, None);
+    while let Some(Reverse((value, index))) = heap.pop() {
+        if let Some(following) = readers[index].next_record()? {
+            heap.push(Reverse((following, index)));
+        }
+        if last == Some(value) {
+            continue;
+        }
+        last = Some(value);
+
+        let mut known = false;
+        for (reader, head) in &mut older_layers {
+            known |= reader.skip_to(head, value)?;
+        }
+        if known {
+            continue;
+        }
+
+        writer.write_all(&value.to_le_bytes())?;
+        len += 1;
+        has_goal |= value == goal;
+    }
+    writer.flush()?;
+
+    io_stats.bytes_written += len as u64 * RECORD_BYTES;
+    io_stats.files_written += 1;
+    io_stats.bytes_read += readers
+        .iter()
+        .chain(older_layers.iter().map(|(reader, _)| reader))
+        .map(|reader| reader.bytes_read)
+        .sum::<u64>();
+    Ok((len, has_goal))
+}
+
+/// Rebuilds a shortest path by finding a predecessor in each earlier layer
+fn reconstruct(layers: &[(PathBuf, usize)], io_stats: &mut IoStats) -> io::Result<Vec<Board>> {
+    let mut path = vec![Board::default()];
+    for (layer, _) in layers[..layers.len() - 1].iter().rev() {
+        let next = path[path.len() - 1];
+        let mut reader = RecordReader::open(layer)?;
+        let predecessor = loop {
+            let Some(encoded) = reader.next_record()? else {
+                return Err(io::Error::other(
+                    "layer file has no predecessor of the next board",
+                ));
+            };
+            let board = Board::from_encoded(encoded);
+            if board.direction_to(next).is_some() {
+                break board;
+            }
+        };
+        io_stats.bytes_read += reader.bytes_read;
+        path.push(predecessor);
+    }
+
+    path.reverse();
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{Rng, SeedableRng, rngs::StdRng};
+
+    use super::*;
+    use crate::database::StateSpaceDatabase;
+
+    #[test]
+    fn finds_shortest_paths_and_cleans_up_its_files() {
+        let scratch = std::env::temp_dir().join(format!("o8-external-test-{}", std::process::id()));
+        let database = StateSpaceDatabase::build();
+        let mut rng = StdRng::seed_from_u64(479);
+        for run_capacity in [64, DEFAULT_RUN_CAPACITY] {
+            let search = ExternalBfs::new(&scratch).with_run_capacity(run_capacity);
+            for _ in 0..3 {
+                let rank = rng.random_range(0..Board::REACHABLE_COUNT);
+                let board = Board::from_reachable_rank(rank).unwrap();
+                let solution = search.solve(board).unwrap().unwrap();
+
+                let distance = database.distance(board).unwrap();
+                assert_eq!(solution.path.len() - 1, usize::from(distance), "{board}");
+                assert_eq!(solution.path.first(), Some(&board));
+                assert!(solution.path.last().is_some_and(|last| last.is_solved()));
+                for pair in solution.path.windows(2) {
+                    assert!(pair[0].successors().any(|(_, next)| next == pair[1]));
+                }
+                assert!(solution.io.bytes_written > 0 && solution.io.bytes_read > 0);
+                if run_capacity == 64 && distance > 10 {
+                    assert!(solution.io.sorted_runs > usize::from(distance));
+                }
+            }
+        }
+        assert_eq!(fs::read_dir(&scratch).unwrap().count(), 0);
+        fs::remove_dir_all(scratch).unwrap();
+    }
+}
@@ -0,0 +1,188 @@
+//! # Fringe Module
+//!
+//! Fringe search (Björnsson et al. 2005): the iterations of IDA*, without
+//! starting each one again from the start. Every iteration walks the fringe
+//! depth-first, expanding the nodes whose f-value is within the threshold and
+//! deferring the others. The next iteration raises the threshold to the
+//! lowest deferred f-value and resumes from the deferred nodes instead of
+//! searching the tree above them again.
+//!
+//! The fringe is kept as two lists: "now", the nodes left to visit in this
+//! iteration, used as a stack so that children are visited right after their
+//! parent, and "later", the nodes deferred to the next iteration in the order
+//! they were met. A cache holds the best known depth and parent of every
+//! reached board; a board is expanded again only when a shorter path to it is
+//! found, which [`Stats::reexpanded_nodes`] counts.
+
+use crate::time::Instant;
+use std::collections::{HashMap, HashSet};
+
+use crate::board::{ALL_DIRECTIONS, Board};
+use crate::stats::Stats;
+
+/// A solution found by fringe search
+#[derive(Clone, Debug)]
+pub struct FringeSolution {
+    /// Every board from the start to the goal
+    pub path: Vec<Board>,
+    /// Number of thresholds the search went through, the last one included
+    pub iterations: usize,
+    /// Work done by every iteration; `max_frontier` counts both lists
+    pub stats: Stats,
+}
+
+/// State of the search
+struct Fringe {
+    /// Nodes to visit in this iteration, as (board, depth), the next one last
+    now: Vec<(Board, usize)>,
+    /// Nodes deferred to the next iteration, as (board, depth), in visit order
+    later: Vec<(Board, usize)>,
+    /// Best known depth and parent of every reached board
+    cache: HashMap<Board, (usize, Option<Board>)>,
+    /// Boards expanded so far
+    expanded: HashSet<Board>,
+    stats: Stats,
+}
+
+impl Fringe {
+    /// Expands `board`, reached at `depth`, pushing the children a shorter path reaches onto "now"
+    fn expand(&mut self, board: Board, depth: usize) {
+        self.stats.nodes_explored += 1;
+        self.stats.max_depth_reached = self.stats.max_depth_reached.max(depth);
+        if !self.expanded.insert(board) {
+            self.stats.reexpanded_nodes += 1;
+        }
+
+        let child_depth = depth + 1;
+        let mut children = Vec::with_capacity(ALL_DIRECTIONS.len());
+        for direction in ALL_DIRECTIONS {
+            let Ok(child) = board.move_space(direction) else {
+                continue;
+            };
+            self.stats.generated_nodes += 1;
+            if self
+                .cache
+                .get(&child)
+                .is_some_and(|&(known, _)| known <= child_depth)
+            {
+                self.stats.duplicates_pruned += 1;
+                continue;
+            }
+            // Any entry left in the lists for a longer path goes stale
+            self.cache.insert(child, (child_depth, Some(board)));
+            self.stats.enqueued_nodes += 1;
+            children.push((child, child_depth));
+        }
+        // Reversed, so the first child is visited first
+        self.now.extend(children.into_iter().rev());
+        self.stats.max_frontier = self
+            .stats
+            .max_frontier
+            .max(self.now.len() + self.later.len());
+    }
+
+    /// Boards from the start to `board`
+    fn path_to(&self, board: Board) -> Vec<Board> {
+        let mut path = vec![board];
+        let mut current = board;
+        while let Some(&(_, Some(parent))) = self.cache.get(&current) {
+            path.push(parent);
+            current = parent;
+        }
+        path.reverse();
+        path
+    }
+}
+
+/// Solves a board with fringe search
+///
+/// The solution is optimal, since the Manhattan distance is admissible.
+///
+/// # Returns
+///
+/// `Some(solution)`, or `None` if the board cannot reach the goal
+#[must_use]
+pub fn solve(board: Board) -> Option<FringeSolution> {
+    let started = Instant::now();
+    if !board.is_solvable() {
+        return None;
+    }
+
+    let mut search = Fringe {
+        now: vec![(board, 0)],
+        later: Vec::new(),
+        cache: HashMap::from([(board, (0, None))]),
+        expanded: HashSet::new(),
+        stats: Stats::default(),
+    };
+    let mut threshold = usize::from(board.heuristic_distance_to_solution());
+    let mut iterations = 1;
+
+    loop {
+        let mut next_threshold = usize::MAX;
+        while let Some((node, depth)) = search.now.pop() {
+            if search.cache[&node].0 != depth {
+                continue;
+            }
+            let f = depth + usize::from(node.heuristic_distance_to_solution());
+            if f > threshold {
+                next_threshold = next_threshold.min(f);
+                search.later.push((node, depth));
+                continue;
+            }
+            if node.is_solved() {
+                search.stats.nodes_explored += 1;
+                let path = search.path_to(node);
+                let mut stats = search.stats;
+                stats.solution_moves = path.len() - 1;
+                stats.solution_cost = stats.solution_moves;
+                stats.duration_ms = started.elapsed().as_millis();
+                return Some(FringeSolution {
+                    path,
+                    iterations,
+                    stats,
+                });
+            }
+            search.expand(node, depth);
+        }
+
+        if search.later.is_empty() {
+            return None;
+        }
+        // "later" becomes "now", visited in the order the nodes were deferred
+        search.now = std::mem::take(&mut search.later);
+        search.now.reverse();
+        threshold = next_threshold;
+        iterations += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{Rng, SeedableRng, rngs::StdRng};
+
+    use super::*;
+    use crate::database::StateSpaceDatabase;
+
+    #[test]
+    fn solution_lengths_match_the_state_space_database() {
+        let database = StateSpaceDatabase::build();
+        let mut rng = StdRng::seed_from_u64(513);
+        for _ in 0..8 {
+            let rank = rng.random_range(0..Board::REACHABLE_COUNT);
+            let board = Board::from_reachable_rank(rank).unwrap();
+            let solution = solve(board).unwrap();
+
+            let distance = database.distance(board).unwrap();
+            assert_eq!(solution.path.len() - 1, usize::from(distance), "{board}");
+            assert_eq!(solution.stats.solution_moves, usize::from(distance));
+            assert_eq!(solution.path.first(), Some(&board));
+            assert!(solution.path.last().is_some_and(|last| last.is_solved()));
+            for pair in solution.path.windows(2) {
+                assert!(pair[0].successors().any(|(_, next)| next == pair[1]));
+            }
+            assert!(solution.iterations >= 1);
+        }
+        assert!(solve("213804765".parse().unwrap()).is_none());
+    }
+}
@@ -0,0 +1,191 @@
+//! # Frontier Search Module
+//!
+//! Breadth-first frontier search with divide-and-conquer path reconstruction
+//! (Korf et al. 2005). The search keeps no closed list and no parent links:
+//! only the boards of the last two depths of each direction are held. The
+//! state graph is bipartite, as every move takes the empty space to a cell
+//! of the other color of the checkerboard, so the successors of a layer lie
+//! in the layer before it or the one after it, and dropping the layer before
+//! it is all the duplicate detection needed.
+//!
+//! Layers grow from the start and the goal at once, the smaller one first,
+//! until they share a board. That board lies on an optimal solution, at
+//! known distances from both ends, so the path is rebuilt by solving the two
+//! halves the same way, recursively, down to single moves. Rebuilding
+//! repeats searches that are each at most half as deep, trading time for
+//! memory that stays bounded by a few layers instead of every reached board.
+
+use crate::time::Instant;
+use std::collections::HashSet;
+
+use crate::board::{ALL_DIRECTIONS, Board};
+use crate::stats::Stats;
+
+/// A solution found by frontier search
+#[derive(Clone, Debug)]
+pub struct FrontierSolution {
+    /// Every board from the start to the goal
+    pub path: Vec<Board>,
+    /// Nodes expanded by the search between the start and the goal
+    pub search_expansions: usize,
+    /// Nodes expanded by the searches that rebuilt the path
+    pub reconstruction_expansions: usize,
+    /// Work of every search combined; `max_frontier` counts every layer held at once
+    pub stats: Stats,
+}
+
+/// The last two layers grown from one end
+struct Layers {
+    /// Boards one move closer to this end than `current`
+    previous: HashSet<Board>,
+    /// Boards at `depth` moves from this end
+    current: HashSet<Board>,
+    depth: usize,
+}
+
+impl Layers {
+    fn new(root: Board) -> Self {
+        Self {
+            previous: HashSet::new(),
+            current: HashSet::from([root]),
+            depth: 0,
+        }
+    }
+
+    /// Replaces the current layer with the next one
+    fn grow(&mut self, stats: &mut Stats) {
+        let mut next = HashSet::with_capacity(self.current.len() * 2);
+        for board in &self.current {
+            stats.nodes_explored += 1;
+            for direction in ALL_DIRECTIONS {
+                let Ok(child) = board.move_space(direction) else {
+                    continue;
+                };
+                stats.generated_nodes += 1;
+                if self.previous.contains(&child) || !next.insert(child) {
+                    stats.duplicates_pruned += 1;
+                } else {
+                    stats.enqueued_nodes += 1;
+                }
+            }
+        }
+        self.previous = std::mem::replace(&mut self.current, next);
+        self.depth += 1;
+    }
+
+    fn len(&self) -> usize {
+        self.previous.len() + self.current.len()
+    }
+}
+
+/// Finds a board on an optimal path from `from` to `to`
+///
+/// # Returns
+///
+/// The board and its distances from `from` and from `to`, or `None` if no
+/// path exists
+fn meeting_point(from: Board, to: Board, stats: &mut Stats) -> Option<(Board, usize, usize)> {
+    let mut forward = Layers::new(from);
+    let mut backward = Layers::new(to);
+    loop {
+        if let Some(&meeting) = forward.current.intersection(&backward.current).next() {
+            return Some((meeting, forward.depth, backward.depth));
+        }
+        if forward.current.len() <= backward.current.len() {
+            forward.grow(stats);
+        } else {
+            backward.grow(stats);
+        }
+        stats.max_frontier = stats.max_frontier.max(forward.len() + backward.len());
+        stats.max_depth_reached = stats
+            .max_depth_reached
+            .max(forward.depth.max(backward.depth));
+        if forward.current.is_empty() || backward.current.is_empty() {
+            return None;
+        }
+    }
+}
+
+/// Appends the boards after `from` on an optimal path of `distance` moves to `to`
+fn rebuild(from: Board, to: Board, distance: usize, path: &mut Vec<Board>, stats: &mut Stats) {
+    if distance <= 1 {
+        if distance == 1 {
+            path.push(to);
+        }
+        return;
+    }
+    let (meeting, before, after) =
+        meeting_point(from, to, stats).expect("Both ends lie on a known path");
+    rebuild(from, meeting, before, path, stats);
+    rebuild(meeting, to, after, path, stats);
+}
+
+/// Solves a board with frontier search
+///
+/// The solution is optimal, like BFS, but memory holds a few layers of
+/// boards at a time instead of every reached board.
+///
+/// # Returns
+///
+/// `Some(solution)`, or `None` if the board cannot reach the goal
+#[must_use]
+pub fn solve(board: Board) -> Option<FrontierSolution> {
+    let started = Instant::now();
+    if !board.is_solvable() {
+        return None;
+    }
+
+    let goal = Board::default();
+    let mut stats = Stats::default();
+    let (meeting, before, after) = meeting_point(board, goal, &mut stats)?;
+    let search_expansions = stats.nodes_explored;
+
+    let mut path = vec![board];
+    rebuild(board, meeting, before, &mut path, &mut stats);
+    rebuild(meeting, goal, after, &mut path, &mut stats);
+
+    stats.solution_moves = path.len() - 1;
+
+    stats.solution_cost = stats.solution_moves;
+    stats.duration_ms = started.elapsed().as_millis();
+    Some(FrontierSolution {
+        path,
+        search_expansions,
+        reconstruction_expansions: stats.nodes_explored - search_expansions,
+        stats,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{Rng, SeedableRng, rngs::StdRng};
+
+    use super::*;
+    use crate::database::StateSpaceDatabase;
+
+    #[test]
+    fn solution_lengths_match_the_state_space_database() {
+        let database = StateSpaceDatabase::build();
+        let mut rng = StdRng::seed_from_u64(514);
+        for _ in 0..8 {
+            let rank = rng.random_range(0..Board::REACHABLE_COUNT);
+            let board = Board::from_reachable_rank(rank).unwrap();
+            let solution = solve(board).unwrap();
+
+            let distance = database.distance(board).unwrap();
+            assert_eq!(solution.path.len() - 1, usize::from(distance), "{board}");
+            assert_eq!(solution.path.first(), Some(&board));
+            assert!(solution.path.last().is_some_and(|last| last.is_solved()));
+            for pair in solution.path.windows(2) {
+                assert!(pair[0].successors().any(|(_, next)| next == pair[1]));
+            }
+        }
+    }
+
+    #[test]
+    fn solves_the_goal_and_rejects_unsolvable_boards() {
+        let solution = solve(Board::default()).unwrap();
+        assert_eq!(solution.path, vec![Board::default()]);
+        assert!(solve("213804765".parse().unwrap()).is_none());
+    }
+}
@@ -0,0 +1,413 @@
+//! # Goal Cache Module
+//!
+//! A distance table rooted at the goal that persists between runs and grows
+//! as boards are solved. Every search towards the same goal explores the same
+//! backward breadth-first layers, so instead of a fresh closed list per board
+//! the cache keeps the layers found so far in a file: one byte per reachable
+//! state, indexed by the same dense rank as the
+//! [`StateSpaceDatabase`](crate::database::StateSpaceDatabase).
+//!
+//! Solving a board whose distance is already known is a pure lookup followed
+//! by a walk down the distances. Otherwise the cache expands further layers,
+//! one complete layer at a time, until the board is reached, and the new
+//! layers are kept for the next board and the next run. Solving many boards
+//! therefore costs at most one full backward search in total.
+//!
+//! With the `mmap` feature (enabled by `cli`) the file is memory-mapped
+//! read-write, so layers are written back by the operating system as they are
+//! expanded. Without it the table is read into memory and written back by
+//! [`GoalCache::flush`] or when the cache is dropped.
+//!
+//! ```text
+//! magic "O8GC" | version u8 | completed depth u8 | distances (181,440 bytes)
+//! ```
+//!
+//! Entries beyond the completed depth may be partially filled if a run was
+//! interrupted while expanding a layer; they are ignored until the layer is
+//! expanded again, which only adds the missing entries.
+
+use crate::time::Instant;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::ops::{Deref, DerefMut};
+use std::path::{Path, PathBuf};
+
+use crate::board::{ALL_DIRECTIONS, Board};
+use crate::database::{STATE_COUNT, rank, unrank};
+use crate::persist::Format;
+use crate::stats::Stats;
+
+/// Magic bytes and version of a cache file
+///
+/// The file is updated in place as layers are expanded, so unlike the other
+/// tables it carries no checksum.
+const FORMAT: Format = Format {
+    magic: *b"O8GC",
+    version: 1,
+    name: "goal cache",
+};
+
+/// Bytes before the distances: magic, version and completed depth
+const HEADER_LEN: usize = FORMAT.magic.len() + 2;
+
+/// Offset of the completed depth inside the file
+const DEPTH_OFFSET: usize = HEADER_LEN - 1;
+
+/// Marker for states whose distance is not known yet
+const UNKNOWN: u8 = u8::MAX;
+
+/// Bytes backing a goal cache
+enum Storage {
+    /// File read into memory, written back on flush
+    #[cfg(not(feature = "mmap"))]
+    Owned { bytes: Box<[u8]>, file: File },
+    /// File memory-mapped read-write
+    #[cfg(feature = "mmap")]
+    Mapped(memmap2::MmapMut),
+}
+
+impl Deref for Storage {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            #[cfg(not(feature = "mmap"))]
+            Storage::Owned { bytes, .. } => bytes,
+            #[cfg(feature = "mmap")]
+            Storage::Mapped(map) => map,
+        }
+    }
+}
+
+impl DerefMut for Storage {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        match self {
+            #[cfg(not(feature = "mmap"))]
+            Storage::Owned { bytes, .. } => bytes,
+            #[cfg(feature = "mmap")]
+            Storage::Mapped(map) => map,
+        }
+    }
+}
+
+/// Result of solving a board through the cache
+#[derive(Clone, Debug)]
+pub struct CachedSolution {
+    /// Boards from the start to the goal, as short as possible
+    pub path: Vec<Board>,
+    /// Statistics of the layers expanded for this board (all zero for a pure lookup)
+    pub stats: Stats,
+    /// Number of layers expanded to reach the board
+    pub layers_expanded: usize,
+    /// Number of boards in the layers added to the cache
+    pub boards_added: usize,
+}
+
+impl CachedSolution {
+    /// Whether the board was already in the cache
+    #[must_use]
+    pub fn was_lookup(&self) -> bool {
+        self.layers_expanded == 0
+    }
+}
+
+/// Persistent, incrementally built table of distances to the goal
+pub struct GoalCache {
+    /// File holding the table
+    path: PathBuf,
+    /// Header and distances, as stored on disk
+    storage: Storage,
+    /// Whether the in-memory table differs from the file
+    dirty: bool,
+}
+
+impl GoalCache {
+    /// Opens the cache file at `path`, creating it if it does not exist
+    ///
+    /// A new cache only knows the goal itself.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be created, read or mapped, or if
+    /// it exists but is not a valid cache
+    ///
+    /// # Panics
+    ///
+    /// Never panics in practice: the goal is always ranked
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)?;
+        if file.metadata()?.len() == 0 {
+            file.write_all(&empty_table())?;
+            file.sync_all()?;
+        }
+
+        let storage = open_storage(&file)?;
+        let invalid = |msg: &str| io::Error::new(io::ErrorKind::InvalidData, msg.to_owned());
+        let [depth, distances @ ..] = FORMAT.read_tag(&storage)? else {
+            return Err(invalid("truncated goal cache"));
+        };
+        let goal = rank(Board::default()).expect("The goal is always reachable");
+        if distances.len() != STATE_COUNT || distances[goal] != 0 || *depth == UNKNOWN {
+            return Err(invalid("corrupt goal cache"));
+        }
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            storage,
+            dirty: false,
+        })
+    }
+
+    /// File holding the table
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Largest distance whose layer is complete in the cache
+    #[must_use]
+    pub fn completed_depth(&self) -> u8 {
+        self.storage[DEPTH_OFFSET]
+    }
+
+    /// Number of boards whose distance is known
+    #[must_use]
+    pub fn known_boards(&self) -> usize {
+        let depth = self.completed_depth();
+        self.distances().iter().filter(|&&d| d <= depth).count()
+    }
+
+    /// Exact number of moves from `board` to the goal, if the cache knows it
+    ///
+    /// # Returns
+    ///
+    /// `Some(distance)`, or `None` if the board is unsolvable or lies beyond
+    /// the completed depth
+    #[must_use]
+    pub fn distance(&self, board: Board) -> Option<u8> {
+        let distance = self.distances()[rank(board)?];
+        (distance <= self.completed_depth()).then_some(distance)
+    }
+
+    /// Finds a shortest solution of `start`, expanding the cache if needed
+    ///
+    /// # Returns
+    ///
+    /// `Some(solution)`, or `None` if the goal cannot be reached from `start`
+    ///
+    /// # Panics
+    ///
+    /// Never panics in practice: every board on the path is ranked
+    pub fn solve(&mut self, start: Board) -> Option<CachedSolution> {
+        let started = Instant::now();
+        let index = rank(start)?;
+        let mut stats = Stats::default();
+        let mut layers_expanded = 0;
+        let mut boards_added = 0;
+
+        while self.distances()[index] > self.completed_depth() {
+            let added = self.expand_layer(&mut stats);
+            if added == 0 {
+                return None;
+            }
+            layers_expanded += 1;
+            boards_added += added;
+        }
+
+        let mut path = vec![start];
+        let mut board = start;
+        while let Some(distance) = self.distance(board).and_then(|d| d.checked_sub(1)) {
+            board = ALL_DIRECTIONS
+                .into_iter()
+                .filter_map(|direction| board.move_space(direction).ok())
+                .find(|&next| self.distance(next) == Some(distance))
+                .expect("Every known board has a neighbour one move closer");
+            path.push(board);
+        }
+
+        stats.solution_moves = path.len() - 1;
+
+        stats.solution_cost = stats.solution_moves;
+        stats.enqueued_nodes = boards_added;
+        stats.duplicates_pruned = stats.generated_nodes - boards_added;
+        stats.max_depth_reached = usize::from(self.completed_depth());
+        stats.duration_ms = started.elapsed().as_millis();
+        Some(CachedSolution {
+            path,
+            stats,
+            layers_expanded,
+            boards_added,
+        })
+    }
+
+    /// Writes the table back to its file
+    ///
+    /// Memory-mapped caches are flushed to disk; caches read into memory are
+    /// rewritten if they changed. Dropping the cache flushes it too, but
+    /// ignores errors.
+    ///
+    /// # Errors
+    ///
+    /// Returns any I/O error raised while writing the file
+    pub fn flush(&mut self) -> io::Result<()> {
+        if !self.dirty {
+            return Ok(());
+        }
+        match &mut self.storage {
+            #[cfg(not(feature = "mmap"))]
+            Storage::Owned { bytes, file } => {
+                use std::io::{Seek, SeekFrom};
+
+                file.seek(SeekFrom::Start(0))?;
+                file.write_all(bytes)?;
+                file.sync_all()?;
+            }
+            #[cfg(feature = "mmap")]
+            Storage::Mapped(map) => map.flush()?,
+        }
+        self.dirty = false;
+        Ok(())
+    }
+
+    /// Distances of every state, indexed by rank
+    fn distances(&self) -> &[u8] {
+        &self.storage[HEADER_LEN..]
+    }
+
+    /// Adds the layer after the completed depth and marks it complete
+    ///
+    /// # Returns
+    ///
+    /// The number of boards in the new layer, 0 once every reachable board is known
+    #[allow(clippy::naive_bytecount)]
+    fn expand_layer(&mut self, stats: &mut Stats) -> usize {
+        let depth = self.completed_depth();
+        let mut layer_len = 0;
+
+        let distances = &mut self.storage[HEADER_LEN..];
+        for index in 0..STATE_COUNT {
+            if distances[index] != depth {
+                continue;
+            }
+            layer_len += 1;
+            let board = unrank(index);
+            for direction in ALL_DIRECTIONS {
+                let Ok(next) = board.move_space(direction) else {
+                    continue;
+                };
+                stats.generated_nodes += 1;
+                let next = rank(next).expect("Moves preserve solvability");
+                // Entries beyond the next layer are unknown or left over from
+                // an interrupted expansion, and are overwritten
+                if distances[next] > depth + 1 {
+                    distances[next] = depth + 1;
+                }
+            }
+        }
+        let added = distances.iter().filter(|&&d| d == depth + 1).count();
+        stats.nodes_explored += layer_len;
+        stats.max_frontier = stats.max_frontier.max(layer_len);
+
+        if added > 0 {
+            self.storage[DEPTH_OFFSET] = depth + 1;
+            self.dirty = true;
+        }
+        added
+    }
+}
+
+impl Drop for GoalCache {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
+
+/// Contents of a new cache file, which only knows the goal
+fn empty_table() -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(HEADER_LEN + STATE_COUNT);
+    bytes.extend_from_slice(&FORMAT.tag());
+    bytes.push(0);
+    bytes.resize(HEADER_LEN + STATE_COUNT, UNKNOWN);
+    bytes[HEADER_LEN + rank(Board::default()).expect("The goal is always reachable")] = 0;
+    bytes
+}
+
+/// Memory-maps a cache file read-write
+#[cfg(feature = "mmap")]
+fn open_storage(file: &File) -> io::Result<Storage> {
+    // SAFETY: the cache file is private to the process using it; concurrent
+    // runs sharing one file would only race on entries they both compute
+    // identically
+    let map = unsafe { memmap2::MmapMut::map_mut(file)? };
+    Ok(Storage::Mapped(map))
+}
+
+/// Reads a cache file into memory, keeping it open for write-back
+#[cfg(not(feature = "mmap"))]
+fn open_storage(file: &File) -> io::Result<Storage> {
+    use std::io::{Read, Seek, SeekFrom};
+
+    let mut file = file.try_clone()?;
+    file.seek(SeekFrom::Start(0))?;
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes)?;
+    Ok(Storage::Owned {
+        bytes: bytes.into_boxed_slice(),
+        file,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use rand::{Rng, SeedableRng, rngs::StdRng};
+
+    use super::*;
+    use crate::database::StateSpaceDatabase;
+
+    #[test]
+    fn grows_persists_and_matches_the_state_space_database() {
+        let dir = std::env::temp_dir().join(format!("o8-goal-cache-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("goal.cache");
+        let database = StateSpaceDatabase::build();
+        let mut rng = StdRng::seed_from_u64(480);
+        let boards: Vec<Board> = (0..6)
+            .map(|_| Board::from_reachable_rank(rng.random_range(0..Board::REACHABLE_COUNT)))
+            .map(Option::unwrap)
+            .collect();
+
+        let mut cache = GoalCache::open(&path).unwrap();
+        assert_eq!((cache.completed_depth(), cache.known_boards()), (0, 1));
+        for &board in &boards {
+            let solution = cache.solve(board).unwrap();
+            let distance = database.distance(board).unwrap();
+            assert_eq!(solution.path.len() - 1, usize::from(distance), "{board}");
+            assert!(solution.path.last().is_some_and(|last| last.is_solved()));
+            for pair in solution.path.windows(2) {
+                assert!(pair[0].successors().any(|(_, next)| next == pair[1]));
+            }
+            assert!(cache.solve(board).unwrap().was_lookup());
+        }
+        assert!(cache.solve("213804765".parse().unwrap()).is_none());
+        let depth = cache.completed_depth();
+        drop(cache);
+
+        let cache = GoalCache::open(&path).unwrap();
+        assert_eq!(cache.completed_depth(), depth);
+        for &board in &boards {
+            assert_eq!(cache.distance(board), database.distance(board));
+        }
+        drop(cache);
+
+        fs::write(&path, b"not a cache").unwrap();
+        assert!(GoalCache::open(&path).is_err());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,314 @@
+//! # HDA* Module
+//!
+//! Hash-distributed A* (Kishimoto et al. 2009): one A* search of a single
+//! board spread over several threads. Every board is owned by one worker,
+//! chosen by a hash of the board, and only its owner keeps it in an open or
+//! closed list. A worker expands the best nodes of its own open list and
+//! sends every child it does not own to the child's owner over a channel, so
+//! the workers never share or lock a list.
+//!
+//! Without a global order, a board may be expanded before its shortest path
+//! is known; a shorter path arriving later reopens it, which
+//! [`Stats::reexpanded_nodes`] counts. Reaching the goal only sets an
+//! incumbent cost: the search ends when no worker holds a node whose f-value
+//! is below the incumbent and no node is still in a channel, which makes the
+//! incumbent optimal.
+
+use crate::time::Instant;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::Duration;
+
+use crate::board::{ALL_DIRECTIONS, Board, BoardWithSteps};
+use crate::stats::Stats;
+
+/// How long an idle worker waits for a node before checking for termination again
+const IDLE_WAIT: Duration = Duration::from_millis(1);
+
+/// A solution found by HDA*
+#[derive(Clone, Debug)]
+pub struct HdaSolution {
+    /// Every board from the start to the goal
+    pub path: Vec<Board>,
+    /// Nodes expanded by each worker
+    pub worker_expansions: Vec<usize>,
+    /// Nodes sent from one worker to another
+    pub messages: usize,
+    /// Work of every worker combined; `max_frontier` sums the peaks of the open lists
+    pub stats: Stats,
+}
+
+/// A node sent to its owner: the board, its depth and the board it was reached from
+type Message = (Board, usize, Option<Board>);
+
+/// State shared by every worker
+struct Shared {
+    senders: Vec<Sender<Message>>,
+    /// Cost of the best solution found so far, `usize::MAX` until one is
+    incumbent: AtomicUsize,
+    /// Workers with something to do plus nodes still in a channel; the search
+    /// is over once it reaches 0, as nothing can then produce new work
+    active: AtomicUsize,
+}
+
+impl Shared {
+    /// Index of the worker that owns `board`
+    fn owner(&self, board: Board) -> usize {
+        // Fibonacci hashing spreads neighboring encodings over the workers
+        let hash = u64::from(board.encoded()).wrapping_mul(0x9E37_79B9_7F4A_7C15) >> 32;
+        usize::try_from(hash).expect("32-bit hash fits in usize") % self.senders.len()
+    }
+}
+
+/// One worker and the boards it owns
+struct Worker<'a> {
+    id: usize,
+    shared: &'a Shared,
+    inbox: Receiver<Message>,
+    open: BinaryHeap<Reverse<BoardWithSteps>>,
+    /// Best known depth and parent of every board seen by this worker
+    reached: HashMap<Board, (usize, Option<Board>)>,
+    /// Boards expanded at least once
+    expanded: HashSet<Board>,
+    messages: usize,
+    stats: Stats,
+}
+
+impl Worker<'_> {
+    /// Records `board` at `depth` if it is new or shorter than known, queueing it for expansion
+    fn receive(&mut self, (board, depth, parent): Message) {
+        if self
+            .reached
+            .get(&board)
+            .is_some_and(|&(known, _)| known <= depth)
+        {
+            self.stats.duplicates_pruned += 1;
+            return;
+        }
+        self.reached.insert(board, (depth, parent));
+        if board.is_solved() {
+            self.shared.incumbent.fetch_min(depth, Ordering::SeqCst);
+            return;
+        }
+        self.stats.enqueued_nodes += 1;
+        self.open.push(Reverse(BoardWithSteps::new(board, depth)));
+        self.stats.max_frontier = self.stats.max_frontier.max(self.open.len());
+    }
+
+    /// Pops the best open node that can still lead to a cheaper solution
+    fn next_node(&mut self) -> Option<BoardWithSteps> {
+        while let Some(Reverse(node)) = self.open.pop() {
+            // Entries left behind by a shorter path are stale
+            if self.reached[&node.0].0 != node.1 {
+                continue;
+            }
+            if node.f() < self.shared.incumbent.load(Ordering::SeqCst) {
+                return Some(node);
+            }
+            // Cheaper solutions cannot come from here, but a lower incumbent never rises again
+            self.open.clear();
+        }
+        None
+    }
+
+    fn expand(&mut self, node: &BoardWithSteps) {
+        let (board, depth) = (node.0, node.1);
+        self.stats.nodes_explored += 1;
+        self.stats.max_depth_reached = self.stats.max_depth_reached.max(depth);
+        if !self.expanded.insert(board) {
+            self.stats.reexpanded_nodes += 1;
+        }
+        for direction in ALL_DIRECTIONS {
+            let Ok(child) = board.move_space(direction) else {
+                continue;
+            };
+            self.stats.generated_nodes += 1;
+            let message = (child, depth + 1, Some(board));
+            let owner = self.shared.owner(child);
+            if owner == self.id {
+                self.receive(message);
+            } else {
+                // Counted before sending, so the search cannot look finished meanwhile
+                self.shared.active.fetch_add(1, Ordering::SeqCst);
+                self.messages += 1;
+                self.shared.senders[owner]
+                    .send(message)
+                    .expect("Workers outlive the search");
+            }
+        }
+    }
+
+    /// Runs until no worker has anything left to do
+    fn run(&mut self) {
+        loop {
+            while let Ok(message) = self.inbox.try_recv() {
+                self.receive(message);
+                self.shared.active.fetch_sub(1, Ordering::SeqCst);
+            }
+            if let Some(node) = self.next_node() {
+                self.expand(&node);
+                continue;
+            }
+
+            // Idle: the count of a node received while idle passes to this worker
+            self.shared.active.fetch_sub(1, Ordering::SeqCst);
+            loop {
+                match self.inbox.recv_timeout(IDLE_WAIT) {
+                    Ok(message) => {
+                        self.receive(message);
+                        break;
+                    }
+                    Err(RecvTimeoutError::Timeout) => {
+                        if self.shared.active.load(Ordering::SeqCst) == 0 {
+                            return;
+                        }
+                    }
+                    Err(RecvTimeoutError::Disconnected) => return,
+                }
+            }
+        }
+    }
+}
+
+/// Solves a board with HDA* on every available core
+///
+/// # Returns
+///
+/// `Some(solution)`, or `None` if the board cannot reach the goal
+#[must_use]
+pub fn solve(board: Board) -> Option<HdaSolution> {
+    let threads = thread::available_parallelism().map_or(1, usize::from);
+    solve_with_threads(board, threads)
+}
+
+/// Solves a board with HDA* on `threads` worker threads
+///
+/// The solution is optimal, since the Manhattan distance is admissible. A
+/// thread count of 0 is treated as 1.
+///
+/// # Returns
+///
+/// `Some(solution)`, or `None` if the board cannot reach the goal
+///
+/// # Panics
+///
+/// Panics if a worker thread panics
+#[must_use]
+pub fn solve_with_threads(board: Board, threads: usize) -> Option<HdaSolution> {
+    let started = Instant::now();
+    if !board.is_solvable() {
+        return None;
+    }
+
+    let threads = threads.max(1);
+    let (senders, inboxes): (Vec<_>, Vec<_>) = (0..threads).map(|_| mpsc::channel()).unzip();
+    let shared = Shared {
+        senders,
+        incumbent: AtomicUsize::new(usize::MAX),
+        // Every worker starts active, plus the start board on its way
+        active: AtomicUsize::new(threads + 1),
+    };
+    shared.senders[shared.owner(board)]
+        .send((board, 0, None))
+        .expect("The inbox is still open");
+
+    let workers: Vec<Worker> = thread::scope(|scope| {
+        let handles: Vec<_> = inboxes
+            .into_iter()
+            .enumerate()
+            .map(|(id, inbox)| {
+                let shared = &shared;
+                scope.spawn(move || {
+                    let mut worker = Worker {
+                        id,
+                        shared,
+                        inbox,
+                        open: BinaryHeap::new(),
+                        reached: HashMap::new(),
+                        expanded: HashSet::new(),
+                        messages: 0,
+                        stats: Stats::default(),
+                    };
+                    worker.run();
+                    worker
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("HDA* worker panicked"))
+            .collect()
+    });
+
+    let mut stats = Stats::default();
+    let mut reached = HashMap::new();
+    let mut messages = 0;
+    let mut worker_expansions = Vec::with_capacity(workers.len());
+    for worker in workers {
+        stats.nodes_explored += worker.stats.nodes_explored;
+        stats.generated_nodes += worker.stats.generated_nodes;
+        stats.enqueued_nodes += worker.stats.enqueued_nodes;
+        stats.duplicates_pruned += worker.stats.duplicates_pruned;
+        stats.reexpanded_nodes += worker.stats.reexpanded_nodes;
+        stats.max_frontier += worker.stats.max_frontier;
+        stats.max_depth_reached = stats.max_depth_reached.max(worker.stats.max_depth_reached);
+        worker_expansions.push(worker.stats.nodes_explored);
+        messages += worker.messages;
+        reached.extend(worker.reached);
+    }
+
+    // Each parent was recorded with a smaller depth than its child, so the walk ends
+    let mut path = vec![Board::default()];
+    while let Some(&(_, Some(parent))) = reached.get(path.last().expect("Never empty")) {
+        path.push(parent);
+    }
+    path.reverse();
+
+    stats.solution_moves = path.len() - 1;
+
+    stats.solution_cost = stats.solution_moves;
+    stats.duration_ms = started.elapsed().as_millis();
+    Some(HdaSolution {
+        path,
+        worker_expansions,
+        messages,
+        stats,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{Rng, SeedableRng, rngs::StdRng};
+
+    use super::*;
+    use crate::database::StateSpaceDatabase;
+
+    fn assert_optimal(board: Board, distance: u8, solution: &HdaSolution) {
+        assert_eq!(solution.path.len() - 1, usize::from(distance), "{board}");
+        assert_eq!(solution.path.first(), Some(&board));
+        assert!(solution.path.last().is_some_and(|last| last.is_solved()));
+        for pair in solution.path.windows(2) {
+            assert!(pair[0].successors().any(|(_, next)| next == pair[1]));
+        }
+    }
+
+    #[test]
+    fn solution_lengths_match_the_state_space_database() {
+        let database = StateSpaceDatabase::build();
+        let mut rng = StdRng::seed_from_u64(526);
+        for threads in 1..=4 {
+            let rank = rng.random_range(0..Board::REACHABLE_COUNT);
+            let board = Board::from_reachable_rank(rank).unwrap();
+            let distance = database.distance(board).unwrap();
+
+            assert_optimal(board, distance, &solve(board).unwrap());
+            let solution = solve_with_threads(board, threads).unwrap();
+            assert_optimal(board, distance, &solution);
+            assert_eq!(solution.worker_expansions.len(), threads);
+        }
+        assert!(solve("213804765".parse().unwrap()).is_none());
+    }
+}
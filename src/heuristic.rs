@@ -0,0 +1,259 @@
+//! # Heuristic Module
+//!
+//! The [`Heuristic`] trait estimates the number of moves from a board to the
+//! goal. The generic [`Solver`](crate::solver::Solver) takes any
+//! [`SharedHeuristic`] and caches each estimate in the node it creates
+//! ([`BoardWithSteps`](crate::board::BoardWithSteps)), so the orderings of
+//! the frontiers compare stored values and never depend on which heuristic
+//! produced them. Adding a heuristic is therefore one `impl` of the trait.
+//!
+//! Built-in heuristics: [`Manhattan`] (the default), [`MisplacedTiles`], the
+//! [walking distance](crate::walking_distance::WalkingDistance) and
+//! [additive pattern databases](crate::pdb::AdditiveHeuristic). Closures
+//! `Fn(Board) -> u32` are heuristics too. [`HeuristicKind`] names them for
+//! the command line and loads the ones that live in files.
+
+use std::fmt::{self, Display};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+
+use crate::board::Board;
+use crate::bootstrap::LearnedHeuristic;
+use crate::pdb::{AdditiveHeuristic, PatternDatabase};
+use crate::walking_distance::WalkingDistance;
+
+/// Estimate of the number of moves from a board to the goal
+///
+/// A heuristic that never overestimates (is admissible) keeps the solutions
+/// of the heuristic search optimal; the doctor spot-checks this with
+/// [`check_admissible`](crate::doctor::check_admissible).
+pub trait Heuristic: Send + Sync {
+    /// Estimated number of moves from `board` to the goal
+    fn estimate(&self, board: Board) -> u32;
+}
+
+/// A heuristic shared by every clone of a solver
+pub type SharedHeuristic = Arc<dyn Heuristic>;
+
+impl<F> Heuristic for F
+where
+    F: Fn(Board) -> u32 + Send + Sync,
+{
+    fn estimate(&self, board: Board) -> u32 {
+        self(board)
+    }
+}
+
+/// Sum of the Manhattan distances of the tiles to their goal cells
+///
+/// See [`Board::heuristic_distance_to_solution`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Manhattan;
+
+impl Heuristic for Manhattan {
+    fn estimate(&self, board: Board) -> u32 {
+        u32::from(board.heuristic_distance_to_solution())
+    }
+}
+
+/// Number of tiles outside their goal cells
+///
+/// See [`Board::misplaced_tiles`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MisplacedTiles;
+
+impl Heuristic for MisplacedTiles {
+    fn estimate(&self, board: Board) -> u32 {
+        u32::from(board.misplaced_tiles())
+    }
+}
+
+/// Heuristic that guides the searches of the generic solver, chosen by name
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum HeuristicKind {
+    /// Sum of the Manhattan distances of the tiles to their goal cells
+    #[default]
+    Manhattan,
+    /// Number of tiles outside their goal cells
+    Misplaced,
+    /// Sum of disjoint pattern databases read from files, never below the Manhattan distance
+    Pdb,
+    /// Row and column walking distances, never below the Manhattan distance
+    WalkingDistance,
+    /// Table learned by [`bootstrap`](crate::bootstrap) and written to this file
+    Learned(PathBuf),
+}
+
+impl Display for HeuristicKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HeuristicKind::Manhattan => f.write_str("manhattan"),
+            HeuristicKind::Misplaced => f.write_str("misplaced"),
+            HeuristicKind::Pdb => f.write_str("pdb"),
+            HeuristicKind::WalkingDistance => f.write_str("walking-distance"),
+            HeuristicKind::Learned(path) => write!(f, "learned:{}", path.display()),
+        }
+    }
+}
+
+/// Parses `manhattan`, `misplaced`, `pdb`, `walking-distance` or `learned:<FILE>`
+impl FromStr for HeuristicKind {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "manhattan" => Ok(HeuristicKind::Manhattan),
+            "misplaced" => Ok(HeuristicKind::Misplaced),
+            "pdb" => Ok(HeuristicKind::Pdb),
+            "walking-distance" => Ok(HeuristicKind::WalkingDistance),
+            other => match other.strip_prefix("learned:") {
+                Some(path) if !path.is_empty() => Ok(HeuristicKind::Learned(PathBuf::from(path))),
+                _ => Err(
+                    "Invalid heuristic: expected manhattan, misplaced, pdb, walking-distance or learned:FILE",
+                ),
+            },
+        }
+    }
+}
+
+/// A heuristic loaded by [`HeuristicKind::load`], ready for the solver
+pub struct LoadedHeuristic {
+    /// The heuristic itself
+    pub function: SharedHeuristic,
+    /// The pattern databases behind `function`, for their lookup counts
+    pub databases: Option<Arc<AdditiveHeuristic>>,
+}
+
+/// Why a heuristic could not be loaded
+#[derive(Debug)]
+pub enum LoadHeuristicError {
+    /// A pattern database could not be read
+    PatternDatabase {
+        /// File of the database
+        path: PathBuf,
+        /// Why it could not be read
+        error: io::Error,
+    },
+    /// A learned table could not be read
+    Learned {
+        /// File of the table
+        path: PathBuf,
+        /// Why it could not be read
+        error: io::Error,
+    },
+    /// Two pattern databases share tiles, so their costs cannot be added
+    Overlapping(&'static str),
+}
+
+impl Display for LoadHeuristicError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadHeuristicError::PatternDatabase { path, error } => {
+                write!(
+                    f,
+                    "Cannot open pattern database {}: {error}",
+                    path.display()
+                )
+            }
+            LoadHeuristicError::Learned { path, error } => {
+                write!(
+                    f,
+                    "Cannot open learned heuristic {}: {error}",
+                    path.display()
+                )
+            }
+            LoadHeuristicError::Overlapping(message) => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for LoadHeuristicError {}
+
+impl HeuristicKind {
+    /// Loads the heuristic, `None` for the Manhattan distance built into the solver
+    ///
+    /// `pdb_files` are the pattern databases summed by [`HeuristicKind::Pdb`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a pattern database or a learned table cannot be
+    /// read, or if two pattern databases share tiles.
+    pub fn load(
+        self,
+        pdb_files: &[PathBuf],
+    ) -> Result<Option<LoadedHeuristic>, LoadHeuristicError> {
+        let function: SharedHeuristic = match self {
+            HeuristicKind::Manhattan => return Ok(None),
+            HeuristicKind::Misplaced => Arc::new(MisplacedTiles),
+            HeuristicKind::WalkingDistance => Arc::new(WalkingDistance),
+            HeuristicKind::Learned(path) => match LearnedHeuristic::open(&path) {
+                Ok(learned) => Arc::new(learned),
+                Err(error) => return Err(LoadHeuristicError::Learned { path, error }),
+            },
+            HeuristicKind::Pdb => {
+                let databases = pdb_files
+                    .iter()
+                    .map(|path| open_pattern_database(path))
+                    .collect::<Result<_, _>>()?;
+                let heuristic = Arc::new(
+                    AdditiveHeuristic::new(databases).map_err(LoadHeuristicError::Overlapping)?,
+                );
+                return Ok(Some(LoadedHeuristic {
+                    function: Arc::clone(&heuristic) as SharedHeuristic,
+                    databases: Some(heuristic),
+                }));
+            }
+        };
+        Ok(Some(LoadedHeuristic {
+            function,
+            databases: None,
+        }))
+    }
+}
+
+/// Reads one pattern database of [`HeuristicKind::Pdb`]
+fn open_pattern_database(path: &Path) -> Result<PatternDatabase, LoadHeuristicError> {
+    PatternDatabase::open(path).map_err(|error| LoadHeuristicError::PatternDatabase {
+        path: path.to_owned(),
+        error,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn heuristic_kinds_round_trip_through_their_names() {
+        for kind in [
+            HeuristicKind::Manhattan,
+            HeuristicKind::Misplaced,
+            HeuristicKind::Pdb,
+            HeuristicKind::WalkingDistance,
+            HeuristicKind::Learned(PathBuf::from("table.bin")),
+        ] {
+            assert_eq!(kind.to_string().parse(), Ok(kind));
+        }
+        assert!("learned:".parse::<HeuristicKind>().is_err());
+        assert!("euclidean".parse::<HeuristicKind>().is_err());
+    }
+
+    #[test]
+    fn loading_reports_the_missing_file() {
+        let path = std::env::temp_dir().join(format!("o8-missing-{}.bin", std::process::id()));
+        let kind = HeuristicKind::Learned(path.clone());
+        assert!(matches!(
+            kind.load(&[]),
+            Err(LoadHeuristicError::Learned { path: missing, .. }) if missing == path
+        ));
+
+        let pdb = HeuristicKind::Pdb.load(std::slice::from_ref(&path));
+        assert!(matches!(
+            pdb,
+            Err(LoadHeuristicError::PatternDatabase { path: missing, .. }) if missing == path
+        ));
+        assert!(HeuristicKind::Manhattan.load(&[]).unwrap().is_none());
+    }
+}
@@ -0,0 +1,150 @@
+//! # Hint Module
+//!
+//! Next-move hints with bounded latency. [`hint_within`] runs an anytime
+//! weighted A*: nodes are ordered by `g + 2h`, which finds a first solution
+//! after expanding only a handful of nodes, and the search then keeps going
+//! to improve it, pruning every node whose admissible `g + h` cannot beat the
+//! best solution so far. When the open list runs dry the best solution is
+//! provably optimal; when the time budget runs out first the best move found
+//! so far is returned without that guarantee.
+
+use crate::time::Instant;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::time::Duration;
+
+use crate::board::{ALL_DIRECTIONS, Board, Direction};
+use crate::database;
+
+/// Weight of `h` in the anytime search's ordering
+const HEURISTIC_WEIGHT: usize = 2;
+
+/// Number of expansions between two checks of the clock
+const CLOCK_CHECK_INTERVAL: usize = 256;
+
+/// Next move suggested by [`hint_within`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Hint {
+    /// Next empty-space move, or `None` if the board is already solved
+    pub direction: Option<Direction>,
+    /// Board after applying the move
+    pub board: Board,
+    /// Moves to the goal along the best solution found, if one was found in time
+    pub moves_left: Option<usize>,
+    /// Whether the move is provably on a shortest solution
+    pub optimal: bool,
+}
+
+/// Suggests the next move of `board`, spending at most about `budget` on the search
+///
+/// The budget is checked every few hundred expansions, so it may be
+/// overrun by a fraction of a millisecond. If no solution is found in time,
+/// the move to the successor with the lowest Manhattan distance is returned.
+///
+/// # Arguments
+///
+/// * `board` - The board to move from
+/// * `budget` - Wall-clock time the search may take
+///
+/// # Returns
+///
+/// `Some(hint)`, or `None` if the board cannot reach the goal
+///
+/// # Panics
+///
+/// Never panics in practice: every solvable board has a legal move
+#[must_use]
+pub fn hint_within(board: Board, budget: Duration) -> Option<Hint> {
+    database::rank(board)?;
+    if board.is_solved() {
+        return Some(Hint {
+            direction: None,
+            board,
+            moves_left: Some(0),
+            optimal: true,
+        });
+    }
+
+    let deadline = Instant::now() + budget;
+    let mut open = BinaryHeap::new();
+    // Best known depth of each board and the first move leading to it
+    let mut reached: HashMap<Board, (usize, Direction)> = HashMap::new();
+    let mut best: Option<(usize, Direction)> = None;
+
+    for direction in ALL_DIRECTIONS {
+        if let Ok(next) = board.move_space(direction) {
+            reached.insert(next, (1, direction));
+            open.push(Reverse((priority(next, 1), 1, next.encoded())));
+        }
+    }
+
+    let mut expansions = 0;
+    let mut exhausted = true;
+    while let Some(Reverse((_, depth, encoded))) = open.pop() {
+        let current = Board::from_encoded(encoded);
+        let (known, first) = reached[&current];
+        let bound = best.map_or(usize::MAX, |(moves, _)| moves);
+        if depth > known || depth + usize::from(current.heuristic_distance_to_solution()) >= bound {
+            continue;
+        }
+
+        expansions += 1;
+        if expansions % CLOCK_CHECK_INTERVAL == 0 && Instant::now() >= deadline {
+            exhausted = false;
+            break;
+        }
+        if current.is_solved() {
+            best = Some((depth, first));
+            continue;
+        }
+
+        for direction in ALL_DIRECTIONS {
+            let Ok(next) = current.move_space(direction) else {
+                continue;
+            };
+            let next_depth = depth + 1;
+            if reached
+                .get(&next)
+                .is_none_or(|&(known, _)| next_depth < known)
+            {
+                reached.insert(next, (next_depth, first));
+                open.push(Reverse((
+                    priority(next, next_depth),
+                    next_depth,
+                    next.encoded(),
+                )));
+            }
+        }
+    }
+
+    let (moves_left, direction) = match best {
+        Some((moves, direction)) => (Some(moves), direction),
+        None => (None, greedy_move(board)),
+    };
+    Some(Hint {
+        direction: Some(direction),
+        board: board
+            .move_space(direction)
+            .expect("Hints only suggest legal moves"),
+        moves_left,
+        optimal: exhausted && best.is_some(),
+    })
+}
+
+/// Ordering key of the anytime search: `g + 2h`
+fn priority(board: Board, depth: usize) -> usize {
+    depth + HEURISTIC_WEIGHT * usize::from(board.heuristic_distance_to_solution())
+}
+
+/// Legal move to the successor with the lowest Manhattan distance
+fn greedy_move(board: Board) -> Direction {
+    ALL_DIRECTIONS
+        .into_iter()
+        .filter_map(|direction| {
+            let next = board.move_space(direction).ok()?;
+            Some((next.heuristic_distance_to_solution(), direction))
+        })
+        .min_by_key(|&(h, _)| h)
+        .map(|(_, direction)| direction)
+        .expect("Every board has a legal move")
+}
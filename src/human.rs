@@ -0,0 +1,226 @@
+//! # Human Module
+//!
+//! A layered solver that works the way people solve sliding puzzles by hand:
+//! first the top row, then the left column, and finally the remaining 2x2
+//! block, which only has one cycle left to rotate.
+//!
+//! Each stage is solved with a small breadth-first search that never moves the
+//! tiles placed by earlier stages, except for the last tile of a row or
+//! column: that one cannot be dropped into its corner without briefly lifting
+//! its neighbor, so its stage only keeps the earlier lines fixed. The result is
+//! an annotated plan ("place 1 in its corner", "rotate the final cycle") whose
+//! solutions are longer than optimal but easy to follow.
+
+use crate::time::Instant;
+use std::collections::{HashMap, VecDeque};
+
+use crate::board::{ALL_DIRECTIONS, BOARD_AREA, BOARD_SIDE, Board, Direction};
+use crate::stats::Stats;
+
+/// One stage of a human-style plan
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Stage {
+    /// What the stage achieves, e.g. `"place 1 in its corner"`
+    pub description: String,
+    /// Moves of the empty space, in order
+    pub moves: Vec<Direction>,
+    /// Board after each move
+    pub boards: Vec<Board>,
+}
+
+/// A complete human-style plan from a start board to the goal
+#[derive(Clone, Debug)]
+pub struct HumanSolution {
+    /// The board the plan starts from
+    pub start: Board,
+    /// Stages in the order they are played
+    pub stages: Vec<Stage>,
+    /// Work done by the stage searches
+    pub stats: Stats,
+}
+
+impl HumanSolution {
+    /// Every board of the plan, from the start to the goal
+    ///
+    /// The path has the same shape as `Solver::step_by_step_solution`.
+    #[must_use]
+    pub fn path(&self) -> Vec<Board> {
+        let mut path = vec![self.start];
+        for stage in &self.stages {
+            path.extend_from_slice(&stage.boards);
+        }
+        path
+    }
+}
+
+/// Definition of one stage: which cells stay fixed and which must be solved
+struct StageGoal {
+    /// What the stage achieves
+    description: String,
+    /// Cells the empty space may not enter
+    locked: Vec<usize>,
+    /// Cells that must hold their goal tile when the stage ends
+    solved: Vec<usize>,
+}
+
+/// Builds the stages: top row, left column, then the final cycle
+fn stage_goals(goal: &[u8; BOARD_AREA as usize]) -> Vec<StageGoal> {
+    let side = usize::from(BOARD_SIDE);
+    let row: Vec<usize> = (0..side).collect();
+    let column: Vec<usize> = (1..side).map(|r| r * side).collect();
+
+    let mut stages = Vec::new();
+    let mut solved = Vec::new();
+
+    for (i, &cell) in row.iter().enumerate() {
+        solved.push(cell);
+        let description = match i {
+            0 => format!("place {} in its corner", goal[cell]),
+            _ if i + 1 == side => format!("place {} to finish the top row", goal[cell]),
+            _ => format!("place {} next to {}", goal[cell], goal[cell - 1]),
+        };
+        // The last tile of a line needs its neighbor lifted briefly
+        let locked = if i + 1 == side {
+            Vec::new()
+        } else {
+            solved[..i].to_vec()
+        };
+        stages.push(StageGoal {
+            description,
+            locked,
+            solved: solved.clone(),
+        });
+    }
+
+    for (i, &cell) in column.iter().enumerate() {
+        solved.push(cell);
+        let description = if i + 1 == column.len() {
+            format!("place {} to finish the left column", goal[cell])
+        } else {
+            format!("place {} below {}", goal[cell], goal[cell - side])
+        };
+        let locked = if i + 1 == column.len() {
+            row.clone()
+        } else {
+            solved[..solved.len() - 1].to_vec()
+        };
+        stages.push(StageGoal {
+            description,
+            locked,
+            solved: solved.clone(),
+        });
+    }
+
+    stages.push(StageGoal {
+        description: "rotate the final cycle".to_owned(),
+        locked: solved,
+        solved: (0..side * side).collect(),
+    });
+
+    stages
+}
+
+/// Breadth-first search for one stage, updating the shared statistics
+///
+/// # Returns
+///
+/// The moves and boards of the stage, or `None` if the stage cannot be completed
+fn solve_stage(
+    start: Board,
+    goal: &[u8; BOARD_AREA as usize],
+    stage: &StageGoal,
+    stats: &mut Stats,
+) -> Option<(Vec<Direction>, Vec<Board>)> {
+    let is_done = |board: Board| {
+        let cells = board.into_arr();
+        stage.solved.iter().all(|&cell| cells[cell] == goal[cell])
+    };
+
+    let mut parents: HashMap<Board, (Board, Direction)> = HashMap::new();
+    let mut depths: HashMap<Board, usize> = HashMap::from([(start, 0)]);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(board) = queue.pop_front() {
+        stats.nodes_explored += 1;
+        if is_done(board) {
+            let mut moves = Vec::new();
+            let mut boards = Vec::new();
+            let mut current = board;
+            while let Some(&(parent, direction)) = parents.get(&current) {
+                moves.push(direction);
+                boards.push(current);
+                current = parent;
+            }
+            moves.reverse();
+            boards.reverse();
+            return Some((moves, boards));
+        }
+
+        let depth = depths[&board];
+        for direction in ALL_DIRECTIONS {
+            let Ok(next) = board.move_space(direction) else {
+                continue;
+            };
+            if stage
+                .locked
+                .contains(&usize::from(next.find_space_position()))
+            {
+                continue;
+            }
+            stats.generated_nodes += 1;
+            if depths.contains_key(&next) {
+                stats.duplicates_pruned += 1;
+                continue;
+            }
+            stats.enqueued_nodes += 1;
+            stats.max_depth_reached = stats.max_depth_reached.max(depth + 1);
+            depths.insert(next, depth + 1);
+            parents.insert(next, (board, direction));
+            queue.push_back(next);
+        }
+        stats.max_frontier = stats.max_frontier.max(queue.len());
+    }
+
+    None
+}
+
+/// Solves a board stage by stage, the way a person would
+///
+/// # Arguments
+///
+/// * `board` - The board to solve
+///
+/// # Returns
+///
+/// `Some(solution)` with the annotated plan, or `None` if the board cannot reach the goal
+#[must_use]
+pub fn solve(board: Board) -> Option<HumanSolution> {
+    let started = Instant::now();
+    let goal = Board::default().into_arr();
+    let mut stats = Stats::default();
+    let mut stages = Vec::new();
+    let mut current = board;
+
+    for stage in stage_goals(&goal) {
+        let (moves, boards) = solve_stage(current, &goal, &stage, &mut stats)?;
+        if let Some(&last) = boards.last() {
+            current = last;
+        }
+        stages.push(Stage {
+            description: stage.description,
+            moves,
+            boards,
+        });
+    }
+
+    stats.solution_moves = stages.iter().map(|s| s.moves.len()).sum();
+
+    stats.solution_cost = stats.solution_moves;
+    let mut solution = HumanSolution {
+        start: board,
+        stages,
+        stats,
+    };
+    solution.stats.duration_ms = started.elapsed().as_millis();
+    Some(solution)
+}
@@ -0,0 +1,212 @@
+//! # JUnit Module
+//!
+//! Turns benchmark results into a JUnit XML report so regressions show up in
+//! CI dashboards and PR checks that already understand JUnit. Every
+//! [`Threshold`] becomes one test case, which fails when the measured
+//! percentile exceeds the limit.
+//!
+//! Thresholds are written as `STRATEGY:METRIC:PERCENTILE<=MAX`, for example
+//! `Heuristic:nodes_explored:p95<=5000`. Strategy names are matched
+//! case-insensitively and metric names are the fields of [`StatsSummary`]
+//! (see [`StatsSummary::METRIC_NAMES`]).
+
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::stats::StatsSummary;
+
+/// Percentile names accepted in a threshold
+const PERCENTILES: [&str; 5] = ["p50", "p75", "p90", "p95", "p99"];
+
+/// An upper bound on one percentile of one strategy's metric
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Threshold {
+    /// Strategy the check applies to, e.g. `"Heuristic"`
+    pub strategy: String,
+    /// Metric name, e.g. `"nodes_explored"`
+    pub metric: String,
+    /// Percentile name, e.g. `"p95"`
+    pub percentile: String,
+    /// Largest accepted value
+    pub max: u64,
+}
+
+impl FromStr for Threshold {
+    type Err = &'static str;
+
+    /// Parses a threshold from `STRATEGY:METRIC:PERCENTILE<=MAX`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        const FORMAT: &str = "Invalid threshold: expected STRATEGY:METRIC:PERCENTILE<=MAX";
+
+        let (target, max) = s.split_once("<=").ok_or(FORMAT)?;
+        let mut parts = target.trim().split(':');
+        let (Some(strategy), Some(metric), Some(percentile), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(FORMAT);
+        };
+
+        if strategy.is_empty() {
+            return Err(FORMAT);
+        }
+        if !StatsSummary::METRIC_NAMES.contains(&metric) {
+            return Err("Invalid threshold: unknown metric");
+        }
+        if !PERCENTILES.contains(&percentile) {
+            return Err("Invalid threshold: percentile must be one of p50, p75, p90, p95, p99");
+        }
+        let max = max
+            .trim()
+            .parse()
+            .map_err(|_| "Invalid threshold: MAX must be a non-negative integer")?;
+
+        Ok(Self {
+            strategy: strategy.to_owned(),
+            metric: metric.to_owned(),
+            percentile: percentile.to_owned(),
+            max,
+        })
+    }
+}
+
+/// Outcome of evaluating one threshold
+enum Outcome {
+    /// The measured value is within the limit
+    Passed(u64),
+    /// The measured value exceeds the limit
+    Failed(u64),
+    /// The threshold names a strategy that was not benchmarked
+    Missing,
+}
+
+impl Threshold {
+    /// Name of the test case generated for this threshold
+    fn case_name(&self) -> String {
+        format!("{}.{} <= {}", self.metric, self.percentile, self.max)
+    }
+
+    /// Evaluates the threshold against the benchmark results
+    fn evaluate(&self, results: &[(&str, &StatsSummary)]) -> Outcome {
+        let value = results
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(&self.strategy))
+            .and_then(|(_, summary)| summary.metric(&self.metric))
+            .and_then(|metric| metric.percentile(&self.percentile));
+
+        match value {
+            Some(v) if v <= self.max => Outcome::Passed(v),
+            Some(v) => Outcome::Failed(v),
+            None => Outcome::Missing,
+        }
+    }
+}
+
+/// Writes a JUnit XML report with one test case per threshold
+///
+/// # Arguments
+///
+/// * `path` - Destination file, overwritten if it exists
+/// * `suite` - Name of the test suite, e.g. `"o8 benchmark"`
+/// * `results` - Strategy names paired with their aggregated statistics
+/// * `thresholds` - Checks to turn into test cases
+///
+/// # Returns
+///
+/// The number of failed checks
+///
+/// # Errors
+///
+/// Returns any I/O error raised while writing the file
+pub fn write_junit_report(
+    path: &Path,
+    suite: &str,
+    results: &[(&str, &StatsSummary)],
+    thresholds: &[Threshold],
+) -> io::Result<usize> {
+    let (xml, failures) = render_report(suite, results, thresholds);
+    let mut file = BufWriter::new(File::create(path)?);
+    file.write_all(xml.as_bytes())?;
+    file.flush()?;
+    Ok(failures)
+}
+
+/// Renders the report, returning the XML and the number of failed checks
+fn render_report(
+    suite: &str,
+    results: &[(&str, &StatsSummary)],
+    thresholds: &[Threshold],
+) -> (String, usize) {
+    let mut cases = String::new();
+    let mut failures = 0;
+    let mut errors = 0;
+
+    for threshold in thresholds {
+        let classname = format!("{}.{}", escape(suite), escape(&threshold.strategy));
+        let name = escape(&threshold.case_name());
+        let _ = writeln!(
+            cases,
+            "    <testcase classname=\"{classname}\" name=\"{name}\">"
+        );
+
+        match threshold.evaluate(results) {
+            Outcome::Passed(value) => {
+                let _ = writeln!(cases, "      <system-out>measured {value}</system-out>");
+            }
+            Outcome::Failed(value) => {
+                failures += 1;
+                let message = escape(&format!(
+                    "{} of {} is {value}, above the limit of {}",
+                    threshold.percentile, threshold.metric, threshold.max
+                ));
+                let _ = writeln!(
+                    cases,
+                    "      <failure type=\"threshold\" message=\"{message}\"/>"
+                );
+            }
+            Outcome::Missing => {
+                errors += 1;
+                let _ = writeln!(
+                    cases,
+                    "      <error type=\"missing\" message=\"no results for this strategy\"/>"
+                );
+            }
+        }
+
+        cases.push_str("    </testcase>\n");
+    }
+
+    let suite = escape(suite);
+    let tests = thresholds.len();
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        xml,
+        "<testsuites name=\"{suite}\" tests=\"{tests}\" failures=\"{failures}\" errors=\"{errors}\">"
+    );
+    let _ = writeln!(
+        xml,
+        "  <testsuite name=\"{suite}\" tests=\"{tests}\" failures=\"{failures}\" errors=\"{errors}\">"
+    );
+    xml.push_str(&cases);
+    xml.push_str("  </testsuite>\n</testsuites>\n");
+
+    (xml, failures + errors)
+}
+
+/// Escapes text for use in XML attributes and content
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,182 @@
+//! # K-Best Module
+//!
+//! The `k` shortest distinct solutions of a board. A* keeps one parent link
+//! per board and stops at the first goal, so it can only ever return one
+//! path. This search instead runs A* over paths: every node of its tree is a
+//! path from the start, so a board reached along several paths appears once
+//! for each of them, and the search carries on after each goal it reaches.
+//!
+//! With an admissible, consistent heuristic the goals come off the open list
+//! in order of path length, so the first `k` of them are the `k` shortest
+//! paths. Paths that visit a board twice are dropped, so the solutions are
+//! simple paths rather than the shortest ones with a loop added.
+
+use crate::time::Instant;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use crate::board::{ALL_DIRECTIONS, Board};
+use crate::stats::Stats;
+
+/// The shortest distinct solutions of a board
+#[derive(Clone, Debug)]
+pub struct KBestSolutions {
+    /// Every solution as boards from the start to the goal, shortest first
+    pub paths: Vec<Vec<Board>>,
+    /// Work of the search; the solution lengths are those of the first path
+    pub stats: Stats,
+}
+
+/// One node of the search tree: a board and the path that reached it
+struct PathNode {
+    board: Board,
+    depth: usize,
+    /// Index of the node this one was reached from, `None` for the start
+    parent: Option<usize>,
+}
+
+/// The search tree, every node stored once and linked to its parent
+struct PathTree {
+    nodes: Vec<PathNode>,
+}
+
+impl PathTree {
+    /// Whether `board` lies on the path to the node at `index`
+    fn on_path(&self, mut index: usize, board: Board) -> bool {
+        loop {
+            let node = &self.nodes[index];
+            if node.board == board {
+                return true;
+            }
+            match node.parent {
+                Some(parent) => index = parent,
+                None => return false,
+            }
+        }
+    }
+
+    /// Boards from the start to the node at `index`
+    fn path(&self, mut index: usize) -> Vec<Board> {
+        let mut path = vec![self.nodes[index].board];
+        while let Some(parent) = self.nodes[index].parent {
+            path.push(self.nodes[parent].board);
+            index = parent;
+        }
+        path.reverse();
+        path
+    }
+}
+
+/// Finds the `k` shortest distinct solutions of `board`
+///
+/// Solutions of equal length come in no particular order. Fewer than `k`
+/// paths are returned only if `k` exceeds the number of simple paths to the
+/// goal, which the search would take very long to establish.
+///
+/// # Returns
+///
+/// `Some(solutions)`, or `None` if the board cannot reach the goal
+#[must_use]
+pub fn solve(board: Board, k: usize) -> Option<KBestSolutions> {
+    let started = Instant::now();
+    if !board.is_solvable() {
+        return None;
+    }
+
+    let mut stats = Stats::default();
+    let mut tree = PathTree {
+        nodes: vec![PathNode {
+            board,
+            depth: 0,
+            parent: None,
+        }],
+    };
+    let h = board.heuristic_distance_to_solution();
+    // Among equal f-values, the node closer to the goal first
+    let mut open = BinaryHeap::from([Reverse((usize::from(h), h, 0))]);
+    let mut paths = Vec::with_capacity(k);
+
+    while paths.len() < k {
+        let Some(Reverse((_, _, index))) = open.pop() else {
+            break;
+        };
+        let (current, depth) = (tree.nodes[index].board, tree.nodes[index].depth);
+        stats.nodes_explored += 1;
+        stats.max_depth_reached = stats.max_depth_reached.max(depth);
+        if current.is_solved() {
+            paths.push(tree.path(index));
+            continue;
+        }
+
+        for direction in ALL_DIRECTIONS {
+            let Ok(child) = current.move_space(direction) else {
+                continue;
+            };
+            stats.generated_nodes += 1;
+            if tree.on_path(index, child) {
+                stats.duplicates_pruned += 1;
+                continue;
+            }
+            let h = child.heuristic_distance_to_solution();
+            open.push(Reverse((depth + 1 + usize::from(h), h, tree.nodes.len())));
+            tree.nodes.push(PathNode {
+                board: child,
+                depth: depth + 1,
+                parent: Some(index),
+            });
+            stats.enqueued_nodes += 1;
+        }
+        stats.max_frontier = stats.max_frontier.max(open.len());
+    }
+
+    if let Some(first) = paths.first() {
+        stats.solution_moves = first.len() - 1;
+        stats.solution_cost = stats.solution_moves;
+    }
+    stats.duration_ms = started.elapsed().as_millis();
+    Some(KBestSolutions { paths, stats })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use super::*;
+    use crate::database::StateSpaceDatabase;
+    use crate::scramble::Walk;
+
+    #[test]
+    fn paths_are_distinct_simple_and_shortest_first() {
+        let database = StateSpaceDatabase::build();
+        let mut rng = StdRng::seed_from_u64(534);
+        for _ in 0..6 {
+            let board = Walk::NonReversing.scramble(14, &mut rng);
+            let solutions = solve(board, 8).unwrap();
+            assert_eq!(solutions.paths.len(), 8, "{board}");
+
+            let distance = usize::from(database.distance(board).unwrap());
+            assert_eq!(solutions.paths[0].len() - 1, distance, "{board}");
+            assert_eq!(solutions.stats.solution_moves, distance);
+            assert!(
+                solutions
+                    .paths
+                    .windows(2)
+                    .all(|pair| pair[0].len() <= pair[1].len())
+            );
+
+            let distinct: HashSet<&Vec<Board>> = solutions.paths.iter().collect();
+            assert_eq!(distinct.len(), solutions.paths.len());
+            for path in &solutions.paths {
+                assert_eq!(path.first(), Some(&board));
+                assert!(path.last().is_some_and(|last| last.is_solved()));
+                assert_eq!(path.iter().collect::<HashSet<_>>().len(), path.len());
+                for pair in path.windows(2) {
+                    assert!(pair[0].successors().any(|(_, next)| next == pair[1]));
+                }
+            }
+        }
+        assert!(solve("213804765".parse().unwrap(), 2).is_none());
+    }
+}
@@ -0,0 +1,191 @@
+//! # O8 - 8-Puzzle Solver Library
+//!
+//! Core building blocks of the o8 solver, usable from any Rust program:
+//!
+//! - [`board`]: The compact 32-bit [`Board`](board::Board) representation, moves and the
+//!   Manhattan-distance heuristic.
+//! - [`solver`]: The generic [`Solver`](solver::Solver), which drives any frontier strategy
+//!   and records detailed statistics.
+//! - [`scramble`]: Scramble models for generated boards: uniform, non-reversing and
+//!   self-avoiding walks, and weighted mixtures of them.
+//! - [`heuristic`]: The [`Heuristic`](heuristic::Heuristic) trait behind the solver's
+//!   estimates, with the Manhattan and misplaced-tiles heuristics.
+//! - [`search_strategies`]: The [`SearchStrategy`](search_strategies::SearchStrategy) trait
+//!   and the built-in DFS/BFS and heuristic (A*-style) frontiers.
+//! - [`registry`]: Strategy names mapped to solver constructors, shared by the CLI, the
+//!   servers and the browser bindings, and open to strategies defined downstream.
+//! - [`stats`]: Per-run [`Stats`](stats::Stats), percentile summaries, bootstrap confidence
+//!   intervals and table rendering.
+//! - [`hint`]: Time-budgeted next-move hints from an anytime search, flagged when provably
+//!   optimal.
+//! - [`human`]: Layered row-then-column solver that explains its plan step by step.
+//! - [`broadening`]: Iterative broadening, a bounded-memory DFS that widens the number of
+//!   successors it follows per node on every restart.
+//! - [`bidirectional`]: Meet-in-the-middle bidirectional heuristic search (MM) from the start
+//!   and the goal at once.
+//! - [`dfbnb`]: Depth-first branch-and-bound, an anytime search that tightens its bound with
+//!   every better solution until the last one is proven optimal.
+//! - [`ehc`]: Enforced hill climbing, breadth-first lookaheads from the current board to the
+//!   nearest board with a lower Manhattan distance.
+//! - [`fringe`]: Fringe search, the thresholds of IDA* resumed from the deferred nodes of the
+//!   last iteration instead of searched again from the start.
+//! - [`frontier_search`]: Breadth-first frontier search from both ends, keeping only the last
+//!   layers and rebuilding the path by divide and conquer.
+//! - [`hda`]: Hash-distributed A* (HDA*), one optimal search of a single board spread over
+//!   worker threads that own the boards by hash.
+//! - [`kbest`]: The `k` shortest distinct solutions of a board, from an A* search over paths
+//!   that carries on past the first goal.
+//! - [`lrta`]: Learning real-time A* (LRTA*), an agent that commits to one move at a time and
+//!   raises the heuristic of the boards it leaves.
+//! - [`perimeter`]: Perimeter search, an A* search from the start that stops at a table of
+//!   exact distances of the boards around the goal.
+//! - [`oracle`]: Optimal solutions read from the state-space database, one lookup per
+//!   successor instead of a search.
+//! - [`portfolio`]: Races DFS, BFS and the heuristic search on threads, keeping the first
+//!   solution and cancelling the rest.
+//! - [`sized`]: Boards other than 3×3, outside the solver: [`SizedBoard`](sized::SizedBoard)
+//!   grids and the 15-puzzle, solved optimally with IDA*.
+//! - [`sma`]: Simplified memory-bounded A* (SMA*), which forgets its worst leaves to stay
+//!   within a node budget and backs their f-values up to their parents.
+//! - [`bootstrap`]: A heuristic table learned from the costs observed on progressively harder
+//!   random boards.
+//! - [`board15`]: The packed 64-bit [`Board15`](board15::Board15) of the 15-puzzle, encoded like
+//!   [`Board`](board::Board) and searched by [`sized`].
+//! - [`boardset`]: Text and JSON board set files with per-board metadata and strict validation,
+//!   plus built-in sets such as the hardest instances (`builtin:hard`).
+//! - [`checkpoint`]: Snapshots of an in-progress search, saved to disk and resumed later.
+//! - [`compress`]: Detour removal that shortens non-optimal solutions (e.g. DFS output) to a
+//!   canonical form.
+//! - [`cost`]: Per-tile move costs, such as tile `k` costing `k`, for cheapest rather than
+//!   shortest solutions.
+//! - [`convention`]: Translation of boards, solutions and moves between goal conventions
+//!   (the spiral goal and row-major goals used by other solvers).
+//! - [`database`]: Exact distance of every reachable board, cached on disk, for perfect
+//!   hints, optimality audits and difficulty grades.
+//! - [`doctor`]: Runtime self-test: strategies solve, replay and match exact distances, and
+//!   heuristics are spot-checked for admissibility.
+//! - [`explore`]: Bounded breadth-first layer counts of the states reachable from a board.
+//! - [`overlap`]: Rank bitsets of explored states and their overlap between two strategies.
+//! - [`external`]: External-memory BFS with sorted layer files on disk and delayed duplicate
+//!   detection, for searches whose closed list exceeds RAM.
+//! - [`goal_cache`]: Goal-rooted distance table persisted in a file and grown layer by layer,
+//!   so solving many boards reuses earlier searches.
+//! - [`pdb`]: Additive pattern databases, cached on disk with integrity checksums.
+//! - [`walking_distance`]: The walking distance heuristic, from exact distances of row and
+//!   column abstractions of the board.
+//! - [`symmetry`]: Rotations and reflections that keep the goal, for searching one board of
+//!   each symmetry class.
+//! - [`render`]: Raster drawing of boards, animated GIF and SVG export of solutions.
+//! - [`visualize`]: Recording of search progress, rendered as an animated HTML page.
+//! - [`async_solve`]: Runtime-agnostic helpers to await solves and stream their progress.
+//! - [`adversarial`]: Hill-climbing generator for boards that maximize a strategy's work.
+//! - `export`: JSON export of benchmark results with a reproducibility manifest
+//!   (requires the `serde` feature, enabled by default).
+//! - [`junit`]: JUnit XML reports that turn benchmark threshold checks into test cases.
+//! - `benchmark`: Parallel strategy comparisons over random boards or a board set, behind
+//!   `o8 benchmark` (requires the `cli` feature).
+//! - `commands`: The other subcommands of the `o8` binary, from solving a board set to
+//!   building pattern databases, returning a `CommandError` instead of exiting (requires `cli`).
+//! - `report`: Terminal output of solutions and of the inspection subcommands (requires `cli`).
+//! - `rpc`: JSON-RPC 2.0 over line-oriented streams, used by `o8 rpc` (requires `serde`).
+//! - `schema`: Versioned JSON shapes for solutions and benchmark results (requires `serde`).
+//! - `wasm`: Browser bindings built with `wasm-bindgen` (requires the `wasm` feature).
+//! - `websocket`: The `rpc` protocol over WebSocket with streamed search progress
+//!   (requires the `websocket` feature).
+//!
+//! ## Feature flags
+//!
+//! - `cli` (default): The `o8` binary and its terminal UI (`clap`, `indicatif`,
+//!   `comfy-table`, `colored`). Implies `parallel` and `serde`.
+//! - `parallel`: Multi-threaded generation and benchmarking with `rayon`.
+//! - `serde`: `Serialize`/`Deserialize` for statistics, JSON/`MessagePack` export and schemas.
+//! - `gif`: Animated GIF export of solutions with the `gif` crate.
+//! - `mmap`: Memory-map cached pattern databases and goal caches with `memmap2` instead of
+//!   reading them.
+//! - `wasm`: Browser bindings built with `wasm-bindgen`.
+//! - `websocket`: JSON-RPC over WebSocket with `tungstenite`. Implies `serde`.
+//!
+//! Library consumers that only need [`Board`](board::Board) and
+//! [`Solver`](solver::Solver) can depend on `o8` with `default-features = false`.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use o8::board::Board;
+//! use o8::search_strategies::HeuristicSearchStrategy;
+//! use o8::solver::Solver;
+//!
+//! let board = Board::random_with_solution(40);
+//! let mut solver = Solver::new(HeuristicSearchStrategy::default());
+//! solver.solve(board).expect("scrambled boards are always solvable");
+//!
+//! for step in solver.step_by_step_solution() {
+//!     println!("{step}\n");
+//! }
+//! println!("{}", solver.get_solution_stats());
+//! ```
+#![warn(clippy::pedantic)]
+
+pub mod adversarial;
+pub mod async_solve;
+#[cfg(feature = "cli")]
+pub mod benchmark;
+pub mod bidirectional;
+pub mod board;
+pub mod board15;
+pub mod boardset;
+pub mod bootstrap;
+pub mod broadening;
+pub mod checkpoint;
+mod checksum;
+#[cfg(feature = "cli")]
+pub mod commands;
+pub mod compress;
+pub mod convention;
+pub mod cost;
+pub mod database;
+pub mod dfbnb;
+pub mod doctor;
+pub mod ehc;
+pub mod explore;
+#[cfg(feature = "serde")]
+pub mod export;
+pub mod external;
+pub mod fringe;
+pub mod frontier_search;
+pub mod goal_cache;
+pub mod hda;
+pub mod heuristic;
+pub mod hint;
+pub mod human;
+pub mod junit;
+pub mod kbest;
+pub mod lrta;
+pub mod oracle;
+pub mod overlap;
+pub mod pdb;
+pub mod perimeter;
+mod persist;
+pub mod portfolio;
+pub mod registry;
+pub mod render;
+#[cfg(feature = "cli")]
+pub mod report;
+#[cfg(feature = "serde")]
+pub mod rpc;
+#[cfg(feature = "serde")]
+pub mod schema;
+pub mod scramble;
+pub mod search_strategies;
+pub mod sized;
+pub mod sma;
+pub mod solver;
+pub mod stats;
+pub mod symmetry;
+mod time;
+pub mod visualize;
+pub mod walking_distance;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(feature = "websocket")]
+pub mod websocket;
@@ -0,0 +1,160 @@
+//! # LRTA* Module
+//!
+//! Learning Real-Time A* (Korf 1990): an agent that plays the board one move
+//! at a time instead of planning the whole solution first. Before every move
+//! it looks a bounded number of moves ahead, values each neighbor by the
+//! cheapest `g + h` among the boards its lookahead reaches, commits to the
+//! best neighbor, and raises the `h` of the board it leaves to that value.
+//!
+//! The raised values live in a learned table that starts out as the Manhattan
+//! distance, so a board the agent comes back to looks worse every time and
+//! the agent cannot loop forever: with an admissible heuristic it always
+//! reaches the goal. The moves it commits to form the solution, detours
+//! included, which is what an animation of the agent playing should show;
+//! [`Stats::record_compression`] measures the path once its detours are cut
+//! out.
+
+use crate::time::Instant;
+use std::collections::HashMap;
+
+use crate::board::{ALL_DIRECTIONS, Board};
+use crate::stats::Stats;
+
+/// Moves the agent looks ahead before each move in [`solve`]
+pub const DEFAULT_LOOKAHEAD: usize = 1;
+
+/// A solution played by LRTA*
+#[derive(Clone, Debug)]
+pub struct LrtaSolution {
+    /// Every board the agent stood on, from the start to the goal
+    pub path: Vec<Board>,
+    /// Moves looked ahead before each move
+    pub lookahead: usize,
+    /// Stats of the play, with the raises of the learned table and its final
+    /// size
+    pub stats: Stats,
+}
+
+/// State of the agent
+struct Agent {
+    /// Learned heuristic values that differ from the Manhattan distance
+    learned: HashMap<Board, usize>,
+    lookahead: usize,
+    stats: Stats,
+}
+
+impl Agent {
+    /// Learned heuristic value of `board`
+    fn h(&self, board: Board) -> usize {
+        self.learned
+            .get(&board)
+            .copied()
+            .unwrap_or_else(|| usize::from(board.heuristic_distance_to_solution()))
+    }
+
+    /// Cheapest `g + h` over the boards up to `depth` moves below `board`, `g` counted from `board`
+    ///
+    /// Boards already on `path` are skipped, so the lookahead never walks in
+    /// circles. The value is never below the learned `h` of `board`, so a move
+    /// is valued above the `h` of the board it leads to: every move either
+    /// lands on a lower `h` or raises the learned value of the board left, and
+    /// the agent cannot walk in circles without learning.
+    fn look_ahead(&mut self, board: Board, depth: usize, path: &mut Vec<Board>) -> usize {
+        if depth == 0 || board.is_solved() {
+            return self.h(board);
+        }
+        self.stats.nodes_explored += 1;
+        let mut best = usize::MAX;
+        for direction in ALL_DIRECTIONS {
+            let Ok(child) = board.move_space(direction) else {
+                continue;
+            };
+            self.stats.generated_nodes += 1;
+            if path.contains(&child) {
+                self.stats.duplicates_pruned += 1;
+                continue;
+            }
+            path.push(child);
+            let value = self.look_ahead(child, depth - 1, path);
+            path.pop();
+            best = best.min(value.saturating_add(1));
+        }
+        best.max(self.h(board))
+    }
+
+    /// Chooses the move from `board`, raising its learned value, and returns the next board
+    fn step(&mut self, board: Board) -> Board {
+        self.stats.nodes_explored += 1;
+        let mut best: Option<(usize, Board)> = None;
+        for direction in ALL_DIRECTIONS {
+            let Ok(child) = board.move_space(direction) else {
+                continue;
+            };
+            self.stats.generated_nodes += 1;
+            let mut path = vec![board, child];
+            let value = self
+                .look_ahead(child, self.lookahead - 1, &mut path)
+                .saturating_add(1);
+            if best.is_none_or(|(lowest, _)| value < lowest) {
+                best = Some((value, child));
+            }
+        }
+        let (value, next) = best.expect("Every board has a legal move");
+        if value > self.h(board) {
+            self.learned.insert(board, value);
+            self.stats.learned_updates += 1;
+        }
+        self.stats.enqueued_nodes += 1;
+        next
+    }
+}
+
+/// Plays a board with LRTA*, looking [`DEFAULT_LOOKAHEAD`] moves ahead
+///
+/// # Returns
+///
+/// `Some(solution)`, or `None` if the board cannot reach the goal
+#[must_use]
+pub fn solve(board: Board) -> Option<LrtaSolution> {
+    solve_with_lookahead(board, DEFAULT_LOOKAHEAD)
+}
+
+/// Plays a board with LRTA*, looking `lookahead` moves ahead before each move
+///
+/// A deeper lookahead costs more work per move but wastes fewer moves on
+/// detours. A lookahead of 0 is treated as 1.
+///
+/// # Returns
+///
+/// `Some(solution)`, or `None` if the board cannot reach the goal
+#[must_use]
+pub fn solve_with_lookahead(board: Board, lookahead: usize) -> Option<LrtaSolution> {
+    let started = Instant::now();
+    if !board.is_solvable() {
+        return None;
+    }
+
+    let mut agent = Agent {
+        learned: HashMap::new(),
+        lookahead: lookahead.max(1),
+        stats: Stats::default(),
+    };
+    let mut path = vec![board];
+    let mut current = board;
+    while !current.is_solved() {
+        current = agent.step(current);
+        path.push(current);
+    }
+
+    let mut stats = agent.stats;
+    stats.solution_moves = path.len() - 1;
+    stats.solution_cost = stats.solution_moves;
+    stats.max_depth_reached = stats.solution_moves;
+    stats.learned_entries = agent.learned.len();
+    stats.duration_ms = started.elapsed().as_millis();
+    Some(LrtaSolution {
+        path,
+        lookahead: agent.lookahead,
+        stats,
+    })
+}
@@ -9,36 +9,67 @@
 //!
 //! ## CLI overview
 //!
-//! This binary exposes two subcommands:
+//! This binary exposes fifteen subcommands:
 //!
-//! - `benchmark`: Generate random solvable boards and compare strategies in parallel.
+//! - `benchmark`: Generate random solvable boards (or read a board set) and compare strategies in parallel.
 //! - `solve-random`: Scramble a solved board and print the step-by-step solution with the chosen strategy.
+//! - `generate`: Write a board set file with seeds, exact distances and difficulty grades.
+//! - `solve`: Solve every board of a board set file with the chosen strategy.
+//! - `adversarial`: Hill-climb towards boards that maximize a strategy's work.
+//! - `lookup`: Exact distance, difficulty and best move of a board from the state-space database.
+//! - `analyze-space`: Distribution of optimal solution lengths and God's number.
+//! - `pdb build`: Build additive pattern databases and cache them on disk.
+//! - `oracle build`: Build the exact-distance table behind the oracle strategy and write it to disk.
+//! - `bootstrap`: Learn a heuristic table from progressively harder random boards.
+//! - `doctor`: Self-test every strategy and heuristic against the exact-distance table.
+//! - `overlap`: Compare the states two strategies explore on the same board.
+//! - `algorithms`: List the registered strategies accepted by `--algorithm` and `rpc`.
+//! - `translate`: Translate a board between the spiral goal and row-major goal conventions.
+//! - `rpc`: Serve JSON-RPC requests (`solve`, `hint`, `distance`, `generate`) on stdin/stdout
+//!   or over WebSocket.
 //!
+//! The solver and every strategy work on 3×3 boards only. Other sizes are limited to
+//! `solve-random`, which solves them with a separate IDA* search: `--size 4` scrambles a
+//! 15-puzzle, `--rows 2 --cols 3` (2-4 each) a 2×3 board. No other subcommand takes them.
+//!
+//! The binary is a thin command-line front end over the `o8` library crate.
 //! See the project README or run with `--help` for full details.
 #![warn(clippy::pedantic)]
 
+use std::env;
+use std::io;
+use std::path::PathBuf;
+use std::process;
+use std::sync::Arc;
+
+use clap::ArgGroup;
 use clap::Parser;
 use clap::Subcommand;
 use clap::ValueEnum;
-use indicatif::ParallelProgressIterator;
-use indicatif::ProgressIterator;
-use rayon::ThreadPoolBuilder;
-use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
-
-use crate::board::BoardWithSteps;
-use crate::search_strategies::HeuristicSearchStrategy;
-use crate::search_strategies::SearchStrategy;
-use crate::search_strategies::SimpleSearchStrategy;
-use crate::{
-    board::Board,
-    solver::{ExplorerStrategy, Solver},
-    stats::{Stats, print_comparison_table, print_run_stats},
-};
-
-pub(crate) mod board;
-pub(crate) mod search_strategies;
-pub(crate) mod solver;
-pub(crate) mod stats;
+use clap::builder::{PossibleValue, PossibleValuesParser, TypedValueParser};
+use rand::{Rng, rng};
+
+use o8::adversarial::{AdversarialConfig, AdversarialObjective};
+use o8::benchmark::{BenchmarkInput, BenchmarkReports, Budget, CiTarget, benchmark};
+use o8::boardset::BoardSource;
+use o8::bootstrap::{self, BootstrapConfig};
+use o8::commands::{self, CommandError, FileSolveOptions, SolutionExports};
+use o8::convention::GoalConvention;
+use o8::cost::MoveCosts;
+use o8::database::StateSpaceDatabase;
+use o8::explore;
+use o8::heuristic::{HeuristicKind, LoadedHeuristic};
+use o8::junit::Threshold;
+use o8::pdb::{Partition, Pattern};
+use o8::registry::{self, StrategyConfig, StrategyEntry};
+use o8::report;
+use o8::rpc;
+use o8::schema::OutputFormat;
+use o8::scramble::{Scramble, ScrambleModel};
+use o8::search_strategies::PruningPolicy;
+use o8::sized::{Puzzle15, SizedBoard};
+use o8::websocket;
+use o8::{board::Board, solver::ChildOrder};
 
 /// Default number of test runs to perform
 const DEFAULT_RUNS: usize = 200;
@@ -46,16 +77,108 @@ const DEFAULT_RUNS: usize = 200;
 /// Default number of scramble steps to generate random boards
 const DEFAULT_SCRAMBLE_STEPS: usize = 200;
 
-/// Available solving algorithms
-#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
-enum SolveAlgorithm {
-    /// Depth-First Search: explores as far as possible along each branch before backtracking
-    Dfs,
-    /// Breadth-First Search: explores all neighbors at the current depth before moving deeper
-    Bfs,
-    /// Heuristic Search: uses a heuristic to prioritize paths that seem most promising
+/// Default upper bound on the number of boards with `benchmark --until-ci`
+const DEFAULT_MAX_RUNS: usize = 20_000;
+
+/// Default number of boards written by `generate`
+const DEFAULT_COUNT: usize = 10;
+
+/// Default number of hill-climbing restarts for the adversarial generator
+const DEFAULT_RESTARTS: usize = 16;
+
+/// Default number of probe solves per hill-climbing restart
+const DEFAULT_ITERATIONS: usize = 50;
+
+/// Default number of adversarial instances to print
+const DEFAULT_TOP: usize = 10;
+
+/// Default number of maximal-distance boards printed by `analyze-space`
+const DEFAULT_EXAMPLES: usize = 3;
+
+/// Default number of distinct states `explore` keeps, enough for a whole component
+const DEFAULT_EXPLORE_NODES: usize = 200_000;
+
+/// Default file written by `solve --checkpoint-every`
+const DEFAULT_CHECKPOINT_FILE: &str = "o8.checkpoint";
+
+/// Default number of explored nodes between two frames of `--visualize` and `--frontier-timeline`
+const DEFAULT_VISUALIZE_INTERVAL: usize = 100;
+
+/// Default number of boards every strategy solves in `doctor`
+const DEFAULT_DOCTOR_SAMPLES: usize = 20;
+
+/// A registered strategy chosen on the command line, e.g. `--algorithm bfs`
+#[derive(Clone, Copy, Debug)]
+struct Algorithm(StrategyEntry);
+
+impl Default for Algorithm {
+    fn default() -> Self {
+        Self::named("heuristic")
+    }
+}
+
+impl Algorithm {
+    /// The built-in strategy called `name`
+    fn named(name: &str) -> Self {
+        Self(registry::lookup(name).expect("built-in strategies are always registered"))
+    }
+
+    /// Registry entry of the algorithm
+    fn entry(self) -> StrategyEntry {
+        self.0
+    }
+}
+
+/// Parser of `--algorithm` values, accepting every registered strategy
+///
+/// `--help` lists the strategies with their descriptions.
+fn algorithm_parser() -> impl TypedValueParser<Value = Algorithm> {
+    let values = registry::strategies()
+        .into_iter()
+        .map(|entry| PossibleValue::new(entry.name).help(entry.description));
+    PossibleValuesParser::new(values).map(|name| Algorithm::named(&name))
+}
+
+/// Named goals of the generic solver's searches
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+enum GoalLayout {
+    /// `123804765`: tiles in a clockwise spiral around a central empty space
     #[default]
-    Heuristic,
+    Spiral,
+    /// `123456780`: tiles in reading order, empty space in the bottom-right corner
+    Classic,
+}
+
+impl GoalLayout {
+    /// The goal board of the layout
+    fn goal(self) -> Board {
+        let convention = match self {
+            GoalLayout::Spiral => GoalConvention::Spiral,
+            GoalLayout::Classic => GoalConvention::RowMajor,
+        };
+        Board::from_arr(&convention.goal())
+    }
+}
+
+/// Board shape of `solve-random`: the 3×3 default, a square `--size` or `--rows` by `--cols`
+#[derive(clap::Args)]
+struct BoardShape {
+    /// Side length of the board: 3 for the 8-puzzle, 4 for the 15-puzzle (solved with IDA*)
+    #[arg(long, default_value_t = 3, value_parser = clap::value_parser!(u8).range(3..=4))]
+    size: u8,
+    /// Rows of a rectangular board, from 2 to 4, with --cols (solved with IDA*)
+    #[arg(long, requires = "cols", conflicts_with = "size", value_parser = clap::value_parser!(u8).range(2..=4))]
+    rows: Option<u8>,
+    /// Columns of a rectangular board, from 2 to 4, with --rows
+    #[arg(long, requires = "rows", value_parser = clap::value_parser!(u8).range(2..=4))]
+    cols: Option<u8>,
+}
+
+impl BoardShape {
+    /// Rows and columns of the chosen board
+    fn dims(&self) -> (u8, u8) {
+        self.rows.zip(self.cols).unwrap_or((self.size, self.size))
+    }
 }
 
 /// Command-line arguments for the 8-puzzle solver
@@ -76,155 +199,801 @@ enum Commands {
         /// Number of scramble steps to generate random puzzle boards
         #[arg(short, long, default_value_t = DEFAULT_SCRAMBLE_STEPS)]
         scramble_steps: usize,
+        /// Random walk that scrambles the boards: uniform, non-reversing, self-avoiding or mix:MODEL=WEIGHT,...
+        #[arg(long, value_name = "MODEL", default_value_t)]
+        scramble_model: ScrambleModel,
         /// Number of worker threads to use (defaults to Rayon automatic)
         #[arg(short, long)]
         threads: Option<usize>,
+        /// Seed for board generation (random if omitted, always reported)
+        #[arg(long)]
+        seed: Option<u64>,
+        /// Write results and a reproducibility manifest to this file
+        #[arg(short, long)]
+        export: Option<PathBuf>,
+        /// Encoding of the export file
+        #[arg(long, value_enum, default_value_t)]
+        output: OutputFormat,
+        /// Write a JUnit XML report with one test case per threshold to this file
+        #[arg(long)]
+        junit: Option<PathBuf>,
+        /// Threshold check for the JUnit report, as STRATEGY:METRIC:PERCENTILE<=MAX (repeatable)
+        #[arg(long = "threshold", value_name = "SPEC")]
+        thresholds: Vec<Threshold>,
+        /// Print where each strategy's expanded nodes had the empty space
+        #[arg(long)]
+        heatmap: bool,
+        /// Also measure every solution with its detours cut out (compressed length)
+        #[arg(long)]
+        compress: bool,
+        /// Keep adding batches of --runs boards until the 95% bootstrap CI on every strategy's median solve time is narrower than this fraction of the median, e.g. 5%
+        #[arg(long, value_name = "WIDTH", conflicts_with_all = ["boards", "budget"])]
+        until_ci: Option<CiTarget>,
+        /// Upper bound on the number of boards with --until-ci
+        #[arg(long, default_value_t = DEFAULT_MAX_RUNS, requires = "until_ci")]
+        max_runs: usize,
+        /// Stop starting new runs of a strategy once it has used this much wall time, e.g. 60s-per-strategy
+        #[arg(long, value_name = "DURATION")]
+        budget: Option<Budget>,
+        /// Compare strategies on a board set file (or `builtin:hard`) instead of random boards
+        #[arg(long, value_name = "FILE", conflicts_with_all = ["runs", "scramble_steps", "scramble_model", "seed"])]
+        boards: Option<BoardSource>,
+        /// Heuristic of DFS, BFS, UCS, the heuristic search and focal search: manhattan, misplaced, pdb, walking-distance or learned:FILE (a table written by `bootstrap`)
+        #[arg(long, value_name = "HEURISTIC", default_value_t)]
+        heuristic: HeuristicKind,
+        /// Pattern database written by `pdb build`, for --heuristic pdb (repeat to add disjoint databases)
+        #[arg(
+            long = "pdb-file",
+            value_name = "FILE",
+            required_if_eq("heuristic", "pdb")
+        )]
+        pdb_files: Vec<PathBuf>,
     },
     /// Solve a single random board and print the path
+    #[command(group(ArgGroup::new("recording").multiple(true)))]
     SolveRandom {
+        #[command(flatten)]
+        shape: BoardShape,
         /// Algorithm to use (defaults to heuristic)
-        #[arg(short, long, value_enum)]
-        algorithm: Option<SolveAlgorithm>,
+        #[arg(short, long, value_parser = algorithm_parser())]
+        algorithm: Option<Algorithm>,
         /// Number of scramble steps to generate random puzzle boards
         #[arg(short, long, default_value_t = DEFAULT_SCRAMBLE_STEPS)]
         scramble_steps: usize,
+        /// Random walk that scrambles the board: uniform, non-reversing, self-avoiding or mix:MODEL=WEIGHT,...
+        #[arg(long, value_name = "MODEL", default_value_t)]
+        scramble_model: ScrambleModel,
+        /// Print the solution as a versioned JSON document instead of a step-by-step listing
+        #[arg(long, conflicts_with = "output")]
+        json: bool,
+        /// Print the solution as a versioned document in this encoding
+        #[arg(long, value_enum)]
+        output: Option<OutputFormat>,
+        /// Print the Manhattan distance of every tile next to each step, and how h(n) changes per move
+        #[arg(long, conflicts_with_all = ["json", "output"])]
+        explain_heuristic: bool,
+        /// Also measure the solution with its detours cut out (compressed length and ratio)
+        #[arg(long)]
+        compress: bool,
+        /// Write the solution as an animated GIF to this file
+        #[arg(long, value_name = "FILE")]
+        export_gif: Option<PathBuf>,
+        /// Write the solution as a static SVG grid of boards to this file
+        #[arg(long, value_name = "FILE")]
+        export_svg: Option<PathBuf>,
+        /// Record the search and write it as an animated HTML page to this file
+        #[arg(long, value_name = "FILE", group = "recording")]
+        visualize: Option<PathBuf>,
+        /// Record the frontier size and best f-value over the search and write them as CSV to this file
+        #[arg(long, value_name = "FILE", group = "recording")]
+        frontier_timeline: Option<PathBuf>,
+        /// Number of explored nodes between two recorded frames (of --visualize and --frontier-timeline)
+        #[arg(long, default_value_t = DEFAULT_VISUALIZE_INTERVAL, requires = "recording")]
+        visualize_interval: usize,
+        /// Keep at most N nodes in the frontier, pruning it with --prune-policy (trades optimality, and possibly the solution, for memory); SMA* holds at most N nodes in all
+        #[arg(long, value_name = "N", visible_alias = "max-frontier-nodes")]
+        frontier_cap: Option<usize>,
+        /// Which frontier nodes to drop when --frontier-cap is exceeded
+        #[arg(long, value_enum, default_value_t, requires = "frontier_cap")]
+        prune_policy: PruningPolicy,
+        /// Never search deeper than N moves, failing if no solution is that short (exact for BFS and heuristic)
+        #[arg(long, value_name = "N")]
+        max_depth: Option<usize>,
+        /// Accept any solution of at most C moves, dropping nodes whose f-value exceeds C and failing if none is that short (DFS, BFS, UCS, the heuristic search and focal search)
+        #[arg(long, value_name = "C")]
+        max_cost: Option<usize>,
+        /// Moves LRTA* looks ahead before committing to each move (default: 1)
+        #[arg(long, value_name = "N")]
+        lookahead: Option<usize>,
+        /// Moves from the goal the table of perimeter search reaches (default: 12)
+        #[arg(long, value_name = "N")]
+        perimeter_depth: Option<usize>,
+        /// Focal search may return solutions up to (1 + E) times the optimal length (default: 0.5)
+        #[arg(long, value_name = "E")]
+        epsilon: Option<f64>,
+        /// Heuristic of DFS, BFS, UCS, the heuristic search and focal search: manhattan, misplaced, pdb, walking-distance or learned:FILE (a table written by `bootstrap`)
+        #[arg(long, value_name = "HEURISTIC", default_value_t)]
+        heuristic: HeuristicKind,
+        /// Pattern database written by `pdb build`, for --heuristic pdb (repeat to add disjoint databases)
+        #[arg(
+            long = "pdb-file",
+            value_name = "FILE",
+            required_if_eq("heuristic", "pdb")
+        )]
+        pdb_files: Vec<PathBuf>,
+        /// Explore one board of each set of rotations and reflections of each other (DFS, BFS, UCS, the heuristic search and focal search)
+        #[arg(long)]
+        symmetry: bool,
+        /// Order successors are enqueued in; heuristic lets DFS descend into the child with the lowest heuristic distance first
+        #[arg(long, value_enum, default_value_t)]
+        child_order: ChildOrder,
+        /// Cost of moving each tile: unit, tile (tile k costs k) or the costs of tiles 1 to 8 separated by commas (DFS, BFS, UCS, the heuristic search and focal search)
+        #[arg(
+            long,
+            value_name = "COSTS",
+            default_value_t,
+            conflicts_with = "symmetry"
+        )]
+        move_cost: MoveCosts,
+        /// Goal to search for instead of the spiral 123804765, in reading order with 0 for the empty space, e.g. 1,2,3,4,5,6,7,8,0 (DFS, BFS, UCS, the heuristic search and focal search, with the Manhattan heuristic)
+        #[arg(long, value_name = "BOARD", conflicts_with_all = ["symmetry", "heuristic"])]
+        goal: Option<Board>,
+        /// Named goal to search for: spiral (123804765) or classic (123456780, the row-major goal of most textbooks)
+        #[arg(long, value_enum, default_value_t, conflicts_with_all = ["goal", "symmetry", "heuristic"])]
+        goal_layout: GoalLayout,
+        /// Run a breadth-first search with its frontier and closed list on disk
+        #[arg(long, conflicts_with_all = ["algorithm", "frontier_cap", "max_depth", "max_cost", "move_cost", "goal", "goal_layout", "recording"])]
+        external_memory: bool,
+        /// Directory for the temporary files of --external-memory (defaults to the system temp directory)
+        #[arg(long, value_name = "DIR", requires = "external_memory")]
+        scratch_dir: Option<PathBuf>,
+        /// Solve through a goal distance table stored in this file, created if missing and grown as needed
+        #[arg(long, value_name = "FILE", conflicts_with_all = ["algorithm", "frontier_cap", "max_depth", "max_cost", "move_cost", "goal", "goal_layout", "external_memory", "recording"])]
+        cache: Option<PathBuf>,
+        /// Find the K shortest distinct solutions instead of one, searching on past the first goal
+        #[arg(long, value_name = "K", conflicts_with_all = ["algorithm", "frontier_cap", "max_depth", "max_cost", "move_cost", "goal", "goal_layout", "external_memory", "cache", "recording"])]
+        num_solutions: Option<usize>,
+    },
+    /// Generate random boards with exact distances and write them as a board set
+    Generate {
+        /// Number of boards to generate
+        #[arg(short = 'n', long, default_value_t = DEFAULT_COUNT)]
+        count: usize,
+        /// Number of scramble steps to generate each board
+        #[arg(short, long, default_value_t = DEFAULT_SCRAMBLE_STEPS)]
+        scramble_steps: usize,
+        /// Random walk that scrambles the boards: uniform, non-reversing, self-avoiding or mix:MODEL=WEIGHT,...
+        #[arg(long, value_name = "MODEL", default_value_t)]
+        scramble_model: ScrambleModel,
+        /// Seed of the whole set (random if omitted, always recorded)
+        #[arg(long)]
+        seed: Option<u64>,
+        /// Write the set to this file (`.json` for JSON) instead of printing it as text
+        #[arg(short, long, value_name = "FILE")]
+        file: Option<PathBuf>,
+        /// Write every board at the maximum distance from the goal instead of random boards
+        #[arg(long, conflicts_with_all = ["count", "scramble_steps", "scramble_model", "seed"])]
+        hardest: bool,
+    },
+    /// Solve every board of a board set file and print per-board and aggregate stats
+    Solve {
+        /// Board set file to solve (`.json` for JSON, text otherwise), or `builtin:hard`
+        #[arg(short, long, value_name = "FILE")]
+        file: BoardSource,
+        /// Algorithm to use (defaults to heuristic)
+        #[arg(short, long, value_parser = algorithm_parser())]
+        algorithm: Option<Algorithm>,
+        /// Keep at most N nodes in the frontier, pruning it with --prune-policy (trades optimality, and possibly the solution, for memory); SMA* holds at most N nodes in all
+        #[arg(long, value_name = "N", visible_alias = "max-frontier-nodes")]
+        frontier_cap: Option<usize>,
+        /// Which frontier nodes to drop when --frontier-cap is exceeded
+        #[arg(long, value_enum, default_value_t, requires = "frontier_cap")]
+        prune_policy: PruningPolicy,
+        /// Never search deeper than N moves, failing if no solution is that short (exact for BFS and heuristic)
+        #[arg(long, value_name = "N")]
+        max_depth: Option<usize>,
+        /// Accept any solution of at most C moves, dropping nodes whose f-value exceeds C and failing if none is that short (DFS, BFS, UCS, the heuristic search and focal search)
+        #[arg(long, value_name = "C")]
+        max_cost: Option<usize>,
+        /// Moves LRTA* looks ahead before committing to each move (default: 1)
+        #[arg(long, value_name = "N")]
+        lookahead: Option<usize>,
+        /// Moves from the goal the table of perimeter search reaches (default: 12)
+        #[arg(long, value_name = "N")]
+        perimeter_depth: Option<usize>,
+        /// Focal search may return solutions up to (1 + E) times the optimal length (default: 0.5)
+        #[arg(long, value_name = "E")]
+        epsilon: Option<f64>,
+        /// Heuristic of DFS, BFS, UCS, the heuristic search and focal search: manhattan, misplaced, pdb, walking-distance or learned:FILE (a table written by `bootstrap`)
+        #[arg(long, value_name = "HEURISTIC", default_value_t)]
+        heuristic: HeuristicKind,
+        /// Pattern database written by `pdb build`, for --heuristic pdb (repeat to add disjoint databases)
+        #[arg(
+            long = "pdb-file",
+            value_name = "FILE",
+            required_if_eq("heuristic", "pdb")
+        )]
+        pdb_files: Vec<PathBuf>,
+        /// Explore one board of each set of rotations and reflections of each other (DFS, BFS, UCS, the heuristic search and focal search)
+        #[arg(long)]
+        symmetry: bool,
+        /// Order successors are enqueued in; heuristic lets DFS descend into the child with the lowest heuristic distance first
+        #[arg(long, value_enum, default_value_t)]
+        child_order: ChildOrder,
+        /// Cost of moving each tile: unit, tile (tile k costs k) or the costs of tiles 1 to 8 separated by commas (DFS, BFS, UCS, the heuristic search and focal search)
+        #[arg(
+            long,
+            value_name = "COSTS",
+            default_value_t,
+            conflicts_with = "symmetry"
+        )]
+        move_cost: MoveCosts,
+        /// Goal to search for instead of the spiral 123804765, in reading order with 0 for the empty space, e.g. 1,2,3,4,5,6,7,8,0 (DFS, BFS, UCS, the heuristic search and focal search, with the Manhattan heuristic)
+        #[arg(long, value_name = "BOARD", conflicts_with_all = ["symmetry", "heuristic"])]
+        goal: Option<Board>,
+        /// Named goal to search for: spiral (123804765) or classic (123456780, the row-major goal of most textbooks)
+        #[arg(long, value_enum, default_value_t, conflicts_with_all = ["goal", "symmetry", "heuristic"])]
+        goal_layout: GoalLayout,
+        /// Run a breadth-first search with its frontier and closed list on disk
+        #[arg(long, conflicts_with_all = ["algorithm", "frontier_cap", "max_depth", "max_cost", "move_cost", "goal", "goal_layout"])]
+        external_memory: bool,
+        /// Directory for the temporary files of --external-memory (defaults to the system temp directory)
+        #[arg(long, value_name = "DIR", requires = "external_memory")]
+        scratch_dir: Option<PathBuf>,
+        /// Solve through a goal distance table stored in this file, created if missing and grown as needed
+        #[arg(long, value_name = "FILE", conflicts_with_all = ["algorithm", "frontier_cap", "max_depth", "max_cost", "move_cost", "goal", "goal_layout", "external_memory"])]
+        cache: Option<PathBuf>,
+        /// Write each solution to this directory as it is found, as `<rank>.json` named by the board's rank
+        #[arg(long, value_name = "DIR")]
+        out_dir: Option<PathBuf>,
+        /// Also measure every solution with its detours cut out (compressed length)
+        #[arg(long)]
+        compress: bool,
+        /// Save the search state to --checkpoint-file every N explored nodes
+        #[arg(long, value_name = "N", conflicts_with_all = ["external_memory", "cache"])]
+        checkpoint_every: Option<usize>,
+        /// File the checkpoints of --checkpoint-every are written to
+        #[arg(long, value_name = "FILE", default_value = DEFAULT_CHECKPOINT_FILE, requires = "checkpoint_every")]
+        checkpoint_file: PathBuf,
+        /// Resume from a checkpoint: skip the boards before its board and continue its search
+        #[arg(long, value_name = "FILE", conflicts_with_all = ["external_memory", "cache"])]
+        resume_from: Option<PathBuf>,
+    },
+    /// Search for boards that maximize a strategy's work via hill climbing
+    Adversarial {
+        /// Algorithm whose work should be maximized (defaults to heuristic)
+        #[arg(short, long, value_parser = algorithm_parser())]
+        algorithm: Option<Algorithm>,
+        /// Quantity to maximize
+        #[arg(short, long, value_enum, default_value_t)]
+        objective: AdversarialObjective,
+        /// Number of independent hill-climbing restarts
+        #[arg(short, long, default_value_t = DEFAULT_RESTARTS)]
+        restarts: usize,
+        /// Number of probe solves per restart
+        #[arg(short, long, default_value_t = DEFAULT_ITERATIONS)]
+        iterations: usize,
+        /// Number of scramble steps used to create the starting boards
+        #[arg(short, long, default_value_t = DEFAULT_SCRAMBLE_STEPS)]
+        scramble_steps: usize,
+        /// Number of hardest boards to print
+        #[arg(long, default_value_t = DEFAULT_TOP)]
+        top: usize,
+    },
+    /// Serve JSON-RPC requests on stdin/stdout, one per line, until stdin closes (or over WebSocket)
+    Rpc {
+        /// Encoding of requests and responses
+        #[arg(long, value_enum, default_value_t)]
+        output: OutputFormat,
+        /// Serve JSON requests over WebSocket on this address instead of stdin/stdout
+        #[arg(long, value_name = "ADDR", conflicts_with = "output")]
+        websocket: Option<String>,
+    },
+    /// Look up the exact distance, difficulty and best move of a board
+    Lookup {
+        /// The board in digit form, e.g. 123804765 (0 is the empty space)
+        board: Board,
+    },
+    /// Count the distinct states reachable from a board within k moves, for every k
+    Explore {
+        /// The board in digit form, e.g. 123804765 (0 is the empty space)
+        board: Board,
+        /// Stop before the layer that would take the number of distinct states past N
+        #[arg(long, value_name = "N", default_value_t = DEFAULT_EXPLORE_NODES)]
+        max_nodes: usize,
+        /// Stop after the layer N moves from the board
+        #[arg(long, value_name = "N")]
+        depth: Option<usize>,
+    },
+    /// Print the distribution of optimal solution lengths over all reachable boards
+    AnalyzeSpace {
+        /// Number of example boards to print at the maximum distance
+        #[arg(short, long, default_value_t = DEFAULT_EXAMPLES)]
+        examples: usize,
+    },
+    /// Manage additive pattern databases
+    Pdb {
+        #[command(subcommand)]
+        command: PdbCommand,
+    },
+    /// Manage the exact-distance table behind the oracle strategy
+    Oracle {
+        #[command(subcommand)]
+        command: OracleCommand,
+    },
+    /// Learn a heuristic table from progressively harder random boards, for --heuristic learned:FILE
+    Bootstrap {
+        /// File to write the learned table to
+        #[arg(short, long, value_name = "FILE")]
+        file: PathBuf,
+        /// Rounds of solving and learning
+        #[arg(short, long, default_value_t = bootstrap::DEFAULT_ITERATIONS)]
+        iterations: usize,
+        /// Random boards solved per round
+        #[arg(short = 'n', long, default_value_t = bootstrap::DEFAULT_BOARDS)]
+        boards: usize,
+        /// Scramble steps of the first round's boards
+        #[arg(long, default_value_t = bootstrap::DEFAULT_START_STEPS)]
+        start_steps: usize,
+        /// Scramble steps added in each later round
+        #[arg(long, default_value_t = bootstrap::DEFAULT_STEP_INCREMENT)]
+        step_increment: usize,
+        /// Nodes one solve may explore before its board is skipped
+        #[arg(long, value_name = "N", default_value_t = bootstrap::DEFAULT_NODE_LIMIT)]
+        node_limit: usize,
+        /// Seed of the boards (random if omitted, always printed)
+        #[arg(long)]
+        seed: Option<u64>,
+    },
+    /// Compare the sets of states two strategies explore on the same board
+    Overlap {
+        /// The board in digit form, e.g. 021358467 (a random board if omitted)
+        board: Option<Board>,
+        /// First strategy to compare
+        #[arg(long, value_parser = algorithm_parser(), default_value = "bfs")]
+        first: Algorithm,
+        /// Second strategy to compare
+        #[arg(long, value_parser = algorithm_parser(), default_value = "heuristic")]
+        second: Algorithm,
+        /// Number of scramble steps to generate the random board
+        #[arg(short, long, default_value_t = DEFAULT_SCRAMBLE_STEPS, conflicts_with = "board")]
+        scramble_steps: usize,
+    },
+    /// Check that every strategy solves, replays and (where promised) is optimal, and heuristics are admissible
+    Doctor {
+        /// Number of random boards every strategy solves
+        #[arg(short = 'n', long, default_value_t = DEFAULT_DOCTOR_SAMPLES)]
+        samples: usize,
+        /// Number of scramble steps to generate each board
+        #[arg(short, long, default_value_t = DEFAULT_SCRAMBLE_STEPS)]
+        scramble_steps: usize,
+        /// Seed of the sample (random if omitted, always printed)
+        #[arg(long)]
+        seed: Option<u64>,
+    },
+    /// List the registered strategies with their names and descriptions
+    Algorithms,
+    /// Translate a board between goal conventions, e.g. to compare with other solvers
+    Translate {
+        /// The board in digit form, relative to the --from goal
+        board: Board,
+        /// Goal convention the board is written for
+        #[arg(long, value_enum, default_value_t)]
+        from: GoalConvention,
+        /// Goal convention to translate the board to
+        #[arg(long, value_enum)]
+        to: GoalConvention,
     },
 }
 
-/// Run a search algorithm on a collection of boards in parallel
-///
-/// # Arguments
-///
-/// * `boards` - A slice of puzzle boards to solve
-/// * `solver` - A configured `Solver` with the chosen strategy
-///
-/// # Returns
-///
-/// A vector of statistics for each solved board
-fn run_search<T>(boards: &[Board], solver: &Solver<T>) -> Vec<Stats>
-where
-    T: SearchStrategy<board::BoardWithSteps> + Default + Send + Sync + Clone,
-{
-    boards
-        .par_iter()
-        .progress()
-        .map(|b| {
-            let mut solver = solver.clone();
-            solver.solve(*b).expect("No solution found");
-            solver.get_solution_stats()
-        })
-        .collect()
+/// Pattern database subcommands
+#[derive(Subcommand)]
+enum PdbCommand {
+    /// Build pattern databases and cache them on disk
+    Build {
+        /// Tiles of one pattern, e.g. 1-2-3-4 (repeat for additive databases)
+        #[arg(
+            short,
+            long = "pattern",
+            value_name = "TILES",
+            required_unless_present = "partition"
+        )]
+        patterns: Vec<Pattern>,
+        /// Split every tile into consecutive disjoint patterns of these sizes, e.g. 4-4
+        #[arg(long, value_name = "SIZES", conflicts_with = "patterns")]
+        partition: Option<Partition>,
+        /// Directory to write the databases to (defaults to the data directory)
+        #[arg(short, long)]
+        dir: Option<PathBuf>,
+    },
 }
 
-/// Benchmark the performance of the available strategies on random boards
-fn benchmark(runs: usize, scramble_steps: usize, threads: Option<usize>) {
-    if let Some(t) = threads {
-        ThreadPoolBuilder::new()
-            .num_threads(t)
-            .build_global()
-            .expect("Failed to build thread pool");
-        println!("Using {t} threads for parallel execution.");
-    }
-
-    println!(
-        "Generating {runs} random boards with {scramble_steps} moves and comparing strategies..."
-    );
-
-    let boards: Vec<Board> = (0..runs)
-        .progress()
-        .map(|_| Board::random_with_solution(scramble_steps))
-        .collect();
-
-    println!("Running DFS...");
-    let dfs_run = run_search(
-        &boards,
-        &Solver::new(SimpleSearchStrategy::new(ExplorerStrategy::Dfs)),
-    );
-    println!("Running BFS...");
-    let bfs_run = run_search(
-        &boards,
-        &Solver::new(SimpleSearchStrategy::new(ExplorerStrategy::Bfs)),
-    );
-    println!("Running Heuristic Search (A*-style) ...");
-    let etc = run_search(&boards, &Solver::new(HeuristicSearchStrategy::default()));
-
-    print_comparison_table(
-        &dfs_run.as_slice().into(),
-        &bfs_run.as_slice().into(),
-        &etc.as_slice().into(),
-    );
+/// Oracle subcommands
+#[derive(Subcommand)]
+enum OracleCommand {
+    /// Build the distance table of every reachable board and write it to disk
+    Build {
+        /// Directory to write the table to (defaults to the data directory)
+        #[arg(short, long)]
+        dir: Option<PathBuf>,
+    },
 }
 
-/// Solve a single board and print the path and per-step heuristic
-fn solve_one<T>(board: Board, mut solver: Solver<T>)
-where
-    T: SearchStrategy<BoardWithSteps> + Clone + Default,
-{
-    solver.solve(board).expect("No solution found");
-    let solution = solver.step_by_step_solution();
-
-    println!(
-        "\nSolution path ({} steps)\n",
-        solution.len().saturating_sub(1)
-    );
-    for (idx, step) in solution.iter().enumerate() {
-        println!(
-            "Step {}/{} h(n): {} ",
-            idx,
-            solution.len() - 1,
-            step.heuristic_distance_to_solution()
-        );
-        println!("{step}");
-    }
-
-    let stats = solver.get_solution_stats();
-    print_run_stats(&stats);
+/// Print the error of a failed command and exit with its status
+fn exit_on_error<T>(result: Result<T, CommandError>) -> T {
+    result.unwrap_or_else(|error| {
+        eprintln!("{error}");
+        process::exit(error.exit_status())
+    })
 }
 
-/// Solve a single random puzzle board and display the solution steps
-fn solve_random(scramble_steps: usize, algo: SolveAlgorithm) {
-    let board = Board::random_with_solution(scramble_steps);
-    println!(
-        "Solving a random board ({} scramble moves) using {}...",
-        scramble_steps,
-        match algo {
-            SolveAlgorithm::Dfs => "DFS",
-            SolveAlgorithm::Bfs => "BFS",
-            SolveAlgorithm::Heuristic => "Heuristic",
-        }
-    );
-
-    match algo {
-        SolveAlgorithm::Dfs => solve_one(
-            board,
-            Solver::new(SimpleSearchStrategy::new(ExplorerStrategy::Dfs)),
-        ),
-        SolveAlgorithm::Bfs => solve_one(
-            board,
-            Solver::new(SimpleSearchStrategy::new(ExplorerStrategy::Bfs)),
-        ),
-        SolveAlgorithm::Heuristic => {
-            solve_one(board, Solver::new(HeuristicSearchStrategy::default()));
-        }
-    }
+/// Load the heuristic chosen with `--heuristic`, exiting if its files cannot be opened
+fn load_heuristic(kind: HeuristicKind, pdb_files: &[PathBuf]) -> Option<LoadedHeuristic> {
+    exit_on_error(kind.load(pdb_files).map_err(CommandError::from))
 }
 
-/// Main function that orchestrates the 8-puzzle solver comparison
+/// Parse the command line and run the chosen subcommand
 ///
-/// Generates random puzzle boards, solves them using both DFS and BFS algorithms,
-/// and displays a comparison table of the performance metrics.
+/// The work itself is done by [`o8::commands`] and [`o8::benchmark`]; a
+/// failed command prints its error and exits with status 2 for invalid
+/// arguments and 1 otherwise.
+#[allow(clippy::too_many_lines)]
 fn main() {
     let Args { command } = Args::parse();
+    if let Commands::SolveRandom {
+        shape,
+        scramble_steps,
+        ..
+    } = &command
+        && shape.dims() != (3, 3)
+    {
+        let (shape, steps) = (shape.dims(), *scramble_steps);
+        exit_on_error(match shape {
+            (2, 2) => commands::solve_random_sized(
+                SizedBoard::<2, 2>::random_with_solution(steps),
+                shape,
+                steps,
+            ),
+            (2, 3) => commands::solve_random_sized(
+                SizedBoard::<2, 3>::random_with_solution(steps),
+                shape,
+                steps,
+            ),
+            (2, 4) => commands::solve_random_sized(
+                SizedBoard::<2, 4>::random_with_solution(steps),
+                shape,
+                steps,
+            ),
+            (3, 2) => commands::solve_random_sized(
+                SizedBoard::<3, 2>::random_with_solution(steps),
+                shape,
+                steps,
+            ),
+            (3, 4) => commands::solve_random_sized(
+                SizedBoard::<3, 4>::random_with_solution(steps),
+                shape,
+                steps,
+            ),
+            (4, 2) => commands::solve_random_sized(
+                SizedBoard::<4, 2>::random_with_solution(steps),
+                shape,
+                steps,
+            ),
+            (4, 3) => commands::solve_random_sized(
+                SizedBoard::<4, 3>::random_with_solution(steps),
+                shape,
+                steps,
+            ),
+            (4, 4) => {
+                commands::solve_random_sized(Puzzle15::random_with_solution(steps), shape, steps)
+            }
+            _ => unreachable!("--rows and --cols are between 2 and 4"),
+        });
+        return;
+    }
 
     match command {
         Commands::Benchmark {
             runs,
             scramble_steps,
+            scramble_model,
             threads,
-        } => benchmark(runs, scramble_steps, threads),
+            seed,
+            export,
+            output,
+            junit,
+            thresholds,
+            heatmap,
+            compress,
+            until_ci,
+            max_runs,
+            budget,
+            boards,
+            heuristic,
+            pdb_files,
+        } => {
+            let heuristic = load_heuristic(heuristic, &pdb_files);
+            exit_on_error(benchmark(
+                &BenchmarkInput {
+                    runs,
+                    scramble: &Scramble {
+                        steps: scramble_steps,
+                        model: scramble_model,
+                    },
+                    seed,
+                    board_source: boards.as_ref(),
+                    budget,
+                    until_ci,
+                    max_runs,
+                    heuristic: heuristic.as_ref().map(|loaded| &loaded.function),
+                    compress,
+                },
+                threads,
+                &BenchmarkReports {
+                    export: export.as_deref(),
+                    format: output,
+                    junit: junit.as_deref(),
+                    thresholds: &thresholds,
+                    heatmap,
+                },
+            ));
+            if let Some(databases) = heuristic.and_then(|loaded| loaded.databases) {
+                report::print_pdb_lookups(&databases);
+            }
+        }
         Commands::SolveRandom {
             algorithm,
             scramble_steps,
-        } => solve_random(scramble_steps, algorithm.unwrap_or_default()),
+            scramble_model,
+            json,
+            output,
+            explain_heuristic,
+            compress,
+            export_gif,
+            export_svg,
+            visualize,
+            frontier_timeline,
+            visualize_interval,
+            frontier_cap,
+            prune_policy,
+            max_depth,
+            max_cost,
+            lookahead,
+            perimeter_depth,
+            epsilon,
+            heuristic,
+            pdb_files,
+            symmetry,
+            child_order,
+            move_cost,
+            goal,
+            goal_layout,
+            external_memory,
+            scratch_dir,
+            cache,
+            num_solutions,
+            shape: _,
+        } => {
+            let heuristic = load_heuristic(heuristic, &pdb_files);
+            let output = output.or(json.then_some(OutputFormat::Json));
+            exit_on_error(commands::solve_random(
+                &Scramble {
+                    steps: scramble_steps,
+                    model: scramble_model,
+                },
+                algorithm.unwrap_or_default().entry(),
+                &StrategyConfig {
+                    frontier_cap: frontier_cap.map(|limit| (limit, prune_policy)),
+                    max_depth,
+                    max_cost,
+                    lookahead,
+                    perimeter_depth,
+                    epsilon,
+                    heuristic: heuristic
+                        .as_ref()
+                        .map(|loaded| Arc::clone(&loaded.function)),
+                    symmetry_reduction: symmetry,
+                    child_order,
+                    move_costs: move_cost,
+                    goal: goal.unwrap_or_else(|| goal_layout.goal()),
+                    ..StrategyConfig::default()
+                },
+                external_memory
+                    .then(|| scratch_dir.unwrap_or_else(env::temp_dir))
+                    .as_deref(),
+                cache.as_deref(),
+                num_solutions,
+                output,
+                SolutionExports {
+                    explain_heuristic,
+                    compress,
+                    gif: export_gif.as_deref(),
+                    svg: export_svg.as_deref(),
+                    visualize: visualize.as_deref(),
+                    frontier_timeline: frontier_timeline.as_deref(),
+                    visualize_interval,
+                },
+            ));
+            if let (Some(databases), None) = (heuristic.and_then(|loaded| loaded.databases), output)
+            {
+                report::print_pdb_lookups(&databases);
+            }
+        }
+        Commands::Generate {
+            file,
+            hardest: true,
+            ..
+        } => exit_on_error(commands::generate_hardest(file.as_deref())),
+        Commands::Generate {
+            count,
+            scramble_steps,
+            scramble_model,
+            seed,
+            file,
+            hardest: false,
+        } => exit_on_error(commands::generate(
+            count,
+            &Scramble {
+                steps: scramble_steps,
+                model: scramble_model,
+            },
+            seed,
+            file.as_deref(),
+        )),
+        Commands::Solve {
+            file,
+            algorithm,
+            frontier_cap,
+            prune_policy,
+            max_depth,
+            max_cost,
+            lookahead,
+            perimeter_depth,
+            epsilon,
+            heuristic,
+            pdb_files,
+            symmetry,
+            child_order,
+            move_cost,
+            goal,
+            goal_layout,
+            external_memory,
+            scratch_dir,
+            cache,
+            out_dir,
+            compress,
+            checkpoint_every,
+            checkpoint_file,
+            resume_from,
+        } => {
+            let algo = algorithm.unwrap_or_default().entry();
+            let heuristic = load_heuristic(heuristic, &pdb_files);
+            let resume =
+                resume_from.map(|path| exit_on_error(commands::open_checkpoint(&path, algo)));
+            let scratch_dir = external_memory.then(|| scratch_dir.unwrap_or_else(env::temp_dir));
+            exit_on_error(commands::solve_file(
+                &file,
+                algo,
+                &StrategyConfig {
+                    frontier_cap: frontier_cap.map(|limit| (limit, prune_policy)),
+                    max_depth,
+                    max_cost,
+                    lookahead,
+                    perimeter_depth,
+                    epsilon,
+                    heuristic: heuristic
+                        .as_ref()
+                        .map(|loaded| Arc::clone(&loaded.function)),
+                    symmetry_reduction: symmetry,
+                    child_order,
+                    move_costs: move_cost,
+                    goal: goal.unwrap_or_else(|| goal_layout.goal()),
+                    checkpoint: checkpoint_every.map(|interval| {
+                        (interval, commands::checkpoint_saver(algo, checkpoint_file))
+                    }),
+                    ..StrategyConfig::default()
+                },
+                FileSolveOptions {
+                    external: scratch_dir.as_deref(),
+                    cache: cache.as_deref(),
+                    out_dir: out_dir.as_deref(),
+                    compress,
+                },
+                resume.as_ref(),
+            ));
+            if let Some(databases) = heuristic.and_then(|loaded| loaded.databases) {
+                report::print_pdb_lookups(&databases);
+            }
+        }
+        Commands::Adversarial {
+            algorithm,
+            objective,
+            restarts,
+            iterations,
+            scramble_steps,
+            top,
+        } => exit_on_error(commands::adversarial(
+            algorithm.unwrap_or_default().entry(),
+            &AdversarialConfig {
+                objective,
+                restarts,
+                iterations,
+                scramble_steps,
+                top,
+            },
+        )),
+        Commands::Rpc {
+            websocket: Some(addr),
+            ..
+        } => {
+            eprintln!("Serving JSON-RPC over WebSocket on {addr}");
+            websocket::serve(&addr).expect("Failed to serve WebSocket requests");
+        }
+        Commands::Rpc {
+            output,
+            websocket: None,
+        } => rpc::serve(io::stdin().lock(), io::stdout().lock(), output)
+            .expect("Failed to serve RPC requests"),
+        Commands::Lookup { board } => report::print_lookup(board),
+        Commands::Explore {
+            board,
+            max_nodes,
+            depth,
+        } => report::print_exploration(board, &explore::explore(board, depth, max_nodes)),
+        Commands::AnalyzeSpace { examples } => {
+            report::print_space_analysis(StateSpaceDatabase::global(), examples);
+        }
+        Commands::Overlap {
+            board,
+            first,
+            second,
+            scramble_steps,
+        } => exit_on_error(commands::overlap(
+            board.unwrap_or_else(|| Board::random_with_solution(scramble_steps)),
+            first.entry(),
+            second.entry(),
+        )),
+        Commands::Algorithms => report::print_algorithms(),
+        Commands::Translate { board, from, to } => {
+            exit_on_error(commands::translate(board, from, to));
+        }
+        Commands::Doctor {
+            samples,
+            scramble_steps,
+            seed,
+        } => exit_on_error(commands::doctor(samples, scramble_steps, seed)),
+        Commands::Pdb {
+            command:
+                PdbCommand::Build {
+                    patterns,
+                    partition,
+                    dir,
+                },
+        } => exit_on_error(match partition {
+            Some(partition) => commands::pdb_build(partition.patterns(), dir),
+            None => commands::pdb_build(&patterns, dir),
+        }),
+        Commands::Oracle {
+            command: OracleCommand::Build { dir },
+        } => exit_on_error(commands::oracle_build(dir)),
+        Commands::Bootstrap {
+            file,
+            iterations,
+            boards,
+            start_steps,
+            step_increment,
+            node_limit,
+            seed,
+        } => exit_on_error(commands::learn_heuristic(
+            &file,
+            &BootstrapConfig {
+                iterations,
+                boards,
+                start_steps,
+                step_increment,
+                node_limit,
+                seed: seed.unwrap_or_else(|| rng().random()),
+            },
+        )),
     }
 }
@@ -0,0 +1,64 @@
+//! # Oracle Module
+//!
+//! Perfect play from the state-space database: the exact distance of every
+//! one of the 181,440 reachable boards, from a single retrograde
+//! breadth-first search back from the goal (see [`StateSpaceDatabase`]).
+//! With the table in hand, solving is no longer a search: from each board,
+//! the oracle takes a move to a neighbor one move closer to the goal, so the
+//! solution is optimal and costs one lookup per successor along the way.
+//!
+//! The table is built on first use and cached in the data directory, or
+//! written ahead of time with `o8 oracle build`; building it takes a fraction
+//! of a second, a solve only microseconds.
+
+use crate::time::Instant;
+
+use crate::board::{ALL_DIRECTIONS, Board};
+use crate::database::StateSpaceDatabase;
+use crate::stats::Stats;
+
+/// A solution read from the distance table
+#[derive(Clone, Debug)]
+pub struct OracleSolution {
+    /// Every board from the start to the goal
+    pub path: Vec<Board>,
+    /// Stats of the walk; every board on the path but the goal counts as
+    /// explored, and its legal moves as generated
+    pub stats: Stats,
+}
+
+/// Solves a board optimally with the process-wide table (see [`StateSpaceDatabase::global`])
+///
+/// # Returns
+///
+/// `Some(solution)`, or `None` if the board cannot reach the goal
+#[must_use]
+pub fn solve(board: Board) -> Option<OracleSolution> {
+    solve_with(board, StateSpaceDatabase::global())
+}
+
+/// Solves a board optimally by walking down the distances of `database`
+///
+/// # Returns
+///
+/// `Some(solution)`, or `None` if the board cannot reach the goal
+#[must_use]
+pub fn solve_with(board: Board, database: &StateSpaceDatabase) -> Option<OracleSolution> {
+    let started = Instant::now();
+    let path = database.solution(board)?;
+
+    let mut stats = Stats::default();
+    for &board in &path[..path.len() - 1] {
+        stats.nodes_explored += 1;
+        stats.generated_nodes += ALL_DIRECTIONS
+            .into_iter()
+            .filter(|&direction| board.move_space(direction).is_ok())
+            .count();
+    }
+    stats.solution_moves = path.len() - 1;
+    stats.solution_cost = stats.solution_moves;
+    stats.enqueued_nodes = stats.solution_moves;
+    stats.max_depth_reached = stats.solution_moves;
+    stats.duration_ms = started.elapsed().as_millis();
+    Some(OracleSolution { path, stats })
+}
@@ -0,0 +1,157 @@
+//! # Overlap Module
+//!
+//! Compares which states two searches explored on the same board. Explored
+//! sets are stored as bitsets indexed by [`Board::rank`], so a whole set costs
+//! 45 KiB and intersections reduce to counting bits of a word-wise AND. The
+//! resulting [`Overlap`] shows how differently, say, BFS and A* traverse the
+//! state space: A* explores a small corner of what BFS explores, while DFS
+//! wanders through states neither of them touches.
+
+use crate::board::Board;
+
+/// Set of boards stored as one bit per [`Board::rank`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RankSet {
+    /// Membership bits, 64 ranks per word
+    words: Vec<u64>,
+    /// Number of boards in the set
+    len: usize,
+}
+
+impl Default for RankSet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RankSet {
+    /// Creates an empty set with room for every board
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            words: vec![0; Board::RANK_COUNT.div_ceil(u64::BITS) as usize],
+            len: 0,
+        }
+    }
+
+    /// Adds a board to the set
+    ///
+    /// # Returns
+    ///
+    /// `true` if the board was not in the set yet
+    pub fn insert(&mut self, board: Board) -> bool {
+        let (word, bit) = Self::position(board);
+        let inserted = self.words[word] & bit == 0;
+        self.words[word] |= bit;
+        self.len += usize::from(inserted);
+        inserted
+    }
+
+    /// Whether the board is in the set
+    #[must_use]
+    pub fn contains(&self, board: Board) -> bool {
+        let (word, bit) = Self::position(board);
+        self.words[word] & bit != 0
+    }
+
+    /// Number of boards in the set
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the set holds no boards
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Number of boards in both sets
+    #[must_use]
+    pub fn intersection_len(&self, other: &RankSet) -> usize {
+        self.words
+            .iter()
+            .zip(&other.words)
+            .map(|(a, b)| (a & b).count_ones() as usize)
+            .sum()
+    }
+
+    /// Word index and bit mask of a board
+    fn position(board: Board) -> (usize, u64) {
+        let rank = board.rank();
+        ((rank / u64::BITS) as usize, 1 << (rank % u64::BITS))
+    }
+}
+
+impl FromIterator<Board> for RankSet {
+    fn from_iter<I: IntoIterator<Item = Board>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for board in iter {
+            set.insert(board);
+        }
+        set
+    }
+}
+
+/// Sizes of two explored sets and of their intersection
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Overlap {
+    /// Number of states explored by the first search
+    pub first: usize,
+    /// Number of states explored by the second search
+    pub second: usize,
+    /// Number of states explored by both searches
+    pub shared: usize,
+}
+
+impl Overlap {
+    /// Measures the overlap of two explored sets
+    #[must_use]
+    pub fn between(first: &RankSet, second: &RankSet) -> Self {
+        Self {
+            first: first.len(),
+            second: second.len(),
+            shared: first.intersection_len(second),
+        }
+    }
+
+    /// Number of states explored by at least one search
+    #[must_use]
+    pub fn union(&self) -> usize {
+        self.first + self.second - self.shared
+    }
+
+    /// Jaccard similarity: shared states over all explored states
+    ///
+    /// # Returns
+    ///
+    /// A value between 0 (disjoint) and 1 (identical), or 1 if both sets are empty
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn jaccard(&self) -> f64 {
+        match self.union() {
+            0 => 1.0,
+            union => self.shared as f64 / union as f64,
+        }
+    }
+
+    /// Share of the first search's states that the second search also explored
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn first_covered(&self) -> f64 {
+        match self.first {
+            0 => 1.0,
+            first => self.shared as f64 / first as f64,
+        }
+    }
+
+    /// Share of the second search's states that the first search also explored
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn second_covered(&self) -> f64 {
+        match self.second {
+            0 => 1.0,
+            second => self.shared as f64 / second as f64,
+        }
+    }
+}
@@ -0,0 +1,711 @@
+//! # Pattern Database Module
+//!
+//! Builds, stores and loads additive pattern databases (PDBs). A pattern is a
+//! subset of the tiles, e.g. `1-2-3-4`; its database records, for every
+//! placement of those tiles, the minimum number of moves *of pattern tiles*
+//! needed to bring them home. Because only pattern-tile moves are counted,
+//! the costs of disjoint patterns can be added together and still never
+//! overestimate the true distance.
+//!
+//! The build is a 0-1 breadth-first search backwards from the goal over the
+//! abstract states (positions of the pattern tiles plus the empty space).
+//! The pipeline is written against the board geometry rather than a fixed
+//! pattern, so the same code serves larger boards and patterns as they become
+//! available.
+//!
+//! ## On-disk format
+//!
+//! Tables are cached on disk (see [`PatternDatabase::open_or_build`]) in a
+//! compact format: entries are packed two per byte whenever every cost fits in
+//! four bits, and the header carries an FNV-1a checksum of the rest of the
+//! file that is verified on load. With the `mmap` feature (enabled by `cli`) the
+//! file is memory-mapped at solve time instead of read into memory, and
+//! lookups read the packed entries directly from the mapping.
+//!
+//! ```text
+//! magic "O8PD" | version u8 | checksum u64 LE | bits per entry u8 | pattern length u8
+//! pattern tiles | entries u32 LE | packed entries
+//! ```
+
+use std::collections::VecDeque;
+use std::fmt::{self, Display};
+use std::fs::{self, File};
+use std::io;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::board::{BOARD_AREA, BOARD_SIDE, Board};
+use crate::heuristic::Heuristic;
+use crate::persist::{Format, HEADER_LEN, encode, read_checked, write_atomic};
+
+/// Number of cells on the board
+const CELLS: usize = BOARD_AREA as usize;
+
+/// Header of a pattern database file
+const FORMAT: Format = Format {
+    magic: *b"O8PD",
+    version: 2,
+    name: "pattern database",
+};
+
+/// Marker for abstract states not reached (yet) by the search
+const UNKNOWN: u8 = u8::MAX;
+
+/// A set of tiles whose placement is tracked by a pattern database
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Pattern {
+    /// Tile numbers, in the order given by the user
+    tiles: Vec<u8>,
+}
+
+impl Pattern {
+    /// Creates a pattern from tile numbers
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the pattern is empty, a tile is out of range or a
+    /// tile appears more than once
+    pub fn new(tiles: &[u8]) -> Result<Self, &'static str> {
+        if tiles.is_empty() {
+            return Err("Invalid pattern: expected at least one tile");
+        }
+        for (i, &tile) in tiles.iter().enumerate() {
+            if tile == 0 || tile >= BOARD_AREA {
+                return Err("Invalid pattern: tiles must be between 1 and 8");
+            }
+            if tiles[..i].contains(&tile) {
+                return Err("Invalid pattern: each tile may appear only once");
+            }
+        }
+        Ok(Self {
+            tiles: tiles.to_vec(),
+        })
+    }
+
+    /// Tile numbers of the pattern
+    #[must_use]
+    pub fn tiles(&self) -> &[u8] {
+        &self.tiles
+    }
+
+    /// Checks that two patterns share no tile, so their costs can be added
+    #[must_use]
+    pub fn is_disjoint(&self, other: &Pattern) -> bool {
+        self.tiles.iter().all(|tile| !other.tiles.contains(tile))
+    }
+
+    /// File name of the pattern's table inside a data directory
+    #[must_use]
+    pub fn file_name(&self) -> String {
+        format!("pdb-{self}.bin")
+    }
+}
+
+impl FromStr for Pattern {
+    type Err = &'static str;
+
+    /// Parses a pattern such as `1-2-3-4`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tiles = s
+            .split('-')
+            .map(|part| part.trim().parse())
+            .collect::<Result<Vec<u8>, _>>()
+            .map_err(|_| "Invalid pattern: expected tiles separated by '-', e.g. 1-2-3-4")?;
+        Self::new(&tiles)
+    }
+}
+
+/// A split of every tile into consecutive disjoint patterns, e.g. `4-4`
+///
+/// Each number is the size of one pattern, taken from the tiles in order:
+/// `4-4` is `1-2-3-4` and `5-6-7-8`, `3-3-2` is `1-2-3`, `4-5-6` and `7-8`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Partition {
+    patterns: Vec<Pattern>,
+}
+
+impl Partition {
+    /// The patterns of the partition, disjoint and covering every tile
+    #[must_use]
+    pub fn patterns(&self) -> &[Pattern] {
+        &self.patterns
+    }
+}
+
+impl FromStr for Partition {
+    type Err = &'static str;
+
+    /// Parses a partition such as `4-4`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let sizes = s
+            .split('-')
+            .map(|part| part.trim().parse())
+            .collect::<Result<Vec<u8>, _>>()
+            .map_err(|_| "Invalid partition: expected sizes separated by '-', e.g. 4-4")?;
+        if sizes.iter().map(|&size| usize::from(size)).sum::<usize>() != CELLS - 1 {
+            return Err("Invalid partition: the sizes must add up to 8 tiles");
+        }
+
+        let mut next = 1;
+        let patterns = sizes
+            .into_iter()
+            .map(|size| {
+                let tiles: Vec<u8> = (next..next + size).collect();
+                next += size;
+                Pattern::new(&tiles)
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { patterns })
+    }
+}
+
+impl Display for Partition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sizes: Vec<String> = self
+            .patterns
+            .iter()
+            .map(|pattern| pattern.tiles().len().to_string())
+            .collect();
+        f.write_str(&sizes.join("-"))
+    }
+}
+
+impl Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, tile) in self.tiles.iter().enumerate() {
+            if i > 0 {
+                f.write_str("-")?;
+            }
+            write!(f, "{tile}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Bytes backing a pattern database
+enum Storage {
+    /// Table built or read into memory
+    Owned(Box<[u8]>),
+    /// Table memory-mapped from disk
+    #[cfg(feature = "mmap")]
+    Mapped(memmap2::Mmap),
+}
+
+impl Deref for Storage {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            Storage::Owned(bytes) => bytes,
+            #[cfg(feature = "mmap")]
+            Storage::Mapped(map) => map,
+        }
+    }
+}
+
+/// An additive pattern database for one pattern
+pub struct PatternDatabase {
+    /// Tiles tracked by this database
+    pattern: Pattern,
+    /// Bits used by each packed entry (4 or 8)
+    bits: u8,
+    /// Number of entries in the table
+    entries: usize,
+    /// Header and packed entries, as stored on disk
+    storage: Storage,
+    /// Offset of the packed entries inside `storage`
+    offset: usize,
+}
+
+impl PatternDatabase {
+    /// Builds the database with a 0-1 breadth-first search from the goal
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - Tiles to track
+    #[must_use]
+    pub fn build(pattern: &Pattern) -> Self {
+        let costs = build_costs(pattern);
+        let bits = if costs.iter().all(|&c| c < 16) { 4 } else { 8 };
+        let packed = pack(&costs, bits);
+
+        let mut body = header(pattern, bits, costs.len());
+        let offset = HEADER_LEN + body.len();
+        body.extend_from_slice(&packed);
+        let bytes = encode(&FORMAT, &body);
+
+        Self {
+            pattern: pattern.clone(),
+            bits,
+            entries: costs.len(),
+            storage: Storage::Owned(bytes.into_boxed_slice()),
+            offset,
+        }
+    }
+
+    /// Opens the cached table for `pattern` in `dir`, building and caching it if needed
+    ///
+    /// A missing or corrupt cache file is rebuilt. Writing the cache is best
+    /// effort: if the directory is not writable, the freshly built table is
+    /// still returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - Data directory holding the cache files
+    /// * `pattern` - Tiles to track
+    #[must_use]
+    pub fn open_or_build(dir: &Path, pattern: &Pattern) -> Self {
+        let path = cache_path(dir, pattern);
+        if let Ok(database) = Self::open(&path)
+            && database.pattern == *pattern
+        {
+            return database;
+        }
+
+        let database = Self::build(pattern);
+        let _ = fs::create_dir_all(dir).and_then(|()| database.save(&path));
+        database
+    }
+
+    /// Opens a table previously written by [`PatternDatabase::save`]
+    ///
+    /// With the `mmap` feature the file is memory-mapped; otherwise it is read
+    /// into memory. Either way the checksum is verified before returning.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read, is not a valid table or
+    /// fails its integrity check
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let storage = read_storage(path)?;
+        let invalid = |msg: &str| io::Error::new(io::ErrorKind::InvalidData, msg.to_owned());
+
+        let [bits, len, rest @ ..] = read_checked(&storage, &FORMAT)? else {
+            return Err(invalid("truncated pattern database"));
+        };
+        if *bits != 4 && *bits != 8 {
+            return Err(invalid("corrupt pattern database"));
+        }
+
+        let (tiles, rest) = rest
+            .split_at_checked(usize::from(*len))
+            .ok_or_else(|| invalid("truncated pattern database"))?;
+        let pattern = Pattern::new(tiles).map_err(invalid)?;
+        let (entries, packed) = rest
+            .split_first_chunk::<4>()
+            .ok_or_else(|| invalid("truncated pattern database"))?;
+
+        let entries = u32::from_le_bytes(*entries) as usize;
+        if entries != table_len(pattern.tiles.len()) || packed.len() != packed_len(entries, *bits) {
+            return Err(invalid("corrupt pattern database"));
+        }
+
+        let bits = *bits;
+        let offset = storage.len() - packed.len();
+        Ok(Self {
+            pattern,
+            bits,
+            entries,
+            storage,
+            offset,
+        })
+    }
+
+    /// Writes the table to `path`, replacing any previous file atomically
+    ///
+    /// # Errors
+    ///
+    /// Returns any I/O error raised while writing the file
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        write_atomic(path, &FORMAT, &self.storage[HEADER_LEN..])
+    }
+
+    /// Tiles tracked by this database
+    #[must_use]
+    pub fn pattern(&self) -> &Pattern {
+        &self.pattern
+    }
+
+    /// Number of entries in the table
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries
+    }
+
+    /// Checks whether the table has no entries (never true for a valid pattern)
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries == 0
+    }
+
+    /// Size of the table in bytes, header included
+    #[must_use]
+    pub fn size_in_bytes(&self) -> usize {
+        self.storage.len()
+    }
+
+    /// Largest cost stored for a reachable placement
+    #[must_use]
+    pub fn max_cost(&self) -> u8 {
+        (0..self.entries)
+            .map(|i| self.entry(i))
+            .filter(|&cost| cost != UNKNOWN)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Minimum number of pattern-tile moves needed to bring the pattern home
+    ///
+    /// Placements that cannot be reached from the goal, which only occur on
+    /// unsolvable boards, cost `u8::MAX`.
+    ///
+    /// # Arguments
+    ///
+    /// * `board` - The board to evaluate
+    #[must_use]
+    pub fn cost(&self, board: Board) -> u8 {
+        let cells = board.into_arr();
+        let positions: Vec<u8> = self
+            .pattern
+            .tiles
+            .iter()
+            .map(|&tile| position_of(&cells, tile))
+            .collect();
+        self.entry(rank(&positions))
+    }
+
+    /// Reads one packed entry
+    fn entry(&self, index: usize) -> u8 {
+        let packed = &self.storage[self.offset..];
+        if self.bits == 8 {
+            packed[index]
+        } else {
+            let byte = packed[index / 2];
+            if index.is_multiple_of(2) {
+                byte & 0x0f
+            } else {
+                byte >> 4
+            }
+        }
+    }
+}
+
+/// Sums the costs of several disjoint pattern databases
+///
+/// The result is an admissible estimate of the distance to the goal as long
+/// as no tile appears in more than one pattern.
+#[must_use]
+pub fn additive_cost(databases: &[PatternDatabase], board: Board) -> usize {
+    databases.iter().map(|db| usize::from(db.cost(board))).sum()
+}
+
+/// Lookups made in one pattern database of an [`AdditiveHeuristic`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PartitionLookups {
+    /// Pattern of the database
+    pub pattern: Pattern,
+    /// Number of boards looked up
+    pub lookups: u64,
+    /// Sum of the costs found
+    pub total_cost: u64,
+}
+
+impl PartitionLookups {
+    /// Mean cost found per lookup, 0 without lookups
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn mean_cost(&self) -> f64 {
+        if self.lookups == 0 {
+            0.0
+        } else {
+            self.total_cost as f64 / self.lookups as f64
+        }
+    }
+}
+
+/// Disjoint pattern databases summed into one solver heuristic
+///
+/// The estimate is the larger of the summed costs and the Manhattan distance:
+/// neither overestimates, so their maximum does not either, and databases
+/// covering only a few tiles never guide the search worse than the Manhattan
+/// distance alone. Every lookup is counted per database, so reports can show
+/// how much each partition contributes.
+pub struct AdditiveHeuristic {
+    databases: Vec<PatternDatabase>,
+    /// Lookups and summed costs per database, in the same order
+    counters: Vec<(AtomicU64, AtomicU64)>,
+}
+
+impl AdditiveHeuristic {
+    /// Combines `databases` into one heuristic
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there are no databases or two of them share a tile,
+    /// since the sum could then overestimate
+    pub fn new(databases: Vec<PatternDatabase>) -> Result<Self, &'static str> {
+        if databases.is_empty() {
+            return Err("Expected at least one pattern database");
+        }
+        for (i, database) in databases.iter().enumerate() {
+            if databases[..i]
+                .iter()
+                .any(|other| !other.pattern().is_disjoint(database.pattern()))
+            {
+                return Err("Pattern databases share tiles, so their costs cannot be added");
+            }
+        }
+        let counters = databases
+            .iter()
+            .map(|_| (AtomicU64::new(0), AtomicU64::new(0)))
+            .collect();
+        Ok(Self {
+            databases,
+            counters,
+        })
+    }
+
+    /// Estimated number of moves from `board` to the goal, counting the lookups
+    #[must_use]
+    pub fn cost(&self, board: Board) -> u8 {
+        let mut total: usize = 0;
+        for (database, (lookups, cost_sum)) in self.databases.iter().zip(&self.counters) {
+            let cost = database.cost(board);
+            lookups.fetch_add(1, Ordering::Relaxed);
+            cost_sum.fetch_add(u64::from(cost), Ordering::Relaxed);
+            total += usize::from(cost);
+        }
+        u8::try_from(total)
+            .unwrap_or(u8::MAX)
+            .max(board.heuristic_distance_to_solution())
+    }
+
+    /// Lookups made so far in each database, in the order they were given
+    #[must_use]
+    pub fn lookups(&self) -> Vec<PartitionLookups> {
+        self.databases
+            .iter()
+            .zip(&self.counters)
+            .map(|(database, (lookups, cost_sum))| PartitionLookups {
+                pattern: database.pattern().clone(),
+                lookups: lookups.load(Ordering::Relaxed),
+                total_cost: cost_sum.load(Ordering::Relaxed),
+            })
+            .collect()
+    }
+}
+
+impl Heuristic for AdditiveHeuristic {
+    fn estimate(&self, board: Board) -> u32 {
+        u32::from(self.cost(board))
+    }
+}
+
+/// Path of the cached table for `pattern` inside `dir`
+#[must_use]
+pub fn cache_path(dir: &Path, pattern: &Pattern) -> PathBuf {
+    dir.join(pattern.file_name())
+}
+
+/// Computes the cost of every placement of the pattern tiles
+fn build_costs(pattern: &Pattern) -> Vec<u8> {
+    let k = pattern.tiles.len();
+    let goal = Board::default().into_arr();
+
+    // Abstract state: positions of the pattern tiles followed by the blank
+    let mut start: Vec<u8> = pattern
+        .tiles
+        .iter()
+        .map(|&tile| position_of(&goal, tile))
+        .collect();
+    start.push(position_of(&goal, 0));
+
+    let mut distances = vec![UNKNOWN; table_len(k + 1)];
+    let mut queue = VecDeque::new();
+    distances[rank(&start)] = 0;
+    queue.push_back((start, 0u8));
+
+    while let Some((state, distance)) = queue.pop_front() {
+        if distance > distances[rank(&state)] {
+            continue;
+        }
+
+        let blank = state[k];
+        for cell in neighbors(blank) {
+            let mut next = state.clone();
+            next[k] = cell;
+            let moved = state[..k].iter().position(|&p| p == cell);
+            if let Some(tile) = moved {
+                next[tile] = blank;
+            }
+
+            let cost = distance + u8::from(moved.is_some());
+            let index = rank(&next);
+            if cost < distances[index] {
+                distances[index] = cost;
+                if moved.is_some() {
+                    queue.push_back((next, cost));
+                } else {
+                    queue.push_front((next, cost));
+                }
+            }
+        }
+    }
+
+    // Project away the blank: keep the cheapest cost over all blank positions
+    let mut costs = vec![UNKNOWN; table_len(k)];
+    let mut positions = vec![0; k + 1];
+    for_each_placement(&mut positions, 0, &mut |state| {
+        let index = rank(&state[..k]);
+        costs[index] = costs[index].min(distances[rank(state)]);
+    });
+    costs
+}
+
+/// Visits every placement of distinct cells into `positions[depth..]`
+fn for_each_placement(positions: &mut [u8], depth: usize, visit: &mut impl FnMut(&[u8])) {
+    if depth == positions.len() {
+        visit(positions);
+        return;
+    }
+    for cell in 0..BOARD_AREA {
+        if !positions[..depth].contains(&cell) {
+            positions[depth] = cell;
+            for_each_placement(positions, depth + 1, visit);
+        }
+    }
+}
+
+/// Dense rank of a placement of distinct cells (a partial permutation)
+fn rank(positions: &[u8]) -> usize {
+    positions.iter().enumerate().fold(0, |index, (i, &p)| {
+        let smaller_used = positions[..i].iter().filter(|&&q| q < p).count();
+        index * (CELLS - i) + usize::from(p) - smaller_used
+    })
+}
+
+/// Number of placements of `k` distinct tiles on the board
+fn table_len(k: usize) -> usize {
+    (CELLS - k + 1..=CELLS).product()
+}
+
+/// Number of bytes used by `entries` packed entries
+fn packed_len(entries: usize, bits: u8) -> usize {
+    if bits == 8 {
+        entries
+    } else {
+        entries.div_ceil(2)
+    }
+}
+
+/// Packs the costs with the given number of bits per entry
+fn pack(costs: &[u8], bits: u8) -> Vec<u8> {
+    if bits == 8 {
+        return costs.to_vec();
+    }
+    costs
+        .chunks(2)
+        .map(|pair| pair[0] | pair.get(1).map_or(0, |high| high << 4))
+        .collect()
+}
+
+/// Serializes the fields of the file that come before the packed entries
+///
+/// # Panics
+///
+/// Panics if the table has more than `u32::MAX` entries, which no board supports
+fn header(pattern: &Pattern, bits: u8, entries: usize) -> Vec<u8> {
+    let mut bytes = vec![bits];
+    bytes.push(u8::try_from(pattern.tiles.len()).expect("Patterns are shorter than the board"));
+    bytes.extend_from_slice(&pattern.tiles);
+    bytes.extend_from_slice(
+        &u32::try_from(entries)
+            .expect("Pattern databases have fewer than 2^32 entries")
+            .to_le_bytes(),
+    );
+    bytes
+}
+
+/// Memory-maps a table file
+#[cfg(feature = "mmap")]
+fn read_storage(path: &Path) -> io::Result<Storage> {
+    let file = File::open(path)?;
+    // SAFETY: the table is only read, and files in the data directory are
+    // replaced by rename rather than modified in place
+    let map = unsafe { memmap2::Mmap::map(&file)? };
+    Ok(Storage::Mapped(map))
+}
+
+/// Reads a table file into memory
+#[cfg(not(feature = "mmap"))]
+fn read_storage(path: &Path) -> io::Result<Storage> {
+    use std::io::Read;
+
+    let mut bytes = Vec::new();
+    File::open(path)?.read_to_end(&mut bytes)?;
+    Ok(Storage::Owned(bytes.into_boxed_slice()))
+}
+
+/// Cell holding `tile` (0 for the empty space)
+///
+/// # Panics
+///
+/// Panics if the tile is not on the board, which cannot happen for valid boards
+fn position_of(cells: &[u8], tile: u8) -> u8 {
+    let position = cells
+        .iter()
+        .position(|&c| c == tile)
+        .expect("Every tile is on the board");
+    u8::try_from(position).expect("Board positions fit in a u8")
+}
+
+/// Cells adjacent to `cell`
+fn neighbors(cell: u8) -> impl Iterator<Item = u8> {
+    let (row, col) = (cell / BOARD_SIDE, cell % BOARD_SIDE);
+    [
+        (row > 0).then(|| cell - BOARD_SIDE),
+        (row + 1 < BOARD_SIDE).then(|| cell + BOARD_SIDE),
+        (col > 0).then(|| cell - 1),
+        (col + 1 < BOARD_SIDE).then(|| cell + 1),
+    ]
+    .into_iter()
+    .flatten()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::StateSpaceDatabase;
+
+    #[test]
+    fn additive_costs_never_overestimate_the_exact_distance() {
+        let exact = StateSpaceDatabase::build();
+        let partition: Partition = "4-4".parse().unwrap();
+        let build = || partition.patterns().iter().map(PatternDatabase::build);
+        let databases: Vec<PatternDatabase> = build().collect();
+        let heuristic = AdditiveHeuristic::new(build().collect()).unwrap();
+
+        assert_eq!(additive_cost(&databases, Board::default()), 0);
+        for rank in 0..Board::REACHABLE_COUNT {
+            let board = Board::from_reachable_rank(rank).unwrap();
+            let distance = exact.distance(board).unwrap();
+            assert!(
+                usize::from(distance) >= additive_cost(&databases, board),
+                "{board}"
+            );
+            assert!(distance >= heuristic.cost(board), "{board}");
+        }
+    }
+
+    #[test]
+    fn rejects_patterns_that_share_tiles() {
+        let databases = ["1-2-3", "3-4"]
+            .into_iter()
+            .map(|tiles| PatternDatabase::build(&tiles.parse().unwrap()))
+            .collect();
+        assert!(AdditiveHeuristic::new(databases).is_err());
+        assert!(AdditiveHeuristic::new(Vec::new()).is_err());
+    }
+}
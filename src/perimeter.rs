@@ -0,0 +1,278 @@
+//! # Perimeter Module
+//!
+//! Perimeter search (Dillenburg and Nelson 1994): a breadth-first search
+//! from the goal first records the exact distance of every board within a
+//! few moves of it, the perimeter. An A* search from the start then only has
+//! to reach the perimeter, as the table already holds the rest of the way.
+//!
+//! The table sharpens the heuristic as well: a board outside the perimeter is
+//! more than `depth` moves from the goal, so its estimate is raised to at
+//! least `depth + 1`, while boards on the perimeter get their exact distance.
+//! Both stay admissible and consistent, so the first perimeter board taken
+//! from the open list completes an optimal solution. A deeper perimeter costs
+//! more memory and set-up time and saves more of the search.
+
+use crate::time::Instant;
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+
+use crate::board::{ALL_DIRECTIONS, Board, BoardWithSteps};
+use crate::stats::Stats;
+
+/// Moves from the goal the perimeter reaches unless told otherwise
+pub const DEFAULT_DEPTH: usize = 12;
+
+/// Exact distances of the boards within a number of moves of the goal
+#[derive(Clone, Debug)]
+pub struct Perimeter {
+    depth: usize,
+    distances: HashMap<Board, u8>,
+}
+
+impl Perimeter {
+    /// Records every board at most `depth` moves from the goal
+    #[must_use]
+    pub fn new(depth: usize) -> Self {
+        let goal = Board::default();
+        let mut distances = HashMap::from([(goal, 0)]);
+        let mut queue = VecDeque::from([goal]);
+        while let Some(board) = queue.pop_front() {
+            let distance = distances[&board];
+            if usize::from(distance) >= depth {
+                continue;
+            }
+            for direction in ALL_DIRECTIONS {
+                let Ok(next) = board.move_space(direction) else {
+                    continue;
+                };
+                if let Entry::Vacant(entry) = distances.entry(next) {
+                    entry.insert(distance + 1);
+                    queue.push_back(next);
+                }
+            }
+        }
+        Self { depth, distances }
+    }
+
+    /// Largest distance from the goal the perimeter covers
+    #[must_use]
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Number of boards on the perimeter, the goal included
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.distances.len()
+    }
+
+    /// Whether the perimeter holds no boards, which never happens as it holds the goal
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.distances.is_empty()
+    }
+
+    /// Exact distance of `board` from the goal, `None` if it lies outside the perimeter
+    #[must_use]
+    pub fn distance(&self, board: Board) -> Option<usize> {
+        self.distances.get(&board).copied().map(usize::from)
+    }
+
+    /// Admissible estimate of the moves from `board` to the goal
+    ///
+    /// Exact on the perimeter; outside it, the Manhattan distance raised to
+    /// at least one more than the depth of the perimeter.
+    fn estimate(&self, board: Board) -> u8 {
+        self.distances.get(&board).copied().unwrap_or_else(|| {
+            let outside = u8::try_from(self.depth + 1).unwrap_or(u8::MAX);
+            board.heuristic_distance_to_solution().max(outside)
+        })
+    }
+
+    /// Boards from `board`, which lies on the perimeter, to the goal
+    ///
+    /// Every step moves to a neighbor one move closer to the goal.
+    fn path_to_goal(&self, board: Board) -> Vec<Board> {
+        let mut path = vec![board];
+        let mut current = board;
+        while let Some(distance) = self.distances.get(&current).copied().filter(|&d| d > 0) {
+            current = ALL_DIRECTIONS
+                .into_iter()
+                .filter_map(|direction| current.move_space(direction).ok())
+                .find(|next| self.distances.get(next) == Some(&(distance - 1)))
+                .expect("A perimeter board has a neighbor one move closer to the goal");
+            path.push(current);
+        }
+        path
+    }
+}
+
+/// A solution found by perimeter search
+#[derive(Clone, Debug)]
+pub struct PerimeterSolution {
+    /// Every board from the start to the goal
+    pub path: Vec<Board>,
+    /// Moves from the start to the first board on the perimeter
+    pub moves_to_perimeter: usize,
+    /// Number of boards on the perimeter
+    pub perimeter_states: usize,
+    /// Work of the search from the start; building the perimeter is not
+    /// counted, except in `duration_ms` for [`solve_with_depth`]
+    pub stats: Stats,
+}
+
+/// Solves a board with a perimeter [`DEFAULT_DEPTH`] moves deep
+///
+/// # Returns
+///
+/// `Some(solution)`, or `None` if the board cannot reach the goal
+#[must_use]
+pub fn solve(board: Board) -> Option<PerimeterSolution> {
+    solve_with_depth(board, DEFAULT_DEPTH)
+}
+
+/// Solves a board with a perimeter `depth` moves deep, built for this board alone
+///
+/// # Returns
+///
+/// `Some(solution)`, or `None` if the board cannot reach the goal
+#[must_use]
+pub fn solve_with_depth(board: Board, depth: usize) -> Option<PerimeterSolution> {
+    let started = Instant::now();
+    if !board.is_solvable() {
+        return None;
+    }
+    let mut solution = solve_with_perimeter(board, &Perimeter::new(depth))?;
+    solution.stats.duration_ms = started.elapsed().as_millis();
+    Some(solution)
+}
+
+/// Solves a board with an A* search that stops at `perimeter`
+///
+/// The solution is optimal. Sharing one perimeter between many boards saves
+/// building it again for each.
+///
+/// # Returns
+///
+/// `Some(solution)`, or `None` if the board cannot reach the goal
+#[must_use]
+pub fn solve_with_perimeter(board: Board, perimeter: &Perimeter) -> Option<PerimeterSolution> {
+    let started = Instant::now();
+    if !board.is_solvable() {
+        return None;
+    }
+
+    let mut stats = Stats::default();
+    let mut open = BinaryHeap::from([Reverse(BoardWithSteps::with_h(
+        board,
+        0,
+        perimeter.estimate(board),
+    ))]);
+    let mut reached: HashMap<Board, (usize, Option<Board>)> = HashMap::from([(board, (0, None))]);
+    let mut closed = HashSet::new();
+
+    while let Some(Reverse(node)) = open.pop() {
+        let (current, depth) = (node.0, node.1);
+        if reached[&current].0 != depth || !closed.insert(current) {
+            continue;
+        }
+        stats.nodes_explored += 1;
+        stats.max_depth_reached = stats.max_depth_reached.max(depth);
+
+        if perimeter.distance(current).is_some() {
+            let mut path = vec![current];
+            let mut board = current;
+            while let Some(&(_, Some(parent))) = reached.get(&board) {
+                path.push(parent);
+                board = parent;
+            }
+            path.reverse();
+            path.extend(perimeter.path_to_goal(current).into_iter().skip(1));
+
+            stats.solution_moves = path.len() - 1;
+
+            stats.solution_cost = stats.solution_moves;
+            stats.duration_ms = started.elapsed().as_millis();
+            return Some(PerimeterSolution {
+                path,
+                moves_to_perimeter: depth,
+                perimeter_states: perimeter.len(),
+                stats,
+            });
+        }
+
+        for direction in ALL_DIRECTIONS {
+            let Ok(child) = current.move_space(direction) else {
+                continue;
+            };
+            stats.generated_nodes += 1;
+            let child_depth = depth + 1;
+            if closed.contains(&child)
+                || reached
+                    .get(&child)
+                    .is_some_and(|&(known, _)| known <= child_depth)
+            {
+                stats.duplicates_pruned += 1;
+                continue;
+            }
+            reached.insert(child, (child_depth, Some(current)));
+            stats.enqueued_nodes += 1;
+            open.push(Reverse(BoardWithSteps::with_h(
+                child,
+                child_depth,
+                perimeter.estimate(child),
+            )));
+        }
+        stats.max_frontier = stats.max_frontier.max(open.len());
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{Rng, SeedableRng, rngs::StdRng};
+
+    use super::*;
+    use crate::database::StateSpaceDatabase;
+
+    #[test]
+    fn perimeter_distances_match_the_state_space_database() {
+        let database = StateSpaceDatabase::build();
+        let perimeter = Perimeter::new(6);
+        assert_eq!(perimeter.depth(), 6);
+        for rank in 0..Board::REACHABLE_COUNT {
+            let board = Board::from_reachable_rank(rank).unwrap();
+            let distance = usize::from(database.distance(board).unwrap());
+            assert_eq!(
+                perimeter.distance(board),
+                (distance <= 6).then_some(distance),
+                "{board}"
+            );
+        }
+    }
+
+    #[test]
+    fn solution_lengths_match_the_state_space_database() {
+        let database = StateSpaceDatabase::build();
+        let mut rng = StdRng::seed_from_u64(532);
+        for depth in [0, 4, 8] {
+            let perimeter = Perimeter::new(depth);
+            for _ in 0..4 {
+                let rank = rng.random_range(0..Board::REACHABLE_COUNT);
+                let board = Board::from_reachable_rank(rank).unwrap();
+                let solution = solve_with_perimeter(board, &perimeter).unwrap();
+
+                let distance = usize::from(database.distance(board).unwrap());
+                assert_eq!(solution.path.len() - 1, distance, "{board}");
+                assert_eq!(solution.moves_to_perimeter, distance.saturating_sub(depth));
+                assert_eq!(solution.path.first(), Some(&board));
+                assert!(solution.path.last().is_some_and(|last| last.is_solved()));
+                for pair in solution.path.windows(2) {
+                    assert!(pair[0].successors().any(|(_, next)| next == pair[1]));
+                }
+            }
+        }
+        assert!(solve("213804765".parse().unwrap()).is_none());
+    }
+}
@@ -0,0 +1,161 @@
+//! # Persist Module
+//!
+//! The file layout shared by the tables cached on disk:
+//!
+//! ```text
+//! magic [u8; 4] | version u8 | checksum u64 LE | body
+//! ```
+//!
+//! Each table has its own [`Format`]: magic bytes naming the kind of file and
+//! the version of the body that follows. The checksum is the FNV-1a hash of
+//! the body, so a truncated or corrupted file is rejected on load instead of
+//! being trusted. Files are written next to their destination and then
+//! renamed over it, so a concurrent reader never sees a partial file and an
+//! interrupted write leaves the previous one intact.
+
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use crate::checksum::fnv1a;
+
+/// Number of bytes before the body: magic, version and checksum
+pub(crate) const HEADER_LEN: usize = 4 + 1 + 8;
+
+/// Identity of one kind of table file
+pub(crate) struct Format {
+    /// Magic bytes at the start of the file
+    pub magic: [u8; 4],
+    /// Version of the body layout
+    pub version: u8,
+    /// What the file holds, for error messages
+    pub name: &'static str,
+}
+
+impl Format {
+    /// Magic bytes followed by the version
+    pub(crate) fn tag(&self) -> [u8; 5] {
+        let [a, b, c, d] = self.magic;
+        [a, b, c, d, self.version]
+    }
+
+    /// Strips the magic bytes and the version off the start of `bytes`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `bytes` do not start with this format's magic bytes
+    /// and version
+    pub(crate) fn read_tag<'a>(&self, bytes: &'a [u8]) -> io::Result<&'a [u8]> {
+        let rest = bytes
+            .strip_prefix(self.magic.as_slice())
+            .ok_or_else(|| invalid(format!("not a {}", self.name)))?;
+        let [version, rest @ ..] = rest else {
+            return Err(invalid(format!("truncated {}", self.name)));
+        };
+        if *version != self.version {
+            return Err(invalid(format!("unsupported {} version", self.name)));
+        }
+        Ok(rest)
+    }
+}
+
+/// An error for a file that is not a valid table
+fn invalid(msg: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+/// Header of a file holding `body`
+fn header(format: &Format, body: &[u8]) -> [u8; HEADER_LEN] {
+    let mut header = [0; HEADER_LEN];
+    let (tag, checksum) = header.split_at_mut(5);
+    tag.copy_from_slice(&format.tag());
+    checksum.copy_from_slice(&fnv1a(body.iter().copied()).to_le_bytes());
+    header
+}
+
+/// Contents of a file holding `body`, header included
+pub(crate) fn encode(format: &Format, body: &[u8]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(HEADER_LEN + body.len());
+    bytes.extend_from_slice(&header(format, body));
+    bytes.extend_from_slice(body);
+    bytes
+}
+
+/// Writes `body` with its header to `path`, replacing the file atomically
+///
+/// # Errors
+///
+/// Returns any I/O error raised while writing the file
+pub(crate) fn write_atomic(path: &Path, format: &Format, body: &[u8]) -> io::Result<()> {
+    let tmp = path.with_extension("tmp");
+    let mut file = BufWriter::new(File::create(&tmp)?);
+    file.write_all(&header(format, body))?;
+    file.write_all(body)?;
+    file.into_inner()?.sync_all()?;
+    fs::rename(tmp, path)
+}
+
+/// Checks the header of a file read into `bytes` and returns its body
+///
+/// # Errors
+///
+/// Returns an error if the file is not of this format or fails its integrity
+/// check
+pub(crate) fn read_checked<'a>(bytes: &'a [u8], format: &Format) -> io::Result<&'a [u8]> {
+    let (checksum, body) = format
+        .read_tag(bytes)?
+        .split_first_chunk::<8>()
+        .ok_or_else(|| invalid(format!("truncated {}", format.name)))?;
+    if fnv1a(body.iter().copied()) != u64::from_le_bytes(*checksum) {
+        return Err(invalid(format!(
+            "{} failed its integrity check",
+            format.name
+        )));
+    }
+    Ok(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TABLE: Format = Format {
+        magic: *b"O8TS",
+        version: 3,
+        name: "test table",
+    };
+
+    #[test]
+    fn reads_back_what_it_wrote() {
+        let dir = std::env::temp_dir().join(format!("o8-persist-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("table.bin");
+        write_atomic(&path, &TABLE, b"body").unwrap();
+
+        let bytes = fs::read(&path).unwrap();
+        assert_eq!(bytes, encode(&TABLE, b"body"));
+        assert_eq!(read_checked(&bytes, &TABLE).unwrap(), b"body");
+        assert!(!path.with_extension("tmp").exists());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn rejects_other_tables_and_corrupt_bodies() {
+        let bytes = encode(&TABLE, b"body");
+        let other = Format {
+            magic: *b"O8XX",
+            ..TABLE
+        };
+        let newer = Format {
+            version: 4,
+            ..TABLE
+        };
+        assert!(read_checked(&bytes, &other).is_err());
+        assert!(read_checked(&bytes, &newer).is_err());
+        assert!(read_checked(&bytes[..HEADER_LEN - 1], &TABLE).is_err());
+
+        let mut corrupt = bytes;
+        *corrupt.last_mut().unwrap() ^= 1;
+        assert!(read_checked(&corrupt, &TABLE).is_err());
+    }
+}
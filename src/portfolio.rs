@@ -0,0 +1,124 @@
+//! # Portfolio Module
+//!
+//! Races several strategies on one board: each entrant solves it on a thread
+//! of its own, the first to reach the goal wins, and a shared cancel flag
+//! stops the others right away. No single strategy is fastest on every
+//! board, as DFS sometimes stumbles on the goal long before BFS has finished
+//! a layer, so a race returns whichever answer comes first at the price of
+//! the cores the losers used.
+//!
+//! The losers keep the statistics of the search they were cancelled in, which
+//! shows how far behind they were.
+
+use crate::time::Instant;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::thread;
+
+use crate::board::Board;
+use crate::registry::{self, StrategyConfig};
+use crate::stats::Stats;
+
+/// Registry names of the strategies [`solve`] races
+pub const ENTRANTS: [&str; 3] = ["dfs", "bfs", "heuristic"];
+
+/// How one entrant of the race fared
+#[derive(Clone, Debug)]
+pub struct Entrant {
+    /// Name of the strategy shown in reports, e.g. `"BFS"`
+    pub label: &'static str,
+    /// Whether it reached the goal before it was cancelled
+    pub finished: bool,
+    /// Statistics of its search, up to the cancellation for the losers
+    pub stats: Stats,
+}
+
+/// A solution found by the winner of a race
+#[derive(Clone, Debug)]
+pub struct PortfolioSolution {
+    /// Every board from the start to the goal, as found by the winner
+    pub path: Vec<Board>,
+    /// Label of the strategy that finished first
+    pub winner: &'static str,
+    /// Every entrant, in the order of [`ENTRANTS`]
+    pub entrants: Vec<Entrant>,
+    /// Statistics of the winner; `duration_ms` is the wall time of the race
+    pub stats: Stats,
+}
+
+/// Races the strategies of [`ENTRANTS`] on `board`
+///
+/// Frontier caps, depth limits, the heuristic and the goal of `config` apply
+/// to every entrant; progress observers and checkpoints do not.
+///
+/// # Returns
+///
+/// `Some(solution)`, or `None` if the board cannot reach the goal or no
+/// entrant found a solution within the limits
+///
+/// # Panics
+///
+/// Panics if an entrant's thread panics
+#[must_use]
+pub fn solve(board: Board, config: &StrategyConfig) -> Option<PortfolioSolution> {
+    let started = Instant::now();
+    if !board.can_reach(config.goal) {
+        return None;
+    }
+
+    let cancel = Arc::new(AtomicBool::new(false));
+    let config = StrategyConfig {
+        progress: None,
+        checkpoint: None,
+        cancel_flag: Some(Arc::clone(&cancel)),
+        ..config.clone()
+    };
+    let winner = AtomicUsize::new(usize::MAX);
+
+    let results: Vec<(Entrant, Vec<Board>)> = thread::scope(|scope| {
+        let handles: Vec<_> = ENTRANTS
+            .iter()
+            .enumerate()
+            .map(|(index, name)| {
+                let entry =
+                    registry::lookup(name).expect("built-in strategies are always registered");
+                let (config, cancel, winner) = (&config, &cancel, &winner);
+                scope.spawn(move || {
+                    let mut solver = entry.build(config);
+                    let finished = solver.solve(board).is_some();
+                    if finished
+                        && winner
+                            .compare_exchange(usize::MAX, index, Ordering::SeqCst, Ordering::SeqCst)
+                            .is_ok()
+                    {
+                        cancel.store(true, Ordering::Relaxed);
+                    }
+                    let entrant = Entrant {
+                        label: entry.label,
+                        finished,
+                        stats: solver.get_solution_stats(),
+                    };
+                    (entrant, solver.step_by_step_solution())
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("Portfolio entrant panicked"))
+            .collect()
+    });
+
+    let winner = winner.into_inner();
+    if winner == usize::MAX {
+        return None;
+    }
+    let (entrants, mut paths): (Vec<Entrant>, Vec<Vec<Board>>) = results.into_iter().unzip();
+    let mut stats = entrants[winner].stats;
+    stats.duration_ms = started.elapsed().as_millis();
+    Some(PortfolioSolution {
+        path: paths.swap_remove(winner),
+        winner: entrants[winner].label,
+        entrants,
+        stats,
+    })
+}
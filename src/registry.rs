@@ -0,0 +1,636 @@
+//! # Strategy Registry
+//!
+//! Maps strategy names such as `"bfs"` to constructors of type-erased solvers,
+//! so the CLI, the JSON-RPC server and the browser bindings all resolve
+//! algorithm names the same way instead of each matching on them.
+//!
+//! The built-in strategies (`dfs`, `bfs`, `ucs`, `heuristic`, `focal`, `human`,
+//! `broadening`, `bidirectional`, `sma`, `fringe`, `frontier`, `dfbnb`, `lrta`,
+//! `ehc`, `hda`, `perimeter`, `oracle` and `portfolio`) are always registered,
+//! except in WebAssembly builds, which have no threads for `hda` and `portfolio`.
+//! A crate using o8 as a library can add its own before serving requests;
+//! every later lookup, including the `rpc` and `websocket` servers, sees them:
+//!
+//! ```no_run
+//! use o8::registry::{self, StrategyConfig, StrategyEntry};
+//! use o8::search_strategies::SimpleSearchStrategy;
+//! use o8::solver::ExplorerStrategy;
+//!
+//! registry::register(StrategyEntry {
+//!     name: "capped-bfs",
+//!     label: "Capped BFS",
+//!     description: "Breadth-first search that keeps at most 1000 frontier nodes",
+//!     construct: |config: &StrategyConfig| {
+//!         let config = StrategyConfig {
+//!             frontier_cap: Some((1000, Default::default())),
+//!             ..config.clone()
+//!         };
+//!         config.apply(SimpleSearchStrategy::new(ExplorerStrategy::Bfs))
+//!     },
+//! })
+//! .expect("name is not taken");
+//!
+//! let mut solver = registry::lookup("capped-bfs").unwrap().build(&StrategyConfig::default());
+//! ```
+use std::fmt;
+use std::sync::atomic::AtomicBool;
+use std::sync::{Arc, OnceLock, PoisonError, RwLock};
+
+use crate::bidirectional;
+use crate::board::{BOARD_AREA, Board, BoardWithSteps};
+use crate::broadening;
+use crate::checkpoint::SolverCheckpoint;
+use crate::cost::MoveCosts;
+use crate::dfbnb;
+use crate::ehc;
+use crate::fringe;
+use crate::frontier_search;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::hda;
+use crate::heuristic::SharedHeuristic;
+use crate::human;
+use crate::lrta;
+use crate::oracle;
+use crate::perimeter;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::portfolio;
+use crate::search_strategies::{
+    self, FocalSearchStrategy, HeuristicSearchStrategy, PruningPolicy, SearchStrategy,
+    SimpleSearchStrategy,
+};
+use crate::sma;
+use crate::solver::{
+    CheckpointObserver, ChildOrder, ExplorerStrategy, ProgressObserver, SolveError, Solver,
+};
+use crate::stats::Stats;
+
+/// A solver whose frontier type has been erased, as built by a [`StrategyEntry`]
+pub trait DynSolver: Send {
+    /// Solves `board`, returning the goal board if a solution was found
+    fn solve(&mut self, board: Board) -> Option<Board>;
+
+    /// Board the solver searches for
+    ///
+    /// The default implementation is the spiral goal, the only goal of the
+    /// strategies outside the generic solver.
+    fn goal(&self) -> Board {
+        Board::default()
+    }
+
+    /// Solves `board`, reporting why no solution was found
+    ///
+    /// The default implementation reports boards that cannot reach
+    /// [`DynSolver::goal`] as [`SolveError::Unsolvable`] without solving them,
+    /// and every other failure as [`SolveError::Exhausted`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the reason the search ended without reaching the goal.
+    fn try_solve(&mut self, board: Board) -> Result<Board, SolveError> {
+        if !board.can_reach(self.goal()) {
+            return Err(SolveError::Unsolvable {
+                parity: board.parity(),
+            });
+        }
+        self.solve(board).ok_or(SolveError::Exhausted)
+    }
+
+    /// Continues a search from a checkpoint
+    ///
+    /// The default implementation cannot resume and solves the checkpoint's
+    /// start board from scratch.
+    ///
+    /// # Errors
+    ///
+    /// Returns the reason the search ended without reaching the goal.
+    fn resume(&mut self, checkpoint: &SolverCheckpoint) -> Result<Board, SolveError> {
+        self.try_solve(checkpoint.start)
+    }
+
+    /// Boards from the start to the goal of the last solve
+    fn step_by_step_solution(&self) -> Vec<Board>;
+    /// Statistics of the last solve
+    fn get_solution_stats(&self) -> Stats;
+
+    /// Number of expanded nodes with the empty space at each cell
+    ///
+    /// The default implementation reports no expansions, for solvers that keep no frontier.
+    fn blank_heatmap(&self) -> [usize; BOARD_AREA as usize] {
+        [0; BOARD_AREA as usize]
+    }
+
+    /// Whether the solver is a single search over the generic solver's frontier
+    ///
+    /// Only those record search progress, checkpoints, blank heatmaps and
+    /// explored boards. The default implementation is `false`.
+    fn is_tree_search(&self) -> bool {
+        false
+    }
+
+    /// Every board expanded by the last solve, in no particular order
+    ///
+    /// The default implementation lists none, for solvers that are not tree searches.
+    fn explored_boards(&self) -> Vec<Board> {
+        Vec::new()
+    }
+}
+
+impl<T> DynSolver for Solver<T>
+where
+    T: SearchStrategy<BoardWithSteps> + Default + Clone + Send,
+{
+    fn solve(&mut self, board: Board) -> Option<Board> {
+        Solver::solve(self, board)
+    }
+
+    fn goal(&self) -> Board {
+        Solver::goal(self)
+    }
+
+    fn try_solve(&mut self, board: Board) -> Result<Board, SolveError> {
+        Solver::try_solve(self, board)
+    }
+
+    fn resume(&mut self, checkpoint: &SolverCheckpoint) -> Result<Board, SolveError> {
+        Solver::resume(self, checkpoint)
+    }
+
+    fn step_by_step_solution(&self) -> Vec<Board> {
+        Solver::step_by_step_solution(self)
+    }
+
+    fn get_solution_stats(&self) -> Stats {
+        Solver::get_solution_stats(self)
+    }
+
+    fn blank_heatmap(&self) -> [usize; BOARD_AREA as usize] {
+        Solver::blank_heatmap(self)
+    }
+
+    fn is_tree_search(&self) -> bool {
+        true
+    }
+
+    fn explored_boards(&self) -> Vec<Board> {
+        Solver::explored_boards(self).collect()
+    }
+}
+
+/// Options applied to every solver a registry entry constructs
+#[derive(Clone, Default)]
+pub struct StrategyConfig {
+    /// Largest frontier size allowed and how to prune past it
+    pub frontier_cap: Option<(usize, PruningPolicy)>,
+    /// Deepest depth successors may be enqueued at
+    pub max_depth: Option<usize>,
+    /// Largest f-value nodes of the generic solver may have, to accept any solution within it
+    pub max_cost: Option<usize>,
+    /// Progress observer and the number of explored nodes between notifications
+    pub progress: Option<(usize, ProgressObserver)>,
+    /// Checkpoint observer and the number of explored nodes between checkpoints
+    pub checkpoint: Option<(usize, CheckpointObserver)>,
+    /// Moves a real-time strategy looks ahead before committing to each move
+    pub lookahead: Option<usize>,
+    /// Moves from the goal the table of perimeter search reaches
+    pub perimeter_depth: Option<usize>,
+    /// Suboptimality ε of focal search, whose solutions are at most (1 + ε) times the optimal length
+    pub epsilon: Option<f64>,
+    /// Heuristic replacing the Manhattan distance in the generic solver
+    pub heuristic: Option<SharedHeuristic>,
+    /// Flag that stops the generic solver's searches when raised
+    pub cancel_flag: Option<Arc<AtomicBool>>,
+    /// Whether the generic solver explores one board per symmetry class
+    pub symmetry_reduction: bool,
+    /// Order in which the generic solver enqueues successors
+    pub child_order: ChildOrder,
+    /// Cost of moving each tile in the generic solver
+    pub move_costs: MoveCosts,
+    /// Board the generic solver searches for, the spiral goal by default
+    pub goal: Board,
+}
+
+impl StrategyConfig {
+    /// Builds a solver for `search_strategy` with these options and erases its type
+    #[must_use]
+    pub fn apply<T>(&self, search_strategy: T) -> Box<dyn DynSolver>
+    where
+        T: SearchStrategy<BoardWithSteps> + Default + Clone + Send + 'static,
+    {
+        let mut builder = Solver::builder(search_strategy);
+        if let Some((limit, policy)) = self.frontier_cap {
+            builder = builder.frontier_cap(limit, policy);
+        }
+        if let Some(max_depth) = self.max_depth {
+            builder = builder.max_depth(max_depth);
+        }
+        if let Some(max_cost) = self.max_cost {
+            builder = builder.max_cost(max_cost);
+        }
+        if let Some(heuristic) = &self.heuristic {
+            builder = builder.heuristic(heuristic.clone());
+        }
+        if let Some(flag) = &self.cancel_flag {
+            builder = builder.cancel_flag(Arc::clone(flag));
+        }
+        if self.symmetry_reduction {
+            builder = builder.symmetry_reduction();
+        }
+        builder = builder.child_order(self.child_order);
+        builder = builder.move_costs(self.move_costs);
+        builder = builder.goal(self.goal);
+        if let Some((interval, observer)) = &self.progress {
+            builder = builder.progress_observer(*interval, observer.clone());
+        }
+        if let Some((interval, observer)) = &self.checkpoint {
+            builder = builder.checkpoint_every(*interval, observer.clone());
+        }
+        Box::new(builder.build())
+    }
+}
+
+/// Builds a solver for a strategy from the shared options
+pub type StrategyConstructor = fn(&StrategyConfig) -> Box<dyn DynSolver>;
+
+/// A named strategy and how to construct it
+#[derive(Clone, Copy)]
+pub struct StrategyEntry {
+    /// Lookup key, e.g. `"bfs"`
+    pub name: &'static str,
+    /// Name shown in reports, e.g. `"BFS"`
+    pub label: &'static str,
+    /// One-line description shown by `o8 algorithms`
+    pub description: &'static str,
+    /// Builds a fresh solver for one or more solves
+    pub construct: StrategyConstructor,
+}
+
+impl StrategyEntry {
+    /// Constructs a fresh solver with `config` applied
+    #[must_use]
+    pub fn build(&self, config: &StrategyConfig) -> Box<dyn DynSolver> {
+        (self.construct)(config)
+    }
+
+    /// Whether the strategy is a single search over the generic solver's frontier
+    ///
+    /// Only those record search progress, blank heatmaps and explored sets
+    /// (see [`DynSolver::is_tree_search`]).
+    #[must_use]
+    pub fn is_tree_search(&self) -> bool {
+        self.build(&StrategyConfig::default()).is_tree_search()
+    }
+}
+
+impl fmt::Debug for StrategyEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StrategyEntry")
+            .field("name", &self.name)
+            .field("label", &self.label)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Returned when registering a strategy under a name that is already taken
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DuplicateStrategy(pub &'static str);
+
+impl fmt::Display for DuplicateStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "A strategy named \"{}\" is already registered", self.0)
+    }
+}
+
+impl std::error::Error for DuplicateStrategy {}
+
+/// A strategy with a search loop of its own behind the [`DynSolver`] interface
+///
+/// `solve` runs the whole search and returns the boards from the start to the
+/// goal, empty if it found none, and the statistics of the search. Such
+/// strategies keep no frontier of the generic solver, so frontier caps,
+/// progress observers and checkpoints never apply; the few options one of
+/// them honours are captured by its constructor.
+struct PathSolver<F>
+where
+    F: Fn(Board) -> (Vec<Board>, Stats),
+{
+    solve: F,
+    goal: Board,
+    path: Vec<Board>,
+    stats: Stats,
+}
+
+impl<F> PathSolver<F>
+where
+    F: Fn(Board) -> (Vec<Board>, Stats) + Send + 'static,
+{
+    /// Erases a solver searching for the spiral goal with `solve`
+    fn boxed(solve: F) -> Box<dyn DynSolver> {
+        Self::boxed_towards(Board::default(), solve)
+    }
+
+    /// Erases a solver searching for `goal` with `solve`
+    fn boxed_towards(goal: Board, solve: F) -> Box<dyn DynSolver> {
+        Box::new(Self {
+            solve,
+            goal,
+            path: Vec::new(),
+            stats: Stats::default(),
+        })
+    }
+}
+
+impl<F> DynSolver for PathSolver<F>
+where
+    F: Fn(Board) -> (Vec<Board>, Stats) + Send,
+{
+    fn solve(&mut self, board: Board) -> Option<Board> {
+        (self.path, self.stats) = (self.solve)(board);
+        self.path.last().copied()
+    }
+
+    fn goal(&self) -> Board {
+        self.goal
+    }
+
+    fn step_by_step_solution(&self) -> Vec<Board> {
+        self.path.clone()
+    }
+
+    fn get_solution_stats(&self) -> Stats {
+        self.stats
+    }
+}
+
+/// Strategies that ship with o8, in display order
+const BUILTIN_STRATEGIES: &[StrategyEntry] = &[
+    StrategyEntry {
+        name: "dfs",
+        label: "DFS",
+        description: "Depth-First Search: explores as far as possible along each branch before backtracking",
+        construct: |config| config.apply(SimpleSearchStrategy::new(ExplorerStrategy::Dfs)),
+    },
+    StrategyEntry {
+        name: "bfs",
+        label: "BFS",
+        description: "Breadth-First Search: explores all neighbors at the current depth before moving deeper",
+        construct: |config| config.apply(SimpleSearchStrategy::new(ExplorerStrategy::Bfs)),
+    },
+    StrategyEntry {
+        name: "ucs",
+        label: "UCS",
+        description: "Uniform-Cost Search (Dijkstra): expands the node with the cheapest path first",
+        construct: |config| config.apply(SimpleSearchStrategy::new(ExplorerStrategy::Ucs)),
+    },
+    StrategyEntry {
+        name: "heuristic",
+        label: "Heuristic",
+        description: "Heuristic Search: uses a heuristic to prioritize paths that seem most promising",
+        construct: |config| config.apply(HeuristicSearchStrategy::default()),
+    },
+    StrategyEntry {
+        name: "focal",
+        label: "Focal",
+        description: "Focal search (A*ε): among the nodes within (1 + ε) of the lowest f, expands the one with the lowest h",
+        construct: |config| {
+            config.apply(FocalSearchStrategy::new(
+                config.epsilon.unwrap_or(search_strategies::DEFAULT_EPSILON),
+            ))
+        },
+    },
+    StrategyEntry {
+        name: "human",
+        label: "Human",
+        description: "Human-style layered solving: top row, left column, then the final cycle",
+        construct: |_| {
+            PathSolver::boxed(|board| {
+                human::solve(board).map_or_else(Default::default, |s| (s.path(), s.stats))
+            })
+        },
+    },
+    StrategyEntry {
+        name: "broadening",
+        label: "Broadening",
+        description: "Iterative broadening: depth-first passes following the 1, 2, 3, then 4 successors with the lowest heuristic",
+        construct: |config| {
+            // The depth limit replaces the default one
+            let max_depth = config.max_depth.unwrap_or(broadening::DEFAULT_MAX_DEPTH);
+            PathSolver::boxed(move |board| {
+                broadening::solve_within(board, max_depth)
+                    .map_or_else(Default::default, |s| (s.path, s.stats))
+            })
+        },
+    },
+    StrategyEntry {
+        name: "bidirectional",
+        label: "Bidirectional",
+        description: "Bidirectional MM search: A*-style searches from both ends that meet in the middle",
+        construct: |_| {
+            PathSolver::boxed(|board| {
+                bidirectional::solve(board).map_or_else(Default::default, |s| (s.path, s.stats))
+            })
+        },
+    },
+    StrategyEntry {
+        name: "sma",
+        label: "SMA*",
+        description: "Simplified memory-bounded A*: forgets the worst leaves when memory is full and backs their f-values up to their parents",
+        construct: |config| {
+            // A frontier cap bounds every node held, interior ones included
+            let max_nodes = config
+                .frontier_cap
+                .map_or(sma::DEFAULT_MAX_NODES, |(limit, _)| limit);
+            PathSolver::boxed(move |board| {
+                sma::solve_within(board, max_nodes)
+                    .map_or_else(Default::default, |s| (s.path, s.stats))
+            })
+        },
+    },
+    StrategyEntry {
+        name: "fringe",
+        label: "Fringe",
+        description: "Fringe search: IDA*'s thresholds, resuming each iteration from the nodes the last one deferred",
+        construct: |_| {
+            PathSolver::boxed(|board| {
+                fringe::solve(board).map_or_else(Default::default, |s| (s.path, s.stats))
+            })
+        },
+    },
+    StrategyEntry {
+        name: "frontier",
+        label: "Frontier",
+        description: "Frontier search: breadth-first layers from both ends without a closed list, rebuilding the path by divide and conquer",
+        construct: |_| {
+            PathSolver::boxed(|board| {
+                frontier_search::solve(board).map_or_else(Default::default, |s| (s.path, s.stats))
+            })
+        },
+    },
+    StrategyEntry {
+        name: "dfbnb",
+        label: "DFBnB",
+        description: "Depth-first branch-and-bound: a DFS that prunes by its best solution so far until that one is proven optimal",
+        construct: |_| {
+            PathSolver::boxed(|board| {
+                dfbnb::solve(board).map_or_else(Default::default, |s| (s.path, s.stats))
+            })
+        },
+    },
+    StrategyEntry {
+        name: "lrta",
+        label: "LRTA*",
+        description: "Learning Real-Time A*: looks a few moves ahead, commits to the best move and raises the h of the board it leaves",
+        construct: |config| {
+            let lookahead = config.lookahead.unwrap_or(lrta::DEFAULT_LOOKAHEAD);
+            PathSolver::boxed(move |board| {
+                lrta::solve_with_lookahead(board, lookahead)
+                    .map_or_else(Default::default, |s| (s.path, s.stats))
+            })
+        },
+    },
+    StrategyEntry {
+        name: "ehc",
+        label: "EHC",
+        description: "Enforced hill climbing: breadth-first lookaheads to the nearest board with a lower h, committing to each",
+        construct: |_| {
+            PathSolver::boxed(|board| {
+                ehc::solve(board).map_or_else(Default::default, |s| (s.path, s.stats))
+            })
+        },
+    },
+    #[cfg(not(target_arch = "wasm32"))]
+    StrategyEntry {
+        name: "hda",
+        label: "HDA*",
+        description: "Hash-distributed A*: one optimal search spread over every core, each thread owning the boards that hash to it",
+        construct: |_| {
+            PathSolver::boxed(|board| {
+                hda::solve(board).map_or_else(Default::default, |s| (s.path, s.stats))
+            })
+        },
+    },
+    StrategyEntry {
+        name: "perimeter",
+        label: "Perimeter",
+        description: "Perimeter search: A* from the start until it reaches a precomputed table of exact distances around the goal",
+        construct: |config| {
+            let depth = config.perimeter_depth.unwrap_or(perimeter::DEFAULT_DEPTH);
+            PathSolver::boxed(move |board| {
+                perimeter::solve_with_depth(board, depth)
+                    .map_or_else(Default::default, |s| (s.path, s.stats))
+            })
+        },
+    },
+    StrategyEntry {
+        name: "oracle",
+        label: "Oracle",
+        description: "Oracle: reads an optimal solution from the exact distances of every board, built by a backward BFS from the goal",
+        construct: |_| {
+            PathSolver::boxed(|board| {
+                oracle::solve(board).map_or_else(Default::default, |s| (s.path, s.stats))
+            })
+        },
+    },
+    #[cfg(not(target_arch = "wasm32"))]
+    StrategyEntry {
+        name: "portfolio",
+        label: "Portfolio",
+        description: "Portfolio: races DFS, BFS and the heuristic search on threads and keeps the first solution",
+        construct: |config| {
+            // Frontier caps, depth limits, the heuristic and the goal apply to every entrant
+            let config = config.clone();
+            PathSolver::boxed_towards(config.goal, move |board| {
+                portfolio::solve(board, &config)
+                    .map_or_else(Default::default, |s| (s.path, s.stats))
+            })
+        },
+    },
+];
+
+/// The process-wide list of strategies, seeded with the built-in ones
+fn entries() -> &'static RwLock<Vec<StrategyEntry>> {
+    static ENTRIES: OnceLock<RwLock<Vec<StrategyEntry>>> = OnceLock::new();
+    ENTRIES.get_or_init(|| RwLock::new(BUILTIN_STRATEGIES.to_vec()))
+}
+
+/// Adds a strategy to the process-wide registry
+///
+/// Names are matched case-insensitively, so `"BFS"` clashes with the built-in `"bfs"`.
+///
+/// # Errors
+///
+/// Returns [`DuplicateStrategy`] if a strategy with the same name is already registered.
+pub fn register(entry: StrategyEntry) -> Result<(), DuplicateStrategy> {
+    let mut entries = entries().write().unwrap_or_else(PoisonError::into_inner);
+    if entries
+        .iter()
+        .any(|existing| existing.name.eq_ignore_ascii_case(entry.name))
+    {
+        return Err(DuplicateStrategy(entry.name));
+    }
+    entries.push(entry);
+    Ok(())
+}
+
+/// Finds a registered strategy by name, ignoring ASCII case
+#[must_use]
+pub fn lookup(name: &str) -> Option<StrategyEntry> {
+    entries()
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .iter()
+        .find(|entry| entry.name.eq_ignore_ascii_case(name))
+        .copied()
+}
+
+/// Every registered strategy, built-in ones first, then in registration order
+#[must_use]
+pub fn strategies() -> Vec<StrategyEntry> {
+    entries()
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()
+}
+
+/// Names of every registered strategy, quoted and joined for error messages
+///
+/// For example `"dfs", "bfs", "heuristic" or "human"`.
+#[must_use]
+pub fn expected_names() -> String {
+    let names: Vec<String> = strategies()
+        .iter()
+        .map(|entry| format!("\"{}\"", entry.name))
+        .collect();
+    match names.split_last() {
+        Some((last, [])) => last.clone(),
+        Some((last, rest)) => format!("{} or {last}", rest.join(", ")),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::Direction;
+
+    #[test]
+    fn try_solve_checks_parity_against_the_configured_goal() {
+        // The row-major goal has the opposite parity of the spiral goal
+        let goal: Board = "123456780".parse().unwrap();
+        let board = goal.move_space(Direction::Up).unwrap();
+        let config = StrategyConfig {
+            goal,
+            ..StrategyConfig::default()
+        };
+
+        let mut portfolio = lookup("portfolio").unwrap().build(&config);
+        assert_eq!(portfolio.goal(), goal);
+        assert_eq!(portfolio.try_solve(board), Ok(goal));
+
+        let mut sma = lookup("sma").unwrap().build(&config);
+        assert_eq!(sma.goal(), Board::default());
+        assert!(matches!(
+            sma.try_solve(board),
+            Err(SolveError::Unsolvable { .. })
+        ));
+    }
+}
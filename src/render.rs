@@ -0,0 +1,378 @@
+//! # Render Module
+//!
+//! Simple raster drawing of boards, used to share solutions outside the
+//! terminal. Each board is drawn as a grid of square tiles with a built-in
+//! pixel font for the tile numbers, so no font or image library is needed.
+//!
+//! Images are palette-indexed (see [`PALETTE`]). With the `gif` feature
+//! (enabled by `cli`), a whole solution path can be encoded as an animated
+//! GIF with [`write_gif`], one frame per step, highlighting the tile that is
+//! about to move.
+//!
+//! Solutions can also be laid out as a static SVG grid with [`write_svg`],
+//! with arrows between consecutive boards naming the tile that moves. SVG
+//! needs no extra dependency and scales cleanly in slides and write-ups.
+
+#[cfg(feature = "gif")]
+use std::borrow::Cow;
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use crate::board::{BOARD_SIDE, Board};
+
+/// Side of a tile in pixels
+const TILE_SIZE: usize = 48;
+
+/// Gap between tiles (and around the grid) in pixels
+const GAP: usize = 4;
+
+/// Side of a board image in pixels
+const IMAGE_SIZE: usize = BOARD_SIDE as usize * TILE_SIZE + (BOARD_SIDE as usize + 1) * GAP;
+
+/// Scale factor applied to the pixel font
+const FONT_SCALE: usize = 6;
+
+/// Width of a glyph in font pixels
+const GLYPH_WIDTH: usize = 3;
+
+/// Height of a glyph in font pixels
+const GLYPH_HEIGHT: usize = 5;
+
+/// Palette index of the background
+const BACKGROUND: u8 = 0;
+
+/// Palette index of a regular tile
+const TILE: u8 = 1;
+
+/// Palette index of the highlighted tile
+const HIGHLIGHT: u8 = 2;
+
+/// Palette index of the tile numbers
+const INK: u8 = 3;
+
+/// RGB colors referenced by the palette indices of a [`Raster`]
+pub const PALETTE: [[u8; 3]; 4] = [
+    [0x2b, 0x2b, 0x2b], // background
+    [0xee, 0xe8, 0xd5], // tile
+    [0xf0, 0xa0, 0x30], // highlighted tile
+    [0x20, 0x20, 0x20], // numbers
+];
+
+/// 3x5 pixel font for the digits 0-9, one row per entry, most significant bit on the left
+#[rustfmt::skip]
+const DIGITS: [[u8; GLYPH_HEIGHT]; 10] = [
+    [0b111, 0b101, 0b101, 0b101, 0b111],
+    [0b010, 0b110, 0b010, 0b010, 0b111],
+    [0b111, 0b001, 0b111, 0b100, 0b111],
+    [0b111, 0b001, 0b111, 0b001, 0b111],
+    [0b101, 0b101, 0b111, 0b001, 0b001],
+    [0b111, 0b100, 0b111, 0b001, 0b111],
+    [0b111, 0b100, 0b111, 0b101, 0b111],
+    [0b111, 0b001, 0b010, 0b010, 0b010],
+    [0b111, 0b101, 0b111, 0b101, 0b111],
+    [0b111, 0b101, 0b111, 0b001, 0b111],
+];
+
+/// A palette-indexed image
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Raster {
+    /// Width in pixels
+    pub width: usize,
+    /// Height in pixels
+    pub height: usize,
+    /// Row-major palette indices (see [`PALETTE`])
+    pub pixels: Vec<u8>,
+}
+
+impl Raster {
+    /// Creates an image filled with one palette index
+    fn filled(width: usize, height: usize, index: u8) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![index; width * height],
+        }
+    }
+
+    /// Fills a rectangle with one palette index
+    fn fill_rect(&mut self, x: usize, y: usize, width: usize, height: usize, index: u8) {
+        for row in y..y + height {
+            let start = row * self.width + x;
+            self.pixels[start..start + width].fill(index);
+        }
+    }
+
+    /// Draws a digit centered in the square at (`x`, `y`) of side `size`
+    fn draw_digit(&mut self, digit: u8, x: usize, y: usize, size: usize) {
+        let left = x + (size - GLYPH_WIDTH * FONT_SCALE) / 2;
+        let top = y + (size - GLYPH_HEIGHT * FONT_SCALE) / 2;
+
+        for (row, bits) in DIGITS[usize::from(digit)].iter().enumerate() {
+            for col in 0..GLYPH_WIDTH {
+                if bits & (1 << (GLYPH_WIDTH - 1 - col)) != 0 {
+                    self.fill_rect(
+                        left + col * FONT_SCALE,
+                        top + row * FONT_SCALE,
+                        FONT_SCALE,
+                        FONT_SCALE,
+                        INK,
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Draws a board
+///
+/// # Arguments
+///
+/// * `board` - The board to draw
+/// * `highlight` - Tile number to draw in the highlight color, if any
+///
+/// # Returns
+///
+/// A square image of the board, with the empty space left blank
+#[must_use]
+pub fn render_board(board: Board, highlight: Option<u8>) -> Raster {
+    let side = usize::from(BOARD_SIDE);
+    let mut raster = Raster::filled(IMAGE_SIZE, IMAGE_SIZE, BACKGROUND);
+
+    for (position, &tile) in board.into_arr().iter().enumerate() {
+        if tile == 0 {
+            continue;
+        }
+        let x = GAP + (position % side) * (TILE_SIZE + GAP);
+        let y = GAP + (position / side) * (TILE_SIZE + GAP);
+        let color = if highlight == Some(tile) {
+            HIGHLIGHT
+        } else {
+            TILE
+        };
+        raster.fill_rect(x, y, TILE_SIZE, TILE_SIZE, color);
+        raster.draw_digit(tile, x, y, TILE_SIZE);
+    }
+
+    raster
+}
+
+/// Finds, for every step of a path, the tile that moves next
+///
+/// # Returns
+///
+/// One entry per board: the tile that slides into the empty space on the way
+/// to the next board, or `None` for the last board
+#[must_use]
+pub fn moving_tiles(path: &[Board]) -> Vec<Option<u8>> {
+    let mut tiles: Vec<Option<u8>> = path
+        .windows(2)
+        .map(|pair| {
+            let next_blank = pair[1].into_arr().iter().position(|&t| t == 0)?;
+            Some(pair[0].into_arr()[next_blank])
+        })
+        .collect();
+    if !path.is_empty() {
+        tiles.push(None);
+    }
+    tiles
+}
+
+/// Delay between frames of an animated GIF, in hundredths of a second
+#[cfg(feature = "gif")]
+const FRAME_DELAY: u16 = 50;
+
+/// Delay on the final (solved) frame of an animated GIF, in hundredths of a second
+#[cfg(feature = "gif")]
+const LAST_FRAME_DELAY: u16 = 200;
+
+/// Writes a solution path as an animated GIF
+///
+/// Every board becomes one frame, with the tile that is about to move
+/// highlighted. Requires the `gif` feature.
+///
+/// # Arguments
+///
+/// * `path` - Destination file, overwritten if it exists
+/// * `boards` - Boards from start to goal
+///
+/// # Errors
+///
+/// Returns any I/O or encoding error raised while writing the file
+///
+/// # Panics
+///
+/// Never panics in practice: board images are far smaller than the GIF size limit
+#[cfg(feature = "gif")]
+pub fn write_gif(path: &Path, boards: &[Board]) -> io::Result<()> {
+    let palette: Vec<u8> = PALETTE.iter().flatten().copied().collect();
+    let side = u16::try_from(IMAGE_SIZE).expect("Board images fit in a GIF");
+
+    let file = BufWriter::new(File::create(path)?);
+    let mut encoder = gif::Encoder::new(file, side, side, &palette).map_err(io::Error::other)?;
+    encoder
+        .set_repeat(gif::Repeat::Infinite)
+        .map_err(io::Error::other)?;
+
+    for (board, tile) in boards.iter().zip(moving_tiles(boards)) {
+        let raster = render_board(*board, tile);
+        let frame = gif::Frame {
+            width: side,
+            height: side,
+            delay: if tile.is_some() {
+                FRAME_DELAY
+            } else {
+                LAST_FRAME_DELAY
+            },
+            buffer: Cow::Owned(raster.pixels),
+            ..gif::Frame::default()
+        };
+        encoder.write_frame(&frame).map_err(io::Error::other)?;
+    }
+
+    // Writes the GIF trailer, which dropping the encoder would do silently
+    encoder.into_inner()?.flush()
+}
+
+/// Number of boards per row of an SVG strip
+const SVG_COLUMNS: usize = 6;
+
+/// Horizontal room between two boards of an SVG strip, where the arrow goes
+const SVG_ARROW_WIDTH: usize = 56;
+
+/// Vertical room between two rows of an SVG strip, where the step labels go
+const SVG_ROW_GAP: usize = 32;
+
+/// Formats a palette entry as an SVG color
+fn svg_color(index: u8) -> String {
+    let [r, g, b] = PALETTE[usize::from(index)];
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+/// Unicode arrow for the way a tile slides between two blank positions
+///
+/// The tile starts where the blank ends up and slides into the old blank.
+fn slide_arrow(from_blank: usize, to_blank: usize) -> char {
+    let side = usize::from(BOARD_SIDE);
+    if to_blank + side == from_blank {
+        '\u{2193}'
+    } else if from_blank + side == to_blank {
+        '\u{2191}'
+    } else if to_blank + 1 == from_blank {
+        '\u{2192}'
+    } else {
+        '\u{2190}'
+    }
+}
+
+/// Lays out a solution path as an SVG grid of boards
+///
+/// Boards are read left to right, top to bottom, [`SVG_COLUMNS`] per row.
+/// Each board is labeled with its step number, and consecutive boards in a
+/// row are joined by an arrow naming the tile that slides.
+///
+/// # Arguments
+///
+/// * `boards` - Boards from start to goal
+///
+/// # Returns
+///
+/// A standalone SVG document
+#[must_use]
+pub fn render_svg(boards: &[Board]) -> String {
+    let side = usize::from(BOARD_SIDE);
+    let columns = boards.len().clamp(1, SVG_COLUMNS);
+    let rows = boards.len().div_ceil(SVG_COLUMNS).max(1);
+    let width = columns * IMAGE_SIZE + (columns - 1) * SVG_ARROW_WIDTH;
+    let height = rows * (IMAGE_SIZE + SVG_ROW_GAP);
+    let blanks: Vec<usize> = boards
+        .iter()
+        .map(|board| board.into_arr().iter().position(|&t| t == 0).unwrap_or(0))
+        .collect();
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+         viewBox=\"0 0 {width} {height}\" font-family=\"sans-serif\" text-anchor=\"middle\">"
+    );
+
+    for (step, (board, tile)) in boards.iter().zip(moving_tiles(boards)).enumerate() {
+        let left = (step % SVG_COLUMNS) * (IMAGE_SIZE + SVG_ARROW_WIDTH);
+        let top = (step / SVG_COLUMNS) * (IMAGE_SIZE + SVG_ROW_GAP) + SVG_ROW_GAP;
+
+        let _ = writeln!(
+            svg,
+            "  <text x=\"{}\" y=\"{}\" font-size=\"16\" fill=\"#555555\">step {step}</text>",
+            left + IMAGE_SIZE / 2,
+            top - 10
+        );
+        let _ = writeln!(
+            svg,
+            "  <rect x=\"{left}\" y=\"{top}\" width=\"{IMAGE_SIZE}\" height=\"{IMAGE_SIZE}\" \
+             rx=\"6\" fill=\"{}\"/>",
+            svg_color(BACKGROUND)
+        );
+
+        for (position, &cell) in board.into_arr().iter().enumerate() {
+            if cell == 0 {
+                continue;
+            }
+            let x = left + GAP + (position % side) * (TILE_SIZE + GAP);
+            let y = top + GAP + (position / side) * (TILE_SIZE + GAP);
+            let color = if tile == Some(cell) { HIGHLIGHT } else { TILE };
+            let _ = writeln!(
+                svg,
+                "  <rect x=\"{x}\" y=\"{y}\" width=\"{TILE_SIZE}\" height=\"{TILE_SIZE}\" \
+                 rx=\"4\" fill=\"{}\"/>",
+                svg_color(color)
+            );
+            let _ = writeln!(
+                svg,
+                "  <text x=\"{}\" y=\"{}\" font-size=\"28\" font-weight=\"bold\" fill=\"{}\">{cell}</text>",
+                x + TILE_SIZE / 2,
+                y + TILE_SIZE / 2 + 10,
+                svg_color(INK)
+            );
+        }
+
+        // Arrows only join boards on the same row; rows read as a continuation
+        let (Some(tile), true) = (tile, step % SVG_COLUMNS + 1 < SVG_COLUMNS) else {
+            continue;
+        };
+        let arrow = slide_arrow(blanks[step], blanks[step + 1]);
+        let x = left + IMAGE_SIZE + SVG_ARROW_WIDTH / 2;
+        let y = top + IMAGE_SIZE / 2;
+        let _ = writeln!(
+            svg,
+            "  <line x1=\"{}\" y1=\"{y}\" x2=\"{}\" y2=\"{y}\" stroke=\"#888888\" stroke-width=\"2\"/>",
+            left + IMAGE_SIZE + 8,
+            left + IMAGE_SIZE + SVG_ARROW_WIDTH - 8
+        );
+        let _ = writeln!(
+            svg,
+            "  <text x=\"{x}\" y=\"{}\" font-size=\"16\" fill=\"#333333\">{tile}{arrow}</text>",
+            y - 8
+        );
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Writes a solution path as a static SVG grid (see [`render_svg`])
+///
+/// # Arguments
+///
+/// * `path` - Destination file, overwritten if it exists
+/// * `boards` - Boards from start to goal
+///
+/// # Errors
+///
+/// Returns any I/O error raised while writing the file
+pub fn write_svg(path: &Path, boards: &[Board]) -> io::Result<()> {
+    let mut file = BufWriter::new(File::create(path)?);
+    file.write_all(render_svg(boards).as_bytes())?;
+    file.flush()
+}
@@ -0,0 +1,336 @@
+//! # Report Module
+//!
+//! Terminal output of the `o8` binary: solution listings, per-board result
+//! lines, cache and disk usage, and the reports of the inspection commands
+//! (`lookup`, `explore`, `analyze-space`, `overlap`, `algorithms`, `translate`
+//! and `doctor`). The comparison tables live in [`stats`](crate::stats).
+//!
+//! Everything here prints to standard output; the work behind it is done by
+//! [`commands`](crate::commands) and the modules it calls.
+
+use crate::adversarial::{AdversarialInstance, AdversarialObjective};
+use crate::board::{Board, Direction, TileSwap, format_moves};
+use crate::convention::GoalConvention;
+use crate::database::{Difficulty, StateSpaceDatabase};
+use crate::doctor::DoctorReport;
+use crate::explore::Exploration;
+use crate::external::IoStats;
+use crate::goal_cache::{CachedSolution, GoalCache};
+use crate::overlap::Overlap;
+use crate::pdb::AdditiveHeuristic;
+use crate::registry::{self, StrategyConfig};
+use crate::solver::SolveError;
+use crate::stats::Stats;
+
+/// Print every board of a solution path with its heuristic value
+///
+/// With `explain_heuristic`, each board is followed on the right by the
+/// Manhattan distance of each of its tiles, and h(n) by its change since the
+/// previous step. Distances are measured to the last board of the path, the
+/// goal the solution was searched for.
+pub fn print_solution_steps(solution: &[Board], explain_heuristic: bool) {
+    println!(
+        "\nSolution path ({} steps)\n",
+        solution.len().saturating_sub(1)
+    );
+    let goal = solution.last().copied().unwrap_or_default();
+    let mut previous_h = None;
+    for (idx, step) in solution.iter().enumerate() {
+        let h = step.manhattan_distance_to(goal);
+        let change = previous_h
+            .filter(|_| explain_heuristic)
+            .map(|previous: u8| format!("({:+})", i16::from(h) - i16::from(previous)))
+            .unwrap_or_default();
+        println!("Step {}/{} h(n): {h} {change}", idx, solution.len() - 1);
+        previous_h = Some(h);
+
+        if !explain_heuristic {
+            println!("{}", step.display_towards(goal));
+            continue;
+        }
+        let distances: Vec<String> = step
+            .manhattan_contributions_to(goal)
+            .into_iter()
+            .zip(step.into_arr())
+            .map(|(distance, tile)| {
+                if tile == 0 {
+                    "   ".to_owned()
+                } else {
+                    format!("{distance:2} ")
+                }
+            })
+            .collect();
+        let drawing = step.display_towards(goal).to_string();
+        for (line, row) in drawing.lines().zip(distances.chunks(3)) {
+            println!("{line}    {}", row.concat());
+        }
+    }
+
+    let moves: Vec<Direction> = solution
+        .windows(2)
+        .filter_map(|pair| pair[0].direction_to(pair[1]))
+        .collect();
+    println!("\nMoves: {}", format_moves(&moves));
+}
+
+/// Print how often each pattern database was looked up and the mean cost it returned
+pub fn print_pdb_lookups(heuristic: &AdditiveHeuristic) {
+    println!("\nPattern database lookups:");
+    for partition in heuristic.lookups() {
+        println!(
+            "  {}: {} lookups, mean cost {:.2}",
+            partition.pattern,
+            partition.lookups,
+            partition.mean_cost()
+        );
+    }
+}
+
+/// Print the disk traffic of an external-memory search
+pub fn print_io_stats(io_stats: &IoStats) {
+    println!(
+        "\nDisk I/O: {} bytes written, {} bytes read, {} files ({} sorted runs)",
+        io_stats.bytes_written, io_stats.bytes_read, io_stats.files_written, io_stats.sorted_runs
+    );
+}
+
+/// Print whether a solve was a cache lookup or how much it grew the cache
+pub fn print_cache_use(solution: &CachedSolution, cache: &GoalCache) {
+    let growth = if solution.was_lookup() {
+        "lookup only".to_owned()
+    } else {
+        format!(
+            "expanded {} layers, added {} boards",
+            solution.layers_expanded, solution.boards_added
+        )
+    };
+    println!(
+        "\nGoal cache {}: {growth} ({} boards known, complete to depth {})",
+        cache.path().display(),
+        cache.known_boards(),
+        cache.completed_depth()
+    );
+}
+
+/// Print how much a goal cache knows after it was written back to its file
+pub fn print_cache_size(cache: &GoalCache) {
+    println!(
+        "\nGoal cache {}: {} boards known, complete to depth {}",
+        cache.path().display(),
+        cache.known_boards(),
+        cache.completed_depth()
+    );
+}
+
+/// Length of a solution, followed by its cost when moves are charged by tile
+/// and its compressed length when that was measured
+#[must_use]
+pub fn moves_summary(limits: &StrategyConfig, stats: &Stats) -> String {
+    let cost = Some(stats.solution_cost)
+        .filter(|_| !limits.move_costs.is_unit())
+        .map(|cost| format!(" cost: {cost}"))
+        .unwrap_or_default();
+    let compressed = Some(stats.compressed_moves)
+        .filter(|&moves| moves > 0)
+        .map(|moves| format!(" compressed: {moves}"))
+        .unwrap_or_default();
+    format!("{}{cost}{compressed}", stats.solution_moves)
+}
+
+/// Nodes dropped by the active limits, as a suffix for a per-board result line
+#[must_use]
+pub fn pruned_summary(limits: &StrategyConfig, stats: &Stats) -> String {
+    let capacity = limits
+        .frontier_cap
+        .map(|_| format!(" capacity pruned: {}", stats.capacity_pruned))
+        .unwrap_or_default();
+    let depth = limits
+        .max_depth
+        .or(limits.max_cost)
+        .map(|_| format!(" depth pruned: {}", stats.depth_pruned))
+        .unwrap_or_default();
+    capacity + &depth
+}
+
+/// Explain why a board cannot reach the goal and which tile swap makes it solvable
+///
+/// The swap is only suggested for the spiral goal, whose distances it minimizes.
+#[must_use]
+pub fn describe_unsolvable(board: Board, goal: Board) -> String {
+    let error = SolveError::Unsolvable {
+        parity: board.parity(),
+    };
+    match board.solvable_swap().filter(|_| goal.is_solved()) {
+        Some(TileSwap {
+            tiles: (a, b),
+            board: swapped,
+        }) => format!(
+            "{error}; swapping tiles {a} and {b} makes it solvable: {}",
+            swapped.to_digit_string()
+        ),
+        None => error.to_string(),
+    }
+}
+
+/// Print the adversarial instances found for the strategy called `label`, hardest first
+pub fn print_adversarial_instances(
+    instances: &[AdversarialInstance],
+    label: &str,
+    objective: AdversarialObjective,
+) {
+    let unit = match objective {
+        AdversarialObjective::Nodes => "nodes explored",
+        AdversarialObjective::Time => "µs",
+    };
+
+    println!("\nTop {} adversarial boards for {label}\n", instances.len());
+    for (idx, instance) in instances.iter().enumerate() {
+        println!(
+            "#{} score: {} {} h(n): {} moves: {}",
+            idx + 1,
+            instance.score,
+            unit,
+            instance.board.heuristic_distance_to_solution(),
+            instance.stats.solution_moves
+        );
+        println!("{}\n", instance.board);
+    }
+}
+
+/// Print what the state-space database knows about a board
+pub fn print_lookup(board: Board) {
+    let database = StateSpaceDatabase::global();
+    println!("{board}");
+
+    let Some(distance) = database.distance(board) else {
+        println!("{}", describe_unsolvable(board, Board::default()));
+        return;
+    };
+    let difficulty = Difficulty::from_distance(distance);
+    println!("Exact distance: {distance} moves ({})", difficulty.name());
+    println!("Heuristic h(n): {}", board.heuristic_distance_to_solution());
+    match database.best_move(board) {
+        Some(direction) => println!("Best move: {}", direction.name()),
+        None => println!("Already solved."),
+    }
+}
+
+/// Print how many distinct states lie within each number of moves of a board
+#[allow(clippy::cast_precision_loss)]
+pub fn print_exploration(board: Board, exploration: &Exploration) {
+    println!("States reachable from {}\n", board.to_digit_string());
+    println!(
+        "{:>5} {:>8} {:>8} {:>9}",
+        "Moves", "New", "Within", "Branching"
+    );
+    for (moves, &new) in exploration.layers.iter().enumerate() {
+        let branching = exploration
+            .branching_factor(moves)
+            .map(|factor| format!("{factor:.3}"))
+            .unwrap_or_default();
+        println!(
+            "{moves:>5} {new:>8} {:>8} {branching:>9}",
+            exploration.reachable_within(moves)
+        );
+    }
+
+    let reachable = exploration.reachable();
+    let deepest = exploration.layers.len() - 1;
+    if exploration.truncated {
+        println!("\nStopped after {deepest} moves with {reachable} states (limit reached)");
+    } else {
+        println!(
+            "\nWhole component: {reachable} states, the farthest {deepest} moves away ({:.1}% of all arrangements)",
+            reachable as f64 * 100.0 / f64::from(Board::RANK_COUNT)
+        );
+    }
+}
+
+/// Print the distance distribution of the whole state space and God's number,
+/// followed by `examples` boards at that distance
+#[allow(clippy::cast_precision_loss)]
+pub fn print_space_analysis(database: &StateSpaceDatabase, examples: usize) {
+    let histogram = database.histogram();
+    let total: usize = histogram.iter().sum();
+    let weighted: usize = histogram.iter().enumerate().map(|(d, n)| d * n).sum();
+
+    println!("Optimal solution lengths over all {total} reachable boards\n");
+    println!("{:>5} {:>8} {:>8}", "Moves", "Boards", "Share");
+    for (distance, count) in histogram.iter().enumerate() {
+        println!(
+            "{distance:>5} {count:>8} {:>7.3}%",
+            *count as f64 * 100.0 / total as f64
+        );
+    }
+
+    let gods_number = database.max_distance();
+    println!(
+        "\nGod's number: {gods_number} moves (mean distance {:.2})",
+        weighted as f64 / total as f64
+    );
+
+    println!(
+        "\nExample boards at distance {gods_number} ({} in total):\n",
+        histogram[usize::from(gods_number)]
+    );
+    for board in database.boards_at(gods_number).take(examples) {
+        println!("{}\n{board}", board.to_digit_string());
+    }
+}
+
+/// Print how much the explored sets of the strategies labelled `first` and
+/// `second` overlap on one board
+pub fn print_overlap(board: Board, (first, second): (&str, &str), overlap: &Overlap) {
+    println!("{board}");
+    match StateSpaceDatabase::global().distance(board) {
+        Some(distance) => println!("Exact distance: {distance} moves\n"),
+        None => println!("This board cannot reach the goal.\n"),
+    }
+
+    println!("{:<10} {:>8} states explored", first, overlap.first);
+    println!("{:<10} {:>8} states explored", second, overlap.second);
+    println!(
+        "{:<10} {:>8} states ({:.1}% of {first}, {:.1}% of {second})",
+        "Shared",
+        overlap.shared,
+        overlap.first_covered() * 100.0,
+        overlap.second_covered() * 100.0
+    );
+    println!("{:<10} {:>8} states", "Union", overlap.union());
+    println!("\nJaccard similarity: {:.3}", overlap.jaccard());
+}
+
+/// Print every registered strategy with its name and description
+pub fn print_algorithms() {
+    let strategies = registry::strategies();
+    let width = strategies
+        .iter()
+        .map(|entry| entry.name.len())
+        .max()
+        .unwrap_or(0);
+    for entry in strategies {
+        println!("{:width$}  {}", entry.name, entry.description);
+    }
+}
+
+/// Print a board and its translation from one goal convention to another
+pub fn print_translation(
+    (board, from): (Board, GoalConvention),
+    (translated, to): (Board, GoalConvention),
+) {
+    println!("{from:>11}: {}", board.to_digit_string());
+    println!("{to:>11}: {}", translated.to_digit_string());
+}
+
+/// Print every check of a self-test and how many passed
+pub fn print_doctor_report(report: &DoctorReport) {
+    for check in &report.checks {
+        println!("{check}");
+    }
+
+    println!(
+        "\n{} of {} checks passed",
+        report.checks.len() - report.failures(),
+        report.checks.len()
+    );
+}
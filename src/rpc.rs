@@ -0,0 +1,487 @@
+//! # RPC Module
+//!
+//! A small JSON-RPC 2.0 protocol spoken over a line-oriented stream, used by
+//! `o8 rpc` so editors, bots and other processes can keep one warm solver
+//! process around instead of spawning the CLI for every request.
+//!
+//! Each line on the input is one request object and each response is written
+//! as one line on the output. With [`OutputFormat::Msgpack`], requests and
+//! responses are instead a plain sequence of `MessagePack` maps. Requests
+//! without an `id` are notifications and get no response. Boards are
+//! exchanged in digit form (`"123804765"`, see [`Board::to_digit_string`]).
+//!
+//! | Method     | Params                                   | Result                                   |
+//! |------------|------------------------------------------|------------------------------------------|
+//! | `solve`    | `{ board, algorithm? }`                  | a [`Solution`] document                  |
+//! | `hint`     | `{ board, budget_ms? }`                  | `{ move, board, distance, optimal }`     |
+//! | `distance` | `{ board }`                              | `{ moves, heuristic }`                   |
+//! | `generate` | `{ scramble_steps?, count?, seed? }`     | `{ boards }`                             |
+//!
+//! `algorithm` names any strategy in the [`registry`](crate::registry): `"dfs"`, `"bfs"`,
+//! `"heuristic"` (the default), `"human"`, or one registered by the embedding program.
+//! `hint` and `distance` always use the heuristic search. With `budget_ms`,
+//! `hint` instead runs the anytime search of [`hint_within`] for at most that
+//! many milliseconds; `optimal` tells whether the move is provably on a
+//! shortest solution, and `distance` is `null` if no solution was found in time.
+//!
+//! On streaming transports (see the `websocket` module), `solve` also accepts
+//! `progress`: the number of explored nodes between two `progress`
+//! notifications sent before the response, each carrying the request `id`
+//! and a snapshot of the search. Line-oriented streams ignore it.
+//!
+//! ```text
+//! -> {"jsonrpc":"2.0","id":1,"method":"hint","params":{"board":"123840765"}}
+//! <- {"jsonrpc":"2.0","id":1,"result":{"move":"left","board":"123804765","distance":1,"optimal":true}}
+//! ```
+//!
+//! Requires the `serde` feature.
+
+use std::io::{self, BufRead, Write};
+use std::sync::Arc;
+use std::time::Duration;
+
+use rand::{Rng, rng};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::board::{Board, BoardWithSteps, Direction};
+use crate::hint::hint_within;
+use crate::registry::{self, StrategyConfig};
+use crate::schema::{OutputFormat, Solution};
+use crate::search_strategies::{HeuristicSearchStrategy, SearchStrategy};
+use crate::solver::{ProgressObserver, SearchProgress, SolveError, Solver};
+use crate::stats::Stats;
+
+/// Version string required by the JSON-RPC 2.0 specification
+const JSONRPC_VERSION: &str = "2.0";
+
+/// Default number of scramble steps for `generate`
+const DEFAULT_SCRAMBLE_STEPS: usize = 200;
+
+/// Invalid JSON was received
+pub const PARSE_ERROR: i64 = -32700;
+/// The JSON sent is not a valid request object
+pub const INVALID_REQUEST: i64 = -32600;
+/// The method does not exist
+pub const METHOD_NOT_FOUND: i64 = -32601;
+/// Invalid method parameters
+pub const INVALID_PARAMS: i64 = -32602;
+/// Internal error while producing the result
+pub const INTERNAL_ERROR: i64 = -32603;
+/// The board has no solution
+pub const NO_SOLUTION: i64 = -32000;
+
+/// A single JSON-RPC request
+#[derive(Debug, Deserialize)]
+struct Request {
+    /// Request id, absent for notifications
+    #[serde(default)]
+    id: Option<Value>,
+    /// Name of the method to invoke
+    method: String,
+    /// Method parameters
+    #[serde(default)]
+    params: Value,
+}
+
+/// A single JSON-RPC response
+#[derive(Debug, Serialize)]
+struct Response {
+    jsonrpc: &'static str,
+    id: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<RpcError>,
+}
+
+/// Sink for notifications sent while a request is running
+pub(crate) type Notifier = Arc<dyn Fn(String) + Send + Sync>;
+
+/// A `progress` notification sent while a `solve` request is running
+#[derive(Debug, Serialize)]
+struct ProgressNotification<'a> {
+    jsonrpc: &'static str,
+    method: &'static str,
+    params: ProgressParams<'a>,
+}
+
+/// Parameters of a `progress` notification
+#[derive(Debug, Serialize)]
+struct ProgressParams<'a> {
+    /// Id of the request being solved
+    id: &'a Value,
+    nodes_explored: usize,
+    frontier_size: usize,
+    max_depth_reached: usize,
+    elapsed_ms: u128,
+}
+
+/// Where a running request reports its progress
+#[derive(Clone)]
+struct ProgressSink {
+    /// Id of the request, echoed in every notification
+    id: Value,
+    /// Destination of the serialized notifications
+    notifier: Notifier,
+}
+
+impl ProgressSink {
+    /// Turns the sink into a solver observer that sends `progress` notifications
+    fn into_observer(self) -> ProgressObserver {
+        Arc::new(move |progress: &SearchProgress| {
+            let notification = ProgressNotification {
+                jsonrpc: JSONRPC_VERSION,
+                method: "progress",
+                params: ProgressParams {
+                    id: &self.id,
+                    nodes_explored: progress.nodes_explored,
+                    frontier_size: progress.frontier_size,
+                    max_depth_reached: progress.max_depth_reached,
+                    elapsed_ms: progress.elapsed.as_millis(),
+                },
+            };
+            if let Ok(text) = serde_json::to_string(&notification) {
+                (self.notifier)(text);
+            }
+        })
+    }
+}
+
+/// Error object returned to the client
+#[derive(Clone, Debug, Serialize)]
+pub struct RpcError {
+    /// JSON-RPC error code (see the constants in this module)
+    pub code: i64,
+    /// Human-readable description of the error
+    pub message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+/// Parameters of `solve`
+#[derive(Debug, Deserialize)]
+struct SolveParams {
+    board: String,
+    #[serde(default)]
+    algorithm: Option<String>,
+    /// Explored nodes between two `progress` notifications, if streaming
+    #[serde(default)]
+    progress: Option<usize>,
+}
+
+/// Parameters of `distance`
+#[derive(Debug, Deserialize)]
+struct BoardParams {
+    board: String,
+}
+
+/// Parameters of `hint`
+#[derive(Debug, Deserialize)]
+struct HintParams {
+    board: String,
+    #[serde(default)]
+    budget_ms: Option<u64>,
+}
+
+/// Parameters of `generate`
+#[derive(Debug, Deserialize)]
+struct GenerateParams {
+    #[serde(default = "default_scramble_steps")]
+    scramble_steps: usize,
+    #[serde(default = "default_count")]
+    count: usize,
+    #[serde(default)]
+    seed: Option<u64>,
+}
+
+/// Result of `hint`
+#[derive(Debug, Serialize)]
+struct Hint {
+    /// Next empty-space move, or `None` if the board is already solved
+    #[serde(rename = "move")]
+    direction: Option<&'static str>,
+    /// Board after applying the move
+    board: String,
+    /// Moves left to the goal before applying the hint, if a solution was found
+    distance: Option<usize>,
+    /// Whether the move is provably on a shortest solution
+    optimal: bool,
+}
+
+/// Result of `distance`
+#[derive(Debug, Serialize)]
+struct Distance {
+    /// Length of the solution found by the heuristic search
+    moves: usize,
+    /// Manhattan distance estimate of the board
+    heuristic: u8,
+}
+
+/// Result of `generate`
+#[derive(Debug, Serialize)]
+struct Generated {
+    boards: Vec<String>,
+}
+
+fn default_scramble_steps() -> usize {
+    DEFAULT_SCRAMBLE_STEPS
+}
+
+fn default_count() -> usize {
+    1
+}
+
+/// Serves requests read from `input` until it is exhausted
+///
+/// With [`OutputFormat::Json`], requests and responses are newline-delimited
+/// JSON. With [`OutputFormat::Msgpack`], both directions are a plain sequence
+/// of `MessagePack` maps with the same fields.
+///
+/// # Arguments
+///
+/// * `input` - Source of requests, e.g. locked stdin
+/// * `output` - Destination of responses, e.g. locked stdout
+/// * `format` - Encoding of requests and responses
+///
+/// # Errors
+///
+/// Returns any I/O error raised while reading requests or writing responses.
+/// Malformed requests are answered with a JSON-RPC error instead.
+pub fn serve(input: impl BufRead, output: impl Write, format: OutputFormat) -> io::Result<()> {
+    match format {
+        OutputFormat::Json => serve_json(input, output),
+        OutputFormat::Msgpack => serve_msgpack(input, output),
+    }
+}
+
+/// Serves newline-delimited JSON requests
+fn serve_json(input: impl BufRead, mut output: impl Write) -> io::Result<()> {
+    for line in input.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        if let Some(response) = handle_line(&line, None) {
+            serde_json::to_writer(&mut output, &response)?;
+            output.write_all(b"\n")?;
+            output.flush()?;
+        }
+    }
+    Ok(())
+}
+
+/// Serves a stream of `MessagePack` requests
+///
+/// A malformed request leaves the stream at an unknown position, so it is
+/// answered with a parse error and ends the session.
+fn serve_msgpack(mut input: impl BufRead, mut output: impl Write) -> io::Result<()> {
+    loop {
+        let response = match rmp_serde::from_read::<_, Request>(&mut input) {
+            Ok(request) => handle_request(request, None),
+            Err(rmp_serde::decode::Error::InvalidMarkerRead(e))
+                if e.kind() == io::ErrorKind::UnexpectedEof =>
+            {
+                return Ok(());
+            }
+            Err(e) => {
+                let response =
+                    error_response(Value::Null, RpcError::new(PARSE_ERROR, e.to_string()));
+                rmp_serde::encode::write_named(&mut output, &response).map_err(io::Error::other)?;
+                return output.flush();
+            }
+        };
+
+        if let Some(response) = response {
+            rmp_serde::encode::write_named(&mut output, &response).map_err(io::Error::other)?;
+            output.flush()?;
+        }
+    }
+}
+
+/// Handles one JSON request on a streaming transport
+///
+/// Notifications raised while the request runs (see the module
+/// documentation) are passed to `notifier` as serialized JSON.
+///
+/// # Returns
+///
+/// The serialized response to send, or `None` for notifications
+#[cfg(feature = "websocket")]
+pub(crate) fn handle_text(text: &str, notifier: Notifier) -> Option<String> {
+    let response = handle_line(text, Some(notifier))?;
+    serde_json::to_string(&response).ok()
+}
+
+/// Handles one request line, returning the response to send, if any
+fn handle_line(line: &str, notifier: Option<Notifier>) -> Option<Response> {
+    let raw: Value = match serde_json::from_str(line) {
+        Ok(raw) => raw,
+        Err(e) => {
+            return Some(error_response(
+                Value::Null,
+                RpcError::new(PARSE_ERROR, e.to_string()),
+            ));
+        }
+    };
+    match serde_json::from_value(raw) {
+        Ok(request) => handle_request(request, notifier),
+        Err(e) => Some(error_response(
+            Value::Null,
+            RpcError::new(INVALID_REQUEST, e.to_string()),
+        )),
+    }
+}
+
+/// Runs a parsed request, returning the response to send, if any
+fn handle_request(request: Request, notifier: Option<Notifier>) -> Option<Response> {
+    let sink = request
+        .id
+        .clone()
+        .zip(notifier)
+        .map(|(id, notifier)| ProgressSink { id, notifier });
+    let outcome = dispatch(&request.method, request.params, sink);
+    let id = request.id?;
+    Some(match outcome {
+        Ok(result) => Response {
+            jsonrpc: JSONRPC_VERSION,
+            id,
+            result: Some(result),
+            error: None,
+        },
+        Err(error) => error_response(id, error),
+    })
+}
+
+fn error_response(id: Value, error: RpcError) -> Response {
+    Response {
+        jsonrpc: JSONRPC_VERSION,
+        id,
+        result: None,
+        error: Some(error),
+    }
+}
+
+/// Runs a method and serializes its result
+fn dispatch(method: &str, params: Value, sink: Option<ProgressSink>) -> Result<Value, RpcError> {
+    let result = match method {
+        "solve" => serde_json::to_value(solve(&parse_params(params)?, sink)?),
+        "hint" => serde_json::to_value(hint(&parse_params(params)?)?),
+        "distance" => serde_json::to_value(distance(&parse_params(params)?)?),
+        "generate" => serde_json::to_value(generate(&parse_params(params)?)),
+        _ => {
+            return Err(RpcError::new(
+                METHOD_NOT_FOUND,
+                format!("Unknown method: {method}"),
+            ));
+        }
+    };
+    result.map_err(|e| RpcError::new(INTERNAL_ERROR, e.to_string()))
+}
+
+fn parse_params<T: DeserializeOwned>(params: Value) -> Result<T, RpcError> {
+    serde_json::from_value(params).map_err(|e| RpcError::new(INVALID_PARAMS, e.to_string()))
+}
+
+fn parse_board(board: &str) -> Result<Board, RpcError> {
+    board
+        .parse()
+        .map_err(|e: &'static str| RpcError::new(INVALID_PARAMS, e))
+}
+
+fn solve(params: &SolveParams, sink: Option<ProgressSink>) -> Result<Solution, RpcError> {
+    let board = parse_board(&params.board)?;
+    let algorithm = params.algorithm.as_deref().unwrap_or("heuristic");
+    let observer = params.progress.zip(sink.map(ProgressSink::into_observer));
+
+    let entry = registry::lookup(algorithm).ok_or_else(|| {
+        RpcError::new(
+            INVALID_PARAMS,
+            format!("Unknown algorithm: expected {}", registry::expected_names()),
+        )
+    })?;
+    let config = StrategyConfig {
+        progress: observer,
+        ..StrategyConfig::default()
+    };
+    let mut solver = entry.build(&config);
+    solver
+        .try_solve(board)
+        .map_err(|e| RpcError::new(NO_SOLUTION, e.to_string()))?;
+    let (path, stats) = (solver.step_by_step_solution(), solver.get_solution_stats());
+
+    Ok(Solution::new(entry.name, &path, stats))
+}
+
+fn hint(params: &HintParams) -> Result<Hint, RpcError> {
+    let board = parse_board(&params.board)?;
+    if !board.is_solvable() {
+        let error = SolveError::Unsolvable {
+            parity: board.parity(),
+        };
+        return Err(RpcError::new(NO_SOLUTION, error.to_string()));
+    }
+
+    if let Some(budget_ms) = params.budget_ms {
+        let hint = hint_within(board, Duration::from_millis(budget_ms))
+            .ok_or_else(|| RpcError::new(NO_SOLUTION, "No solution found"))?;
+        return Ok(Hint {
+            direction: hint.direction.map(Direction::name),
+            board: hint.board.to_digit_string(),
+            distance: hint.moves_left,
+            optimal: hint.optimal,
+        });
+    }
+
+    let (path, _) = solve_with(board, Solver::new(HeuristicSearchStrategy::default()))?;
+    let next = path.get(1).copied().unwrap_or(board);
+
+    Ok(Hint {
+        direction: board.direction_to(next).map(Direction::name),
+        board: next.to_digit_string(),
+        distance: Some(path.len().saturating_sub(1)),
+        optimal: true,
+    })
+}
+
+fn distance(params: &BoardParams) -> Result<Distance, RpcError> {
+    let board = parse_board(&params.board)?;
+    let (path, _) = solve_with(board, Solver::new(HeuristicSearchStrategy::default()))?;
+
+    Ok(Distance {
+        moves: path.len().saturating_sub(1),
+        heuristic: board.heuristic_distance_to_solution(),
+    })
+}
+
+fn generate(params: &GenerateParams) -> Generated {
+    let seed = params.seed.unwrap_or_else(|| rng().random());
+
+    Generated {
+        boards: (0..params.count)
+            .map(|index| {
+                Board::random_in_set(seed, index as u64, params.scramble_steps).to_digit_string()
+            })
+            .collect(),
+    }
+}
+
+/// Solves a board, returning the solution path and statistics
+fn solve_with<T>(board: Board, mut solver: Solver<T>) -> Result<(Vec<Board>, Stats), RpcError>
+where
+    T: SearchStrategy<BoardWithSteps> + Default + Clone,
+{
+    solver
+        .try_solve(board)
+        .map_err(|e| RpcError::new(NO_SOLUTION, e.to_string()))?;
+    Ok((solver.step_by_step_solution(), solver.get_solution_stats()))
+}
@@ -0,0 +1,325 @@
+//! # Schema Module
+//!
+//! Stable, versioned JSON shapes for the data this crate emits: a single
+//! [`Solution`] and a whole [`BenchmarkResult`]. Both carry a `schema_version`
+//! field so downstream tooling can tell which shape it is reading. Benchmark
+//! results list every individual run as a [`RunRecord`].
+//!
+//! ## Compatibility rules
+//!
+//! - Adding a field (e.g. a new metric) does **not** bump the version. Readers
+//!   ignore unknown fields, and missing fields fall back to their defaults, so
+//!   old files load with new code and new files load with old code.
+//! - Renaming, removing or changing the meaning of a field bumps
+//!   [`SCHEMA_VERSION`]. Readers reject documents newer than they understand
+//!   with [`SchemaError::UnsupportedVersion`] instead of misreading them.
+//! - Documents without a `schema_version` predate versioning and are read as
+//!   version 0, which has the same shape as version 1.
+//!
+//! ## Encodings
+//!
+//! Documents are written as JSON by default. [`OutputFormat::Msgpack`] writes
+//! the same shapes as `MessagePack` (with named fields), which is much more
+//! compact when streaming large numbers of records.
+//!
+//! Requires the `serde` feature.
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
+use std::io::{self, Read, Write};
+
+#[cfg(feature = "cli")]
+use clap::ValueEnum;
+use rand::{Rng, rng};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::board::Board;
+use crate::boardset::BoardSet;
+use crate::export::{Manifest, board_set_hash};
+use crate::stats::{Stats, StatsSummary};
+
+/// Current version of the JSON shapes defined in this module
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Encoding used when writing documents
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(ValueEnum))]
+pub enum OutputFormat {
+    /// Human-readable, pretty-printed JSON
+    #[default]
+    Json,
+    /// Compact binary `MessagePack` with named fields
+    Msgpack,
+}
+
+/// A solved board: the path from start to goal and the run statistics
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Solution {
+    /// Version of this shape (see [`SCHEMA_VERSION`])
+    #[serde(default)]
+    pub schema_version: u32,
+    /// Name of the algorithm that produced the solution
+    pub algorithm: String,
+    /// The starting board in digit form
+    pub start: String,
+    /// Empty-space moves from start to goal (`"up"`, `"down"`, `"left"`, `"right"`)
+    pub moves: Vec<String>,
+    /// Every board on the path in digit form, start first
+    pub boards: Vec<String>,
+    /// Statistics of the search that found the solution
+    pub stats: Stats,
+}
+
+impl Solution {
+    /// Builds a solution document from a solution path
+    ///
+    /// # Arguments
+    ///
+    /// * `algorithm` - Name of the algorithm that produced the path
+    /// * `path` - Boards from start to goal, as returned by `Solver::step_by_step_solution`
+    /// * `stats` - Statistics of the search
+    #[must_use]
+    pub fn new(algorithm: &str, path: &[Board], stats: Stats) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            algorithm: algorithm.to_owned(),
+            start: path.first().copied().unwrap_or_default().to_digit_string(),
+            moves: path
+                .windows(2)
+                .filter_map(|pair| pair[0].direction_to(pair[1]))
+                .map(|d| d.name().to_owned())
+                .collect(),
+            boards: path.iter().map(|b| b.to_digit_string()).collect(),
+            stats,
+        }
+    }
+}
+
+/// Benchmark output: reproducibility manifest plus per-strategy summaries
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BenchmarkResult {
+    /// Version of this shape (see [`SCHEMA_VERSION`])
+    #[serde(default)]
+    pub schema_version: u32,
+    /// Reproducibility information for the run
+    pub manifest: Manifest,
+    /// The board set every strategy was run on, in digit form and in order
+    /// (empty in exports that predate it)
+    #[serde(default)]
+    pub boards: Vec<String>,
+    /// Aggregated statistics keyed by strategy name
+    pub results: BTreeMap<String, StatsSummary>,
+    /// Every individual run, grouped by strategy in display order (empty in
+    /// exports that predate it)
+    #[serde(default)]
+    pub runs: Vec<RunRecord>,
+}
+
+/// One strategy run on one board, with everything needed to replay it
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RunRecord {
+    /// Random UUID (version 4) identifying this run
+    pub run_id: String,
+    /// Name of the strategy
+    pub strategy: String,
+    /// The board in digit form, e.g. for `o8 lookup` or a board set file
+    pub board: String,
+    /// Dense rank of the board (see `Board::rank`)
+    pub rank: u32,
+    /// Position of the board in the benchmark's board list
+    pub index: usize,
+    /// Seed the board was generated from (see `Board::random_in_set`), if known
+    pub seed: Option<u64>,
+    /// Statistics of the run
+    pub stats: Stats,
+}
+
+impl Default for RunRecord {
+    fn default() -> Self {
+        Self {
+            run_id: String::new(),
+            strategy: String::new(),
+            board: Board::default().to_digit_string(),
+            rank: Board::default().rank(),
+            index: 0,
+            seed: None,
+            stats: Stats::default(),
+        }
+    }
+}
+
+impl RunRecord {
+    /// Records a run under a fresh run id
+    ///
+    /// # Arguments
+    ///
+    /// * `strategy` - Name of the strategy that ran
+    /// * `board` - The board it solved
+    /// * `index` - Position of the board in the benchmark's board list
+    /// * `seed` - Seed the board was generated from, if known
+    /// * `stats` - Statistics of the run
+    #[must_use]
+    pub fn new(
+        strategy: &str,
+        board: Board,
+        index: usize,
+        seed: Option<u64>,
+        stats: Stats,
+    ) -> Self {
+        Self {
+            run_id: new_run_id(),
+            strategy: strategy.to_owned(),
+            board: board.to_digit_string(),
+            rank: board.rank(),
+            index,
+            seed,
+            stats,
+        }
+    }
+}
+
+/// Formats 128 random bits as a version 4 UUID
+fn new_run_id() -> String {
+    let mut bytes: [u8; 16] = rng().random();
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+
+    let hex = format!("{:032x}", u128::from_be_bytes(bytes));
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
+impl BenchmarkResult {
+    /// The board set of the run, with the manifest's seed
+    ///
+    /// The boards are checked against the manifest's `board_set_hash`, so a
+    /// hand-edited or truncated list is rejected rather than silently replayed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a board is malformed, the list is missing or it
+    /// does not match the manifest's fingerprint
+    pub fn board_set(&self) -> Result<BoardSet, &'static str> {
+        if self.boards.is_empty() {
+            return Err("The export does not record its board set");
+        }
+        let boards = self
+            .boards
+            .iter()
+            .map(|digits| digits.parse())
+            .collect::<Result<Vec<Board>, _>>()?;
+        if format!("{:016x}", board_set_hash(&boards)) != self.manifest.board_set_hash {
+            return Err("The board set does not match the manifest's board_set_hash");
+        }
+        Ok(BoardSet::from_boards(&boards, Some(self.manifest.seed)))
+    }
+}
+
+/// Errors raised while reading a versioned document
+#[derive(Debug)]
+pub enum SchemaError {
+    /// The document could not be read
+    Io(io::Error),
+    /// The document is not valid JSON or does not match the expected shape
+    Json(serde_json::Error),
+    /// The document was written by a newer, incompatible version of the schema
+    UnsupportedVersion {
+        /// Version found in the document
+        found: u32,
+        /// Newest version this build understands
+        supported: u32,
+    },
+}
+
+impl Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaError::Io(e) => write!(f, "failed to read document: {e}"),
+            SchemaError::Json(e) => write!(f, "invalid document: {e}"),
+            SchemaError::UnsupportedVersion { found, supported } => write!(
+                f,
+                "unsupported schema version {found} (this build supports up to {supported})"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SchemaError {}
+
+impl From<io::Error> for SchemaError {
+    fn from(value: io::Error) -> Self {
+        SchemaError::Io(value)
+    }
+}
+
+impl From<serde_json::Error> for SchemaError {
+    fn from(value: serde_json::Error) -> Self {
+        SchemaError::Json(value)
+    }
+}
+
+/// Minimal view of a document used to check its version before full parsing
+#[derive(Deserialize)]
+struct VersionProbe {
+    #[serde(default)]
+    schema_version: u32,
+}
+
+/// Reads a versioned document, rejecting versions newer than [`SCHEMA_VERSION`]
+///
+/// # Errors
+///
+/// Returns an error if the document cannot be read, is not valid JSON for `T`,
+/// or declares an unsupported schema version
+pub fn read_versioned<T: DeserializeOwned>(mut reader: impl Read) -> Result<T, SchemaError> {
+    let mut raw = Vec::new();
+    reader.read_to_end(&mut raw)?;
+
+    // Check the version first so newer shapes fail with a clear message
+    // rather than an obscure missing-field error
+    let probe: VersionProbe = serde_json::from_slice(&raw)?;
+    if probe.schema_version > SCHEMA_VERSION {
+        return Err(SchemaError::UnsupportedVersion {
+            found: probe.schema_version,
+            supported: SCHEMA_VERSION,
+        });
+    }
+
+    Ok(serde_json::from_slice(&raw)?)
+}
+
+/// Writes a document in the requested encoding
+///
+/// # Arguments
+///
+/// * `writer` - Destination of the encoded document
+/// * `format` - Encoding to use
+/// * `document` - The document to write
+///
+/// # Errors
+///
+/// Returns any I/O or serialization error raised while writing
+pub fn write_document<T: Serialize>(
+    mut writer: impl Write,
+    format: OutputFormat,
+    document: &T,
+) -> io::Result<()> {
+    match format {
+        OutputFormat::Json => {
+            serde_json::to_writer_pretty(&mut writer, document)?;
+            writer.write_all(b"\n")?;
+        }
+        OutputFormat::Msgpack => {
+            rmp_serde::encode::write_named(&mut writer, document).map_err(io::Error::other)?;
+        }
+    }
+    writer.flush()
+}
@@ -3,23 +3,52 @@
 //! This module defines a small strategy trait and a couple of concrete queue
 //! types to drive the solver:
 //!
-//! - `SimpleSearchStrategy` implements FIFO (BFS) or LIFO (DFS) behavior using
-//!   a `LinkedList`, depending on the configured `ExplorerStrategy`.
+//! - `SimpleSearchStrategy` implements FIFO (BFS) behavior on a `VecDeque`,
+//!   LIFO (DFS) behavior on a `Vec`, or uniform-cost (UCS) behavior on a
+//!   `BTreeMap` ordered by path cost, depending on the configured
+//!   `ExplorerStrategy`.
 //! - `HeuristicSearchStrategy` implements a best-first priority queue using a
 //!   `BinaryHeap`, suitable for A*-like expansions when paired with a type that
 //!   implements `Ord` based on f(n) = g(n)+h(n). In this project we use
 //!   `Reverse<BoardWithSteps>` so that lower cost pops first.
+//! - `MinMaxQueue` implements a double-ended priority queue on a `BTreeSet`:
+//!   the smallest node pops first, and the largest can be dropped just as
+//!   cheaply, which memory-bounded searches such as SMA* need.
+//! - `FocalSearchStrategy` implements focal search (A*ε): among the nodes whose
+//!   f-value is within a factor of the lowest, the one with the lowest h pops
+//!   first, which bounds the suboptimality of the solution.
 //!
 //! The solver is generic over `SearchStrategy<T>`, so new frontier policies can
 //! be plugged in easily.
+//!
+//! Frontiers can also be pruned down to a size with a [`PruningPolicy`], which
+//! the solver uses to bound memory (see `Solver::with_frontier_cap`).
 use std::{
     cmp::Reverse,
-    collections::{BinaryHeap, VecDeque},
+    collections::{BTreeMap, BTreeSet, BinaryHeap, VecDeque},
 };
 
+#[cfg(feature = "cli")]
+use clap::ValueEnum;
+
+use crate::board::BoardWithSteps;
 use crate::solver::ExplorerStrategy;
 
+/// Which nodes to drop when a frontier is pruned to a smaller size.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(ValueEnum))]
+pub enum PruningPolicy {
+    /// Drop the nodes that order last (highest f = g + h), as in beam search
+    #[default]
+    WorstF,
+    /// Drop the nodes that entered the frontier first
+    Oldest,
+}
+
 /// Minimal frontier abstraction used by the solver.
+///
+/// Only `get_next`, `enqueue` and `len` are required; the other methods have
+/// default implementations built on them.
 pub trait SearchStrategy<T> {
     /// Pop the next node to expand according to the policy.
     fn get_next(&mut self) -> Option<T>;
@@ -27,56 +56,589 @@ pub trait SearchStrategy<T> {
     fn enqueue(&mut self, node: T);
     /// Current frontier size.
     fn len(&self) -> usize;
+
+    /// Whether the frontier holds no nodes.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The node `get_next` would return, without removing it.
+    ///
+    /// The default implementation cannot inspect the frontier and returns
+    /// `None`; strategies that can should override it.
+    fn peek(&self) -> Option<&T> {
+        None
+    }
+
+    /// Every node in the frontier, in an order that rebuilds it when enqueued again.
+    ///
+    /// Used to checkpoint a search. The default implementation cannot inspect
+    /// the frontier and returns `None`, so searches using it cannot be checkpointed.
+    fn snapshot(&self) -> Option<Vec<T>>
+    where
+        T: Clone,
+    {
+        None
+    }
+
+    /// Remove every node from the frontier.
+    ///
+    /// The default implementation pops nodes one by one; strategies should
+    /// override it to keep their allocated capacity in a single call.
+    fn clear(&mut self) {
+        while self.get_next().is_some() {}
+    }
+
+    /// Remove nodes chosen by `policy` until at most `len` remain.
+    ///
+    /// Returns the removed nodes. The default implementation cannot choose
+    /// nodes and removes nothing, so the frontier stays unbounded.
+    fn prune(&mut self, len: usize, policy: PruningPolicy) -> Vec<T>
+    where
+        T: Ord,
+    {
+        let _ = (len, policy);
+        Vec::new()
+    }
+}
+
+/// Cost of the path that reached a frontier node, g(n).
+///
+/// Uniform-cost search expands nodes in increasing order of it.
+pub trait PathCost {
+    /// Cost of the moves from the start to this node.
+    fn path_cost(&self) -> usize;
+}
+
+impl PathCost for BoardWithSteps {
+    fn path_cost(&self) -> usize {
+        self.1
+    }
 }
 
-/// A simple FIFO or LIFO queue based on `VecDeque`.
-#[derive(Default, Clone)]
-pub struct SimpleSearchStrategy<T> {
-    nodes: VecDeque<T>,
-    strategy: ExplorerStrategy,
+/// Splits off the `count` largest nodes, keeping the others in their order.
+///
+/// Returns the kept nodes and the removed ones. Runs in linear time on average.
+fn split_largest<T: Ord>(nodes: Vec<T>, count: usize) -> (Vec<T>, Vec<T>) {
+    if count == 0 {
+        return (nodes, Vec::new());
+    }
+    if count >= nodes.len() {
+        return (Vec::new(), nodes);
+    }
+
+    let mut order: Vec<usize> = (0..nodes.len()).collect();
+    order.select_nth_unstable_by(count - 1, |&a, &b| nodes[b].cmp(&nodes[a]));
+    let mut removed = vec![false; nodes.len()];
+    for &index in &order[..count] {
+        removed[index] = true;
+    }
+
+    let mut kept = Vec::with_capacity(nodes.len() - count);
+    let mut dropped = Vec::with_capacity(count);
+    for (node, removed) in nodes.into_iter().zip(removed) {
+        if removed {
+            dropped.push(node);
+        } else {
+            kept.push(node);
+        }
+    }
+    (kept, dropped)
+}
+
+/// A simple FIFO queue (`VecDeque`), LIFO stack (`Vec`) or path-cost queue (`BTreeMap`).
+#[derive(Clone)]
+pub enum SimpleSearchStrategy<T> {
+    /// Breadth-first: nodes are expanded in insertion order
+    Fifo(VecDeque<T>),
+    /// Depth-first: the most recently inserted node is expanded first
+    Lifo(Vec<T>),
+    /// Uniform-cost: the node with the cheapest path is expanded first, ties
+    /// in insertion order, so with unit move costs it expands like `Fifo`
+    Cost {
+        /// Nodes keyed by path cost and insertion number
+        nodes: BTreeMap<(usize, u64), T>,
+        /// Insertion number of the next node
+        next_serial: u64,
+    },
+}
+
+impl<T> Default for SimpleSearchStrategy<T> {
+    fn default() -> Self {
+        Self::new(ExplorerStrategy::default())
+    }
 }
 
 impl<T> SimpleSearchStrategy<T> {
     /// Create a new simple search strategy with the given algorithm.
+    #[must_use]
     pub fn new(algorithm: ExplorerStrategy) -> Self {
-        Self {
-            nodes: VecDeque::default(),
-            strategy: algorithm,
+        match algorithm {
+            ExplorerStrategy::Bfs => Self::Fifo(VecDeque::new()),
+            ExplorerStrategy::Dfs => Self::Lifo(Vec::new()),
+            ExplorerStrategy::Ucs => Self::Cost {
+                nodes: BTreeMap::new(),
+                next_serial: 0,
+            },
         }
     }
 }
 
-impl<T> SearchStrategy<T> for SimpleSearchStrategy<T> {
+impl<T: PathCost> SearchStrategy<T> for SimpleSearchStrategy<T> {
     fn get_next(&mut self) -> Option<T> {
-        match self.strategy {
-            ExplorerStrategy::Bfs => self.nodes.pop_front(),
-            ExplorerStrategy::Dfs => self.nodes.pop_back(),
+        match self {
+            Self::Fifo(nodes) => nodes.pop_front(),
+            Self::Lifo(nodes) => nodes.pop(),
+            Self::Cost { nodes, .. } => nodes.pop_first().map(|(_, node)| node),
         }
     }
 
     fn enqueue(&mut self, node: T) {
-        self.nodes.push_back(node);
+        match self {
+            Self::Fifo(nodes) => nodes.push_back(node),
+            Self::Lifo(nodes) => nodes.push(node),
+            Self::Cost { nodes, next_serial } => {
+                nodes.insert((node.path_cost(), *next_serial), node);
+                *next_serial += 1;
+            }
+        }
     }
 
     fn len(&self) -> usize {
-        self.nodes.len()
+        match self {
+            Self::Fifo(nodes) => nodes.len(),
+            Self::Lifo(nodes) => nodes.len(),
+            Self::Cost { nodes, .. } => nodes.len(),
+        }
+    }
+
+    fn peek(&self) -> Option<&T> {
+        match self {
+            Self::Fifo(nodes) => nodes.front(),
+            Self::Lifo(nodes) => nodes.last(),
+            Self::Cost { nodes, .. } => nodes.values().next(),
+        }
+    }
+
+    /// Nodes in insertion order, so equal-cost nodes keep their order after a restore
+    fn snapshot(&self) -> Option<Vec<T>>
+    where
+        T: Clone,
+    {
+        Some(match self {
+            Self::Fifo(nodes) => nodes.iter().cloned().collect(),
+            Self::Lifo(nodes) => nodes.clone(),
+            Self::Cost { nodes, .. } => {
+                let mut stamped: Vec<(&(usize, u64), &T)> = nodes.iter().collect();
+                stamped.sort_unstable_by_key(|((_, serial), _)| *serial);
+                stamped.into_iter().map(|(_, node)| node.clone()).collect()
+            }
+        })
+    }
+
+    fn clear(&mut self) {
+        match self {
+            Self::Fifo(nodes) => nodes.clear(),
+            Self::Lifo(nodes) => nodes.clear(),
+            Self::Cost { nodes, next_serial } => {
+                nodes.clear();
+                *next_serial = 0;
+            }
+        }
+    }
+
+    fn prune(&mut self, len: usize, policy: PruningPolicy) -> Vec<T>
+    where
+        T: Ord,
+    {
+        let excess = self.len().saturating_sub(len);
+        if excess == 0 {
+            return Vec::new();
+        }
+
+        match (self, policy) {
+            (Self::Fifo(nodes), PruningPolicy::Oldest) => nodes.drain(..excess).collect(),
+            (Self::Lifo(nodes), PruningPolicy::Oldest) => nodes.drain(..excess).collect(),
+            (Self::Fifo(nodes), PruningPolicy::WorstF) => {
+                let (kept, dropped) = split_largest(Vec::from(std::mem::take(nodes)), excess);
+                *nodes = VecDeque::from(kept);
+                dropped
+            }
+            (Self::Lifo(nodes), PruningPolicy::WorstF) => {
+                let (kept, dropped) = split_largest(std::mem::take(nodes), excess);
+                *nodes = kept;
+                dropped
+            }
+            (Self::Cost { nodes, .. }, policy) => {
+                let mut entries: Vec<((usize, u64), T)> =
+                    std::mem::take(nodes).into_iter().collect();
+                match policy {
+                    PruningPolicy::WorstF => {
+                        entries.select_nth_unstable_by(excess - 1, |a, b| b.1.cmp(&a.1));
+                    }
+                    PruningPolicy::Oldest => {
+                        entries.select_nth_unstable_by_key(excess - 1, |((_, serial), _)| *serial);
+                    }
+                }
+                *nodes = entries.split_off(excess).into_iter().collect();
+                entries.into_iter().map(|(_, node)| node).collect()
+            }
+        }
+    }
+}
+
+/// A frontier node stamped with its insertion number
+///
+/// Ordering ignores the stamp, so the heap pops nodes exactly as it would
+/// without it; the stamp only lets pruning find the oldest nodes.
+#[derive(Clone)]
+struct Stamped<T> {
+    node: T,
+    serial: u64,
+}
+
+impl<T: PartialEq> PartialEq for Stamped<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.node == other.node
+    }
+}
+
+impl<T: Eq> Eq for Stamped<T> {}
+
+impl<T: Ord> PartialOrd for Stamped<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Ord> Ord for Stamped<T> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.node.cmp(&other.node)
     }
 }
 
 /// A best-first priority queue based on `Ord`.
-#[derive(Default, Clone)]
-pub struct HeuristicSearchStrategy<T: Ord + PartialOrd>(BinaryHeap<T>);
+#[derive(Clone)]
+pub struct HeuristicSearchStrategy<T: Ord + PartialOrd> {
+    heap: BinaryHeap<Stamped<T>>,
+    next_serial: u64,
+}
+
+impl<T: Ord + PartialOrd> Default for HeuristicSearchStrategy<T> {
+    fn default() -> Self {
+        Self {
+            heap: BinaryHeap::new(),
+            next_serial: 0,
+        }
+    }
+}
 
 impl<T: Ord + PartialOrd> SearchStrategy<T> for HeuristicSearchStrategy<Reverse<T>> {
     fn get_next(&mut self) -> Option<T> {
-        self.0.pop().map(|b| b.0)
+        self.heap.pop().map(|b| b.node.0)
+    }
+
+    fn enqueue(&mut self, node: T) {
+        self.heap.push(Stamped {
+            node: Reverse(node),
+            serial: self.next_serial,
+        });
+        self.next_serial += 1;
+    }
+
+    fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.heap.peek().map(|b| &b.node.0)
+    }
+
+    /// Nodes oldest first, so pruning by age still works after a restore;
+    /// nodes with equal f-values may pop in a different order afterwards
+    fn snapshot(&self) -> Option<Vec<T>>
+    where
+        T: Clone,
+    {
+        let mut stamped: Vec<&Stamped<Reverse<T>>> = self.heap.iter().collect();
+        stamped.sort_unstable_by_key(|b| b.serial);
+        Some(stamped.into_iter().map(|b| b.node.0.clone()).collect())
+    }
+
+    fn clear(&mut self) {
+        self.heap.clear();
+        self.next_serial = 0;
+    }
+
+    fn prune(&mut self, len: usize, policy: PruningPolicy) -> Vec<T> {
+        let excess = self.heap.len().saturating_sub(len);
+        if excess == 0 {
+            return Vec::new();
+        }
+
+        let mut nodes = std::mem::take(&mut self.heap).into_vec();
+        match policy {
+            // The worst nodes are the smallest `Reverse` values
+            PruningPolicy::WorstF => {
+                nodes.select_nth_unstable(excess - 1);
+            }
+            PruningPolicy::Oldest => {
+                nodes.select_nth_unstable_by_key(excess - 1, |b| b.serial);
+            }
+        }
+        let kept = nodes.split_off(excess);
+        self.heap = BinaryHeap::from(kept);
+        nodes.into_iter().map(|b| b.node.0).collect()
+    }
+}
+
+/// A double-ended priority queue based on `Ord`.
+///
+/// `get_next` pops the smallest node and [`MinMaxQueue::pop_worst`] the
+/// largest, both in `O(log n)`. Equal nodes are kept apart by their insertion
+/// number, so none are lost.
+#[derive(Clone)]
+pub struct MinMaxQueue<T: Ord> {
+    nodes: BTreeSet<(T, u64)>,
+    next_serial: u64,
+}
+
+impl<T: Ord> Default for MinMaxQueue<T> {
+    fn default() -> Self {
+        Self {
+            nodes: BTreeSet::new(),
+            next_serial: 0,
+        }
+    }
+}
+
+impl<T: Ord> MinMaxQueue<T> {
+    /// Pop the largest node, the one `get_next` would return last.
+    pub fn pop_worst(&mut self) -> Option<T> {
+        self.nodes.pop_last().map(|(node, _)| node)
+    }
+
+    /// The node `pop_worst` would return, without removing it.
+    #[must_use]
+    pub fn peek_worst(&self) -> Option<&T> {
+        self.nodes.last().map(|(node, _)| node)
+    }
+
+    /// Remove one node equal to `node`, returning whether there was one.
+    pub fn remove(&mut self, node: &T) -> bool
+    where
+        T: Clone,
+    {
+        let key = self
+            .nodes
+            .range((node.clone(), 0)..=(node.clone(), u64::MAX))
+            .next()
+            .cloned();
+        key.is_some_and(|key| self.nodes.remove(&key))
+    }
+}
+
+impl<T: Ord> SearchStrategy<T> for MinMaxQueue<T> {
+    fn get_next(&mut self) -> Option<T> {
+        self.nodes.pop_first().map(|(node, _)| node)
     }
 
     fn enqueue(&mut self, node: T) {
-        self.0.push(Reverse(node));
+        self.nodes.insert((node, self.next_serial));
+        self.next_serial += 1;
+    }
+
+    fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.nodes.first().map(|(node, _)| node)
+    }
+
+    fn snapshot(&self) -> Option<Vec<T>>
+    where
+        T: Clone,
+    {
+        let mut stamped: Vec<&(T, u64)> = self.nodes.iter().collect();
+        stamped.sort_unstable_by_key(|(_, serial)| *serial);
+        Some(stamped.into_iter().map(|(node, _)| node.clone()).collect())
+    }
+
+    fn clear(&mut self) {
+        self.nodes.clear();
+        self.next_serial = 0;
+    }
+
+    fn prune(&mut self, len: usize, policy: PruningPolicy) -> Vec<T> {
+        let excess = self.nodes.len().saturating_sub(len);
+        if excess == 0 {
+            return Vec::new();
+        }
+
+        match policy {
+            PruningPolicy::WorstF => (0..excess).filter_map(|_| self.pop_worst()).collect(),
+            PruningPolicy::Oldest => {
+                let mut serials: Vec<u64> = self.nodes.iter().map(|&(_, serial)| serial).collect();
+                let (_, &mut newest_dropped, _) = serials.select_nth_unstable(excess - 1);
+                let (dropped, kept): (BTreeSet<_>, BTreeSet<_>) = std::mem::take(&mut self.nodes)
+                    .into_iter()
+                    .partition(|&(_, serial)| serial <= newest_dropped);
+                self.nodes = kept;
+                dropped.into_iter().map(|(node, _)| node).collect()
+            }
+        }
+    }
+}
+
+/// Suboptimality allowed by [`FocalSearchStrategy::default`]
+pub const DEFAULT_EPSILON: f64 = 0.5;
+
+/// Focal search (A*ε, Pearl and Kim 1982), a bounded-suboptimal best-first queue.
+///
+/// The open list orders every node by f(n) = g(n) + h(n). The focal list holds
+/// the open nodes whose f-value is at most (1 + ε) times the lowest one, and
+/// `get_next` pops the focal node with the lowest h, the one that looks
+/// closest to the goal, instead of the one with the lowest f. Every expanded
+/// node thus stays within (1 + ε) of the best f-value in the open list, which
+/// bounds the solution to (1 + ε) times the optimal length, while going for
+/// the goal lets it expand far fewer nodes than A* on hard boards. With ε = 0
+/// it expands like A* with ties broken towards the lower h.
+#[derive(Clone)]
+pub struct FocalSearchStrategy {
+    epsilon: f64,
+    /// Every node, keyed by f-value and insertion number
+    open: BTreeMap<(usize, u64), BoardWithSteps>,
+    /// Keys of the open nodes within the bound, ordered by h, then f-value
+    focal: BTreeSet<(u8, usize, u64)>,
+    /// Largest f-value the focal list admits, `None` while the open list is empty
+    bound: Option<usize>,
+    next_serial: u64,
+}
+
+impl Default for FocalSearchStrategy {
+    fn default() -> Self {
+        Self::new(DEFAULT_EPSILON)
+    }
+}
+
+impl FocalSearchStrategy {
+    /// Create a focal queue allowing solutions (1 + `epsilon`) times the optimal length.
+    ///
+    /// Negative values are treated as 0.
+    #[must_use]
+    pub fn new(epsilon: f64) -> Self {
+        Self {
+            epsilon: epsilon.max(0.0),
+            open: BTreeMap::new(),
+            focal: BTreeSet::new(),
+            bound: None,
+            next_serial: 0,
+        }
+    }
+
+    /// The suboptimality ε this queue was created with.
+    #[must_use]
+    pub fn epsilon(&self) -> f64 {
+        self.epsilon
+    }
+
+    /// Largest f-value admitted to the focal list when the lowest is `f_min`.
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    fn bound_for(&self, f_min: usize) -> usize {
+        ((1.0 + self.epsilon) * f_min as f64).floor() as usize
+    }
+
+    /// Bring the focal list in line with the lowest f-value of the open list.
+    fn refocus(&mut self) {
+        let Some(&(f_min, _)) = self.open.keys().next() else {
+            self.focal.clear();
+            self.bound = None;
+            return;
+        };
+        let bound = self.bound_for(f_min);
+        if self.bound.is_some_and(|old| bound < old) {
+            self.focal.retain(|&(_, f, _)| f <= bound);
+        }
+        let from = self.bound.map_or(0, |old| old + 1);
+        if from <= bound {
+            for (&(f, serial), node) in self.open.range((from, 0)..=(bound, u64::MAX)) {
+                self.focal.insert((node.h(), f, serial));
+            }
+        }
+        self.bound = Some(bound);
+    }
+}
+
+impl SearchStrategy<BoardWithSteps> for FocalSearchStrategy {
+    fn get_next(&mut self) -> Option<BoardWithSteps> {
+        let (_, f, serial) = self.focal.pop_first()?;
+        let node = self.open.remove(&(f, serial));
+        self.refocus();
+        node
+    }
+
+    fn enqueue(&mut self, node: BoardWithSteps) {
+        let key = (node.f(), self.next_serial);
+        self.next_serial += 1;
+        if self.bound.is_some_and(|bound| key.0 <= bound) {
+            self.focal.insert((node.h(), key.0, key.1));
+        }
+        self.open.insert(key, node);
+        // A new lowest f-value moves the bound
+        if self.open.keys().next() == Some(&key) {
+            self.refocus();
+        }
     }
 
     fn len(&self) -> usize {
-        self.0.len()
+        self.open.len()
+    }
+
+    fn peek(&self) -> Option<&BoardWithSteps> {
+        let &(_, f, serial) = self.focal.first()?;
+        self.open.get(&(f, serial))
+    }
+
+    /// Nodes oldest first, so pruning by age still works after a restore
+    fn snapshot(&self) -> Option<Vec<BoardWithSteps>> {
+        let mut stamped: Vec<(&(usize, u64), &BoardWithSteps)> = self.open.iter().collect();
+        stamped.sort_unstable_by_key(|((_, serial), _)| *serial);
+        Some(stamped.into_iter().map(|(_, node)| node.clone()).collect())
+    }
+
+    fn clear(&mut self) {
+        self.open.clear();
+        self.focal.clear();
+        self.bound = None;
+        self.next_serial = 0;
+    }
+
+    fn prune(&mut self, len: usize, policy: PruningPolicy) -> Vec<BoardWithSteps> {
+        let excess = self.open.len().saturating_sub(len);
+        if excess == 0 {
+            return Vec::new();
+        }
+
+        let mut entries: Vec<((usize, u64), BoardWithSteps)> =
+            std::mem::take(&mut self.open).into_iter().collect();
+        let dropped = match policy {
+            // Entries come in increasing f-value, so the worst are the last ones
+            PruningPolicy::WorstF => entries.split_off(entries.len() - excess),
+            PruningPolicy::Oldest => {
+                entries.select_nth_unstable_by_key(excess - 1, |((_, serial), _)| *serial);
+                let kept = entries.split_off(excess);
+                std::mem::replace(&mut entries, kept)
+            }
+        };
+        self.open = entries.into_iter().collect();
+        self.focal.clear();
+        self.bound = None;
+        self.refocus();
+        dropped.into_iter().map(|(_, node)| node).collect()
     }
 }
@@ -0,0 +1,464 @@
+//! # Sized Board Module
+//!
+//! [`Board`] packs the 8-puzzle into 32 bits, and [`Board`] is the only board
+//! the [`Solver`](crate::solver::Solver) and its strategies search. This module
+//! covers the other sizes with a separate, much smaller solver: [`solve`] runs
+//! IDA* (iterative-deepening A*) on any [`Puzzle`], which only remembers the
+//! path it is on and finds optimal solutions in memory linear in their length.
+//!
+//! [`SizedBoard`] is the puzzle on a grid of `ROWS`×`COLS` tiles stored as a
+//! plain array, used for rectangular boards such as 2×3 ([`Puzzle2x3`]). The
+//! 15-puzzle ([`Puzzle15`]) is the packed [`Board15`], which `SizedBoard<4>`
+//! only serves to parse and convert. A `SizedBoard<3>` converts to and from a
+//! [`Board`] without loss.
+//!
+//! The goal generalizes the spiral of [`Board::default`]: tiles `1` to
+//! `ROWS·COLS - 1` run clockwise from the top-left corner towards the centre, and the
+//! empty space takes the last cell of the spiral.
+
+use crate::time::Instant;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use rand::{Rng, rng, seq::IndexedRandom};
+
+use crate::board::{ALL_DIRECTIONS, BOARD_AREA, Board, Direction, MoveError, paint_tile};
+use crate::board15::Board15;
+use crate::stats::Stats;
+
+/// The 8-puzzle as a sized board
+pub type Puzzle8 = SizedBoard<3>;
+
+/// The 15-puzzle, packed into 64 bits
+pub type Puzzle15 = Board15;
+
+/// The 5-puzzle on two rows of three cells
+pub type Puzzle2x3 = SizedBoard<2, 3>;
+
+/// The 7-puzzle on two rows of four cells
+pub type Puzzle2x4 = SizedBoard<2, 4>;
+
+/// The 11-puzzle on three rows of four cells
+pub type Puzzle3x4 = SizedBoard<3, 4>;
+
+/// A board that [`solve`] can search
+pub trait Puzzle: Copy + Eq {
+    /// The solved board
+    fn goal() -> Self;
+
+    /// Moves the empty space in `direction`
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`MoveError`] if the empty space would leave the board
+    fn move_space(self, direction: Direction) -> Result<Self, MoveError>;
+
+    /// Sum of the Manhattan distances of the tiles to their goal cells
+    fn manhattan(&self) -> usize;
+
+    /// Whether the board can reach the goal
+    fn is_solvable(&self) -> bool;
+}
+
+/// A sliding-tile puzzle on a grid of `ROWS` rows and `COLS` columns
+///
+/// The number of columns defaults to the number of rows, so `SizedBoard<3>`
+/// is the 8-puzzle. Cells hold the tile numbers `1` to `ROWS·COLS - 1` and `0` for the empty space,
+/// whose position is kept alongside so moves need not search for it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SizedBoard<const ROWS: usize, const COLS: usize = ROWS> {
+    cells: [[u8; COLS]; ROWS],
+    /// Row and column of the empty space
+    blank: (usize, usize),
+}
+
+impl<const ROWS: usize, const COLS: usize> Default for SizedBoard<ROWS, COLS> {
+    fn default() -> Self {
+        Self::goal()
+    }
+}
+
+impl<const ROWS: usize, const COLS: usize> SizedBoard<ROWS, COLS> {
+    /// Number of cells on the board
+    pub const AREA: usize = ROWS * COLS;
+
+    /// The solved board, tiles in a clockwise spiral around the empty space
+    ///
+    /// # Panics
+    ///
+    /// Panics if the board has no cells or the board has more than 256 cells
+    #[must_use]
+    pub fn goal() -> Self {
+        let mut cells = [[0; COLS]; ROWS];
+        let (mut top, mut bottom, mut left, mut right) = (0, ROWS - 1, 0, COLS - 1);
+        let mut order = Vec::with_capacity(Self::AREA);
+        while top <= bottom && left <= right {
+            order.extend((left..=right).map(|col| (top, col)));
+            order.extend((top + 1..=bottom).map(|row| (row, right)));
+            if top < bottom {
+                order.extend((left..right).rev().map(|col| (bottom, col)));
+            }
+            if left < right {
+                order.extend((top + 1..bottom).rev().map(|row| (row, left)));
+            }
+            top += 1;
+            left += 1;
+            let (Some(b), Some(r)) = (bottom.checked_sub(1), right.checked_sub(1)) else {
+                break;
+            };
+            (bottom, right) = (b, r);
+        }
+
+        for (tile, &(row, col)) in order.iter().enumerate().take(Self::AREA - 1) {
+            cells[row][col] = u8::try_from(tile + 1).expect("Boards have at most 256 cells");
+        }
+        Self {
+            cells,
+            blank: order[Self::AREA - 1],
+        }
+    }
+
+    /// Creates a board from its rows
+    ///
+    /// # Errors
+    ///
+    /// Fails unless every number from `0` to `ROWS·COLS - 1` appears exactly once
+    pub fn from_cells(cells: [[u8; COLS]; ROWS]) -> Result<Self, &'static str> {
+        let mut seen = vec![false; Self::AREA];
+        let mut blank = None;
+        for (row, line) in cells.iter().enumerate() {
+            for (col, &tile) in line.iter().enumerate() {
+                let slot = seen
+                    .get_mut(usize::from(tile))
+                    .ok_or("Invalid board: a tile number is larger than the board")?;
+                if *slot {
+                    return Err("Invalid board: each tile must appear exactly once");
+                }
+                *slot = true;
+                if tile == 0 {
+                    blank = Some((row, col));
+                }
+            }
+        }
+        Ok(Self {
+            cells,
+            blank: blank.ok_or("Invalid board: no empty space")?,
+        })
+    }
+
+    /// The rows of the board, `0` marking the empty space
+    #[must_use]
+    pub fn cells(&self) -> [[u8; COLS]; ROWS] {
+        self.cells
+    }
+
+    /// Row and column of the empty space
+    #[must_use]
+    pub fn blank(&self) -> (usize, usize) {
+        self.blank
+    }
+
+    /// Checks if the board is the goal
+    #[must_use]
+    pub fn is_solved(&self) -> bool {
+        *self == Self::goal()
+    }
+
+    /// Moves the empty space in `direction`, sliding the tile there into its cell
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MoveError::OffBoard`] if the empty space would leave the board
+    pub fn move_space(mut self, direction: Direction) -> Result<Self, MoveError> {
+        let (row, col) = self.blank;
+        let next = match direction {
+            Direction::Up => row.checked_sub(1).map(|r| (r, col)),
+            Direction::Down => (row + 1 < ROWS).then_some((row + 1, col)),
+            Direction::Left => col.checked_sub(1).map(|c| (row, c)),
+            Direction::Right => (col + 1 < COLS).then_some((row, col + 1)),
+        };
+        let Some((next_row, next_col)) = next else {
+            return Err(MoveError::OffBoard {
+                from: u8::try_from(row * COLS + col).unwrap_or(u8::MAX),
+                direction,
+            });
+        };
+        self.cells[row][col] = self.cells[next_row][next_col];
+        self.cells[next_row][next_col] = 0;
+        self.blank = (next_row, next_col);
+        Ok(self)
+    }
+
+    /// Whether the board can reach the goal
+    ///
+    /// Every move swaps the empty space with a tile, flipping the parity of
+    /// the permutation of all cells, and moves the empty space one cell,
+    /// flipping the parity of its distance to its goal cell. The two parities
+    /// therefore agree on every board reachable from the goal, and on no
+    /// other.
+    #[must_use]
+    pub fn is_solvable(&self) -> bool {
+        let goal = Self::goal();
+        // Cell of each number on the goal, then the goal cell of each cell's number
+        let mut goal_cell = vec![0; Self::AREA];
+        for (index, &tile) in goal.cells.iter().flatten().enumerate() {
+            goal_cell[usize::from(tile)] = index;
+        }
+        let permutation: Vec<usize> = self
+            .cells
+            .iter()
+            .flatten()
+            .map(|&tile| goal_cell[usize::from(tile)])
+            .collect();
+
+        let mut visited = vec![false; Self::AREA];
+        let mut transpositions = 0;
+        for start in 0..Self::AREA {
+            let mut length = 0;
+            let mut index = start;
+            while !visited[index] {
+                visited[index] = true;
+                index = permutation[index];
+                length += 1;
+            }
+            transpositions += length.max(1) - 1;
+        }
+
+        let blank_distance =
+            self.blank.0.abs_diff(goal.blank.0) + self.blank.1.abs_diff(goal.blank.1);
+        transpositions % 2 == blank_distance % 2
+    }
+
+    /// Sum of the Manhattan distances of the tiles to their goal cells
+    #[must_use]
+    pub fn manhattan(&self) -> usize {
+        let targets = goal_positions::<ROWS, COLS>();
+        let mut distance = 0;
+        for (row, line) in self.cells.iter().enumerate() {
+            for (col, &tile) in line.iter().enumerate() {
+                if tile != 0 {
+                    let (goal_row, goal_col) = targets[usize::from(tile)];
+                    distance += row.abs_diff(goal_row) + col.abs_diff(goal_col);
+                }
+            }
+        }
+        distance
+    }
+
+    /// Scrambles the goal with `steps` random moves of the empty space
+    #[must_use]
+    pub fn random_with_solution(steps: usize) -> Self {
+        Self::random_with_solution_using(steps, &mut rng())
+    }
+
+    /// Scrambles the goal with `steps` random moves drawn from `rng`
+    ///
+    /// # Panics
+    ///
+    /// Never panics in practice: the direction list is never empty
+    pub fn random_with_solution_using<R: Rng + ?Sized>(steps: usize, rng: &mut R) -> Self {
+        let mut board = Self::goal();
+        for _ in 0..steps {
+            let direction = *ALL_DIRECTIONS
+                .choose(rng)
+                .expect("This should never happen");
+            if let Ok(next) = board.move_space(direction) {
+                board = next;
+            }
+        }
+        board
+    }
+}
+
+/// Goal cell of every tile, indexed by tile number
+fn goal_positions<const ROWS: usize, const COLS: usize>() -> Vec<(usize, usize)> {
+    let mut targets = vec![(0, 0); ROWS * COLS];
+    for (row, line) in SizedBoard::<ROWS, COLS>::goal().cells.iter().enumerate() {
+        for (col, &tile) in line.iter().enumerate() {
+            targets[usize::from(tile)] = (row, col);
+        }
+    }
+    targets
+}
+
+impl<const ROWS: usize, const COLS: usize> Puzzle for SizedBoard<ROWS, COLS> {
+    fn goal() -> Self {
+        Self::goal()
+    }
+
+    fn move_space(self, direction: Direction) -> Result<Self, MoveError> {
+        self.move_space(direction)
+    }
+
+    fn manhattan(&self) -> usize {
+        self.manhattan()
+    }
+
+    fn is_solvable(&self) -> bool {
+        self.is_solvable()
+    }
+}
+
+impl From<Board> for SizedBoard<3> {
+    fn from(board: Board) -> Self {
+        let arr = board.into_arr();
+        let cells = std::array::from_fn(|row| std::array::from_fn(|col| arr[row * 3 + col]));
+        Self::from_cells(cells).expect("A board holds every tile once")
+    }
+}
+
+impl From<SizedBoard<3>> for Board {
+    fn from(board: SizedBoard<3>) -> Self {
+        let mut arr = [0; BOARD_AREA as usize];
+        for (cell, &tile) in arr.iter_mut().zip(board.cells.iter().flatten()) {
+            *cell = tile;
+        }
+        Board::from_arr(&arr)
+    }
+}
+
+/// Parses a board from its tile numbers in reading order
+///
+/// Numbers are separated by commas or whitespace, e.g. `"1,2,3,4,12,13,14,5,..."`,
+/// with `0` as the empty space; boards of at most ten cells may also be
+/// written as one run of digits, like [`Board`]'s `"123804765"`.
+impl<const ROWS: usize, const COLS: usize> FromStr for SizedBoard<ROWS, COLS> {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tokens: Vec<&str> = s
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|token| !token.is_empty())
+            .collect();
+        let numbers: Vec<u8> = match tokens.as_slice() {
+            [digits] if Self::AREA <= 10 && digits.len() == Self::AREA => digits
+                .chars()
+                .map(|c| c.to_digit(10).and_then(|d| u8::try_from(d).ok()))
+                .collect::<Option<_>>()
+                .ok_or("Invalid board: expected tile numbers")?,
+            _ => tokens
+                .iter()
+                .map(|token| token.parse().ok())
+                .collect::<Option<_>>()
+                .ok_or("Invalid board: expected tile numbers")?,
+        };
+        if numbers.len() != Self::AREA {
+            return Err("Invalid board: expected one number per cell");
+        }
+        Self::from_cells(std::array::from_fn(|row| {
+            std::array::from_fn(|col| numbers[row * COLS + col])
+        }))
+    }
+}
+
+/// Displays the board as a grid, colored like [`Board`] with the `cli` feature
+impl<const ROWS: usize, const COLS: usize> Display for SizedBoard<ROWS, COLS> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let goal = Self::goal();
+        for (row, line) in self.cells.iter().enumerate() {
+            if row != 0 {
+                writeln!(f)?;
+            }
+            for (col, &tile) in line.iter().enumerate() {
+                if tile == 0 {
+                    write!(f, "   ")?;
+                } else {
+                    let label = format!("{tile:2} ");
+                    write!(f, "{}", paint_tile(&label, tile == goal.cells[row][col]))?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An optimal solution of a sized board
+#[derive(Clone, Debug)]
+pub struct SizedSolution<P> {
+    /// Every board from the start to the goal
+    pub path: Vec<P>,
+    /// Work of the search; `max_frontier` is the longest path held
+    pub stats: Stats,
+}
+
+/// State of one IDA* search
+struct IdaStar<P> {
+    goal: P,
+    path: Vec<P>,
+    stats: Stats,
+}
+
+impl<P: Puzzle> IdaStar<P> {
+    /// Depth-first search below the last board of the path, within `bound`
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` once the path ends at the goal, otherwise the lowest f-value
+    /// that exceeded the bound
+    fn search(&mut self, h: usize, bound: usize, back: Option<Direction>) -> Result<(), usize> {
+        let board = *self.path.last().expect("The path holds the start");
+        let depth = self.path.len() - 1;
+        let f = depth + h;
+        if f > bound {
+            return Err(f);
+        }
+        self.stats.nodes_explored += 1;
+        self.stats.max_depth_reached = self.stats.max_depth_reached.max(depth);
+        if h == 0 && board == self.goal {
+            return Ok(());
+        }
+
+        let mut next_bound = usize::MAX;
+        for direction in ALL_DIRECTIONS {
+            if Some(direction) == back {
+                continue;
+            }
+            let Ok(child) = board.move_space(direction) else {
+                continue;
+            };
+            self.stats.generated_nodes += 1;
+
+            self.path.push(child);
+            self.stats.max_frontier = self.stats.max_frontier.max(self.path.len());
+            match self.search(child.manhattan(), bound, Some(direction.opposite())) {
+                Ok(()) => return Ok(()),
+                Err(exceeded) => next_bound = next_bound.min(exceeded),
+            }
+            self.path.pop();
+        }
+        Err(next_bound)
+    }
+}
+
+/// Solves a board optimally with IDA* and the Manhattan distance
+///
+/// # Returns
+///
+/// `Some(solution)`, or `None` if the board cannot reach the goal
+#[must_use]
+pub fn solve<P: Puzzle>(board: P) -> Option<SizedSolution<P>> {
+    let started = Instant::now();
+    if !board.is_solvable() {
+        return None;
+    }
+
+    let h = board.manhattan();
+    let mut search = IdaStar {
+        goal: P::goal(),
+        path: vec![board],
+        stats: Stats::default(),
+    };
+    let mut bound = h;
+    while let Err(next) = search.search(h, bound, None) {
+        bound = next;
+    }
+
+    let mut stats = search.stats;
+    stats.solution_moves = search.path.len() - 1;
+    stats.solution_cost = stats.solution_moves;
+    stats.duration_ms = started.elapsed().as_millis();
+    Some(SizedSolution {
+        path: search.path,
+        stats,
+    })
+}
@@ -0,0 +1,470 @@
+//! # SMA* Module
+//!
+//! Simplified memory-bounded A* (SMA*, Russell 1992): an A* tree search that
+//! never holds more than a fixed number of nodes. Each step picks the node
+//! with the lowest f-value, deepest first on ties, and generates one more of
+//! its successors. When memory runs out it forgets the leaf with the highest
+//! f-value, shallowest first on ties, but never the node being expanded, and
+//! records that leaf's f-value in its parent. The parent then regenerates the
+//! forgotten child as soon as that f-value is the lowest one left.
+//!
+//! Once all successors of a node were generated, its f-value is backed up to
+//! the lowest f-value among them, and further up its ancestors. A node's
+//! f-value is thus the best estimate of any solution through it, forgotten
+//! subtrees included, so the search resumes the most promising one when it is
+//! regenerated.
+//!
+//! A successor that is not the goal and would fill the memory with its own
+//! path gets an infinite f-value, as no solution through it fits. The search
+//! is therefore complete and optimal whenever the memory holds the shortest
+//! path, that is one node more than its number of moves.
+
+use crate::time::Instant;
+use std::cmp::Reverse;
+
+use crate::board::Board;
+use crate::search_strategies::{MinMaxQueue, SearchStrategy};
+use crate::stats::Stats;
+
+/// Number of nodes [`solve`] keeps in memory
+pub const DEFAULT_MAX_NODES: usize = 10_000;
+
+/// f-value of a node that cannot lead to a solution within the memory bound
+const UNREACHABLE: usize = usize::MAX;
+
+/// A solution found by SMA*
+#[derive(Clone, Debug)]
+pub struct SmaSolution {
+    /// Every board from the start to the goal
+    pub path: Vec<Board>,
+    /// Work done by the search; `max_frontier` counts every node held,
+    /// interior ones included, and `capacity_pruned` the forgotten leaves
+    pub stats: Stats,
+}
+
+/// Ordering key of a node: f-value, then deepest first, then slot
+type Key = (usize, Reverse<usize>, usize);
+
+/// A successor of a node, in the order it is generated
+#[derive(Clone, Copy)]
+enum Successor {
+    /// Not generated yet
+    Pending(Board),
+    /// Held in memory in this slot
+    Held(usize),
+    /// Forgotten with this f-value
+    Forgotten(Board, usize),
+}
+
+/// A node of the search tree held in memory
+struct Node {
+    board: Board,
+    depth: usize,
+    /// Backed-up f-value: the lowest f-value known below the node
+    f: usize,
+    parent: Option<usize>,
+    /// Successors off the path to the root, listed on the first expansion
+    successors: Option<Vec<Successor>>,
+    /// Key of the node in [`Sma::open`], if queued
+    open_key: Option<Key>,
+    /// Key of the node in [`Sma::leaves`], if queued
+    leaf_key: Option<Key>,
+}
+
+impl Node {
+    fn new(board: Board, depth: usize, f: usize, parent: Option<usize>) -> Self {
+        Self {
+            board,
+            depth,
+            f,
+            parent,
+            successors: None,
+            open_key: None,
+            leaf_key: None,
+        }
+    }
+}
+
+/// State of the search
+struct Sma {
+    /// Node slots, `None` once forgotten
+    nodes: Vec<Option<Node>>,
+    /// Slots of forgotten nodes, reused first
+    free: Vec<usize>,
+    /// Number of nodes held
+    live: usize,
+    /// Most nodes that may be held at once
+    max_nodes: usize,
+    /// Nodes with a successor left to generate or regenerate, keyed by the
+    /// lowest f-value it may have
+    open: MinMaxQueue<Key>,
+    /// Nodes without successors in memory, keyed by their f-value
+    leaves: MinMaxQueue<Key>,
+    stats: Stats,
+}
+
+impl Sma {
+    fn node(&self, id: usize) -> &Node {
+        self.nodes[id].as_ref().expect("Live node")
+    }
+
+    fn node_mut(&mut self, id: usize) -> &mut Node {
+        self.nodes[id].as_mut().expect("Live node")
+    }
+
+    /// Holds a new node, queues it and returns its slot
+    fn insert(&mut self, node: Node) -> usize {
+        self.live += 1;
+        self.stats.max_frontier = self.stats.max_frontier.max(self.live);
+        let id = if let Some(id) = self.free.pop() {
+            self.nodes[id] = Some(node);
+            id
+        } else {
+            self.nodes.push(Some(node));
+            self.nodes.len() - 1
+        };
+        self.requeue(id);
+        id
+    }
+
+    /// Moves `id` to where it belongs in [`Sma::open`] and [`Sma::leaves`]
+    fn requeue(&mut self, id: usize) {
+        let node = self.node(id);
+        let depth = Reverse(node.depth);
+        let (open, leaf) = match &node.successors {
+            None => (Some(node.f), true),
+            Some(successors) => {
+                let open = successors
+                    .iter()
+                    .filter_map(|&successor| match successor {
+                        Successor::Pending(_) => Some(node.f),
+                        Successor::Forgotten(_, f) => Some(f),
+                        Successor::Held(_) => None,
+                    })
+                    .min();
+                let leaf = !successors
+                    .iter()
+                    .any(|successor| matches!(successor, Successor::Held(_)));
+                (open, leaf)
+            }
+        };
+        let open = open.map(|f| (f, depth, id));
+        let leaf = leaf.then_some((node.f, depth, id));
+        let (old_open, old_leaf) = (node.open_key, node.leaf_key);
+
+        if old_open != open {
+            if let Some(key) = old_open {
+                self.open.remove(&key);
+            }
+            if let Some(key) = open {
+                self.open.enqueue(key);
+            }
+        }
+        if old_leaf != leaf {
+            if let Some(key) = old_leaf {
+                self.leaves.remove(&key);
+            }
+            if let Some(key) = leaf {
+                self.leaves.enqueue(key);
+            }
+        }
+        let node = self.node_mut(id);
+        node.open_key = open;
+        node.leaf_key = leaf;
+    }
+
+    /// Whether `board` lies on the path from the root to `id`
+    fn on_path(&self, mut id: usize, board: Board) -> bool {
+        loop {
+            let node = self.node(id);
+            if node.board == board {
+                return true;
+            }
+            match node.parent {
+                Some(parent) => id = parent,
+                None => return false,
+            }
+        }
+    }
+
+    /// Lists the successors of `id` off its path, counting the others as duplicates
+    fn list_successors(&mut self, id: usize) {
+        let board = self.node(id).board;
+        let mut successors = Vec::new();
+        for (_, child) in board.successors() {
+            self.stats.generated_nodes += 1;
+            if self.on_path(id, child) {
+                self.stats.duplicates_pruned += 1;
+            } else {
+                successors.push(Successor::Pending(child));
+            }
+        }
+        self.node_mut(id).successors = Some(successors);
+    }
+
+    /// The successor of `id` to generate next: the first pending one, else
+    /// the forgotten one with the lowest f-value
+    fn next_successor(&self, id: usize) -> Option<usize> {
+        let successors = self.node(id).successors.as_ref()?;
+        let pending = successors
+            .iter()
+            .position(|successor| matches!(successor, Successor::Pending(_)));
+        pending.or_else(|| {
+            successors
+                .iter()
+                .enumerate()
+                .filter_map(|(index, &successor)| match successor {
+                    Successor::Forgotten(_, f) => Some((f, index)),
+                    _ => None,
+                })
+                .min()
+                .map(|(_, index)| index)
+        })
+    }
+
+    /// Forgets the worst leaf other than `keep`, returning whether there was one
+    ///
+    /// Its f-value is kept in its parent, which regenerates it once that
+    /// f-value is the lowest one left.
+    fn forget_worst(&mut self, keep: usize) -> bool {
+        let Some(mut worst) = self.leaves.pop_worst() else {
+            return false;
+        };
+        if worst.2 == keep {
+            let kept = worst;
+            let next = self.leaves.pop_worst();
+            self.leaves.enqueue(kept);
+            match next {
+                Some(next) => worst = next,
+                None => return false,
+            }
+        }
+        let id = worst.2;
+        let node = self.nodes[id].take().expect("Live node");
+        if let Some(key) = node.open_key {
+            self.open.remove(&key);
+        }
+        self.free.push(id);
+        self.live -= 1;
+        self.stats.capacity_pruned += 1;
+
+        let parent_id = node
+            .parent
+            .expect("The root is never a leaf next to another");
+        let successors = self
+            .node_mut(parent_id)
+            .successors
+            .as_mut()
+            .expect("Expanded parent");
+        for successor in successors.iter_mut() {
+            if matches!(*successor, Successor::Held(child) if child == id) {
+                *successor = Successor::Forgotten(node.board, node.f);
+            }
+        }
+        self.requeue(parent_id);
+        true
+    }
+
+    /// Raises the f-values of `id` and its ancestors to the lowest f-value
+    /// among their successors, once all of those were generated
+    fn back_up(&mut self, mut id: usize) {
+        loop {
+            let node = self.node(id);
+            let Some(successors) = &node.successors else {
+                return;
+            };
+            let mut f = UNREACHABLE;
+            for &successor in successors {
+                f = f.min(match successor {
+                    Successor::Pending(_) => return,
+                    Successor::Held(child) => self.node(child).f,
+                    Successor::Forgotten(_, child_f) => child_f,
+                });
+            }
+            if f == node.f {
+                return;
+            }
+            let parent = node.parent;
+            self.stats.f_backups += 1;
+            self.node_mut(id).f = f;
+            self.requeue(id);
+            match parent {
+                Some(parent) => id = parent,
+                None => return,
+            }
+        }
+    }
+
+    /// Generates one successor of `id`, forgetting a worse leaf to make room
+    fn expand(&mut self, id: usize) {
+        if self.node(id).successors.is_none() {
+            self.list_successors(id);
+        }
+        let Some(index) = self.next_successor(id) else {
+            // A dead end: nothing but its own path around it
+            self.back_up(id);
+            self.requeue(id);
+            return;
+        };
+
+        let node = self.node(id);
+        let (depth, f) = (node.depth + 1, node.f);
+        let (board, child_f) = match node.successors.as_ref().expect("Listed")[index] {
+            Successor::Pending(board) => {
+                let h = usize::from(board.heuristic_distance_to_solution());
+                (board, f.max(depth + h))
+            }
+            Successor::Forgotten(board, child_f) => {
+                self.stats.regenerated_nodes += 1;
+                (board, child_f)
+            }
+            Successor::Held(_) => unreachable!("Held successors are never picked"),
+        };
+        // No solution through a board whose path alone fills the memory
+        let child_f = if depth + 1 >= self.max_nodes && !board.is_solved() {
+            UNREACHABLE
+        } else {
+            child_f
+        };
+
+        let successor = if self.live < self.max_nodes || self.forget_worst(id) {
+            let child = self.insert(Node::new(board, depth, child_f, Some(id)));
+            self.stats.enqueued_nodes += 1;
+            Successor::Held(child)
+        } else {
+            Successor::Forgotten(board, UNREACHABLE)
+        };
+        self.node_mut(id).successors.as_mut().expect("Listed")[index] = successor;
+        self.requeue(id);
+        self.back_up(id);
+    }
+
+    /// Boards from the root to `id`
+    fn path_to(&self, id: usize) -> Vec<Board> {
+        let mut path = Vec::with_capacity(self.node(id).depth + 1);
+        let mut current = Some(id);
+        while let Some(id) = current {
+            let node = self.node(id);
+            path.push(node.board);
+            current = node.parent;
+        }
+        path.reverse();
+        path
+    }
+}
+
+/// Solves a board with SMA*, holding at most [`DEFAULT_MAX_NODES`] nodes
+///
+/// # Returns
+///
+/// `Some(solution)`, or `None` if the board cannot reach the goal
+#[must_use]
+pub fn solve(board: Board) -> Option<SmaSolution> {
+    solve_within(board, DEFAULT_MAX_NODES)
+}
+
+/// Solves a board with SMA*, holding at most `max_nodes` nodes at once
+///
+/// # Returns
+///
+/// `Some(solution)`, or `None` if the board cannot reach the goal or its
+/// shortest solution has `max_nodes` moves or more
+#[must_use]
+pub fn solve_within(board: Board, max_nodes: usize) -> Option<SmaSolution> {
+    let started = Instant::now();
+    if !board.is_solvable() || max_nodes == 0 {
+        return None;
+    }
+
+    let mut search = Sma {
+        nodes: Vec::new(),
+        free: Vec::new(),
+        live: 0,
+        max_nodes,
+        open: MinMaxQueue::default(),
+        leaves: MinMaxQueue::default(),
+        stats: Stats::default(),
+    };
+    let h = usize::from(board.heuristic_distance_to_solution());
+    search.insert(Node::new(board, 0, h, None));
+
+    while let Some(&(f, Reverse(depth), id)) = search.open.peek() {
+        if f == UNREACHABLE {
+            return None;
+        }
+        search.stats.nodes_explored += 1;
+        search.stats.max_depth_reached = search.stats.max_depth_reached.max(depth);
+        if search.node(id).board.is_solved() {
+            let path = search.path_to(id);
+            let mut stats = search.stats;
+            stats.solution_moves = path.len() - 1;
+            stats.solution_cost = stats.solution_moves;
+            stats.duration_ms = started.elapsed().as_millis();
+            return Some(SmaSolution { path, stats });
+        }
+        search.expand(id);
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::collections::hash_map::Entry;
+
+    use super::*;
+
+    /// Every board within `max_depth` moves of the goal, with its distance
+    fn boards_near_goal(max_depth: usize) -> HashMap<Board, usize> {
+        let mut distances = HashMap::from([(Board::default(), 0)]);
+        let mut layer = vec![Board::default()];
+        for depth in 1..=max_depth {
+            let mut next = Vec::new();
+            for (_, child) in layer.into_iter().flat_map(Board::successors) {
+                if let Entry::Vacant(entry) = distances.entry(child) {
+                    entry.insert(depth);
+                    next.push(child);
+                }
+            }
+            layer = next;
+        }
+        distances
+    }
+
+    fn assert_shortest(board: Board, distance: usize, solution: &SmaSolution) {
+        assert_eq!(solution.path.first(), Some(&board));
+        assert!(solution.path.last().is_some_and(|last| last.is_solved()));
+        assert_eq!(solution.path.len() - 1, distance, "{board}");
+        for pair in solution.path.windows(2) {
+            assert!(pair[0].successors().any(|(_, next)| next == pair[1]));
+        }
+    }
+
+    #[test]
+    fn solves_shallow_boards_with_room_for_the_path_plus_one() {
+        for (board, distance) in boards_near_goal(8) {
+            let solution = solve_within(board, distance + 2)
+                .unwrap_or_else(|| panic!("{board} unsolved within {}", distance + 2));
+            assert_shortest(board, distance, &solution);
+            assert!(solution.stats.max_frontier <= distance + 2);
+        }
+    }
+
+    #[test]
+    fn solves_a_one_move_board_within_three_nodes() {
+        let board = Board::default()
+            .successors()
+            .next()
+            .map(|(_, board)| board)
+            .expect("The goal has successors");
+        let solution = solve_within(board, 3).expect("Fits in three nodes");
+        assert_shortest(board, 1, &solution);
+    }
+
+    #[test]
+    fn gives_up_when_the_path_does_not_fit() {
+        for (board, distance) in boards_near_goal(4) {
+            assert!(solve_within(board, distance).is_none(), "{board}");
+        }
+    }
+}
@@ -2,27 +2,308 @@
 //!
 //! This module implements the core solver for the 8-puzzle.
 //! It supports multiple frontier strategies, including Depth-First Search (DFS),
-//! Breadth-First Search (BFS), and a heuristic best-first approach when combined
+//! Breadth-First Search (BFS), Uniform-Cost Search (UCS), and a heuristic best-first approach when combined
 //! with a priority queue strategy. The solver produces detailed statistics about
 //! the search process.
+#[cfg(feature = "cli")]
 use clap::ValueEnum;
 
-use crate::board::{ALL_DIRECTIONS, Board, BoardWithSteps};
-use crate::search_strategies::SearchStrategy;
+use crate::board::{ALL_DIRECTIONS, BOARD_AREA, Board, BoardWithSteps, Direction, Parity};
+use crate::checkpoint::SolverCheckpoint;
+use crate::cost::MoveCosts;
+use crate::heuristic::SharedHeuristic;
+use crate::search_strategies::{PruningPolicy, SearchStrategy};
 use crate::stats::Stats;
+use crate::symmetry;
+use crate::time::Instant;
+use std::cmp::Reverse;
 use std::collections::{HashMap, HashSet};
-use std::time::Instant;
+use std::fmt::{self, Display};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
 
 /// Search strategy enumeration for the puzzle solver
 ///
 /// Determines the order in which nodes are explored during the search.
-#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "cli", derive(ValueEnum))]
 pub enum ExplorerStrategy {
     /// Depth-First Search: explores as far as possible along each branch before backtracking
     Dfs,
     /// Breadth-First Search: explores all neighbors at the current depth before moving deeper
     #[default]
     Bfs,
+    /// Uniform-Cost Search (Dijkstra): expands the node with the cheapest path first
+    Ucs,
+}
+
+/// Which successors the solver discards as duplicates
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(ValueEnum))]
+pub enum DuplicatePolicy {
+    /// Discard successors that were already expanded (a closed list)
+    #[default]
+    Closed,
+    /// Also discard successors already waiting in the frontier, keeping the
+    /// frontier free of repeats at the cost of optimality for best-first searches
+    Reached,
+}
+
+/// Order in which the solver enqueues the successors of a node
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(ValueEnum))]
+pub enum ChildOrder {
+    /// Always up, down, left, right
+    #[default]
+    Fixed,
+    /// From the highest heuristic distance to the lowest, so DFS descends
+    /// into the most promising child first
+    Heuristic,
+}
+
+/// How much detail the solver records beyond the counters of [`Stats`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(ValueEnum))]
+pub enum StatsLevel {
+    /// Every statistic, including the blank-position heatmap
+    #[default]
+    Full,
+    /// Only the counters of [`Stats`]; the blank-position heatmap stays empty
+    Basic,
+}
+
+/// Why a search ended without reaching the goal
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SolveError {
+    /// The board's parity differs from the goal's, so no sequence of moves reaches it
+    Unsolvable {
+        /// Parity of the board (the goal has the other one)
+        parity: Parity,
+    },
+    /// Successors past the depth limit were dropped and no shallower path reached the goal
+    NotWithinDepth {
+        /// The depth limit, in moves
+        max_depth: usize,
+    },
+    /// Nodes whose f-value exceeds the cost bound were dropped and no cheaper path reached the goal
+    NotWithinCost {
+        /// The cost bound, in moves
+        max_cost: usize,
+    },
+    /// The node or time limit stopped the search first
+    LimitReached,
+    /// The cancel flag was raised before the goal was reached
+    Cancelled,
+    /// The frontier ran out, e.g. because the frontier cap pruned every path to the goal
+    Exhausted,
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolveError::Unsolvable { parity } => write!(
+                f,
+                "Unsolvable board: its tiles have {} inversion parity, the goal's have {}",
+                parity.name(),
+                parity.flipped().name()
+            ),
+            SolveError::NotWithinDepth { max_depth } => {
+                write!(f, "No solution found within {max_depth} moves")
+            }
+            SolveError::NotWithinCost { max_cost } => {
+                write!(
+                    f,
+                    "No solution found within the cost bound of {max_cost} moves"
+                )
+            }
+            SolveError::LimitReached => {
+                write!(f, "No solution found before the node or time limit")
+            }
+            SolveError::Cancelled => write!(f, "Search cancelled before reaching the goal"),
+            SolveError::Exhausted => write!(
+                f,
+                "No solution found (the frontier cap pruned every path to the goal)"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+/// Snapshot of an in-progress search, delivered to progress observers
+#[derive(Clone, Debug, Default)]
+pub struct SearchProgress {
+    /// Number of board states explored so far
+    pub nodes_explored: usize,
+    /// Current size of the frontier
+    pub frontier_size: usize,
+    /// Deepest depth reached so far
+    pub max_depth_reached: usize,
+    /// Time elapsed since the search started
+    pub elapsed: Duration,
+    /// Number of expanded nodes with the empty space at each cell, in reading order
+    pub blank_counts: [usize; BOARD_AREA as usize],
+    /// Number of frontier nodes with each f-value (`steps + h`), indexed by f
+    pub frontier_f_counts: Vec<usize>,
+}
+
+impl SearchProgress {
+    /// Smallest f-value (`steps + h`) in the frontier, `None` when it is empty
+    #[must_use]
+    pub fn best_f(&self) -> Option<usize> {
+        self.frontier_f_counts.iter().position(|&count| count > 0)
+    }
+}
+
+/// A legal move from a board and how promising it looks
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MoveEvaluation {
+    /// Direction the empty space moves
+    pub direction: Direction,
+    /// Board after the move
+    pub board: Board,
+    /// Heuristic distance of the board after the move (the Manhattan distance unless replaced)
+    pub h: u8,
+    /// Moves from the board after the move to the goal, if a budgeted search found them
+    ///
+    /// This is the length of the solution the solver found, so it is exact
+    /// for BFS and the heuristic search and an upper bound for DFS.
+    pub distance: Option<usize>,
+}
+
+/// Callback invoked periodically with a [`SearchProgress`] snapshot
+pub type ProgressObserver = Arc<dyn Fn(&SearchProgress) + Send + Sync>;
+
+/// Callback invoked periodically with a [`SolverCheckpoint`] of the search
+pub type CheckpointObserver = Arc<dyn Fn(&SolverCheckpoint) + Send + Sync>;
+
+/// Largest rank space stored in a flat parent array (64 MiB of `u32`s)
+const MAX_FLAT_PARENTS: u32 = 1 << 24;
+
+/// Number of parents after which a hashed map moves to a flat array
+///
+/// Short searches, including most heuristic solves, stay hashed: touching a
+/// fresh 1.4 MiB array costs more page faults than the hashes they save.
+const FLAT_PARENTS_THRESHOLD: usize = 16_384;
+
+/// Number of explored nodes between two checks of the time limit
+const TIME_LIMIT_CHECK_INTERVAL: usize = 1024;
+
+/// Fraction of the frontier cap freed by each pruning pass (1/8)
+///
+/// Pruning takes linear time in the frontier size, so cutting below the cap
+/// lets it run once per batch of enqueued nodes rather than after every
+/// expansion.
+const FRONTIER_CAP_HEADROOM_DIVISOR: usize = 8;
+
+/// How the last search ended
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum Outcome {
+    /// The frontier ran out, or no search ran yet
+    #[default]
+    Exhausted,
+    /// The goal was reached
+    Solved,
+    /// The node or time limit stopped the search
+    LimitReached,
+    /// The cancel flag stopped the search
+    Cancelled,
+}
+
+/// Parent of each reached board, used to reconstruct the solution path
+#[derive(Clone)]
+enum ParentMap {
+    /// Hashed storage, used for short searches and boards too large to rank flatly
+    Hashed(HashMap<Board, Board>),
+    /// Parent encodings indexed by [`Board::rank`], 0 when unset (no board encodes to 0)
+    ///
+    /// Sized over every arrangement, as the goal may lie in either parity class.
+    Flat(Vec<u32>),
+}
+
+impl Default for ParentMap {
+    fn default() -> Self {
+        ParentMap::Hashed(HashMap::new())
+    }
+}
+
+impl ParentMap {
+    /// Forgets every parent, keeping the allocated storage for the next search
+    fn clear(&mut self) {
+        match self {
+            ParentMap::Hashed(parents) => parents.clear(),
+            ParentMap::Flat(parents) => parents.fill(0),
+        }
+    }
+
+    /// Records (or replaces) the parent of `child`
+    fn insert(&mut self, child: Board, parent: Board) {
+        match self {
+            ParentMap::Hashed(parents) => {
+                parents.insert(child, parent);
+                if parents.len() >= FLAT_PARENTS_THRESHOLD && Board::RANK_COUNT <= MAX_FLAT_PARENTS
+                {
+                    let mut flat = vec![0; Board::RANK_COUNT as usize];
+                    for (child, parent) in parents.drain() {
+                        flat[child.rank() as usize] = parent.encoded();
+                    }
+                    *self = ParentMap::Flat(flat);
+                }
+            }
+            ParentMap::Flat(parents) => parents[child.rank() as usize] = parent.encoded(),
+        }
+    }
+
+    /// The parent of `board`, if it was reached from another board
+    fn get(&self, board: Board) -> Option<Board> {
+        match self {
+            ParentMap::Hashed(parents) => parents.get(&board).copied(),
+            ParentMap::Flat(parents) => Some(parents[board.rank() as usize])
+                .filter(|&parent| parent != 0)
+                .map(Board::from_encoded),
+        }
+    }
+}
+
+/// Runs of expansions that did not improve on the best heuristic value seen
+///
+/// A plateau starts with the first expansion whose `h` is no lower than the
+/// best expanded so far and ends with the next one that is.
+#[derive(Clone, Copy, Debug, Default)]
+struct PlateauTracker {
+    /// Lowest `h` of any expanded node
+    best_h: Option<u8>,
+    /// Length of the plateau in progress, 0 right after an improvement
+    current: usize,
+    /// Number of finished plateaus
+    plateaus: usize,
+    /// Expansions spent on plateaus
+    expansions: usize,
+    /// Length of the longest finished plateau
+    longest: usize,
+}
+
+impl PlateauTracker {
+    /// Records the expansion of a node with heuristic value `h`
+    fn expand(&mut self, h: u8) {
+        if self.best_h.is_some_and(|best| h >= best) {
+            self.current += 1;
+            self.expansions += 1;
+        } else {
+            self.best_h = Some(h);
+            self.finish();
+        }
+    }
+
+    /// Ends the plateau in progress, if any
+    fn finish(&mut self) {
+        if self.current > 0 {
+            self.plateaus += 1;
+            self.longest = self.longest.max(self.current);
+            self.current = 0;
+        }
+    }
 }
 
 /// 8-puzzle solver with comprehensive statistics tracking
@@ -36,24 +317,74 @@ where
     T: Default + Clone,
 {
     /// Parent relationships for reconstructing the solution path
-    parents: HashMap<Board, Board>,
+    parents: ParentMap,
     /// Set of already explored board states
     boards_checked: HashSet<Board>,
     boards_to_check: T,
-    /// History of frontier sizes throughout the search
-    to_check_size: Vec<usize>,
+    /// Largest frontier size seen so far, tracked in constant memory
+    max_frontier: usize,
     /// Depth of each board state in the search tree
     depth_by_board: HashMap<Board, usize>,
+    /// Cost of the cheapest known path to each board, only kept under non-unit move costs
+    cost_by_board: HashMap<Board, usize>,
     /// Total number of successor states generated
     generated_nodes: usize,
     /// Total number of states added to the frontier
     enqueued_nodes: usize,
     /// Number of duplicate states that were pruned
     duplicates_pruned: usize,
+    /// Largest frontier size allowed and how to shrink the frontier past it
+    frontier_cap: Option<(usize, PruningPolicy)>,
+    /// Number of frontier nodes dropped to stay under the frontier cap
+    capacity_pruned: usize,
     /// Maximum depth reached during the search
     max_depth_reached: usize,
+    /// Number of expanded nodes with the empty space at each cell
+    blank_counts: [usize; BOARD_AREA as usize],
+    /// Heuristic plateaus met by the expansions
+    plateaus: PlateauTracker,
+    /// Number of frontier nodes per f-value, only tracked while observed
+    frontier_f_counts: Vec<usize>,
     /// Time taken to solve the puzzle in milliseconds
     solve_duration_ms: u128,
+    /// Board the last search started from
+    start: Board,
+    /// Optional observer notified of search progress
+    progress_observer: Option<ProgressObserver>,
+    /// Optional observer handed a checkpoint every `checkpoint_interval` explored nodes
+    checkpoint_observer: Option<CheckpointObserver>,
+    /// Number of explored nodes between two checkpoints
+    checkpoint_interval: usize,
+    /// Number of explored nodes between two progress notifications
+    progress_interval: usize,
+    /// Largest number of nodes a solve may explore
+    node_limit: Option<usize>,
+    /// Longest wall-clock time a solve may run
+    time_limit: Option<Duration>,
+    /// Deepest depth successors may be enqueued at
+    max_depth: Option<usize>,
+    /// Largest f-value a node may have to be enqueued
+    max_cost: Option<usize>,
+    /// Number of nodes dropped for lying past `max_depth` or `max_cost`
+    depth_pruned: usize,
+    /// How the last search ended
+    outcome: Outcome,
+    /// Flag that stops the search when another thread raises it
+    cancel_flag: Option<Arc<AtomicBool>>,
+    /// Which successors are discarded as duplicates
+    duplicate_policy: DuplicatePolicy,
+    /// How much detail is recorded beyond the counters
+    stats_level: StatsLevel,
+    /// Heuristic of the nodes, the Manhattan distance if `None`
+    heuristic: Option<SharedHeuristic>,
+    /// Whether boards are replaced by the representatives of their symmetry classes
+    symmetry_reduction: bool,
+    /// Cost of moving each tile, one per move by default
+    move_costs: MoveCosts,
+    /// Board the search ends at, the spiral goal by default
+    goal: Board,
+    /// Order in which the successors of a node are enqueued
+    child_order: ChildOrder,
 }
 
 impl<T> Solver<T>
@@ -69,25 +400,201 @@ where
     /// # Returns
     ///
     /// `Some(solved_board)` if a solution is found, `None` if no solution exists
+    /// or the node or time limit was reached first
     pub fn solve(&mut self, board: Board) -> Option<Board> {
-        self.init_search(board);
+        self.search(board, None)
+    }
+
+    /// Solves the puzzle, reporting why no solution was found
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SolveError::Unsolvable`] without searching if the board's
+    /// parity differs from the goal's, [`SolveError::NotWithinDepth`] or
+    /// [`SolveError::NotWithinCost`] if the depth limit or the cost bound, whichever
+    /// is tighter, dropped nodes and the goal was not reached, [`SolveError::LimitReached`] if
+    /// the node or time limit stopped the search, [`SolveError::Cancelled`] if
+    /// the cancel flag did and [`SolveError::Exhausted`] if the frontier ran
+    /// out otherwise.
+    pub fn try_solve(&mut self, board: Board) -> Result<Board, SolveError> {
+        self.search(board, None).ok_or_else(|| self.failure())
+    }
+
+    /// Continues a search from a checkpoint taken by this or another solver
+    ///
+    /// The solver should be configured like the one that took the checkpoint;
+    /// its own strategy, limits and observers apply from here on, and the
+    /// statistics and time include the work done before the checkpoint.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Solver::try_solve`].
+    pub fn resume(&mut self, checkpoint: &SolverCheckpoint) -> Result<Board, SolveError> {
+        self.restore(checkpoint);
+        let start = Instant::now()
+            .checked_sub(checkpoint.elapsed)
+            .unwrap_or_else(Instant::now);
+        self.run(start, None).ok_or_else(|| self.failure())
+    }
+
+    /// Snapshot of the search state, to be resumed with [`Solver::resume`]
+    ///
+    /// # Returns
+    ///
+    /// `None` if the frontier strategy cannot list its nodes (see [`SearchStrategy::snapshot`])
+    pub fn checkpoint(&self) -> Option<SolverCheckpoint> {
+        self.checkpoint_at(self.solve_duration())
+    }
+
+    /// Why the last search ended without reaching the goal
+    fn failure(&self) -> SolveError {
+        if !self.start.can_reach(self.goal) {
+            SolveError::Unsolvable {
+                parity: self.start.parity(),
+            }
+        } else if self.outcome == Outcome::Cancelled {
+            SolveError::Cancelled
+        } else if self.outcome == Outcome::LimitReached {
+            SolveError::LimitReached
+        } else if self.depth_pruned > 0 {
+            match (self.max_depth, self.max_cost) {
+                (Some(max_depth), Some(max_cost)) if max_depth < max_cost => {
+                    SolveError::NotWithinDepth { max_depth }
+                }
+                (_, Some(max_cost)) => SolveError::NotWithinCost { max_cost },
+                (Some(max_depth), None) => SolveError::NotWithinDepth { max_depth },
+                (None, None) => SolveError::Exhausted,
+            }
+        } else {
+            SolveError::Exhausted
+        }
+    }
+
+    /// Evaluates every legal move from `board`, best first
+    ///
+    /// Each successor gets its heuristic distance and, with a `node_budget`,
+    /// a search from it that explores at most that many nodes. Moves are
+    /// ranked by the distance found, then by `h`; moves whose search ran out
+    /// of budget come after those with a distance.
+    ///
+    /// The solver is reset before each successor's search, so afterwards it
+    /// holds the state of the last one.
+    ///
+    /// # Arguments
+    ///
+    /// * `board` - The board to move from
+    /// * `node_budget` - Nodes each successor's search may explore (`None` skips the searches)
+    pub fn evaluate_moves(
+        &mut self,
+        board: Board,
+        node_budget: Option<usize>,
+    ) -> Vec<MoveEvaluation> {
+        let mut moves: Vec<MoveEvaluation> = ALL_DIRECTIONS
+            .into_iter()
+            .filter_map(|direction| {
+                let next = board.move_space(direction).ok()?;
+                let distance = node_budget.and_then(|budget| {
+                    self.reset();
+                    self.search(next, Some(budget))?;
+                    Some(self.step_by_step_solution().len() - 1)
+                });
+                Some(MoveEvaluation {
+                    direction,
+                    board: next,
+                    h: self.h(next),
+                    distance,
+                })
+            })
+            .collect();
+
+        moves.sort_by_key(|evaluation| {
+            (
+                evaluation.distance.is_none(),
+                evaluation.distance,
+                evaluation.h,
+            )
+        });
+        moves
+    }
+
+    /// Runs the search, giving up once `node_budget` nodes have been explored
+    ///
+    /// The configured node limit applies as well, whichever is smaller.
+    ///
+    /// # Returns
+    ///
+    /// `Some(solved_board)` if a solution is found within the budget, `None` otherwise
+    fn search(&mut self, board: Board, node_budget: Option<usize>) -> Option<Board> {
         let start = Instant::now();
+        self.start = board;
+        if !board.can_reach(self.goal) {
+            self.finish_without_solution(start, Outcome::Exhausted);
+            return None;
+        }
+        self.init_search(board);
+        self.run(start, node_budget)
+    }
 
-        while let Some(board) = self.boards_to_check.get_next() {
+    /// Expands nodes until the goal is reached, the frontier runs out or a limit is hit
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - The time the search began, earlier than now for resumed searches
+    /// * `node_budget` - Nodes the search may explore in total
+    fn run(&mut self, start: Instant, node_budget: Option<usize>) -> Option<Board> {
+        let node_budget = match (node_budget, self.node_limit) {
+            (Some(budget), Some(limit)) => Some(budget.min(limit)),
+            (budget, limit) => budget.or(limit),
+        };
+
+        let mut outcome = Outcome::Exhausted;
+        // Limits are checked before taking the next node, so a checkpoint
+        // taken after the search stops still holds it
+        while !self.boards_to_check.is_empty() {
+            if node_budget.is_some_and(|budget| self.boards_checked.len() >= budget)
+                || self.time_limit_reached(start)
+            {
+                outcome = Outcome::LimitReached;
+                break;
+            }
+            if self
+                .cancel_flag
+                .as_ref()
+                .is_some_and(|flag| flag.load(Ordering::Relaxed))
+            {
+                outcome = Outcome::Cancelled;
+                break;
+            }
+            let Some(board) = self.boards_to_check.get_next() else {
+                break;
+            };
+
+            self.track_frontier_f(&board, false);
             self.mark_explored(board.0);
+            self.plateaus.expand(board.h());
             self.record_frontier_size();
+            self.report_progress(start);
 
-            if board.0.is_solved() {
+            if board.0 == self.goal {
                 return Some(self.finish_with_solution(start, board.0));
             }
 
             self.expand_neighbors(&board);
+            self.enforce_frontier_cap();
+            self.report_checkpoint(start);
         }
 
-        self.finish_without_solution(start);
+        self.finish_without_solution(start, outcome);
         None
     }
 
+    /// Starts configuring a solver for `search_strategy`
+    ///
+    /// See [`SolverBuilder`] for the available options.
+    pub fn builder(search_strategy: T) -> SolverBuilder<T> {
+        SolverBuilder::new(search_strategy)
+    }
+
     /// Creates a new solver with the specified search strategy
     ///
     /// # Arguments
@@ -104,25 +611,194 @@ where
         }
     }
 
+    /// Clears the state of the previous search so the solver can be reused
+    ///
+    /// The configured strategy, limits, policies, observer and the capacity of every
+    /// container are kept, so solving many boards with one solver avoids
+    /// re-allocating them for each board.
+    pub fn reset(&mut self) {
+        self.boards_to_check.clear();
+        self.parents.clear();
+        self.boards_checked.clear();
+        self.depth_by_board.clear();
+        self.cost_by_board.clear();
+        self.max_frontier = 0;
+        self.generated_nodes = 0;
+        self.enqueued_nodes = 0;
+        self.duplicates_pruned = 0;
+        self.capacity_pruned = 0;
+        self.depth_pruned = 0;
+        self.outcome = Outcome::Exhausted;
+        self.max_depth_reached = 0;
+        self.blank_counts = [0; BOARD_AREA as usize];
+        self.plateaus = PlateauTracker::default();
+        self.frontier_f_counts.clear();
+        self.solve_duration_ms = 0;
+    }
+
+    /// Registers an observer that is notified of the search progress
+    ///
+    /// The observer is called every `interval` explored nodes with a
+    /// [`SearchProgress`] snapshot. It runs on the solving thread, so it should
+    /// return quickly.
+    ///
+    /// # Arguments
+    ///
+    /// * `interval` - Number of explored nodes between notifications (0 disables them)
+    /// * `observer` - The callback to invoke
+    #[must_use]
+    pub fn with_progress_observer(mut self, interval: usize, observer: ProgressObserver) -> Self {
+        self.progress_interval = interval;
+        self.progress_observer = Some(observer);
+        self
+    }
+
+    /// Replaces the Manhattan distance with `heuristic` for every node
+    ///
+    /// # Arguments
+    ///
+    /// * `heuristic` - Estimate of the moves from a board to the goal
+    #[must_use]
+    pub fn with_heuristic(mut self, heuristic: SharedHeuristic) -> Self {
+        self.heuristic = Some(heuristic);
+        self
+    }
+
+    /// Stops the search as soon as another thread raises `flag`
+    ///
+    /// The flag is checked before every expansion; a cancelled search keeps
+    /// its statistics and [`Solver::try_solve`] reports [`SolveError::Cancelled`].
+    /// Several solvers can share one flag, e.g. to stop the losers of a race.
+    #[must_use]
+    pub fn with_cancel_flag(mut self, flag: Arc<AtomicBool>) -> Self {
+        self.cancel_flag = Some(flag);
+        self
+    }
+
+    /// Searches one board of each symmetry class instead of every board
+    ///
+    /// Every board is replaced by its [canonical](symmetry::canonical)
+    /// representative before it enters the frontier, so boards that are
+    /// rotations or reflections of each other are explored once, which
+    /// roughly halves the explored nodes. The explored boards are the
+    /// representatives, while [`Solver::step_by_step_solution`] still
+    /// returns the moves from the real start. The lengths of the solutions
+    /// do not change, as a board and its representative are equally far from
+    /// the goal. A symmetry renames the tiles, so the reduction is skipped
+    /// under non-unit move costs.
+    #[must_use]
+    pub fn with_symmetry_reduction(mut self) -> Self {
+        self.symmetry_reduction = true;
+        self
+    }
+
+    /// Charges each move the cost of the tile it slides instead of one
+    ///
+    /// The g-cost of every node is then the cost of its path, so UCS and the
+    /// heuristic search return the cheapest solution, which may take more
+    /// moves than the shortest one. Unless [`Solver::with_heuristic`] replaces
+    /// it, the heuristic becomes the
+    /// [weighted Manhattan distance](MoveCosts::weighted_manhattan). DFS and
+    /// BFS ignore the costs when choosing what to expand.
+    #[must_use]
+    pub fn with_move_costs(mut self, costs: MoveCosts) -> Self {
+        self.move_costs = costs;
+        self
+    }
+
+    /// Searches for `goal` instead of the spiral goal of [`Board::default`]
+    ///
+    /// Boards reach `goal` when their parity matches its parity, and the
+    /// default heuristic becomes the Manhattan distance to `goal`. A heuristic
+    /// set with [`Solver::with_heuristic`] is used as it is, so it should
+    /// estimate the distance to `goal` too. The symmetries of the spiral goal
+    /// need not keep `goal`, so symmetry reduction is skipped for any other goal.
+    #[must_use]
+    pub fn with_goal(mut self, goal: Board) -> Self {
+        self.goal = goal;
+        self
+    }
+
+    /// Chooses the order in which the successors of each node are enqueued
+    ///
+    /// [`ChildOrder::Heuristic`] enqueues them from the highest `h` to the
+    /// lowest. It is meant for DFS: its stack pops the last successor first,
+    /// so it always descends into the child that looks closest to the goal
+    /// instead of following the fixed order of [`ALL_DIRECTIONS`], and reaches
+    /// the goal after far fewer nodes. Successors with equal `h` keep the
+    /// fixed order. The priority queue of the heuristic search orders its
+    /// nodes by itself, and BFS and UCS would merely visit the siblings of a
+    /// layer in another order.
+    #[must_use]
+    pub fn with_child_order(mut self, order: ChildOrder) -> Self {
+        self.child_order = order;
+        self
+    }
+
+    /// Bounds the frontier to `limit` nodes, pruning it with `policy` when it overflows
+    ///
+    /// This trades completeness and optimality for memory: pruned nodes are
+    /// forgotten, so the search may return a longer path or none at all. With
+    /// [`PruningPolicy::WorstF`] and a heuristic strategy it behaves like a beam
+    /// search whose width grows with `limit`. Each time the frontier exceeds
+    /// `limit`, it is pruned an eighth below it.
+    ///
+    /// # Arguments
+    ///
+    /// * `limit` - Largest number of nodes the frontier may hold after an expansion
+    /// * `policy` - Which nodes to drop when the limit is exceeded
+    #[must_use]
+    pub fn with_frontier_cap(mut self, limit: usize, policy: PruningPolicy) -> Self {
+        self.frontier_cap = Some((limit, policy));
+        self
+    }
+
+    /// How often each cell held the empty space across all expanded nodes
+    ///
+    /// # Returns
+    ///
+    /// One count per cell, in reading order
+    pub fn blank_heatmap(&self) -> [usize; BOARD_AREA as usize] {
+        self.blank_counts
+    }
+
+    /// Board the search ends at
+    #[must_use]
+    pub fn goal(&self) -> Board {
+        self.goal
+    }
+
+    /// Every board expanded by the last search, in no particular order
+    pub fn explored_boards(&self) -> impl Iterator<Item = Board> + '_ {
+        self.boards_checked.iter().copied()
+    }
+
     /// Generates comprehensive statistics about the search process
     ///
     /// # Returns
     ///
-    /// A `Stats` struct containing detailed metrics about the search performance
+    /// A `Stats` struct containing detailed metrics about the search performance,
+    /// with no solution moves if the last search did not reach the goal
     pub fn get_solution_stats(&self) -> Stats {
-        let max_frontier = self.to_check_size.iter().copied().max().unwrap_or(0);
-
-        let solution_moves = self.step_by_step_solution().len().saturating_sub(1);
+        let solution = self.step_by_step_solution();
+        let solution_moves = solution.len().saturating_sub(1);
 
         Stats {
             nodes_explored: self.boards_checked.len(),
             solution_moves,
-            max_frontier,
+            solution_cost: self.move_costs.path_cost(&solution),
+            max_frontier: self.max_frontier,
             generated_nodes: self.generated_nodes,
             enqueued_nodes: self.enqueued_nodes,
             duplicates_pruned: self.duplicates_pruned,
+            capacity_pruned: self.capacity_pruned,
+            depth_pruned: self.depth_pruned,
             max_depth_reached: self.max_depth_reached,
+            plateaus: self.plateaus.plateaus,
+            plateau_expansions: self.plateaus.expansions,
+            longest_plateau: self.plateaus.longest,
             duration_ms: self.solve_duration_ms,
+            ..Stats::default()
         }
     }
 
@@ -133,17 +809,25 @@ where
     ///
     /// # Returns
     ///
-    /// A vector of board states representing the solution path
+    /// A vector of board states representing the solution path, empty if the
+    /// last search did not reach the goal
     pub fn step_by_step_solution(&self) -> Vec<Board> {
-        let mut c = Board::default();
+        if self.outcome != Outcome::Solved {
+            return Vec::new();
+        }
+
+        let mut c = self.goal;
         let mut solution = vec![c];
 
-        while let Some(nc) = self.parents.get(&c) {
-            solution.push(*nc);
-            c = *nc;
+        while let Some(nc) = self.parents.get(c) {
+            solution.push(nc);
+            c = nc;
         }
 
         solution.reverse();
+        if self.symmetry_reduction {
+            return symmetry::unreflect(self.start, &solution);
+        }
         solution
     }
 
@@ -153,13 +837,237 @@ where
     ///
     /// * `start` - The initial board state to begin searching from
     fn init_search(&mut self, start: Board) {
-        self.boards_to_check.enqueue(BoardWithSteps(start, 0));
+        let start = self.searched(start);
+        let node = self.node(start, 0);
+        if self.beyond_cost(&node) {
+            self.depth_pruned += 1;
+            return;
+        }
+        self.track_frontier_f(&node, true);
+        self.boards_to_check.enqueue(node);
         self.depth_by_board.insert(start, 0);
+        if !self.move_costs.is_unit() {
+            self.cost_by_board.insert(start, 0);
+        }
+    }
+
+    /// Whether the configured time limit has passed since `start`
+    ///
+    /// The clock is only read every `TIME_LIMIT_CHECK_INTERVAL` explored nodes.
+    fn time_limit_reached(&self, start: Instant) -> bool {
+        self.time_limit.is_some_and(|limit| {
+            self.boards_checked
+                .len()
+                .is_multiple_of(TIME_LIMIT_CHECK_INTERVAL)
+                && start.elapsed() >= limit
+        })
     }
 
     /// Records the current frontier size for statistics
+    ///
+    /// Only the running maximum is kept, so long searches do not accumulate a
+    /// sample per explored node.
     fn record_frontier_size(&mut self) {
-        self.to_check_size.push(self.boards_to_check.len());
+        self.max_frontier = self.max_frontier.max(self.boards_to_check.len());
+    }
+
+    /// Notifies the progress observer, if any, every `progress_interval` nodes
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - The time when the search began
+    fn report_progress(&self, start: Instant) {
+        if let Some(observer) = &self.progress_observer
+            && self.progress_interval > 0
+            && self
+                .boards_checked
+                .len()
+                .is_multiple_of(self.progress_interval)
+        {
+            observer(&SearchProgress {
+                nodes_explored: self.boards_checked.len(),
+                frontier_size: self.boards_to_check.len(),
+                max_depth_reached: self.max_depth_reached,
+                elapsed: start.elapsed(),
+                blank_counts: self.blank_counts,
+                frontier_f_counts: self.frontier_f_counts.clone(),
+            });
+        }
+    }
+
+    /// Hands a checkpoint to the checkpoint observer, if any, every `checkpoint_interval` nodes
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - The time when the search began
+    fn report_checkpoint(&self, start: Instant) {
+        if let Some(observer) = &self.checkpoint_observer
+            && self.checkpoint_interval > 0
+            && self
+                .boards_checked
+                .len()
+                .is_multiple_of(self.checkpoint_interval)
+            && let Some(checkpoint) = self.checkpoint_at(start.elapsed())
+        {
+            observer(&checkpoint);
+        }
+    }
+
+    /// Time the last search took, as recorded when it finished
+    fn solve_duration(&self) -> Duration {
+        Duration::from_millis(u64::try_from(self.solve_duration_ms).unwrap_or(u64::MAX))
+    }
+
+    /// Snapshot of the search state after `elapsed` of searching
+    fn checkpoint_at(&self, elapsed: Duration) -> Option<SolverCheckpoint> {
+        let frontier = self.boards_to_check.snapshot()?;
+        Some(SolverCheckpoint {
+            strategy: String::new(),
+            start: self.start,
+            elapsed,
+            stats: Stats {
+                nodes_explored: self.boards_checked.len(),
+                max_frontier: self.max_frontier,
+                generated_nodes: self.generated_nodes,
+                enqueued_nodes: self.enqueued_nodes,
+                duplicates_pruned: self.duplicates_pruned,
+                capacity_pruned: self.capacity_pruned,
+                max_depth_reached: self.max_depth_reached,
+                plateaus: self.plateaus.plateaus,
+                plateau_expansions: self.plateaus.expansions,
+                longest_plateau: self.plateaus.longest,
+                ..Stats::default()
+            },
+            depth_pruned: self.depth_pruned,
+            blank_counts: self.blank_counts,
+            best_h: self.plateaus.best_h,
+            plateau_len: self.plateaus.current,
+            frontier: frontier.into_iter().map(|node| (node.0, node.1)).collect(),
+            explored: self.boards_checked.iter().copied().collect(),
+            depths: self
+                .depth_by_board
+                .iter()
+                .map(|(&board, &depth)| (board, depth))
+                .collect(),
+            parents: self
+                .depth_by_board
+                .keys()
+                .filter_map(|&board| Some((board, self.parents.get(board)?)))
+                .collect(),
+        })
+    }
+
+    /// Replaces the search state with the one saved in `checkpoint`
+    fn restore(&mut self, checkpoint: &SolverCheckpoint) {
+        self.reset();
+        self.start = checkpoint.start;
+        for &(board, steps) in &checkpoint.frontier {
+            let node = self.node(board, steps);
+            self.track_frontier_f(&node, true);
+            self.boards_to_check.enqueue(node);
+            if !self.move_costs.is_unit() {
+                let known = self.cost_by_board.entry(board).or_insert(steps);
+                *known = (*known).min(steps);
+            }
+        }
+        self.boards_checked
+            .extend(checkpoint.explored.iter().copied());
+        self.depth_by_board
+            .extend(checkpoint.depths.iter().copied());
+        for &(child, parent) in &checkpoint.parents {
+            self.parents.insert(child, parent);
+        }
+
+        let stats = &checkpoint.stats;
+        self.max_frontier = stats.max_frontier;
+        self.generated_nodes = stats.generated_nodes;
+        self.enqueued_nodes = stats.enqueued_nodes;
+        self.duplicates_pruned = stats.duplicates_pruned;
+        self.capacity_pruned = stats.capacity_pruned;
+        self.max_depth_reached = stats.max_depth_reached;
+        self.depth_pruned = checkpoint.depth_pruned;
+        self.blank_counts = checkpoint.blank_counts;
+        self.plateaus = PlateauTracker {
+            best_h: checkpoint.best_h,
+            current: checkpoint.plateau_len,
+            plateaus: stats.plateaus,
+            expansions: stats.plateau_expansions,
+            longest: stats.longest_plateau,
+        };
+    }
+
+    /// Heuristic distance of `board` under the configured heuristic
+    ///
+    /// Without one, the Manhattan distance weighted by the move costs.
+    /// Estimates beyond `u8::MAX` are capped, which keeps them admissible.
+    fn h(&self, board: Board) -> u8 {
+        self.heuristic.as_ref().map_or_else(
+            || self.move_costs.weighted_manhattan_to(board, self.goal),
+            |heuristic| u8::try_from(heuristic.estimate(board)).unwrap_or(u8::MAX),
+        )
+    }
+
+    /// The board the search stores for `board`
+    fn searched(&self, board: Board) -> Board {
+        if self.symmetry_reduction && self.move_costs.is_unit() && self.goal.is_solved() {
+            symmetry::canonical(board)
+        } else {
+            board
+        }
+    }
+
+    /// A node for `board` reached in `steps` moves, with its heuristic distance
+    fn node(&self, board: Board, steps: usize) -> BoardWithSteps {
+        if self.heuristic.is_none() && self.move_costs.is_unit() && self.goal.is_solved() {
+            BoardWithSteps::new(board, steps)
+        } else {
+            BoardWithSteps::with_h(board, steps, self.h(board))
+        }
+    }
+
+    /// Updates the frontier f-value histogram when a node enters or leaves the frontier
+    ///
+    /// The histogram only feeds progress snapshots, so it is skipped when no
+    /// observer is registered.
+    ///
+    /// # Arguments
+    ///
+    /// * `node` - The node entering or leaving the frontier
+    /// * `entering` - `true` when the node is enqueued, `false` when it is popped
+    fn track_frontier_f(&mut self, node: &BoardWithSteps, entering: bool) {
+        if self.progress_observer.is_none() {
+            return;
+        }
+
+        let f = node.f();
+        if f >= self.frontier_f_counts.len() {
+            self.frontier_f_counts.resize(f + 1, 0);
+        }
+        if entering {
+            self.frontier_f_counts[f] += 1;
+        } else {
+            self.frontier_f_counts[f] -= 1;
+        }
+    }
+
+    /// Prunes the frontier back under the configured cap, if any
+    ///
+    /// Once the cap is exceeded the frontier is cut to
+    /// `FRONTIER_CAP_HEADROOM_DIVISOR` below it, and every dropped node is
+    /// counted in the statistics.
+    fn enforce_frontier_cap(&mut self) {
+        let Some((limit, policy)) = self.frontier_cap else {
+            return;
+        };
+        if self.boards_to_check.len() <= limit {
+            return;
+        }
+
+        let target = limit - limit / FRONTIER_CAP_HEADROOM_DIVISOR;
+        for node in self.boards_to_check.prune(target, policy) {
+            self.track_frontier_f(&node, false);
+            self.capacity_pruned += 1;
+        }
     }
 
     /// Marks a board as explored to avoid revisiting it
@@ -168,6 +1076,9 @@ where
     ///
     /// * `board` - The board state to mark as explored
     fn mark_explored(&mut self, board: Board) {
+        if self.stats_level == StatsLevel::Full {
+            self.blank_counts[usize::from(board.find_space_position())] += 1;
+        }
         self.boards_checked.insert(board);
     }
 
@@ -182,6 +1093,8 @@ where
     ///
     /// The solved board state
     fn finish_with_solution(&mut self, start: Instant, board: Board) -> Board {
+        self.outcome = Outcome::Solved;
+        self.plateaus.finish();
         self.solve_duration_ms = start.elapsed().as_millis();
         board
     }
@@ -191,63 +1104,343 @@ where
     /// # Arguments
     ///
     /// * `start` - The time when the search began
-    fn finish_without_solution(&mut self, start: Instant) {
+    /// * `outcome` - Why the search stopped
+    fn finish_without_solution(&mut self, start: Instant, outcome: Outcome) {
+        self.outcome = outcome;
+        self.plateaus.finish();
         self.solve_duration_ms = start.elapsed().as_millis();
     }
 
     /// Adds a successor board to the frontier with proper bookkeeping
     ///
-    /// Updates parent relationships, depth tracking, and statistics.
+    /// Updates parent relationships, depth tracking, and statistics. A board
+    /// already waiting in the frontier keeps its shallower parent, or its
+    /// cheaper one under non-unit move costs, so a later, longer route to it
+    /// cannot lengthen the reconstructed solution. A successor past the depth
+    /// limit or the cost bound is dropped instead.
     ///
     /// # Arguments
     ///
     /// * `parent` - The parent board state
     /// * `child` - The successor board state to enqueue
     fn enqueue_successor(&mut self, parent: &BoardWithSteps, child: BoardWithSteps) {
-        self.enqueued_nodes += 1;
-        self.parents.insert(child.0, parent.0);
-
+        let unit_costs = self.move_costs.is_unit();
         let parent_depth = *self.depth_by_board.get(&parent.0).unwrap_or(&0);
         let depth = parent_depth + 1;
-        self.depth_by_board.insert(child.0, depth);
+        // Under unit costs the g-cost counts the moves of this very route
+        let moves = if unit_costs { child.1 } else { depth };
+        if self.max_depth.is_some_and(|max_depth| moves > max_depth) || self.beyond_cost(&child) {
+            self.depth_pruned += 1;
+            return;
+        }
+        self.enqueued_nodes += 1;
+
+        let improves = if unit_costs {
+            self.depth_by_board
+                .get(&child.0)
+                .is_none_or(|&known| depth < known)
+        } else {
+            self.cost_by_board
+                .get(&child.0)
+                .is_none_or(|&known| child.1 < known)
+        };
+        if improves {
+            self.parents.insert(child.0, parent.0);
+            self.depth_by_board.insert(child.0, depth);
+            if !unit_costs {
+                self.cost_by_board.insert(child.0, child.1);
+            }
+        }
 
         if depth > self.max_depth_reached {
             self.max_depth_reached = depth;
         }
 
+        self.track_frontier_f(&child, true);
         self.boards_to_check.enqueue(child);
     }
 
-    /// Processes a single move attempt from a parent board
+    /// Whether the f-value of `node` exceeds the cost bound, so no solution through it is short enough
+    fn beyond_cost(&self, node: &BoardWithSteps) -> bool {
+        self.max_cost.is_some_and(|max_cost| node.f() > max_cost)
+    }
+
+    /// Processes a single move from a parent board
     ///
-    /// Generates a successor state and either enqueues it or records it as a duplicate.
+    /// Either enqueues the successor or records it as a duplicate.
+    /// The successor remembers `dir` unless symmetry reduction replaced it by
+    /// another board, from which the opposite move would not lead back.
     ///
     /// # Arguments
     ///
     /// * `parent` - The parent board state
-    /// * `dir` - The direction to move the empty space
-    fn process_move(&mut self, parent: &BoardWithSteps, dir: crate::board::Direction) {
-        if let Ok(child) = parent.0.move_space(dir) {
-            self.generated_nodes += 1;
-            if self.boards_checked.contains(&child) {
-                self.duplicates_pruned += 1;
-            } else {
-                self.enqueue_successor(parent, BoardWithSteps(child, parent.1 + 1));
+    /// * `dir` - The direction the empty space moved
+    /// * `moved` - The board after the move
+    fn process_move(&mut self, parent: &BoardWithSteps, dir: Direction, moved: Board) {
+        let child = self.searched(moved);
+        self.generated_nodes += 1;
+        let duplicate = match self.duplicate_policy {
+            DuplicatePolicy::Closed => self.boards_checked.contains(&child),
+            DuplicatePolicy::Reached => self.depth_by_board.contains_key(&child),
+        };
+        if duplicate {
+            self.duplicates_pruned += 1;
+        } else {
+            let cost = self.move_costs.move_cost(parent.0, moved);
+            let mut child = self.node(child, parent.1 + cost);
+            if child.0 == moved {
+                child = child.reached_by(dir);
             }
+            self.enqueue_successor(parent, child);
         }
     }
 
     /// Expands all possible successor states from the current board
     ///
-    /// Attempts to move the empty space in all four directions to generate
-    /// all valid successor states.
+    /// Processes every [successor](Board::successors) but the one that
+    /// undoes the move reaching `board`, since that only leads back to its
+    /// parent, which is always a duplicate. With [`ChildOrder::Heuristic`], the
+    /// moves are tried from the successor with the highest `h` to the lowest.
     ///
     /// # Arguments
     ///
     /// * `board` - The current board state to expand
     fn expand_neighbors(&mut self, board: &BoardWithSteps) {
-        for direction in &ALL_DIRECTIONS {
-            self.process_move(board, *direction);
+        let back = board.last_move().map(Direction::opposite);
+        let mut successors: Vec<_> = board
+            .0
+            .successors()
+            .filter(|&(direction, _)| Some(direction) != back)
+            .collect();
+        if self.child_order == ChildOrder::Heuristic {
+            // Stable, so successors with equal estimates keep the fixed order
+            successors.sort_by_cached_key(|&(_, child)| Reverse(self.h(self.searched(child))));
+        }
+        for (direction, child) in successors {
+            self.process_move(board, direction, child);
         }
     }
 }
+
+/// Fluent configuration of a [`Solver`]
+///
+/// Every option defaults to the behavior of [`Solver::new`]: no limits, a
+/// closed list, no observer and full statistics.
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use o8::board::Board;
+/// use o8::search_strategies::{HeuristicSearchStrategy, PruningPolicy};
+/// use o8::solver::{DuplicatePolicy, Solver};
+///
+/// let mut solver = Solver::builder(HeuristicSearchStrategy::default())
+///     .node_limit(100_000)
+///     .time_limit(Duration::from_secs(1))
+///     .frontier_cap(50_000, PruningPolicy::WorstF)
+///     .duplicate_policy(DuplicatePolicy::Closed)
+///     .build();
+/// assert!(solver.solve(Board::random_with_solution(20)).is_some());
+/// ```
+///
+/// The heuristic is the Manhattan distance unless [`SolverBuilder::heuristic`]
+/// replaces it, and the goal is the spiral arrangement of [`Board::default`]
+/// unless [`SolverBuilder::goal`] picks another.
+#[must_use]
+pub struct SolverBuilder<T>
+where
+    T: Default + Clone,
+{
+    solver: Solver<T>,
+}
+
+impl<T> SolverBuilder<T>
+where
+    T: SearchStrategy<BoardWithSteps> + Default + Clone,
+{
+    /// Starts from a solver with `search_strategy` and default options
+    pub fn new(search_strategy: T) -> Self {
+        Self {
+            solver: Solver::new(search_strategy),
+        }
+    }
+
+    /// Gives up after exploring `limit` nodes (see [`Solver::solve`])
+    pub fn node_limit(mut self, limit: usize) -> Self {
+        self.solver.node_limit = Some(limit);
+        self
+    }
+
+    /// Gives up once a solve has run for `limit`
+    ///
+    /// The clock is checked every 1024 explored nodes, so a solve may overrun
+    /// the limit by the time those take.
+    pub fn time_limit(mut self, limit: Duration) -> Self {
+        self.solver.time_limit = Some(limit);
+        self
+    }
+
+    /// Never enqueues successors more than `max_depth` moves from the start
+    ///
+    /// Breadth-first and best-first searches then find a solution exactly when
+    /// one exists within `max_depth` moves, and report
+    /// [`SolveError::NotWithinDepth`] from [`Solver::try_solve`] otherwise. A
+    /// depth-first search with a closed list may miss a solution that a deeper
+    /// route to a shared board hid.
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.solver.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Accepts any solution of at most `max_cost` moves, dropping nodes whose f-value exceeds it
+    ///
+    /// With an admissible heuristic no dropped node leads to such a solution,
+    /// so the search stops at the first goal it reaches, which is at most
+    /// `max_cost` moves away, and [`Solver::try_solve`] reports
+    /// [`SolveError::NotWithinCost`] when there is none. Pairing the bound with
+    /// a greedy order, such as DFS with [`ChildOrder::Heuristic`], finds a
+    /// good enough solution quickly. Like [`SolverBuilder::max_depth`], a
+    /// depth-first search with a closed list may miss a solution.
+    pub fn max_cost(mut self, max_cost: usize) -> Self {
+        self.solver.max_cost = Some(max_cost);
+        self
+    }
+
+    /// Bounds memory by capping the frontier (see [`Solver::with_frontier_cap`])
+    pub fn frontier_cap(mut self, limit: usize, policy: PruningPolicy) -> Self {
+        self.solver.frontier_cap = Some((limit, policy));
+        self
+    }
+
+    /// Chooses which successors are discarded as duplicates
+    pub fn duplicate_policy(mut self, policy: DuplicatePolicy) -> Self {
+        self.solver.duplicate_policy = policy;
+        self
+    }
+
+    /// Chooses how much detail is recorded beyond the counters
+    pub fn stats_level(mut self, level: StatsLevel) -> Self {
+        self.solver.stats_level = level;
+        self
+    }
+
+    /// Charges each move the cost of the tile it slides (see [`Solver::with_move_costs`])
+    pub fn move_costs(mut self, costs: MoveCosts) -> Self {
+        self.solver = self.solver.with_move_costs(costs);
+        self
+    }
+
+    /// Replaces the Manhattan distance (see [`Solver::with_heuristic`])
+    pub fn heuristic(mut self, heuristic: SharedHeuristic) -> Self {
+        self.solver = self.solver.with_heuristic(heuristic);
+        self
+    }
+
+    /// Stops the search when another thread raises `flag` (see [`Solver::with_cancel_flag`])
+    pub fn cancel_flag(mut self, flag: Arc<AtomicBool>) -> Self {
+        self.solver = self.solver.with_cancel_flag(flag);
+        self
+    }
+
+    /// Searches for `goal` instead of the spiral goal (see [`Solver::with_goal`])
+    pub fn goal(mut self, goal: Board) -> Self {
+        self.solver = self.solver.with_goal(goal);
+        self
+    }
+
+    /// Explores one board per symmetry class (see [`Solver::with_symmetry_reduction`])
+    pub fn symmetry_reduction(mut self) -> Self {
+        self.solver = self.solver.with_symmetry_reduction();
+        self
+    }
+
+    /// Chooses the order successors are enqueued in (see [`Solver::with_child_order`])
+    pub fn child_order(mut self, order: ChildOrder) -> Self {
+        self.solver = self.solver.with_child_order(order);
+        self
+    }
+
+    /// Registers a progress observer (see [`Solver::with_progress_observer`])
+    pub fn progress_observer(mut self, interval: usize, observer: ProgressObserver) -> Self {
+        self.solver = self.solver.with_progress_observer(interval, observer);
+        self
+    }
+
+    /// Hands a [`SolverCheckpoint`] to `observer` every `interval` explored nodes
+    ///
+    /// The observer runs on the solving thread between two expansions, so
+    /// saving the checkpoint pauses the search. Frontier strategies that cannot
+    /// list their nodes produce no checkpoints.
+    pub fn checkpoint_every(mut self, interval: usize, observer: CheckpointObserver) -> Self {
+        self.solver.checkpoint_interval = interval;
+        self.solver.checkpoint_observer = Some(observer);
+        self
+    }
+
+    /// Finishes the configuration
+    pub fn build(self) -> Solver<T> {
+        self.solver
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parent_map_turns_flat_and_indexes_parents_by_rank() {
+        let pairs: Vec<(Board, Board)> = (0..=u32::try_from(FLAT_PARENTS_THRESHOLD).unwrap())
+            .map(|i| {
+                let child = Board::unrank(i * 11).unwrap();
+                (child, Board::unrank(i * 11 + 1).unwrap())
+            })
+            .collect();
+
+        let mut parents = ParentMap::default();
+        for &(child, parent) in &pairs {
+            parents.insert(child, parent);
+        }
+        assert!(
+            matches!(&parents, ParentMap::Flat(flat) if flat.len() == Board::RANK_COUNT as usize)
+        );
+        for &(child, parent) in &pairs {
+            assert_eq!(parents.get(child), Some(parent));
+        }
+        assert_eq!(parents.get(Board::unrank(5).unwrap()), None);
+
+        parents.clear();
+        assert_eq!(parents.get(pairs[0].0), None);
+    }
+
+    #[test]
+    fn failed_searches_report_no_solution() {
+        use crate::scramble::Walk;
+        use crate::search_strategies::SimpleSearchStrategy;
+        use rand::{SeedableRng, rngs::StdRng};
+
+        let mut rng = StdRng::seed_from_u64(488);
+        let board = Walk::SelfAvoiding.scramble(20, &mut rng);
+        let bfs = || SimpleSearchStrategy::new(ExplorerStrategy::Bfs);
+
+        let mut limited = Solver::builder(bfs()).node_limit(10).build();
+        assert!(matches!(
+            limited.try_solve(board),
+            Err(SolveError::LimitReached)
+        ));
+        let mut shallow = Solver::builder(bfs()).max_depth(3).build();
+        assert!(matches!(
+            shallow.try_solve(board),
+            Err(SolveError::NotWithinDepth { .. })
+        ));
+        for solver in [&limited, &shallow] {
+            assert!(solver.step_by_step_solution().is_empty());
+            assert_eq!(solver.get_solution_stats().solution_moves, 0);
+        }
+
+        let mut solver = Solver::new(bfs());
+        assert!(solver.try_solve(board).is_ok());
+        assert!(solver.get_solution_stats().solution_moves > 3);
+        solver.reset();
+        assert!(solver.step_by_step_solution().is_empty());
+    }
+}
@@ -6,23 +6,54 @@
 //! different search strategies side-by-side.
 
 use std::fmt::{self, Display};
+use std::time::Duration;
 
-use comfy_table::{Attribute, Cell, CellAlignment, ContentArrangement, Table, modifiers, presets};
+use rand::Rng;
+
+use crate::board::Board;
+use crate::compress::compressed_moves;
+
+#[cfg(feature = "cli")]
+use comfy_table::{
+    Attribute, Cell, CellAlignment, Color, ContentArrangement, Table, modifiers, presets,
+};
+
+#[cfg(feature = "cli")]
+use crate::board::BOARD_SIDE;
 
 // Type aliases to keep signatures readable when describing comparison sections
+#[cfg(feature = "cli")]
 type SectionAccessor = fn(&StatsSummary) -> &Metric;
+#[cfg(feature = "cli")]
 type SectionDesc = (&'static str, &'static str, SectionAccessor);
 
+/// Label of the compressed solution length, shown only where it was measured
+#[cfg(feature = "cli")]
+const COMPRESSED_LABEL: &str = "Compressed length (moves)";
+
 /// Individual statistics for a single puzzle solve
 ///
 /// Contains detailed metrics about the search process for one puzzle instance,
 /// including performance data, search space exploration, and solution quality.
 #[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
 pub struct Stats {
     /// Total number of board states explored
     pub nodes_explored: usize,
     /// Number of moves in the optimal solution found
     pub solution_moves: usize,
+    /// Total cost of the moves in the solution, equal to `solution_moves`
+    /// unless moves are charged by tile (see [`MoveCosts`](crate::cost::MoveCosts))
+    pub solution_cost: usize,
+    /// Number of moves left after cutting the detours out of the solution
+    /// (see [`compress`](crate::compress::compress)); equal to `solution_moves`
+    /// for optimal solutions, and 0 until [`Stats::record_compression`]
+    /// measures it
+    pub compressed_moves: usize,
     /// Maximum size of the frontier during search
     pub max_frontier: usize,
     /// Total number of successor states generated
@@ -31,24 +62,91 @@ pub struct Stats {
     pub enqueued_nodes: usize,
     /// Number of duplicate states that were pruned
     pub duplicates_pruned: usize,
+    /// Number of frontier nodes dropped to respect a frontier cap
+    pub capacity_pruned: usize,
+    /// Number of successors dropped for lying past a depth limit, or for an
+    /// f-value above a cost bound
+    pub depth_pruned: usize,
     /// Maximum depth reached in the search tree
     pub max_depth_reached: usize,
+    /// Number of heuristic plateaus: runs of consecutive expansions that did
+    /// not improve on the lowest `h` expanded so far
+    pub plateaus: usize,
+    /// Expansions spent on plateaus, over all of them
+    pub plateau_expansions: usize,
+    /// Number of expansions in the longest plateau
+    pub longest_plateau: usize,
+    /// Number of successors generated again after a memory bound forgot them
+    pub regenerated_nodes: usize,
+    /// Number of times a node's f-value was raised to the best f-value below it
+    pub f_backups: usize,
+    /// Number of expansions of nodes expanded before, after a shorter path to them was found
+    pub reexpanded_nodes: usize,
+    /// Number of breadth-first lookaheads run by enforced hill climbing
+    pub lookaheads: usize,
+    /// Number of boards expanded by the largest lookahead
+    pub longest_lookahead: usize,
+    /// Number of times LRTA* raised a board's value in its learned table
+    pub learned_updates: usize,
+    /// Number of boards in LRTA*'s learned table at the end of the play
+    pub learned_entries: usize,
+    /// Number of children cut by branch-and-bound for an f-value no lower than
+    /// the cost of the best solution found so far
+    pub bound_pruned: usize,
     /// Time taken to solve the puzzle in milliseconds
     pub duration_ms: u128,
 }
 
+impl Stats {
+    /// Measures the compressed length of `path`, the solution these stats describe
+    ///
+    /// Compression searches around every board of the path, which costs more
+    /// than many solves, so strategies leave it to callers that want it.
+    pub fn record_compression(&mut self, path: &[Board]) {
+        self.compressed_moves = compressed_moves(path);
+    }
+
+    /// Raw solution length divided by the compressed one
+    ///
+    /// `1.0` for solutions without detours, including empty ones, and for
+    /// solutions whose compression was not measured; larger values mean more
+    /// of the path was a detour.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn compression_ratio(&self) -> f64 {
+        if self.compressed_moves == 0 {
+            return 1.0;
+        }
+        self.solution_moves as f64 / self.compressed_moves as f64
+    }
+}
+
 impl Display for Stats {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "explored={}, moves={}, max_frontier={}, gen={}, enq={}, pruned={}, max_depth={}, time={}ms",
+            "explored={}, moves={}, cost={}, compressed={}, max_frontier={}, gen={}, enq={}, pruned={}, cap_pruned={}, depth_pruned={}, max_depth={}, plateaus={}, longest_plateau={}, regen={}, backups={}, reexpanded={}, lookaheads={}, longest_lookahead={}, learned_updates={}, learned_entries={}, bound_pruned={}, time={}ms",
             self.nodes_explored,
             self.solution_moves,
+            self.solution_cost,
+            self.compressed_moves,
             self.max_frontier,
             self.generated_nodes,
             self.enqueued_nodes,
             self.duplicates_pruned,
+            self.capacity_pruned,
+            self.depth_pruned,
             self.max_depth_reached,
+            self.plateaus,
+            self.longest_plateau,
+            self.regenerated_nodes,
+            self.f_backups,
+            self.reexpanded_nodes,
+            self.lookaheads,
+            self.longest_lookahead,
+            self.learned_updates,
+            self.learned_entries,
+            self.bound_pruned,
             self.duration_ms,
         )
     }
@@ -59,6 +157,11 @@ impl Display for Stats {
 /// Provides averaged metrics across multiple puzzle solves for comparing
 /// the overall performance characteristics of different search strategies.
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
 pub struct StatsSummary {
     /// Number of puzzle instances included in this summary
     pub runs: usize,
@@ -66,6 +169,10 @@ pub struct StatsSummary {
     pub nodes_explored: Metric,
     /// Number of moves in solutions found (mean ± std)
     pub solution_moves: Metric,
+    /// Total move cost of the solutions found (mean ± std)
+    pub solution_cost: Metric,
+    /// Number of moves in the compressed solutions (mean ± std)
+    pub compressed_moves: Metric,
     /// Maximum frontier size per run (mean ± std)
     pub max_frontier: Metric,
     /// Successor states generated per run (mean ± std)
@@ -74,23 +181,132 @@ pub struct StatsSummary {
     pub enqueued_nodes: Metric,
     /// Duplicate states pruned per run (mean ± std)
     pub duplicates_pruned: Metric,
+    /// Frontier nodes dropped for capacity per run (mean ± std)
+    pub capacity_pruned: Metric,
+    /// Successors dropped past the depth limit per run (mean ± std)
+    pub depth_pruned: Metric,
     /// Maximum depth reached per run (mean ± std)
     pub max_depth_reached: Metric,
+    /// Heuristic plateaus per run (mean ± std)
+    pub plateaus: Metric,
+    /// Expansions spent on plateaus per run (mean ± std)
+    pub plateau_expansions: Metric,
+    /// Longest plateau per run (mean ± std)
+    pub longest_plateau: Metric,
+    /// Regenerated successors per run (mean ± std)
+    pub regenerated_nodes: Metric,
+    /// f-value backups per run (mean ± std)
+    pub f_backups: Metric,
+    /// Re-expanded nodes per run (mean ± std)
+    pub reexpanded_nodes: Metric,
+    /// Lookaheads per run (mean ± std)
+    pub lookaheads: Metric,
+    /// Largest lookahead per run (mean ± std)
+    pub longest_lookahead: Metric,
+    /// Learned-table raises per run (mean ± std)
+    pub learned_updates: Metric,
+    /// Learned-table size per run (mean ± std)
+    pub learned_entries: Metric,
+    /// Children cut by the incumbent's bound per run (mean ± std)
+    pub bound_pruned: Metric,
     /// Solve time per run in milliseconds (mean ± std)
     pub duration_ms: Metric,
 }
 
+impl StatsSummary {
+    /// Names of the metrics accepted by [`StatsSummary::metric`]
+    pub const METRIC_NAMES: [&'static str; 23] = [
+        "nodes_explored",
+        "solution_moves",
+        "solution_cost",
+        "compressed_moves",
+        "max_frontier",
+        "generated_nodes",
+        "enqueued_nodes",
+        "duplicates_pruned",
+        "capacity_pruned",
+        "depth_pruned",
+        "max_depth_reached",
+        "plateaus",
+        "plateau_expansions",
+        "longest_plateau",
+        "regenerated_nodes",
+        "f_backups",
+        "reexpanded_nodes",
+        "lookaheads",
+        "longest_lookahead",
+        "learned_updates",
+        "learned_entries",
+        "bound_pruned",
+        "duration_ms",
+    ];
+
+    /// Returns a metric by its field name (see [`StatsSummary::METRIC_NAMES`])
+    #[must_use]
+    pub fn metric(&self, name: &str) -> Option<&Metric> {
+        match name {
+            "nodes_explored" => Some(&self.nodes_explored),
+            "solution_moves" => Some(&self.solution_moves),
+            "solution_cost" => Some(&self.solution_cost),
+            "compressed_moves" => Some(&self.compressed_moves),
+            "max_frontier" => Some(&self.max_frontier),
+            "generated_nodes" => Some(&self.generated_nodes),
+            "enqueued_nodes" => Some(&self.enqueued_nodes),
+            "duplicates_pruned" => Some(&self.duplicates_pruned),
+            "capacity_pruned" => Some(&self.capacity_pruned),
+            "depth_pruned" => Some(&self.depth_pruned),
+            "max_depth_reached" => Some(&self.max_depth_reached),
+            "plateaus" => Some(&self.plateaus),
+            "plateau_expansions" => Some(&self.plateau_expansions),
+            "longest_plateau" => Some(&self.longest_plateau),
+            "regenerated_nodes" => Some(&self.regenerated_nodes),
+            "f_backups" => Some(&self.f_backups),
+            "reexpanded_nodes" => Some(&self.reexpanded_nodes),
+            "lookaheads" => Some(&self.lookaheads),
+            "longest_lookahead" => Some(&self.longest_lookahead),
+            "learned_updates" => Some(&self.learned_updates),
+            "learned_entries" => Some(&self.learned_entries),
+            "bound_pruned" => Some(&self.bound_pruned),
+            "duration_ms" => Some(&self.duration_ms),
+            _ => None,
+        }
+    }
+}
+
 /// A numeric metric summarized by common percentiles
 #[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
 pub struct Metric {
+    /// 50th percentile (median)
     pub p50: u64,
+    /// 75th percentile
     pub p75: u64,
+    /// 90th percentile
     pub p90: u64,
+    /// 95th percentile
     pub p95: u64,
+    /// 99th percentile
     pub p99: u64,
 }
 
 impl Metric {
+    /// Returns a percentile by name (`"p50"`, `"p75"`, `"p90"`, `"p95"` or `"p99"`)
+    #[must_use]
+    pub fn percentile(&self, name: &str) -> Option<u64> {
+        match name {
+            "p50" => Some(self.p50),
+            "p75" => Some(self.p75),
+            "p90" => Some(self.p90),
+            "p95" => Some(self.p95),
+            "p99" => Some(self.p99),
+            _ => None,
+        }
+    }
+
     #[inline]
     fn new(p50: u64, p75: u64, p90: u64, p95: u64, p99: u64) -> Self {
         Self {
@@ -138,11 +354,26 @@ impl From<&[Stats]> for StatsSummary {
             runs: value.len(),
             nodes_explored: Metric::from_slice(value, |s| s.nodes_explored as u64),
             solution_moves: Metric::from_slice(value, |s| s.solution_moves as u64),
+            solution_cost: Metric::from_slice(value, |s| s.solution_cost as u64),
+            compressed_moves: Metric::from_slice(value, |s| s.compressed_moves as u64),
             max_frontier: Metric::from_slice(value, |s| s.max_frontier as u64),
             generated_nodes: Metric::from_slice(value, |s| s.generated_nodes as u64),
             enqueued_nodes: Metric::from_slice(value, |s| s.enqueued_nodes as u64),
             duplicates_pruned: Metric::from_slice(value, |s| s.duplicates_pruned as u64),
+            capacity_pruned: Metric::from_slice(value, |s| s.capacity_pruned as u64),
+            depth_pruned: Metric::from_slice(value, |s| s.depth_pruned as u64),
             max_depth_reached: Metric::from_slice(value, |s| s.max_depth_reached as u64),
+            plateaus: Metric::from_slice(value, |s| s.plateaus as u64),
+            plateau_expansions: Metric::from_slice(value, |s| s.plateau_expansions as u64),
+            longest_plateau: Metric::from_slice(value, |s| s.longest_plateau as u64),
+            regenerated_nodes: Metric::from_slice(value, |s| s.regenerated_nodes as u64),
+            f_backups: Metric::from_slice(value, |s| s.f_backups as u64),
+            reexpanded_nodes: Metric::from_slice(value, |s| s.reexpanded_nodes as u64),
+            lookaheads: Metric::from_slice(value, |s| s.lookaheads as u64),
+            longest_lookahead: Metric::from_slice(value, |s| s.longest_lookahead as u64),
+            learned_updates: Metric::from_slice(value, |s| s.learned_updates as u64),
+            learned_entries: Metric::from_slice(value, |s| s.learned_entries as u64),
+            bound_pruned: Metric::from_slice(value, |s| s.bound_pruned as u64),
             duration_ms: Metric::from_slice(value, |s| {
                 u64::try_from(s.duration_ms).unwrap_or(u64::MAX)
             }),
@@ -150,8 +381,159 @@ impl From<&[Stats]> for StatsSummary {
     }
 }
 
-// ---------- Rendering helpers (SRP: isolate table rendering) ----------
+/// Statistics of the board-generation phase of a benchmark
+///
+/// Describes the instance mix every strategy is compared on, so a skewed set
+/// (e.g. mostly near-solved boards) is visible before the results are trusted.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct GenerationStats {
+    /// Seed of the board set
+    pub seed: u64,
+    /// Number of boards in the set
+    pub boards: usize,
+    /// Time taken to generate (or load) the boards in milliseconds
+    pub duration_ms: u128,
+    /// Initial Manhattan distance of the boards
+    pub heuristic: Metric,
+    /// Smallest initial Manhattan distance
+    pub min_heuristic: u8,
+    /// Largest initial Manhattan distance
+    pub max_heuristic: u8,
+    /// Number of boards with each initial Manhattan distance, indexed by distance
+    pub heuristic_histogram: Vec<usize>,
+}
+
+impl GenerationStats {
+    /// Describes a generated board set
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - Seed the boards were generated from
+    /// * `boards` - The generated boards
+    /// * `duration` - Time taken to generate them
+    #[must_use]
+    pub fn new(seed: u64, boards: &[Board], duration: Duration) -> Self {
+        let heuristics: Vec<u8> = boards
+            .iter()
+            .map(|board| board.heuristic_distance_to_solution())
+            .collect();
+        let max_heuristic = heuristics.iter().copied().max().unwrap_or(0);
+        let mut heuristic_histogram = vec![0; usize::from(max_heuristic) + 1];
+        for &h in &heuristics {
+            heuristic_histogram[usize::from(h)] += 1;
+        }
+
+        Self {
+            seed,
+            boards: boards.len(),
+            duration_ms: duration.as_millis(),
+            heuristic: Metric::from_slice(&heuristics, |&h| u64::from(h)),
+            min_heuristic: heuristics.iter().copied().min().unwrap_or(0),
+            max_heuristic,
+            heuristic_histogram,
+        }
+    }
+}
+
+/// Number of resamples drawn by [`bootstrap_median`]
+pub const BOOTSTRAP_RESAMPLES: usize = 1000;
 
+/// Confidence level of the intervals computed by [`bootstrap_median`]
+pub const BOOTSTRAP_CONFIDENCE: f64 = 0.95;
+
+/// Confidence interval around a median
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ConfidenceInterval {
+    /// Median of the original sample
+    pub median: u64,
+    /// Lower bound of the interval
+    pub low: u64,
+    /// Upper bound of the interval
+    pub high: u64,
+}
+
+impl ConfidenceInterval {
+    /// Width of the interval as a fraction of the median
+    ///
+    /// A zero-width interval is 0 even around a zero median (e.g. sub-millisecond
+    /// solve times); any other interval around a zero median is infinitely wide.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn relative_width(&self) -> f64 {
+        if self.high == self.low {
+            0.0
+        } else if self.median == 0 {
+            f64::INFINITY
+        } else {
+            (self.high - self.low) as f64 / self.median as f64
+        }
+    }
+}
+
+/// Nearest-rank median, partially sorting `values` in place
+fn median_in_place(values: &mut [u64]) -> u64 {
+    let index = values.len().div_ceil(2).saturating_sub(1);
+    *values.select_nth_unstable(index).1
+}
+
+/// Percentile bootstrap confidence interval of the median
+///
+/// Draws [`BOOTSTRAP_RESAMPLES`] resamples with replacement and returns the
+/// central [`BOOTSTRAP_CONFIDENCE`] range of their medians.
+///
+/// # Arguments
+///
+/// * `values` - The sample, e.g. solve times in milliseconds
+/// * `rng` - Source of the resampling, seed it for reproducible intervals
+///
+/// # Returns
+///
+/// The interval, or `None` for an empty sample
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+pub fn bootstrap_median<R: Rng + ?Sized>(
+    values: &[u64],
+    rng: &mut R,
+) -> Option<ConfidenceInterval> {
+    if values.is_empty() {
+        return None;
+    }
+
+    let mut sample = values.to_vec();
+    let median = median_in_place(&mut sample);
+
+    let mut medians: Vec<u64> = (0..BOOTSTRAP_RESAMPLES)
+        .map(|_| {
+            for slot in &mut sample {
+                *slot = values[rng.random_range(0..values.len())];
+            }
+            median_in_place(&mut sample)
+        })
+        .collect();
+    medians.sort_unstable();
+
+    let tail = (1.0 - BOOTSTRAP_CONFIDENCE) / 2.0;
+    let last = medians.len() - 1;
+    let at = |q: f64| medians[((q * last as f64).round() as usize).min(last)];
+
+    Some(ConfidenceInterval {
+        median,
+        low: at(tail),
+        high: at(1.0 - tail),
+    })
+}
+
+// ---------- Rendering helpers (SRP: isolate table rendering, `cli` feature) ----------
+
+#[cfg(feature = "cli")]
 fn new_base_table() -> Table {
     let mut t = Table::new();
     t.load_preset(presets::UTF8_FULL_CONDENSED);
@@ -160,6 +542,7 @@ fn new_base_table() -> Table {
     t
 }
 
+#[cfg(feature = "cli")]
 fn add_percentile_row(t: &mut Table, label: &str, m: &Metric) {
     t.add_row([
         Cell::new(label).add_attribute(Attribute::Bold),
@@ -171,6 +554,7 @@ fn add_percentile_row(t: &mut Table, label: &str, m: &Metric) {
     ]);
 }
 
+#[cfg(feature = "cli")]
 fn add_value_row(t: &mut Table, metric: &str, value: &dyn Display) {
     t.add_row([
         Cell::new(metric).add_attribute(Attribute::Bold),
@@ -178,6 +562,7 @@ fn add_value_row(t: &mut Table, metric: &str, value: &dyn Display) {
     ]);
 }
 
+#[cfg(feature = "cli")]
 fn print_percentile_section<'a>(
     title: &str,
     desc: &str,
@@ -202,73 +587,138 @@ fn print_percentile_section<'a>(
     println!("{t}\n");
 }
 
-/// Prints a formatted comparison table of two search strategies
+/// Sections of the comparison table: label, description and accessor to the
+/// metric in a [`StatsSummary`]
+#[cfg(feature = "cli")]
+const COMPARISON_SECTIONS: [SectionDesc; 18] = [
+    (
+        "Time per run (ms)",
+        "Wall-clock time to solve one instance (milliseconds).",
+        |s| &s.duration_ms,
+    ),
+    (
+        "Nodes explored",
+        "Unique states that were expanded (visited).",
+        |s| &s.nodes_explored,
+    ),
+    (
+        "Nodes generated",
+        "Total successors produced before filtering (may include duplicates).",
+        |s| &s.generated_nodes,
+    ),
+    (
+        "Enqueued",
+        "Generated states accepted into the frontier after filtering.",
+        |s| &s.enqueued_nodes,
+    ),
+    (
+        "Discards (duplicates)",
+        "Generated states dropped because they were duplicates or already seen.",
+        |s| &s.duplicates_pruned,
+    ),
+    (
+        "Solution length (moves)",
+        "Number of moves in the solution path found.",
+        |s| &s.solution_moves,
+    ),
+    (
+        COMPRESSED_LABEL,
+        "Moves left after cutting detours out of the solution path.",
+        |s| &s.compressed_moves,
+    ),
+    (
+        "Peak frontier",
+        "Maximum size of the frontier observed (proxy for peak memory).",
+        |s| &s.max_frontier,
+    ),
+    (
+        "Max depth",
+        "Deepest depth reached in the search tree.",
+        |s| &s.max_depth_reached,
+    ),
+    (
+        "Plateaus",
+        "Runs of expansions that did not improve on the best heuristic value seen.",
+        |s| &s.plateaus,
+    ),
+    (
+        "Longest plateau",
+        "Expansions in the longest run without heuristic improvement.",
+        |s| &s.longest_plateau,
+    ),
+    (
+        "Regenerated nodes",
+        "Successors generated again after a memory bound forgot them (SMA*).",
+        |s| &s.regenerated_nodes,
+    ),
+    (
+        "Re-expanded nodes",
+        "Nodes expanded again after a shorter path to them was found (Fringe).",
+        |s| &s.reexpanded_nodes,
+    ),
+    (
+        "Lookaheads",
+        "Breadth-first searches run to escape plateaus and local minima (EHC).",
+        |s| &s.lookaheads,
+    ),
+    (
+        "Longest lookahead",
+        "Boards expanded by the largest breadth-first lookahead (EHC).",
+        |s| &s.longest_lookahead,
+    ),
+    (
+        "Learned updates",
+        "Raises of a board's value in the learned heuristic table (LRTA*).",
+        |s| &s.learned_updates,
+    ),
+    (
+        "Learned entries",
+        "Boards whose value the learned heuristic table holds at the end (LRTA*).",
+        |s| &s.learned_entries,
+    ),
+    (
+        "Discards (bound)",
+        "Children cut for an f-value no lower than the best solution found so far (DFBnB).",
+        |s| &s.bound_pruned,
+    ),
+];
+
+/// Prints a formatted comparison table of search strategies
+///
+/// Requires the `cli` feature.
 ///
 /// Displays a comprehensive side-by-side comparison of performance metrics
-/// for two different search strategies (typically DFS vs BFS).
+/// for several search strategies (typically DFS vs BFS vs Heuristic).
 ///
 /// # Arguments
 ///
-/// * `left` - Statistics summary for the first strategy
-/// * `right` - Statistics summary for the second strategy
-pub fn print_comparison_table(left: &StatsSummary, right: &StatsSummary, other: &StatsSummary) {
-    let title = format!(
-        "Strategy Comparison (runs: {}, Dfs vs Bfs vs Heuristic)",
-        left.runs
-    );
+/// * `strategies` - Strategy names paired with their statistics summaries, in display order
+#[cfg(feature = "cli")]
+pub fn print_comparison_table(strategies: &[(&str, &StatsSummary)]) {
+    let names: Vec<&str> = strategies.iter().map(|(name, _)| *name).collect();
+    let runs = strategies.first().map_or(0, |(_, summary)| summary.runs);
+    let title = if strategies.iter().all(|(_, summary)| summary.runs == runs) {
+        format!("Strategy Comparison (runs: {runs}, {})", names.join(" vs "))
+    } else {
+        // Budgets can stop strategies after different numbers of runs
+        let runs: Vec<String> = strategies
+            .iter()
+            .map(|(name, summary)| format!("{name} {}", summary.runs))
+            .collect();
+        format!("Strategy Comparison (runs: {})", runs.join(", "))
+    };
     println!("\n{title}\n");
 
-    let strategies: [(&str, &StatsSummary); 3] =
-        [("DFS", left), ("BFS", right), ("Heuristic", other)];
-
-    // Descriptor: label, description, accessor to metric in a StatsSummary
-    let sections: [SectionDesc; 8] = [
-        (
-            "Time per run (ms)",
-            "Wall-clock time to solve one instance (milliseconds).",
-            |s| &s.duration_ms,
-        ),
-        (
-            "Nodes explored",
-            "Unique states that were expanded (visited).",
-            |s| &s.nodes_explored,
-        ),
-        (
-            "Nodes generated",
-            "Total successors produced before filtering (may include duplicates).",
-            |s| &s.generated_nodes,
-        ),
-        (
-            "Enqueued",
-            "Generated states accepted into the frontier after filtering.",
-            |s| &s.enqueued_nodes,
-        ),
-        (
-            "Discards (duplicates)",
-            "Generated states dropped because they were duplicates or already seen.",
-            |s| &s.duplicates_pruned,
-        ),
-        (
-            "Solution length (moves)",
-            "Number of moves in the solution path found.",
-            |s| &s.solution_moves,
-        ),
-        (
-            "Peak frontier",
-            "Maximum size of the frontier observed (proxy for peak memory).",
-            |s| &s.max_frontier,
-        ),
-        (
-            "Max depth",
-            "Deepest depth reached in the search tree.",
-            |s| &s.max_depth_reached,
-        ),
-    ];
-
-    for (label, desc, accessor) in sections {
-        let rows = strategies
-            .into_iter()
-            .map(|(name, ss)| (name, accessor(ss)));
+    for (label, desc, accessor) in COMPARISON_SECTIONS {
+        // Compressed lengths are only measured on request
+        if label == COMPRESSED_LABEL
+            && strategies
+                .iter()
+                .all(|(_, summary)| summary.compressed_moves.p99 == 0)
+        {
+            continue;
+        }
+        let rows = strategies.iter().map(|&(name, ss)| (name, accessor(ss)));
         print_percentile_section(label, desc, rows);
     }
 
@@ -279,7 +729,8 @@ pub fn print_comparison_table(left: &StatsSummary, right: &StatsSummary, other:
 /// Prints a formatted table for a single run's statistics
 ///
 /// Mirrors the labels used in the comparison table so outputs feel consistent
-/// between `benchmark` and `solve-random` commands.
+/// between `benchmark` and `solve-random` commands. Requires the `cli` feature.
+#[cfg(feature = "cli")]
 pub fn print_run_stats(stats: &Stats) {
     let mut table = new_base_table();
     table.set_header(["Metric", "Value"]);
@@ -293,9 +744,94 @@ pub fn print_run_stats(stats: &Stats) {
         "Discards (duplicates)",
         &stats.duplicates_pruned,
     );
+    add_value_row(&mut table, "Discards (capacity)", &stats.capacity_pruned);
+    add_value_row(&mut table, "Discards (depth)", &stats.depth_pruned);
+    add_value_row(&mut table, "Discards (bound)", &stats.bound_pruned);
     add_value_row(&mut table, "Solution length (moves)", &stats.solution_moves);
+    add_value_row(&mut table, "Solution cost", &stats.solution_cost);
+    if stats.compressed_moves > 0 {
+        add_value_row(&mut table, COMPRESSED_LABEL, &stats.compressed_moves);
+        add_value_row(
+            &mut table,
+            "Compression ratio",
+            &format!("{:.2}", stats.compression_ratio()),
+        );
+    }
     add_value_row(&mut table, "Peak frontier", &stats.max_frontier);
     add_value_row(&mut table, "Max depth", &stats.max_depth_reached);
+    add_value_row(&mut table, "Plateaus", &stats.plateaus);
+    add_value_row(&mut table, "Plateau expansions", &stats.plateau_expansions);
+    add_value_row(&mut table, "Longest plateau", &stats.longest_plateau);
+    add_value_row(&mut table, "Regenerated nodes", &stats.regenerated_nodes);
+    add_value_row(&mut table, "f-value backups", &stats.f_backups);
+    add_value_row(&mut table, "Re-expanded nodes", &stats.reexpanded_nodes);
+    add_value_row(&mut table, "Lookaheads", &stats.lookaheads);
+    add_value_row(&mut table, "Longest lookahead", &stats.longest_lookahead);
+    add_value_row(&mut table, "Learned updates", &stats.learned_updates);
+    add_value_row(&mut table, "Learned entries", &stats.learned_entries);
 
     println!("\nRun statistics\n\n{table}");
 }
+
+/// Prints the generation time, seed and initial Manhattan distances of a board set
+///
+/// The histogram has one row per distance that occurs, with its share of the
+/// boards. Requires the `cli` feature.
+#[cfg(feature = "cli")]
+pub fn print_generation_stats(stats: &GenerationStats) {
+    let mut table = new_base_table();
+    table.set_header(["h(n)", "Boards", "Share"]);
+    for (h, &count) in stats.heuristic_histogram.iter().enumerate() {
+        if count == 0 {
+            continue;
+        }
+        let per_mille = (count * 1000).checked_div(stats.boards).unwrap_or(0);
+        table.add_row([
+            Cell::new(h).set_alignment(CellAlignment::Right),
+            Cell::new(count).set_alignment(CellAlignment::Right),
+            Cell::new(format!("{}.{}%", per_mille / 10, per_mille % 10))
+                .set_alignment(CellAlignment::Right),
+        ]);
+    }
+
+    println!(
+        "\nBoard set: {} boards in {} ms (seed {})",
+        stats.boards, stats.duration_ms, stats.seed
+    );
+    println!(
+        "Initial h(n): min {}, p50 {}, p90 {}, max {}\n\n{table}",
+        stats.min_heuristic, stats.heuristic.p50, stats.heuristic.p90, stats.max_heuristic
+    );
+}
+
+/// Prints where the empty space was in the expanded nodes as a 3x3 heatmap
+///
+/// Each cell shows its share of the expansions, colored from green (rarely
+/// the blank) to red (most often the blank). Requires the `cli` feature.
+///
+/// # Arguments
+///
+/// * `label` - Heading of the heatmap, e.g. the strategy name
+/// * `counts` - Expansions per cell in reading order, as returned by `Solver::blank_heatmap`
+#[cfg(feature = "cli")]
+pub fn print_blank_heatmap(label: &str, counts: &[usize]) {
+    let total: usize = counts.iter().sum();
+    let max = counts.iter().copied().max().unwrap_or(0);
+
+    let mut table = new_base_table();
+    for row in counts.chunks(usize::from(BOARD_SIDE)) {
+        table.add_row(row.iter().map(|&count| {
+            let per_mille = (count * 1000).checked_div(total).unwrap_or(0);
+            let color = match (count * 3).checked_div(max).unwrap_or(0) {
+                0 => Color::Green,
+                1 | 2 => Color::Yellow,
+                _ => Color::Red,
+            };
+            Cell::new(format!("{}.{}%", per_mille / 10, per_mille % 10))
+                .fg(color)
+                .set_alignment(CellAlignment::Right)
+        }));
+    }
+
+    println!("\nBlank position heatmap: {label} ({total} expansions)\n\n{table}");
+}
@@ -0,0 +1,169 @@
+//! # Symmetry Module
+//!
+//! The goal has the empty space in the centre, so each of the eight rotations
+//! and reflections of the square maps it onto itself once its tiles are
+//! renamed after the goal cells they land on. Such a symmetry turns every
+//! board into one exactly as far from the goal, and turns moves into moves,
+//! so a search only needs one board of each class: [`canonical`] picks the
+//! one whose cells are lexicographically smallest.
+//!
+//! A search over canonical boards finds a path of canonical boards, which
+//! [`unreflect`] turns back into moves from the real start.
+
+use std::sync::LazyLock;
+
+use crate::board::{ALL_DIRECTIONS, BOARD_AREA, BOARD_SIDE, Board};
+
+const SIDE: usize = BOARD_SIDE as usize;
+const CELLS: usize = BOARD_AREA as usize;
+
+/// How one symmetry moves the cells and renames the tiles
+struct Symmetry {
+    /// `cells[cell]`: cell the contents of `cell` move to
+    cells: [usize; CELLS],
+    /// `tiles[tile]`: new name of `tile`, with 0 staying the empty space
+    tiles: [u8; CELLS],
+}
+
+impl Symmetry {
+    /// Builds the symmetry moving the cell at row `r`, column `c` to `map(r, c)`
+    fn new(map: fn(usize, usize) -> (usize, usize)) -> Self {
+        let mut cells = [0; CELLS];
+        for (cell, target) in cells.iter_mut().enumerate() {
+            let (row, column) = map(cell / SIDE, cell % SIDE);
+            *target = row * SIDE + column;
+        }
+        let goal = Board::default().into_arr();
+        let mut tiles = [0; CELLS];
+        for (cell, &tile) in goal.iter().enumerate() {
+            tiles[usize::from(tile)] = goal[cells[cell]];
+        }
+        Self { cells, tiles }
+    }
+
+    /// Cells of the image of `board`
+    fn apply(&self, board: [u8; CELLS]) -> [u8; CELLS] {
+        let mut image = [0; CELLS];
+        for (cell, tile) in board.into_iter().enumerate() {
+            image[self.cells[cell]] = self.tiles[usize::from(tile)];
+        }
+        image
+    }
+}
+
+/// The seven symmetries other than the identity
+static SYMMETRIES: LazyLock<[Symmetry; 7]> = LazyLock::new(|| {
+    const LAST: usize = SIDE - 1;
+    [
+        Symmetry::new(|r, c| (c, LAST - r)),
+        Symmetry::new(|r, c| (LAST - r, LAST - c)),
+        Symmetry::new(|r, c| (LAST - c, r)),
+        Symmetry::new(|r, c| (r, LAST - c)),
+        Symmetry::new(|r, c| (LAST - r, c)),
+        Symmetry::new(|r, c| (c, r)),
+        Symmetry::new(|r, c| (LAST - c, LAST - r)),
+    ]
+});
+
+/// The representative of the symmetry class of `board`
+///
+/// Boards of one class get the same representative, which is exactly as far
+/// from the goal as each of them; the goal is its own representative.
+#[must_use]
+pub fn canonical(board: Board) -> Board {
+    let cells = board.into_arr();
+    let smallest = SYMMETRIES
+        .iter()
+        .map(|symmetry| symmetry.apply(cells))
+        .fold(cells, Ord::min);
+    if smallest == cells {
+        board
+    } else {
+        Board::from_arr(&smallest)
+    }
+}
+
+/// Turns a path of canonical boards back into moves from `start`
+///
+/// Each step takes the move from the current board whose successor has the
+/// next canonical board as its representative, so the result has the length
+/// of `path` and ends at the goal whenever `path` does.
+///
+/// # Arguments
+///
+/// * `start` - The real board the search started from
+/// * `path` - Canonical boards from the representative of `start` on
+///
+/// # Panics
+///
+/// Panics if two consecutive boards of `path` are not one move apart up to symmetry
+#[must_use]
+pub fn unreflect(start: Board, path: &[Board]) -> Vec<Board> {
+    let mut real = Vec::with_capacity(path.len());
+    let mut current = start;
+    real.push(current);
+    for &next in path.iter().skip(1) {
+        current = ALL_DIRECTIONS
+            .into_iter()
+            .filter_map(|direction| current.move_space(direction).ok())
+            .find(|&successor| canonical(successor) == next)
+            .expect("Consecutive canonical boards are one move apart");
+        real.push(current);
+    }
+    real
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use rand::{Rng, SeedableRng, rngs::StdRng};
+
+    use super::*;
+    use crate::database::StateSpaceDatabase;
+
+    #[test]
+    fn representatives_are_stable_and_as_far_from_the_goal() {
+        let database = StateSpaceDatabase::build();
+        assert_eq!(canonical(Board::default()), Board::default());
+
+        let mut classes = HashSet::new();
+        for rank in 0..Board::REACHABLE_COUNT {
+            let board = Board::from_reachable_rank(rank).unwrap();
+            let representative = canonical(board);
+            assert_eq!(canonical(representative), representative, "{board}");
+            assert_eq!(database.distance(representative), database.distance(board));
+            classes.insert(representative);
+        }
+        assert!(classes.len() * 8 >= Board::REACHABLE_COUNT as usize);
+        assert!(classes.len() * 4 < Board::REACHABLE_COUNT as usize);
+    }
+
+    #[test]
+    fn unreflect_turns_canonical_paths_into_real_moves() {
+        let database = StateSpaceDatabase::build();
+        let mut rng = StdRng::seed_from_u64(528);
+        for _ in 0..20 {
+            let start =
+                Board::from_reachable_rank(rng.random_range(0..Board::REACHABLE_COUNT)).unwrap();
+            let mut path = vec![start];
+            while let Some(&board) = path.last().filter(|board| !board.is_solved()) {
+                let distance = database.distance(board).unwrap();
+                let (_, next) = board
+                    .successors()
+                    .find(|&(_, next)| database.distance(next) == Some(distance - 1))
+                    .unwrap();
+                path.push(next);
+            }
+
+            let canonical_path: Vec<Board> = path.iter().copied().map(canonical).collect();
+            let real = unreflect(start, &canonical_path);
+            assert_eq!(real.len(), path.len());
+            assert_eq!(real.first(), Some(&start));
+            assert!(real.last().is_some_and(|last| last.is_solved()));
+            for pair in real.windows(2) {
+                assert!(pair[0].successors().any(|(_, next)| next == pair[1]));
+            }
+        }
+    }
+}
@@ -0,0 +1,11 @@
+//! # Time Module
+//!
+//! Clock of the searches. `std::time::Instant::now` panics on
+//! `wasm32-unknown-unknown`, which has no system clock, so with the `wasm`
+//! feature the searches read the browser's `performance.now()` through
+//! `web-time`; on every other target `web-time` is `std::time` itself.
+
+#[cfg(not(feature = "wasm"))]
+pub(crate) use std::time::Instant;
+#[cfg(feature = "wasm")]
+pub(crate) use web_time::Instant;
@@ -0,0 +1,419 @@
+//! # Visualize Module
+//!
+//! Records how a search unfolds and renders it as a self-contained HTML page.
+//!
+//! A [`SearchRecorder`] plugs into [`Solver::with_progress_observer`] and
+//! keeps every [`SearchProgress`] snapshot. [`render_html`] then draws one SVG
+//! frame per snapshot with three panels:
+//!
+//! - the frontier size over time,
+//! - the distribution of f-values (`steps + h`) in the frontier,
+//! - a heatmap of where the empty space was in the expanded nodes,
+//!
+//! and wraps the frames in a page with a slider and a play button. The page
+//! has no external dependencies, so it can be attached to a report as-is.
+//!
+//! [`render_timeline_csv`] turns the same snapshots into a CSV table of the
+//! frontier size and best f-value over time, for plotting several searches of
+//! one board on shared axes.
+//!
+//! [`Solver::with_progress_observer`]: crate::solver::Solver::with_progress_observer
+
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use crate::board::BOARD_SIDE;
+use crate::render::PALETTE;
+use crate::solver::{ProgressObserver, SearchProgress};
+
+/// Width of a frame in pixels
+const FRAME_WIDTH: usize = 780;
+
+/// Height of a frame in pixels
+const FRAME_HEIGHT: usize = 280;
+
+/// Side of a chart panel in pixels
+const PANEL_SIZE: usize = 200;
+
+/// Room above each panel for its title
+const PANEL_TOP: usize = 50;
+
+/// Horizontal offset of each of the three panels
+const PANEL_LEFT: [usize; 3] = [40, 300, 560];
+
+/// Header row of the frontier timeline CSV
+const TIMELINE_HEADER: &str = "nodes_explored,elapsed_ms,frontier_size,best_f";
+
+/// Heatmap color of a cell that never held the empty space
+const COLD: [u8; 3] = PALETTE[1];
+
+/// Heatmap color of the cell that held the empty space most often
+const HOT: [u8; 3] = [0xc0, 0x39, 0x2b];
+
+/// Collects progress snapshots of a search
+///
+/// Cloning a recorder shares the underlying list, so the observer returned by
+/// [`SearchRecorder::observer`] can be moved into a solver while the original
+/// is kept to read the frames back.
+#[derive(Clone, Default)]
+pub struct SearchRecorder {
+    /// Snapshots received so far, oldest first
+    frames: Arc<Mutex<Vec<SearchProgress>>>,
+}
+
+impl SearchRecorder {
+    /// Creates an empty recorder
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns an observer that appends every snapshot to this recorder
+    #[must_use]
+    pub fn observer(&self) -> ProgressObserver {
+        let frames = Arc::clone(&self.frames);
+        Arc::new(move |progress: &SearchProgress| {
+            frames
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .push(progress.clone());
+        })
+    }
+
+    /// Returns a copy of the snapshots received so far, oldest first
+    #[must_use]
+    pub fn frames(&self) -> Vec<SearchProgress> {
+        self.frames
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+}
+
+/// Scales shared by every frame, so panels are comparable across frames
+struct Scales {
+    /// Largest frontier size of any frame
+    frontier_size: usize,
+    /// Largest f-value present in any frame
+    f_value: usize,
+    /// Largest number of frontier nodes sharing one f-value in any frame
+    f_count: usize,
+}
+
+impl Scales {
+    /// Computes the scales of a recording
+    fn new(frames: &[SearchProgress]) -> Self {
+        let max_frontier = frames.iter().map(|p| p.frontier_size).max().unwrap_or(0);
+        let max_f = frames
+            .iter()
+            .filter_map(|p| p.frontier_f_counts.iter().rposition(|&c| c > 0))
+            .max()
+            .unwrap_or(0);
+        let max_f_count = frames
+            .iter()
+            .flat_map(|p| p.frontier_f_counts.iter().copied())
+            .max()
+            .unwrap_or(0);
+
+        Self {
+            frontier_size: max_frontier.max(1),
+            f_value: max_f,
+            f_count: max_f_count.max(1),
+        }
+    }
+}
+
+/// Formats an RGB triple as an SVG color
+fn hex([r, g, b]: [u8; 3]) -> String {
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+/// Blends [`COLD`] into [`HOT`] in proportion to `value / max`
+fn heat_color(value: usize, max: usize) -> String {
+    let max = max.max(1);
+    let mix = |(cold, hot): (u8, u8)| {
+        let blended = (usize::from(cold) * (max - value) + usize::from(hot) * value) / max;
+        u8::try_from(blended).unwrap_or(u8::MAX)
+    };
+    let [r, g, b] = [(COLD[0], HOT[0]), (COLD[1], HOT[1]), (COLD[2], HOT[2])].map(mix);
+    hex([r, g, b])
+}
+
+/// Writes the title of a panel
+fn panel_title(svg: &mut String, panel: usize, title: &str) {
+    let _ = writeln!(
+        svg,
+        "  <text x=\"{}\" y=\"{}\" font-size=\"14\" text-anchor=\"middle\">{title}</text>",
+        PANEL_LEFT[panel] + PANEL_SIZE / 2,
+        PANEL_TOP - 14
+    );
+}
+
+/// Draws the frontier size of frames `0..=index` as a line chart
+fn frontier_panel(svg: &mut String, frames: &[SearchProgress], index: usize, scales: &Scales) {
+    let left = PANEL_LEFT[0];
+    let bottom = PANEL_TOP + PANEL_SIZE;
+    let steps = (frames.len() - 1).max(1);
+
+    panel_title(svg, 0, "frontier size");
+    let _ = writeln!(
+        svg,
+        "  <rect x=\"{left}\" y=\"{PANEL_TOP}\" width=\"{PANEL_SIZE}\" height=\"{PANEL_SIZE}\" \
+         fill=\"none\" stroke=\"#bbbbbb\"/>"
+    );
+
+    let points: Vec<String> = frames[..=index]
+        .iter()
+        .enumerate()
+        .map(|(i, p)| {
+            let x = left + i * PANEL_SIZE / steps;
+            let y = bottom - p.frontier_size * PANEL_SIZE / scales.frontier_size;
+            format!("{x},{y}")
+        })
+        .collect();
+    let _ = writeln!(
+        svg,
+        "  <polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"2\"/>",
+        points.join(" "),
+        hex(PALETTE[2])
+    );
+    let _ = writeln!(
+        svg,
+        "  <text x=\"{}\" y=\"{}\" font-size=\"12\" text-anchor=\"middle\">{} nodes</text>",
+        left + PANEL_SIZE / 2,
+        bottom + 18,
+        frames[index].frontier_size
+    );
+}
+
+/// Draws the f-value histogram of the frontier as a bar chart
+fn f_value_panel(svg: &mut String, progress: &SearchProgress, scales: &Scales) {
+    let left = PANEL_LEFT[1];
+    let bottom = PANEL_TOP + PANEL_SIZE;
+    let bars = scales.f_value + 1;
+    let bar_width = (PANEL_SIZE / bars).max(1);
+
+    panel_title(svg, 1, "frontier f-values");
+    let _ = writeln!(
+        svg,
+        "  <rect x=\"{left}\" y=\"{PANEL_TOP}\" width=\"{PANEL_SIZE}\" height=\"{PANEL_SIZE}\" \
+         fill=\"none\" stroke=\"#bbbbbb\"/>"
+    );
+
+    for (f, &count) in progress.frontier_f_counts.iter().enumerate() {
+        if count == 0 {
+            continue;
+        }
+        let height = (count * PANEL_SIZE / scales.f_count).max(1);
+        let _ = writeln!(
+            svg,
+            "  <rect x=\"{}\" y=\"{}\" width=\"{bar_width}\" height=\"{height}\" fill=\"{}\"/>",
+            left + f * PANEL_SIZE / bars,
+            bottom - height,
+            hex(PALETTE[0])
+        );
+    }
+
+    let _ = writeln!(
+        svg,
+        "  <text x=\"{left}\" y=\"{}\" font-size=\"12\">0</text>\n  \
+         <text x=\"{}\" y=\"{}\" font-size=\"12\" text-anchor=\"end\">{}</text>",
+        bottom + 18,
+        left + PANEL_SIZE,
+        bottom + 18,
+        scales.f_value
+    );
+}
+
+/// Draws where the empty space was in the expanded nodes as a board-shaped heatmap
+fn heatmap_panel(svg: &mut String, progress: &SearchProgress) {
+    let left = PANEL_LEFT[2];
+    let side = usize::from(BOARD_SIDE);
+    let cell = PANEL_SIZE / side;
+    let total: usize = progress.blank_counts.iter().sum();
+    let max = progress.blank_counts.iter().copied().max().unwrap_or(0);
+
+    panel_title(svg, 2, "blank position");
+    for (position, &count) in progress.blank_counts.iter().enumerate() {
+        let x = left + (position % side) * cell;
+        let y = PANEL_TOP + (position / side) * cell;
+        let share = (count * 100).checked_div(total).unwrap_or(0);
+        let _ = writeln!(
+            svg,
+            "  <rect x=\"{x}\" y=\"{y}\" width=\"{cell}\" height=\"{cell}\" fill=\"{}\" \
+             stroke=\"#ffffff\" stroke-width=\"2\"/>\n  \
+             <text x=\"{}\" y=\"{}\" font-size=\"16\" text-anchor=\"middle\">{share}%</text>",
+            heat_color(count, max),
+            x + cell / 2,
+            y + cell / 2 + 6
+        );
+    }
+}
+
+/// Draws one frame of a recording
+///
+/// # Arguments
+///
+/// * `frames` - The whole recording, used for the frontier history and shared scales
+/// * `index` - Index of the frame to draw
+///
+/// # Returns
+///
+/// A standalone SVG document
+///
+/// # Panics
+///
+/// Panics if `index` is out of bounds
+#[must_use]
+pub fn render_frame(frames: &[SearchProgress], index: usize) -> String {
+    render_frame_with(frames, index, &Scales::new(frames))
+}
+
+/// Draws one frame with precomputed scales
+fn render_frame_with(frames: &[SearchProgress], index: usize, scales: &Scales) -> String {
+    let progress = &frames[index];
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{FRAME_WIDTH}\" height=\"{FRAME_HEIGHT}\" \
+         viewBox=\"0 0 {FRAME_WIDTH} {FRAME_HEIGHT}\" font-family=\"sans-serif\">"
+    );
+    let _ = writeln!(
+        svg,
+        "  <text x=\"{}\" y=\"16\" font-size=\"13\" fill=\"#555555\">{} nodes explored, depth {}, {} ms</text>",
+        PANEL_LEFT[0],
+        progress.nodes_explored,
+        progress.max_depth_reached,
+        progress.elapsed.as_millis()
+    );
+
+    frontier_panel(&mut svg, frames, index, scales);
+    f_value_panel(&mut svg, progress, scales);
+    heatmap_panel(&mut svg, progress);
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Renders a recording as an HTML page with one frame per snapshot
+///
+/// # Arguments
+///
+/// * `frames` - Snapshots from a [`SearchRecorder`], oldest first
+/// * `title` - Page heading, e.g. the strategy name
+///
+/// # Returns
+///
+/// A self-contained HTML document; with no frames it only holds the heading
+#[must_use]
+pub fn render_html(frames: &[SearchProgress], title: &str) -> String {
+    let scales = Scales::new(frames);
+    let last = frames.len().saturating_sub(1);
+
+    let mut html = String::from("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    let _ = writeln!(html, "<title>{title}</title>");
+    html.push_str(
+        "<style>body{font-family:sans-serif;margin:2em}.frame{display:none}.frame.shown{display:block}</style>\n",
+    );
+    let _ = writeln!(html, "</head>\n<body>\n<h1>{title}</h1>");
+
+    if !frames.is_empty() {
+        let _ = writeln!(
+            html,
+            "<p><button id=\"play\">play</button> \
+             <input id=\"frame\" type=\"range\" min=\"0\" max=\"{last}\" value=\"0\"> \
+             <span id=\"label\">1 / {}</span></p>",
+            frames.len()
+        );
+    }
+    for index in 0..frames.len() {
+        let class = if index == 0 { "frame shown" } else { "frame" };
+        let _ = writeln!(html, "<div class=\"{class}\">");
+        html.push_str(&render_frame_with(frames, index, &scales));
+        html.push_str("</div>\n");
+    }
+    if !frames.is_empty() {
+        html.push_str(
+            "<script>\n\
+             const frames = document.querySelectorAll('.frame');\n\
+             const slider = document.getElementById('frame');\n\
+             const label = document.getElementById('label');\n\
+             let timer = null;\n\
+             function show(i) {\n\
+             \x20 frames.forEach((f, j) => f.classList.toggle('shown', j === i));\n\
+             \x20 slider.value = i;\n\
+             \x20 label.textContent = (i + 1) + ' / ' + frames.length;\n\
+             }\n\
+             slider.addEventListener('input', () => show(Number(slider.value)));\n\
+             document.getElementById('play').addEventListener('click', () => {\n\
+             \x20 if (timer) { clearInterval(timer); timer = null; return; }\n\
+             \x20 timer = setInterval(() => show((Number(slider.value) + 1) % frames.length), 200);\n\
+             });\n\
+             </script>\n",
+        );
+    }
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+/// Writes a recording as an HTML page (see [`render_html`])
+///
+/// # Arguments
+///
+/// * `path` - Destination file, overwritten if it exists
+/// * `frames` - Snapshots from a [`SearchRecorder`], oldest first
+/// * `title` - Page heading, e.g. the strategy name
+///
+/// # Errors
+///
+/// Returns any I/O error raised while writing the file
+pub fn write_html(path: &Path, frames: &[SearchProgress], title: &str) -> io::Result<()> {
+    let mut file = BufWriter::new(File::create(path)?);
+    file.write_all(render_html(frames, title).as_bytes())?;
+    file.flush()
+}
+
+/// Renders the frontier size over a recording as CSV
+///
+/// Each snapshot becomes one row with the nodes explored so far, the elapsed
+/// milliseconds, the frontier size and the smallest f-value in the frontier
+/// (empty when the frontier is empty).
+///
+/// # Arguments
+///
+/// * `frames` - Snapshots from a [`SearchRecorder`], oldest first
+#[must_use]
+pub fn render_timeline_csv(frames: &[SearchProgress]) -> String {
+    let mut csv = format!("{TIMELINE_HEADER}\n");
+    for frame in frames {
+        let best_f = frame.best_f().map(|f| f.to_string()).unwrap_or_default();
+        let _ = writeln!(
+            csv,
+            "{},{},{},{best_f}",
+            frame.nodes_explored,
+            frame.elapsed.as_millis(),
+            frame.frontier_size
+        );
+    }
+    csv
+}
+
+/// Writes a recording as a frontier timeline CSV (see [`render_timeline_csv`])
+///
+/// # Arguments
+///
+/// * `path` - Destination file, overwritten if it exists
+/// * `frames` - Snapshots from a [`SearchRecorder`], oldest first
+///
+/// # Errors
+///
+/// Returns any I/O error raised while writing the file
+pub fn write_timeline_csv(path: &Path, frames: &[SearchProgress]) -> io::Result<()> {
+    let mut file = BufWriter::new(File::create(path)?);
+    file.write_all(render_timeline_csv(frames).as_bytes())?;
+    file.flush()
+}
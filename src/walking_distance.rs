@@ -0,0 +1,171 @@
+//! # Walking Distance Module
+//!
+//! The walking distance heuristic (Takahashi 2002). The board is abstracted
+//! once by rows and once by columns: the abstract state only records how many
+//! tiles bound for each goal row sit in each row, and which row holds the
+//! empty space. A vertical move takes any tile from a row next to the empty
+//! space into its row, which changes the counts, while horizontal moves leave
+//! the row abstraction unchanged; columns work the same way with the axes
+//! swapped.
+//!
+//! There are only a few thousand abstract states per axis, so a breadth-first
+//! search from the goal abstraction computes the exact distance of each one up
+//! front. The row and column distances count disjoint sets of moves, so their
+//! sum never overestimates. Since a tile needs at least one vertical move per
+//! row it is away from its goal row, the sum never falls below the Manhattan
+//! distance, and tiles that share a line and block each other raise it further.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::LazyLock;
+
+use crate::board::{BOARD_AREA, BOARD_SIDE, Board};
+use crate::heuristic::Heuristic;
+
+const SIDE: usize = BOARD_SIDE as usize;
+const CELLS: usize = BOARD_AREA as usize;
+
+/// Direction along which a board is abstracted
+#[derive(Clone, Copy)]
+enum Axis {
+    Rows,
+    Columns,
+}
+
+impl Axis {
+    /// Line of `cell` along this axis
+    fn line(self, cell: usize) -> usize {
+        match self {
+            Axis::Rows => cell / SIDE,
+            Axis::Columns => cell % SIDE,
+        }
+    }
+}
+
+/// A board abstracted along one axis
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct LineState {
+    /// `counts[line][goal]`: tiles in `line` whose goal cell lies in line `goal`
+    counts: [[u8; SIDE]; SIDE],
+    /// Line of the empty space
+    blank: usize,
+}
+
+impl LineState {
+    /// Abstracts `board` along `axis`
+    fn new(board: Board, axis: Axis, goal_cells: &[usize; CELLS]) -> Self {
+        let mut counts = [[0; SIDE]; SIDE];
+        let mut blank = 0;
+        for (cell, tile) in board.into_arr().into_iter().enumerate() {
+            let line = axis.line(cell);
+            if tile == 0 {
+                blank = line;
+            } else {
+                counts[line][axis.line(goal_cells[usize::from(tile)])] += 1;
+            }
+        }
+        Self { counts, blank }
+    }
+
+    /// States one move away: a tile from a line next to the empty space moves into its line
+    fn neighbors(self) -> impl Iterator<Item = Self> {
+        [self.blank.checked_sub(1), Some(self.blank + 1)]
+            .into_iter()
+            .flatten()
+            .filter(|&line| line < SIDE)
+            .flat_map(move |line| {
+                (0..SIDE)
+                    .filter(move |&goal| self.counts[line][goal] > 0)
+                    .map(move |goal| {
+                        let mut next = self;
+                        next.counts[line][goal] -= 1;
+                        next.counts[self.blank][goal] += 1;
+                        next.blank = line;
+                        next
+                    })
+            })
+    }
+}
+
+/// Exact distances of the abstract states of both axes to the goal
+struct Tables {
+    /// Goal cell of every tile, indexed by tile
+    goal_cells: [usize; CELLS],
+    rows: HashMap<LineState, u8>,
+    columns: HashMap<LineState, u8>,
+}
+
+impl Tables {
+    fn new() -> Self {
+        let goal = Board::default();
+        let mut goal_cells = [0; CELLS];
+        for (cell, tile) in goal.into_arr().into_iter().enumerate() {
+            goal_cells[usize::from(tile)] = cell;
+        }
+        let rows = distances(LineState::new(goal, Axis::Rows, &goal_cells));
+        let columns = distances(LineState::new(goal, Axis::Columns, &goal_cells));
+        Self {
+            goal_cells,
+            rows,
+            columns,
+        }
+    }
+}
+
+/// Breadth-first distances of every abstract state reachable from `goal`
+///
+/// Moves are reversible, so the distance from `goal` is the distance to it.
+fn distances(goal: LineState) -> HashMap<LineState, u8> {
+    let mut distances = HashMap::from([(goal, 0)]);
+    let mut queue = VecDeque::from([goal]);
+    while let Some(state) = queue.pop_front() {
+        let distance = distances[&state] + 1;
+        for next in state.neighbors() {
+            distances.entry(next).or_insert_with(|| {
+                queue.push_back(next);
+                distance
+            });
+        }
+    }
+    distances
+}
+
+static TABLES: LazyLock<Tables> = LazyLock::new(Tables::new);
+
+/// Walking distance of `board` to the goal
+///
+/// The tables are built on first use.
+#[must_use]
+pub fn cost(board: Board) -> u8 {
+    let tables = &*TABLES;
+    let row = LineState::new(board, Axis::Rows, &tables.goal_cells);
+    let column = LineState::new(board, Axis::Columns, &tables.goal_cells);
+    tables.rows.get(&row).copied().unwrap_or(0) + tables.columns.get(&column).copied().unwrap_or(0)
+}
+
+/// The walking distance as a [`Heuristic`], building the tables on first use
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct WalkingDistance;
+
+impl Heuristic for WalkingDistance {
+    fn estimate(&self, board: Board) -> u32 {
+        u32::from(cost(board))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::StateSpaceDatabase;
+
+    #[test]
+    fn lies_between_the_manhattan_and_the_exact_distance() {
+        let exact = StateSpaceDatabase::build();
+        assert_eq!(cost(Board::default()), 0);
+        for rank in 0..Board::REACHABLE_COUNT {
+            let board = Board::from_reachable_rank(rank).unwrap();
+            let walking = cost(board);
+            assert!(walking <= exact.distance(board).unwrap(), "{board}");
+            assert!(walking >= board.heuristic_distance_to_solution(), "{board}");
+        }
+    }
+}
@@ -0,0 +1,155 @@
+//! # WASM Module
+//!
+//! `wasm-bindgen` bindings that let the solver run directly in the browser,
+//! e.g. to power a web demo or game without a server. Enabled with the `wasm`
+//! feature.
+//!
+//! Boards cross the JS boundary in their compact digit form (`"123804765"`,
+//! see [`Board::to_digit_string`]), and results are returned as plain JS
+//! objects:
+//!
+//! ```text
+//! {
+//!   moves: ["up", "left", ...],          // empty-space moves from start to goal
+//!   boards: ["123804765", ...],          // every board on the path, start first
+//!   stats: { nodesExplored, solutionMoves, maxFrontier, ..., durationMs }
+//! }
+//! ```
+//!
+//! `stats` has one camelCase key per field of [`Stats`].
+
+use js_sys::{Array, Object, Reflect};
+use wasm_bindgen::prelude::*;
+
+use crate::board::Board;
+use crate::registry::{self, DynSolver, StrategyConfig};
+use crate::stats::Stats;
+
+/// Solves a board and returns the solution moves, boards and statistics
+///
+/// # Arguments
+///
+/// * `board_str` - The board in digit form, e.g. `"123804765"`
+/// * `algorithm` - A registered strategy name, e.g. `"dfs"`, `"bfs"` or `"heuristic"`
+///
+/// # Errors
+///
+/// Returns a JS error if the board or algorithm cannot be parsed, or if the
+/// board has no solution
+#[wasm_bindgen]
+pub fn solve(board_str: &str, algorithm: &str) -> Result<JsValue, JsError> {
+    let board: Board = board_str.parse().map_err(JsError::new)?;
+    let entry = registry::lookup(algorithm).ok_or_else(|| {
+        JsError::new(&format!(
+            "Unknown algorithm: expected {}",
+            registry::expected_names()
+        ))
+    })?;
+
+    solve_with(board, entry.build(&StrategyConfig::default()).as_mut())
+}
+
+/// Generates a random solvable board in digit form
+///
+/// # Arguments
+///
+/// * `scramble_steps` - Number of random moves applied to the solved board
+#[wasm_bindgen(js_name = randomBoard)]
+#[must_use]
+pub fn random_board(scramble_steps: usize) -> String {
+    Board::random_with_solution(scramble_steps).to_digit_string()
+}
+
+/// Runs the solver and converts its results into a JS object
+fn solve_with(board: Board, solver: &mut dyn DynSolver) -> Result<JsValue, JsError> {
+    solver
+        .try_solve(board)
+        .map_err(|e| JsError::new(&e.to_string()))?;
+    let path = solver.step_by_step_solution();
+
+    let boards: Array = path
+        .iter()
+        .map(|b| JsValue::from_str(&b.to_digit_string()))
+        .collect();
+    let moves: Array = path
+        .windows(2)
+        .filter_map(|pair| pair[0].direction_to(pair[1]))
+        .map(|d| JsValue::from_str(d.name()))
+        .collect();
+
+    let stats = stats_object(&solver.get_solution_stats())?;
+    let result = Object::new();
+    set(&result, "moves", &moves)?;
+    set(&result, "boards", &boards)?;
+    set(&result, "stats", &stats)?;
+    Ok(result.into())
+}
+
+/// Converts run statistics into a JS object with camelCase keys
+#[allow(clippy::cast_precision_loss)]
+fn stats_object(stats: &Stats) -> Result<Object, JsError> {
+    // Destructured in full, so a new field cannot be left out of the object
+    let Stats {
+        nodes_explored,
+        solution_moves,
+        solution_cost,
+        compressed_moves,
+        max_frontier,
+        generated_nodes,
+        enqueued_nodes,
+        duplicates_pruned,
+        capacity_pruned,
+        depth_pruned,
+        max_depth_reached,
+        plateaus,
+        plateau_expansions,
+        longest_plateau,
+        regenerated_nodes,
+        f_backups,
+        reexpanded_nodes,
+        lookaheads,
+        longest_lookahead,
+        learned_updates,
+        learned_entries,
+        bound_pruned,
+        duration_ms,
+    } = *stats;
+    let fields: &[(&str, f64)] = &[
+        ("nodesExplored", nodes_explored as f64),
+        ("solutionMoves", solution_moves as f64),
+        ("solutionCost", solution_cost as f64),
+        ("compressedMoves", compressed_moves as f64),
+        ("maxFrontier", max_frontier as f64),
+        ("generatedNodes", generated_nodes as f64),
+        ("enqueuedNodes", enqueued_nodes as f64),
+        ("duplicatesPruned", duplicates_pruned as f64),
+        ("capacityPruned", capacity_pruned as f64),
+        ("depthPruned", depth_pruned as f64),
+        ("maxDepthReached", max_depth_reached as f64),
+        ("plateaus", plateaus as f64),
+        ("plateauExpansions", plateau_expansions as f64),
+        ("longestPlateau", longest_plateau as f64),
+        ("regeneratedNodes", regenerated_nodes as f64),
+        ("fBackups", f_backups as f64),
+        ("reexpandedNodes", reexpanded_nodes as f64),
+        ("lookaheads", lookaheads as f64),
+        ("longestLookahead", longest_lookahead as f64),
+        ("learnedUpdates", learned_updates as f64),
+        ("learnedEntries", learned_entries as f64),
+        ("boundPruned", bound_pruned as f64),
+        ("durationMs", duration_ms as f64),
+    ];
+
+    let object = Object::new();
+    for &(key, value) in fields {
+        set(&object, key, &JsValue::from_f64(value))?;
+    }
+    Ok(object)
+}
+
+/// Sets a property on a JS object
+fn set(target: &Object, key: &str, value: &JsValue) -> Result<(), JsError> {
+    Reflect::set(target, &JsValue::from_str(key), value)
+        .map(|_| ())
+        .map_err(|_| JsError::new("Failed to build result object"))
+}
@@ -0,0 +1,107 @@
+//! # WebSocket Module
+//!
+//! Serves the JSON-RPC protocol of the [`rpc`](crate::rpc) module over
+//! WebSocket, so web UIs can drive a solver and show a live search without
+//! polling. Each text message is one request and each response is sent as one
+//! text message.
+//!
+//! A `solve` request with a `progress` interval streams `progress`
+//! notifications while the search runs, followed by the response:
+//!
+//! ```text
+//! -> {"jsonrpc":"2.0","id":7,"method":"solve","params":{"board":"867254301","algorithm":"bfs","progress":5000}}
+//! <- {"jsonrpc":"2.0","method":"progress","params":{"id":7,"nodes_explored":5000,"frontier_size":3120,"max_depth_reached":14,"elapsed_ms":6}}
+//! <- ...
+//! <- {"jsonrpc":"2.0","id":7,"result":{...}}
+//! ```
+//!
+//! Every connection runs on its own thread and handles its requests one at a
+//! time. Requires the `websocket` feature (enabled by `cli`).
+
+use std::io;
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::{Arc, mpsc};
+use std::thread;
+
+use tungstenite::{Message, WebSocket};
+
+use crate::rpc;
+
+/// Message from a running request to its connection
+enum Event {
+    /// A serialized notification to forward
+    Notification(String),
+    /// The request finished, with the serialized response to send, if any
+    Done(Option<String>),
+}
+
+/// Accepts WebSocket connections on `addr` and serves JSON-RPC requests on them
+///
+/// Runs until the listener fails. Errors on a single connection only close
+/// that connection.
+///
+/// # Arguments
+///
+/// * `addr` - Address to listen on, e.g. `"127.0.0.1:8080"`
+///
+/// # Errors
+///
+/// Returns an error if the address cannot be bound or the listener fails
+pub fn serve(addr: impl ToSocketAddrs) -> io::Result<()> {
+    let listener = TcpListener::bind(addr)?;
+    for stream in listener.incoming() {
+        let stream = stream?;
+        thread::spawn(move || {
+            let _ = serve_connection(stream);
+        });
+    }
+    Ok(())
+}
+
+/// Performs the WebSocket handshake and serves requests until the client leaves
+fn serve_connection(stream: TcpStream) -> io::Result<()> {
+    let mut socket = tungstenite::accept(stream).map_err(|e| io::Error::other(e.to_string()))?;
+
+    loop {
+        let text = match socket.read() {
+            Ok(Message::Text(text)) => text.as_str().to_owned(),
+            Ok(Message::Close(_))
+            | Err(tungstenite::Error::ConnectionClosed | tungstenite::Error::AlreadyClosed) => {
+                return Ok(());
+            }
+            Ok(_) => continue,
+            Err(e) => return Err(io::Error::other(e)),
+        };
+
+        run_request(&mut socket, text)?;
+    }
+}
+
+/// Runs one request on a worker thread, forwarding its notifications as they arrive
+fn run_request(socket: &mut WebSocket<TcpStream>, text: String) -> io::Result<()> {
+    let (sender, events) = mpsc::channel();
+    let notifications = sender.clone();
+    let notifier: rpc::Notifier = Arc::new(move |notification| {
+        let _ = notifications.send(Event::Notification(notification));
+    });
+
+    thread::spawn(move || {
+        let response = rpc::handle_text(&text, notifier);
+        let _ = sender.send(Event::Done(response));
+    });
+
+    for event in events {
+        let (message, done) = match event {
+            Event::Notification(notification) => (notification, false),
+            Event::Done(Some(response)) => (response, true),
+            Event::Done(None) => break,
+        };
+        socket
+            .send(Message::text(message))
+            .map_err(io::Error::other)?;
+        if done {
+            break;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,52 @@
+//! Browser bindings run under `wasm-bindgen-test`, in Node.js:
+//!
+//! ```text
+//! wasm-pack test --node --no-default-features --features wasm
+//! ```
+#![cfg(all(target_arch = "wasm32", feature = "wasm"))]
+
+use js_sys::{Array, Reflect};
+use o8::wasm::solve;
+use wasm_bindgen::JsValue;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+/// Reads a property of a JS object
+fn get(object: &JsValue, key: &str) -> JsValue {
+    Reflect::get(object, &JsValue::from_str(key)).expect("result is an object")
+}
+
+#[wasm_bindgen_test]
+fn solve_returns_moves_boards_and_stats() {
+    // Two moves from the spiral goal: the empty space went right, then down
+    let Ok(result) = solve("123845760", "bfs") else {
+        panic!("BFS solves a solvable board");
+    };
+
+    let moves: Vec<String> = Array::from(&get(&result, "moves"))
+        .iter()
+        .filter_map(|m| m.as_string())
+        .collect();
+    assert_eq!(moves, ["up", "left"]);
+    let boards: Vec<String> = Array::from(&get(&result, "boards"))
+        .iter()
+        .filter_map(|b| b.as_string())
+        .collect();
+    assert_eq!(boards, ["123845760", "123840765", "123804765"]);
+
+    // Timing the search reads the clock, which std::time cannot do here
+    let stats = get(&result, "stats");
+    assert_eq!(get(&stats, "solutionMoves").as_f64(), Some(2.0));
+    assert!(
+        get(&stats, "durationMs")
+            .as_f64()
+            .is_some_and(|ms| ms >= 0.0)
+    );
+}
+
+#[wasm_bindgen_test]
+fn threaded_strategies_are_not_registered() {
+    for algorithm in ["hda", "portfolio"] {
+        assert!(solve("123845760", algorithm).is_err());
+    }
+    assert!(solve("123845760", "heuristic").is_ok());
+}